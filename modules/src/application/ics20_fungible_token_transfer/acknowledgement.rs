@@ -0,0 +1,94 @@
+//! Acknowledgements written by the ICS20 module when it receives a packet. These follow the JSON
+//! encoding of the ICS04 `Acknowledgement` type, i.e., `{"result":"AQ=="}` upon success and
+//! `{"error":"<reason>"}` upon failure.
+
+use std::fmt::Display;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+
+/// The result bytes of a successful acknowledgement.
+const SUCCESS_RESULT: &[u8] = &[1];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Acknowledgement {
+    /// The packet was processed successfully. Carries the (base64-encoded) result bytes.
+    #[serde(rename = "result", with = "base64_bytes")]
+    Result(Vec<u8>),
+
+    /// The packet could not be processed. Carries the reason for the failure.
+    #[serde(rename = "error")]
+    Error(String),
+}
+
+impl Acknowledgement {
+    pub fn success() -> Self {
+        Acknowledgement::Result(SUCCESS_RESULT.to_vec())
+    }
+
+    pub fn from_error(e: impl Display) -> Self {
+        Acknowledgement::Error(e.to_string())
+    }
+
+    pub fn is_successful(&self) -> bool {
+        matches!(self, Acknowledgement::Result(_))
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(serde_json::from_slice(bytes).map_err(|e| Kind::InvalidAcknowledgement.context(e))?)
+    }
+}
+
+pub(crate) mod base64_bytes {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use subtle_encoding::base64;
+
+    pub fn serialize<S, T>(data: T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: AsRef<[u8]>,
+    {
+        let encoded = String::from_utf8(base64::encode(data)).unwrap();
+        encoded.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let encoded = String::deserialize(deserializer)?;
+        base64::decode(encoded).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Acknowledgement;
+
+    #[test]
+    fn acknowledgement_json_encoding() {
+        let success = Acknowledgement::success();
+        assert_eq!(success.to_json_bytes(), br#"{"result":"AQ=="}"#.to_vec());
+        assert_eq!(
+            Acknowledgement::from_json_bytes(&success.to_json_bytes()).unwrap(),
+            success
+        );
+
+        let error = Acknowledgement::from_error("insufficient funds");
+        assert_eq!(
+            error.to_json_bytes(),
+            br#"{"error":"insufficient funds"}"#.to_vec()
+        );
+        assert!(!Acknowledgement::from_json_bytes(&error.to_json_bytes())
+            .unwrap()
+            .is_successful());
+
+        assert!(Acknowledgement::from_json_bytes(b"AQ==").is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! The amounts of tokens transferred by the ICS20 module. As in the Cosmos SDK, whose amounts are
+//! arbitrary-precision integers bounded to 256 bits, an amount can be as large as `2^256 - 1`,
+//! which is needed for tokens with 18 decimals.
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+
+/// Number of 64-bit limbs of an `Amount`.
+const LIMBS: usize = 4;
+
+/// The largest power of ten which fits in a limb, used to convert amounts from and to decimal.
+const DECIMAL_BASE: u64 = 10_000_000_000_000_000_000;
+const DECIMAL_BASE_DIGITS: usize = 19;
+
+/// An unsigned 256-bit integer amount of tokens, encoded as a decimal string.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Amount {
+    /// The limbs of the amount, from the least significant one to the most significant one.
+    limbs: [u64; LIMBS],
+}
+
+impl Amount {
+    pub fn is_zero(&self) -> bool {
+        self.limbs.iter().all(|limb| *limb == 0)
+    }
+
+    /// Returns the sum of `self` and `other`, or `None` if it overflows 256 bits.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let mut limbs = [0; LIMBS];
+        let mut carry = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (sum, overflow1) = self.limbs[i].overflowing_add(other.limbs[i]);
+            let (sum, overflow2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = overflow1 || overflow2;
+        }
+
+        if carry {
+            None
+        } else {
+            Some(Self { limbs })
+        }
+    }
+
+    /// Returns the difference of `self` and `other`, or `None` if `other` is larger than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let mut limbs = [0; LIMBS];
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (diff, overflow1) = self.limbs[i].overflowing_sub(other.limbs[i]);
+            let (diff, overflow2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = overflow1 || overflow2;
+        }
+
+        if borrow {
+            None
+        } else {
+            Some(Self { limbs })
+        }
+    }
+
+    /// Multiplies the amount by `factor` and adds `term`, or returns `None` if it overflows.
+    fn checked_mul_add(self, factor: u64, term: u64) -> Option<Self> {
+        let mut limbs = [0; LIMBS];
+        let mut carry = term as u128;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let product = self.limbs[i] as u128 * factor as u128 + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+
+        if carry != 0 {
+            None
+        } else {
+            Some(Self { limbs })
+        }
+    }
+
+    /// Divides the amount by `divisor` in place and returns the remainder.
+    fn div_rem(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for limb in self.limbs.iter_mut().rev() {
+            let dividend = (remainder << 64) | *limb as u128;
+            *limb = (dividend / divisor as u128) as u64;
+            remainder = dividend % divisor as u128;
+        }
+        remainder as u64
+    }
+}
+
+impl From<u64> for Amount {
+    fn from(amount: u64) -> Self {
+        Self {
+            limbs: [amount, 0, 0, 0],
+        }
+    }
+}
+
+impl TryFrom<Amount> for u64 {
+    type Error = Error;
+
+    fn try_from(amount: Amount) -> Result<Self, Self::Error> {
+        match amount.limbs {
+            [limb, 0, 0, 0] => Ok(limb),
+            _ => Err(Kind::InvalidTokenAmount(amount.to_string())
+                .context("the amount does not fit in 64 bits")
+                .into()),
+        }
+    }
+}
+
+impl Ord for Amount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs.iter().rev().cmp(other.limbs.iter().rev())
+    }
+}
+
+impl PartialOrd for Amount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Amount {
+    type Err = Error;
+
+    /// Parses a decimal amount, made of digits only, like the Cosmos SDK does.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Kind::InvalidTokenAmount(s.to_string()).into());
+        }
+
+        s.as_bytes()
+            .chunks(DECIMAL_BASE_DIGITS)
+            .try_fold(Amount::default(), |amount, chunk| {
+                let digits = std::str::from_utf8(chunk).unwrap();
+                // A chunk has at most 19 digits, hence fits in a limb.
+                let value = digits.parse::<u64>().unwrap();
+                amount.checked_mul_add(10u64.pow(chunk.len() as u32), value)
+            })
+            .ok_or_else(|| Kind::InvalidTokenAmount(s.to_string()).into())
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut amount = *self;
+        let mut chunks = vec![];
+        loop {
+            chunks.push(amount.div_rem(DECIMAL_BASE));
+            if amount.is_zero() {
+                break;
+            }
+        }
+
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap())?;
+        for chunk in chunks {
+            write!(f, "{:019}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::Amount;
+
+    const MAX: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    #[test]
+    fn decimal_encoding() {
+        for s in [
+            "0",
+            "1",
+            "18446744073709551616",
+            "10000000000000000000",
+            MAX,
+        ]
+        .iter()
+        {
+            assert_eq!(s.parse::<Amount>().unwrap().to_string(), *s);
+        }
+
+        assert_eq!("007".parse::<Amount>().unwrap(), Amount::from(7));
+        assert_eq!(
+            "18446744073709551615".parse::<Amount>().unwrap(),
+            Amount::from(u64::MAX)
+        );
+        assert_eq!(u64::try_from(Amount::from(u64::MAX)).unwrap(), u64::MAX);
+        assert!(u64::try_from("18446744073709551616".parse::<Amount>().unwrap()).is_err());
+
+        // 2^256 overflows.
+        let overflow =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        for s in ["", "-1", "+1", "1.5", "1e3", " 1", overflow].iter() {
+            assert!(s.parse::<Amount>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let max: Amount = MAX.parse().unwrap();
+        let one = Amount::from(1);
+
+        assert_eq!(
+            Amount::from(u64::MAX).checked_add(one).unwrap().to_string(),
+            "18446744073709551616"
+        );
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(max.checked_sub(max), Some(Amount::default()));
+        assert_eq!(Amount::default().checked_sub(one), None);
+
+        assert!(one < max);
+        assert!(Amount::from(u64::MAX) < Amount::from(u64::MAX).checked_add(one).unwrap());
+        assert!(Amount::default().is_zero());
+    }
+}
@@ -1,5 +1,35 @@
+use crate::application::ics20_fungible_token_transfer::amount::Amount;
+use crate::application::ics20_fungible_token_transfer::error::Error;
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::ics05_port::context::CapabilityKeeper;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+
+/// Captures the bank-like functionality through which the ICS20 module moves tokens between
+/// accounts of the host chain, and creates or destroys vouchers for tokens of foreign origin.
+pub trait BankKeeper {
+    /// Transfers `amount` tokens of denomination `denom` from account `from` to account `to`.
+    fn send_coins(
+        &mut self,
+        from: &Signer,
+        to: &Signer,
+        denom: &str,
+        amount: Amount,
+    ) -> Result<(), Error>;
+
+    /// Creates `amount` new tokens of denomination `denom` and credits them to `account`.
+    fn mint_coins(&mut self, account: &Signer, denom: &str, amount: Amount) -> Result<(), Error>;
+
+    /// Removes `amount` tokens of denomination `denom` from `account` and destroys them.
+    fn burn_coins(&mut self, account: &Signer, denom: &str, amount: Amount) -> Result<(), Error>;
+}
 
 /// Captures all the dependencies which the ICS20 module requires to be able to dispatch and
 /// process IBC messages.
-pub trait Ics20Context: ChannelReader + ChannelKeeper + Clone {}
+pub trait Ics20Context:
+    ChannelReader + ChannelKeeper + CapabilityKeeper + BankKeeper + Clone
+{
+    /// Returns the account holding the tokens escrowed for transfers over the channel identified
+    /// by `port_id` and `channel_id`.
+    fn get_channel_escrow_address(&self, port_id: &PortId, channel_id: &ChannelId) -> Signer;
+}
@@ -0,0 +1,122 @@
+//! Denomination tracing for tokens moved over ICS20 channels. A token which leaves the chain it
+//! originates from is represented on the receiving chain by a voucher, whose denomination carries
+//! the destination port and channel as a prefix (e.g., `transfer/channel-0/uatom`). Sending the
+//! voucher back over the same channel strips this prefix again.
+
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
+/// Returns the prefix that a denomination receives when its tokens cross the channel end
+/// identified by `port_id` and `channel_id`.
+pub fn denom_prefix(port_id: &PortId, channel_id: &ChannelId) -> String {
+    format!("{}/{}/", port_id, channel_id)
+}
+
+/// Prefixes `denom` with the given port and channel identifiers.
+pub fn prefix_denom(port_id: &PortId, channel_id: &ChannelId, denom: &str) -> String {
+    format!("{}{}", denom_prefix(port_id, channel_id), denom)
+}
+
+/// Strips the prefix of the given port and channel identifiers from `denom`. Returns `None` if
+/// `denom` does not carry this prefix.
+pub fn unprefix_denom<'a>(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    denom: &'a str,
+) -> Option<&'a str> {
+    let prefix = denom_prefix(port_id, channel_id);
+    if denom.len() > prefix.len() && denom.starts_with(&prefix) {
+        Some(&denom[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// Returns true if the tokens of `denom`, sent over `source_port` and `source_channel`, originally
+/// came from the receiving chain. This is the case if the sending chain previously received them
+/// over this same channel, i.e., if `denom` carries the prefix of the source port and channel.
+pub fn receiver_chain_is_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    denom: &str,
+) -> bool {
+    unprefix_denom(source_port, source_channel, denom).is_some()
+}
+
+/// Returns true if the tokens of `denom`, sent over `source_port` and `source_channel`, are not
+/// returning to the chain they came from. The sending chain must then escrow the tokens, instead
+/// of burning the vouchers it holds.
+pub fn sender_chain_is_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    denom: &str,
+) -> bool {
+    !receiver_chain_is_source(source_port, source_channel, denom)
+}
+
+/// Checks that `denom` is a non-empty sequence of `/`-separated, non-empty path segments, none of
+/// which contains whitespace.
+pub fn validate_denom(denom: &str) -> Result<(), Error> {
+    if denom
+        .split('/')
+        .any(|segment| segment.is_empty() || segment.contains(char::is_whitespace))
+    {
+        return Err(Kind::InvalidDenom(denom.to_string()).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denom_prefixing() {
+        let port_id: PortId = "transfer".parse().unwrap();
+        let channel_id = ChannelId::new(0);
+
+        let prefixed = prefix_denom(&port_id, &channel_id, "uatom");
+        assert_eq!(prefixed, "transfer/channel-0/uatom");
+        assert_eq!(
+            unprefix_denom(&port_id, &channel_id, &prefixed),
+            Some("uatom")
+        );
+
+        // Only the outermost prefix is stripped.
+        let twice = prefix_denom(&port_id, &ChannelId::new(1), &prefixed);
+        assert_eq!(
+            unprefix_denom(&port_id, &ChannelId::new(1), &twice),
+            Some(prefixed.as_str())
+        );
+        assert_eq!(unprefix_denom(&port_id, &channel_id, &twice), None);
+
+        assert!(sender_chain_is_source(&port_id, &channel_id, "uatom"));
+        assert!(receiver_chain_is_source(&port_id, &channel_id, &prefixed));
+        assert!(sender_chain_is_source(
+            &port_id,
+            &ChannelId::new(1),
+            &prefixed
+        ));
+    }
+
+    #[test]
+    fn denom_validation() {
+        assert!(validate_denom("uatom").is_ok());
+        assert!(validate_denom("transfer/channel-0/uatom").is_ok());
+
+        for denom in &[
+            "",
+            "/uatom",
+            "transfer/channel-0/",
+            "transfer//uatom",
+            "u atom",
+        ] {
+            assert!(
+                validate_denom(denom).is_err(),
+                "denom {:?} should be invalid",
+                denom
+            );
+        }
+    }
+}
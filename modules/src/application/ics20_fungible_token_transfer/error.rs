@@ -1,7 +1,9 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics04_channel::channel::Order;
 use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
 
 pub type Error = anomaly::Error<Kind>;
 
@@ -23,6 +25,36 @@ pub enum Kind {
         "Destination channel not found in the counterparty of port_id {0} and channel_id {1} "
     )]
     DestinationChannelNotFound(PortId, ChannelId),
+
+    #[error("the transfer module holds no capability for port_id {0} and channel_id {1}")]
+    ChannelCapabilityNotFound(PortId, ChannelId),
+
+    #[error("the transfer message does not specify a token")]
+    EmptyToken,
+
+    #[error("invalid token amount {0}")]
+    InvalidTokenAmount(String),
+
+    #[error("invalid denomination {0}")]
+    InvalidDenom(String),
+
+    #[error("invalid fungible token packet data")]
+    InvalidPacketData,
+
+    #[error("invalid acknowledgement")]
+    InvalidAcknowledgement,
+
+    #[error("account {0} has insufficient funds of denomination {1}")]
+    InsufficientFunds(Signer, String),
+
+    #[error("expected {0} channel, got {1}")]
+    InvalidChannelOrder(Order, Order),
+
+    #[error("expected version {0}, got {1}")]
+    InvalidVersion(String, String),
+
+    #[error("channel {1} on port {0} cannot be closed by users")]
+    ChannelClosingNotAllowed(PortId, ChannelId),
 }
 
 impl Kind {
@@ -0,0 +1,113 @@
+//! Types for the IBC events emitted by the ICS20 (fungible token transfer) module.
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::application::ics20_fungible_token_transfer::amount::Amount;
+use crate::events::IbcEvent;
+use crate::ics02_client::height::Height;
+use crate::signer::Signer;
+
+/// Signals that tokens were escrowed or burned for a transfer to another chain.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Transfer {
+    pub height: Height,
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub denom: String,
+    pub amount: Amount,
+}
+
+impl Transfer {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<Transfer> for IbcEvent {
+    fn from(v: Transfer) -> Self {
+        IbcEvent::Transfer(v)
+    }
+}
+
+impl std::fmt::Display for Transfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, {}{} {}->{}",
+            self.height, self.amount, self.denom, self.sender, self.receiver
+        )
+    }
+}
+
+/// Signals the processing of an incoming ICS20 packet, or of the acknowledgement for an outgoing
+/// one. The `success` flag indicates whether the transfer went through.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct FungibleTokenPacket {
+    pub height: Height,
+    pub receiver: Signer,
+    pub denom: String,
+    pub amount: Amount,
+    pub success: bool,
+}
+
+impl FungibleTokenPacket {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<FungibleTokenPacket> for IbcEvent {
+    fn from(v: FungibleTokenPacket) -> Self {
+        IbcEvent::FungibleTokenPacket(v)
+    }
+}
+
+impl std::fmt::Display for FungibleTokenPacket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, {}{} ->{}, success:{}",
+            self.height, self.amount, self.denom, self.receiver, self.success
+        )
+    }
+}
+
+/// Signals that the tokens of an ICS20 packet which timed out were refunded to their sender.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TransferTimeout {
+    pub height: Height,
+    pub refund_receiver: Signer,
+    pub refund_denom: String,
+    pub refund_amount: Amount,
+}
+
+impl TransferTimeout {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<TransferTimeout> for IbcEvent {
+    fn from(v: TransferTimeout) -> Self {
+        IbcEvent::TransferTimeout(v)
+    }
+}
+
+impl std::fmt::Display for TransferTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, refund {}{} ->{}",
+            self.height, self.refund_amount, self.refund_denom, self.refund_receiver
+        )
+    }
+}
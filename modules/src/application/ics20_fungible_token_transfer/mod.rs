@@ -1,5 +1,20 @@
 //! ICS 20: IBC Transfer implementation
+pub mod acknowledgement;
+pub mod amount;
 pub mod context;
+pub mod denom;
 pub mod error;
+pub mod events;
+pub mod module;
 pub mod msgs;
+pub mod packet;
 pub mod relay_application_logic;
+
+/// The identifier of the ICS20 module, in whose scope it claims the capabilities of its channels.
+pub const MODULE_ID: &str = "transfer";
+
+/// The port identifier to which the ICS20 application is conventionally bound.
+pub const PORT_ID: &str = "transfer";
+
+/// The version string negotiated by channels carrying ICS20 packets.
+pub const VERSION: &str = "ics20-1";
@@ -10,6 +10,7 @@ use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
 use crate::ics02_client::height::Height;
 use crate::ics24_host::identifier::{ChannelId, PortId};
 use crate::signer::Signer;
+use crate::timestamp::Timestamp;
 use crate::tx_msg::Msg;
 
 pub const TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
@@ -32,9 +33,9 @@ pub struct MsgTransfer {
     /// Timeout height relative to the current block height.
     /// The timeout is disabled when set to 0.
     pub timeout_height: Height,
-    /// Timeout timestamp (in nanoseconds) relative to the current block timestamp.
-    /// The timeout is disabled when set to 0.
-    pub timeout_timestamp: u64,
+    /// Timeout timestamp relative to the current block timestamp.
+    /// The timeout is disabled when there is no timestamp.
+    pub timeout_timestamp: Timestamp,
 }
 
 impl Msg for MsgTransfer {
@@ -63,7 +64,7 @@ impl TryFrom<RawMsgTransfer> for MsgTransfer {
             sender: raw_msg.sender.into(),
             receiver: raw_msg.receiver.into(),
             timeout_height: raw_msg.timeout_height.unwrap().try_into().unwrap(),
-            timeout_timestamp: raw_msg.timeout_timestamp,
+            timeout_timestamp: Timestamp::from_nanoseconds(raw_msg.timeout_timestamp),
         })
     }
 }
@@ -77,16 +78,19 @@ impl From<MsgTransfer> for RawMsgTransfer {
             sender: domain_msg.sender.to_string(),
             receiver: domain_msg.receiver.to_string(),
             timeout_height: Some(domain_msg.timeout_height.try_into().unwrap()),
-            timeout_timestamp: domain_msg.timeout_timestamp,
+            timeout_timestamp: domain_msg.timeout_timestamp.as_nanoseconds(),
         }
     }
 }
 
 #[cfg(test)]
 pub mod test_util {
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
     use crate::{
         ics24_host::identifier::{ChannelId, PortId},
         test_utils::get_dummy_account_id,
+        timestamp::Timestamp,
         Height,
     };
 
@@ -99,10 +103,13 @@ pub mod test_util {
         MsgTransfer {
             source_port: PortId::default(),
             source_channel: ChannelId::default(),
-            token: None,
+            token: Some(Coin {
+                denom: "uatom".to_string(),
+                amount: "10".to_string(),
+            }),
             sender: id.clone(),
             receiver: id,
-            timeout_timestamp: 1,
+            timeout_timestamp: Timestamp::from_nanoseconds(10),
             timeout_height: Height {
                 revision_number: 0,
                 revision_height: height,
@@ -0,0 +1,135 @@
+//! The payload of the packets exchanged by the ICS20 module, together with its JSON encoding.
+
+use std::convert::{TryFrom, TryInto};
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_proto::ibc::apps::transfer::v1::FungibleTokenPacketData as RawFungibleTokenPacketData;
+
+use crate::application::ics20_fungible_token_transfer::amount::Amount;
+use crate::application::ics20_fungible_token_transfer::denom::validate_denom;
+use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::signer::Signer;
+
+/// The data carried by an ICS20 packet. Packets carry this data JSON-encoded, with the fields in
+/// alphabetical order and the amount encoded as a string, so that the packet commitments match
+/// those computed by other ICS20 implementations.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FungibleTokenPacketData {
+    /// The token amount to be transferred.
+    pub amount: Amount,
+    /// The token denomination to be transferred, including its trace prefixes.
+    pub denom: String,
+    /// The recipient address on the destination chain.
+    pub receiver: Signer,
+    /// The sender address on the source chain.
+    pub sender: Signer,
+}
+
+impl FungibleTokenPacketData {
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.amount.is_zero() {
+            return Err(Kind::InvalidTokenAmount(self.amount.to_string()).into());
+        }
+
+        if self.sender.as_str().trim().is_empty() {
+            return Err(Kind::InvalidPacketData.context("empty sender").into());
+        }
+
+        if self.receiver.as_str().trim().is_empty() {
+            return Err(Kind::InvalidPacketData.context("empty receiver").into());
+        }
+
+        validate_denom(&self.denom)
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    /// Decodes and validates the JSON-encoded data of an ICS20 packet.
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let data: FungibleTokenPacketData =
+            serde_json::from_slice(bytes).map_err(|e| Kind::InvalidPacketData.context(e))?;
+        data.validate_basic()?;
+        Ok(data)
+    }
+}
+
+impl TryFrom<RawFungibleTokenPacketData> for FungibleTokenPacketData {
+    type Error = Error;
+
+    fn try_from(raw: RawFungibleTokenPacketData) -> Result<Self, Self::Error> {
+        let data = FungibleTokenPacketData {
+            amount: raw.amount.into(),
+            denom: raw.denom,
+            receiver: raw.receiver.into(),
+            sender: raw.sender.into(),
+        };
+        data.validate_basic()?;
+        Ok(data)
+    }
+}
+
+/// The protobuf encoding of the packet data carries the amount as a 64-bit integer, hence larger
+/// amounts cannot be converted to it.
+impl TryFrom<FungibleTokenPacketData> for RawFungibleTokenPacketData {
+    type Error = Error;
+
+    fn try_from(data: FungibleTokenPacketData) -> Result<Self, Self::Error> {
+        Ok(RawFungibleTokenPacketData {
+            denom: data.denom,
+            amount: data.amount.try_into()?,
+            sender: data.sender.to_string(),
+            receiver: data.receiver.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+
+    #[test]
+    fn packet_data_json_encoding() {
+        let data = FungibleTokenPacketData {
+            amount: Amount::from(100),
+            denom: "transfer/channel-0/uatom".to_string(),
+            receiver: "bob".parse().unwrap(),
+            sender: "alice".parse().unwrap(),
+        };
+
+        let bytes = data.to_json_bytes();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            r#"{"amount":"100","denom":"transfer/channel-0/uatom","receiver":"bob","sender":"alice"}"#
+        );
+        assert_eq!(
+            FungibleTokenPacketData::from_json_bytes(&bytes).unwrap(),
+            data
+        );
+
+        // Amounts are not bounded to 64 bits, as tokens with 18 decimals exceed them.
+        let bytes = br#"{"amount":"1000000000000000000000000","denom":"uatom","receiver":"bob","sender":"alice"}"#;
+        let data = FungibleTokenPacketData::from_json_bytes(bytes).unwrap();
+        assert_eq!(data.amount.to_string(), "1000000000000000000000000");
+        assert_eq!(data.to_json_bytes(), bytes.to_vec());
+
+        let invalid: [&[u8]; 5] = [
+            // Not JSON.
+            &[0],
+            // The amount is not encoded as a string.
+            br#"{"amount":100,"denom":"uatom","receiver":"bob","sender":"alice"}"#,
+            // Zero amount.
+            br#"{"amount":"0","denom":"uatom","receiver":"bob","sender":"alice"}"#,
+            // Empty denomination.
+            br#"{"amount":"1","denom":"","receiver":"bob","sender":"alice"}"#,
+            // Missing receiver.
+            br#"{"amount":"1","denom":"uatom","sender":"alice"}"#,
+        ];
+        for bytes in invalid.iter() {
+            assert!(FungibleTokenPacketData::from_json_bytes(bytes).is_err());
+        }
+    }
+}
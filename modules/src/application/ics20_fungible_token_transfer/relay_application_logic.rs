@@ -1,3 +1,84 @@
 //! This module implements the processing logic for ICS20 (token transfer) message.
 
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::sender_chain_is_source;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::ics04_channel::packet::Packet;
+
+pub mod on_acknowledgement_packet;
+pub mod on_recv_packet;
+pub mod on_timeout_packet;
 pub mod send_transfer;
+
+/// Gives the tokens of a packet which could not be delivered back to their sender. This reverts
+/// the effect of `send_transfer`: tokens which were escrowed are released from the escrow account,
+/// and vouchers which were burned are minted again.
+fn refund_packet_token<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    data: &FungibleTokenPacketData,
+) -> Result<(), Error>
+where
+    Ctx: Ics20Context,
+{
+    if sender_chain_is_source(&packet.source_port, &packet.source_channel, &data.denom) {
+        let escrow_address =
+            ctx.get_channel_escrow_address(&packet.source_port, &packet.source_channel);
+        ctx.send_coins(&escrow_address, &data.sender, &data.denom, data.amount)
+    } else {
+        ctx.mint_coins(&data.sender, &data.denom, data.amount)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use std::time::Duration;
+
+    use crate::application::ics20_fungible_token_transfer::{MODULE_ID, VERSION};
+    use crate::ics03_connection::connection::ConnectionEnd;
+    use crate::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::ics03_connection::connection::State as ConnectionState;
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics05_port::capabilities::ModuleId;
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    /// Returns a context with an open channel on the default port and channel identifiers, over
+    /// which ICS20 packets can be sent.
+    pub fn get_dummy_transfer_context() -> MockContext {
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            VERSION.to_string(),
+        );
+
+        MockContext::default()
+            .with_client(&ClientId::default(), Height::default())
+            .with_connection(ConnectionId::default(), connection_end)
+            .with_port_capability(PortId::default())
+            .with_channel(PortId::default(), ChannelId::default(), channel_end)
+            .with_channel_capability_claimed_by(
+                ModuleId::new(MODULE_ID),
+                PortId::default(),
+                ChannelId::default(),
+            )
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
+    }
+}
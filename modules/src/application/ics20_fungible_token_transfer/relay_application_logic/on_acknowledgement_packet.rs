@@ -0,0 +1,91 @@
+use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::events::FungibleTokenPacket;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::refund_packet_token;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes the acknowledgement which the receiving chain wrote for an ICS20 packet sent by the
+/// host chain. If the acknowledgement signals an error, the tokens are refunded to the sender.
+pub fn on_acknowledgement_packet<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    acknowledgement: &[u8],
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics20Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let data = FungibleTokenPacketData::from_json_bytes(&packet.data)?;
+    let ack = Acknowledgement::from_json_bytes(acknowledgement)?;
+
+    if let Acknowledgement::Error(e) = &ack {
+        refund_packet_token(ctx, packet, &data)?;
+        output.log(format!(
+            "success: refunded {}{} after error acknowledgement: {}",
+            data.amount, data.denom, e
+        ));
+    }
+
+    output.emit(IbcEvent::FungibleTokenPacket(FungibleTokenPacket {
+        height: Default::default(),
+        receiver: data.receiver,
+        denom: data.denom,
+        amount: data.amount,
+        success: ack.is_successful(),
+    }));
+
+    Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_acknowledgement_packet::on_acknowledgement_packet;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn acknowledgement_packet_processing() {
+        let mut ctx = MockContext::default();
+        let escrow_address =
+            ctx.get_channel_escrow_address(&PortId::default(), &ChannelId::default());
+        ctx.mint_coins(&escrow_address, "uatom", Amount::from(10))
+            .unwrap();
+
+        let data = FungibleTokenPacketData {
+            amount: Amount::from(10),
+            denom: "uatom".to_string(),
+            receiver: "receiver".parse().unwrap(),
+            sender: "sender".parse().unwrap(),
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+        packet.data = data.to_json_bytes();
+
+        // A malformed acknowledgement is rejected.
+        assert!(on_acknowledgement_packet(&mut ctx, &packet, &[1]).is_err());
+
+        // A successful acknowledgement leaves the tokens in escrow.
+        let ack = Acknowledgement::success().to_json_bytes();
+        assert!(on_acknowledgement_packet(&mut ctx, &packet, &ack).is_ok());
+        assert_eq!(ctx.balance(&escrow_address, "uatom"), Amount::from(10));
+
+        // An error acknowledgement refunds the sender.
+        let ack = Acknowledgement::from_error("failure").to_json_bytes();
+        let output = on_acknowledgement_packet(&mut ctx, &packet, &ack).unwrap();
+        assert_eq!(ctx.balance(&escrow_address, "uatom"), Amount::from(0));
+        assert_eq!(ctx.balance(&data.sender, "uatom"), Amount::from(10));
+        assert!(!output.events.is_empty());
+    }
+}
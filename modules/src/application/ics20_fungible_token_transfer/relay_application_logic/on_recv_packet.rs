@@ -0,0 +1,138 @@
+use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::{prefix_denom, unprefix_denom};
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::events::FungibleTokenPacket;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes an ICS20 packet received by the host chain, and returns the acknowledgement to be
+/// written for it. Tokens which return to this chain are released from escrow, whereas tokens
+/// originating from other chains are represented by freshly minted vouchers. This function does
+/// not fail: any error is reported to the sending chain through an error acknowledgement.
+pub fn on_recv_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> HandlerOutput<Acknowledgement>
+where
+    Ctx: Ics20Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let data = match FungibleTokenPacketData::from_json_bytes(&packet.data) {
+        Ok(data) => data,
+        Err(e) => {
+            output.log(format!("failure: cannot decode packet data: {}", e));
+            return output.with_result(Acknowledgement::from_error(e));
+        }
+    };
+
+    let (ack, denom) = match receive_tokens(ctx, packet, &data) {
+        Ok(denom) => {
+            output.log(format!("success: received {}{}", data.amount, denom));
+            (Acknowledgement::success(), denom)
+        }
+        Err(e) => {
+            output.log(format!("failure: cannot receive tokens: {}", e));
+            (Acknowledgement::from_error(e), data.denom)
+        }
+    };
+
+    output.emit(IbcEvent::FungibleTokenPacket(FungibleTokenPacket {
+        height: Default::default(),
+        receiver: data.receiver,
+        denom,
+        amount: data.amount,
+        success: ack.is_successful(),
+    }));
+
+    output.with_result(ack)
+}
+
+/// Credits the receiver of `packet` with its tokens. Returns the denomination of the tokens on
+/// the host chain.
+fn receive_tokens<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    data: &FungibleTokenPacketData,
+) -> Result<String, Error>
+where
+    Ctx: Ics20Context,
+{
+    match unprefix_denom(&packet.source_port, &packet.source_channel, &data.denom) {
+        // The sending chain prefixed the denomination when it first received the tokens over this
+        // channel, so they originate from this chain and are held in escrow here.
+        Some(denom) => {
+            let escrow_address = ctx
+                .get_channel_escrow_address(&packet.destination_port, &packet.destination_channel);
+            ctx.send_coins(&escrow_address, &data.receiver, denom, data.amount)?;
+            Ok(denom.to_string())
+        }
+        // Otherwise, the tokens are represented by vouchers tracing the channel they arrived on.
+        None => {
+            let denom = prefix_denom(
+                &packet.destination_port,
+                &packet.destination_channel,
+                &data.denom,
+            );
+            ctx.mint_coins(&data.receiver, &denom, data.amount)?;
+            Ok(denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+    use crate::application::ics20_fungible_token_transfer::denom::prefix_denom;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_recv_packet::on_recv_packet;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn recv_packet_processing() {
+        let mut ctx = MockContext::default();
+        let receiver = "receiver".parse().unwrap();
+        let escrow_address =
+            ctx.get_channel_escrow_address(&PortId::default(), &ChannelId::default());
+        let voucher = prefix_denom(&PortId::default(), &ChannelId::default(), "uatom");
+
+        let mut data = FungibleTokenPacketData {
+            amount: Amount::from(10),
+            denom: "uatom".to_string(),
+            receiver,
+            sender: "sender".parse().unwrap(),
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+
+        // Undecodable packet data yields an error acknowledgement.
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert!(!output.result.is_successful());
+
+        // Tokens from the sending chain are represented by vouchers.
+        packet.data = data.to_json_bytes();
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert_eq!(output.result, Acknowledgement::success());
+        assert_eq!(ctx.balance(&data.receiver, &voucher), Amount::from(10));
+
+        // Tokens returning to this chain must be held in escrow.
+        data.denom = voucher;
+        packet.data = data.to_json_bytes();
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert!(!output.result.is_successful());
+
+        ctx.mint_coins(&escrow_address, "uatom", Amount::from(10))
+            .unwrap();
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert_eq!(output.result, Acknowledgement::success());
+        assert_eq!(ctx.balance(&data.receiver, "uatom"), Amount::from(10));
+        assert_eq!(ctx.balance(&escrow_address, "uatom"), Amount::from(0));
+        assert!(!output.events.is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::error::Error;
+use crate::application::ics20_fungible_token_transfer::events::TransferTimeout;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::application::ics20_fungible_token_transfer::relay_application_logic::refund_packet_token;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes an ICS20 packet sent by the host chain which timed out before being received, by
+/// refunding its tokens to the sender.
+pub fn on_timeout_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics20Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let data = FungibleTokenPacketData::from_json_bytes(&packet.data)?;
+
+    refund_packet_token(ctx, packet, &data)?;
+
+    output.log(format!(
+        "success: refunded {}{} after timeout",
+        data.amount, data.denom
+    ));
+    output.emit(IbcEvent::TransferTimeout(TransferTimeout {
+        height: Default::default(),
+        refund_receiver: data.sender,
+        refund_denom: data.denom,
+        refund_amount: data.amount,
+    }));
+
+    Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::denom::prefix_denom;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::on_timeout_packet::on_timeout_packet;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn timeout_packet_processing() {
+        let mut ctx = MockContext::default();
+
+        // The sender transferred vouchers, which were burned and must be minted again.
+        let voucher = prefix_denom(&PortId::default(), &ChannelId::default(), "uatom");
+        let data = FungibleTokenPacketData {
+            amount: Amount::from(10),
+            denom: voucher.clone(),
+            receiver: "receiver".parse().unwrap(),
+            sender: "sender".parse().unwrap(),
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+
+        // The packet data cannot be decoded.
+        assert!(on_timeout_packet(&mut ctx, &packet).is_err());
+
+        packet.data = data.to_json_bytes();
+        let output = on_timeout_packet(&mut ctx, &packet).unwrap();
+        assert_eq!(ctx.balance(&data.sender, &voucher), Amount::from(10));
+        assert!(!output.events.is_empty());
+    }
+}
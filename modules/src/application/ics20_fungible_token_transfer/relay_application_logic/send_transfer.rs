@@ -1,13 +1,22 @@
 use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use crate::application::ics20_fungible_token_transfer::denom::sender_chain_is_source;
 use crate::application::ics20_fungible_token_transfer::error::{Error, Kind};
+use crate::application::ics20_fungible_token_transfer::events::Transfer;
 use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+use crate::events::IbcEvent;
 use crate::handler::HandlerOutput;
 use crate::ics04_channel::handler::send_packet::send_packet;
 use crate::ics04_channel::packet::Packet;
 use crate::ics04_channel::packet::PacketResult;
+use crate::ics05_port::capabilities::CapabilityName;
+use crate::ics05_port::context::ScopedCapabilityKeeper;
 
+/// Sends the tokens of `msg` in a packet over the channel of `msg`, presenting the capability
+/// which the ICS20 module holds in `scope` for that channel.
 pub(crate) fn send_transfer<Ctx>(
-    ctx: &Ctx,
+    ctx: &mut Ctx,
+    scope: &ScopedCapabilityKeeper,
     msg: MsgTransfer,
 ) -> Result<HandlerOutput<PacketResult>, Error>
 where
@@ -34,7 +43,14 @@ where
             Kind::SequenceSendNotFound(msg.source_port.clone(), msg.source_channel.clone())
         })?;
 
-    //TODO: Application LOGIC.
+    let token = msg.token.ok_or(Kind::EmptyToken)?;
+    let data = FungibleTokenPacketData {
+        amount: token.amount.parse()?,
+        denom: token.denom,
+        receiver: msg.receiver,
+        sender: msg.sender,
+    };
+    data.validate_basic()?;
 
     let packet = Packet {
         sequence,
@@ -42,14 +58,116 @@ where
         source_channel: msg.source_channel,
         destination_port,
         destination_channel: destination_channel.clone(),
-        data: vec![0],
+        data: data.to_json_bytes(),
         timeout_height: msg.timeout_height,
         timeout_timestamp: msg.timeout_timestamp,
     };
 
-    let handler_output =
-        send_packet(ctx, packet).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+    let channel_cap = scope
+        .get_capability(
+            ctx,
+            &CapabilityName::channel(&packet.source_port, &packet.source_channel),
+        )
+        .ok_or_else(|| {
+            Kind::ChannelCapabilityNotFound(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+            )
+        })?;
+
+    let mut handler_output = send_packet(ctx, &channel_cap, packet.clone())
+        .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+    // The packet is valid, so the tokens can now leave the sender's account. If the tokens
+    // originate from this chain, they are held in escrow until they return (or are refunded).
+    // Otherwise, the sender holds vouchers, which are burned.
+    if sender_chain_is_source(&packet.source_port, &packet.source_channel, &data.denom) {
+        let escrow_address =
+            ctx.get_channel_escrow_address(&packet.source_port, &packet.source_channel);
+        ctx.send_coins(&data.sender, &escrow_address, &data.denom, data.amount)?;
+    } else {
+        ctx.burn_coins(&data.sender, &data.denom, data.amount)?;
+    }
+
+    handler_output.log.push(format!(
+        "success: escrowed or burned {}{}",
+        data.amount, data.denom
+    ));
+    handler_output.events.push(IbcEvent::Transfer(Transfer {
+        height: Default::default(),
+        sender: data.sender,
+        receiver: data.receiver,
+        denom: data.denom,
+        amount: data.amount,
+    }));
 
-    //TODO:  add event/atributes and writes to the store issued by the application logic for packet sending.
     Ok(handler_output)
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+    use crate::application::ics20_fungible_token_transfer::denom::prefix_denom;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::send_transfer::send_transfer;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics20_fungible_token_transfer::MODULE_ID;
+    use crate::events::IbcEvent;
+    use crate::ics04_channel::packet::PacketResult;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+
+    #[test]
+    fn send_transfer_processing() {
+        let mut context = get_dummy_transfer_context();
+        let scope = context.scope_to_module(MODULE_ID).unwrap();
+        let msg = get_dummy_msg_transfer(1);
+        let sender = msg.sender.clone();
+        let escrow_address =
+            context.get_channel_escrow_address(&PortId::default(), &ChannelId::default());
+
+        // The sender does not hold any tokens.
+        assert!(send_transfer(&mut context.clone(), &scope, msg.clone()).is_err());
+
+        // The message does not specify a token.
+        let mut ctx = context.clone();
+        ctx.mint_coins(&sender, "uatom", Amount::from(10)).unwrap();
+        let mut msg_no_token = msg.clone();
+        msg_no_token.token = None;
+        assert!(send_transfer(&mut ctx, &scope, msg_no_token).is_err());
+
+        // Tokens native to the host chain are escrowed.
+        let output = send_transfer(&mut ctx, &scope, msg.clone()).unwrap();
+        assert_eq!(ctx.balance(&sender, "uatom"), Amount::from(0));
+        assert_eq!(ctx.balance(&escrow_address, "uatom"), Amount::from(10));
+        assert!(output
+            .events
+            .iter()
+            .any(|e| matches!(e, IbcEvent::Transfer(_))));
+        match output.result {
+            PacketResult::Send(res) => {
+                let data = FungibleTokenPacketData::from_json_bytes(&res.data).unwrap();
+                assert_eq!(data.denom, "uatom");
+                assert_eq!(data.amount, Amount::from(10));
+                assert_eq!(data.sender, sender);
+            }
+            _ => panic!("unexpected packet result"),
+        }
+
+        // Vouchers for tokens which arrived over the same channel are burned.
+        let voucher = prefix_denom(&PortId::default(), &ChannelId::default(), "uatom");
+        let mut ctx = context;
+        ctx.mint_coins(&sender, &voucher, Amount::from(10)).unwrap();
+        let mut msg_voucher = msg;
+        msg_voucher.token = Some(Coin {
+            denom: voucher.clone(),
+            amount: "10".to_string(),
+        });
+        send_transfer(&mut ctx, &scope, msg_voucher).unwrap();
+        assert_eq!(ctx.balance(&sender, &voucher), Amount::from(0));
+        assert_eq!(ctx.balance(&escrow_address, &voucher), Amount::from(0));
+    }
+}
@@ -0,0 +1,100 @@
+//! Derivation of the interchain account addresses and of the port identifiers of their owners.
+
+use sha2::{Digest, Sha256};
+
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::{
+    CONTROLLER_PORT_PREFIX, HOST_PORT_ID, MODULE_NAME,
+};
+use crate::ics24_host::identifier::{ConnectionId, PortId};
+use crate::signer::Signer;
+
+/// Length, in bytes, of the address of the interchain accounts module account, which is the root
+/// of all the interchain account addresses.
+const MODULE_ADDRESS_LEN: usize = 20;
+
+/// Returns the port which the controller module binds for the interchain accounts of `owner`.
+pub fn controller_port_id(owner: &Signer) -> Result<PortId, Error> {
+    if owner.as_str().trim().is_empty() {
+        return Err(Kind::EmptyOwner.into());
+    }
+
+    format!("{}{}", CONTROLLER_PORT_PREFIX, owner)
+        .parse()
+        .map_err(|e| Kind::IdentifierError.context(e).into())
+}
+
+/// Returns the port to which the host module is bound.
+pub fn host_port_id() -> PortId {
+    HOST_PORT_ID.parse().unwrap()
+}
+
+/// Returns whether `port_id` is one of the ports bound by the controller module.
+pub fn is_controller_port(port_id: &PortId) -> bool {
+    port_id.as_str().starts_with(CONTROLLER_PORT_PREFIX)
+}
+
+/// Derives the raw address of the interchain account which the controller port `port_id`
+/// controls over the host chain connection `connection_id`. As in ibc-go, the address is derived
+/// (see `address.Derive` in the Cosmos SDK) from the address of the module account, itself the
+/// truncated SHA-256 hash of the module name `MODULE_NAME`, as
+/// `sha256(sha256(module_address) || connection_id || port_id)`, so that every owner gets a
+/// distinct account over each connection, which nobody holds the keys to.
+pub fn derive_account_address(connection_id: &ConnectionId, port_id: &PortId) -> Vec<u8> {
+    let module_address = &Sha256::digest(MODULE_NAME.as_bytes())[..MODULE_ADDRESS_LEN];
+
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(module_address));
+    hasher.update(connection_id.as_bytes());
+    hasher.update(port_id.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::ics27_interchain_accounts::account::{
+        controller_port_id, derive_account_address, is_controller_port,
+    };
+    use subtle_encoding::hex;
+
+    use crate::ics24_host::identifier::{ConnectionId, PortId};
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    #[test]
+    fn controller_ports() {
+        let owner = Signer::new(get_dummy_bech32_account());
+        let port_id = controller_port_id(&owner).unwrap();
+        assert_eq!(
+            port_id.as_str(),
+            "icacontroller-cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+        );
+        assert!(is_controller_port(&port_id));
+        assert!(!is_controller_port(&PortId::default()));
+
+        assert!(controller_port_id(&Signer::new("")).is_err());
+        assert!(controller_port_id(&Signer::new("owner/with/slashes")).is_err());
+    }
+
+    #[test]
+    fn account_address_derivation() {
+        let port_id = controller_port_id(&Signer::new(get_dummy_bech32_account())).unwrap();
+        let other_port_id = controller_port_id(&Signer::new("other")).unwrap();
+
+        // The expected address is the one which `GenerateAddress` of ibc-go derives from the
+        // address of the interchain accounts module, `67d77474ca8e3a5812de323a28d7c6da6b3e4f29`.
+        let address = derive_account_address(&ConnectionId::new(0), &port_id);
+        assert_eq!(
+            hex::encode(&address),
+            b"0a16914b85f10ce8fecb9bea2aa825af8f0f5b064ee8d0b4364de5edbbbda012".to_vec()
+        );
+        assert_ne!(
+            address,
+            derive_account_address(&ConnectionId::new(1), &port_id)
+        );
+        assert_ne!(
+            address,
+            derive_account_address(&ConnectionId::new(0), &other_port_id)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use prost_types::Any;
+
+use crate::application::ics27_interchain_accounts::error::Error;
+use crate::ics04_channel::channel::State;
+use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::ics05_port::context::PortKeeper;
+use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::signer::Signer;
+
+/// Captures all the dependencies which the ICS27 controller and host modules require to be able
+/// to dispatch and process IBC messages. Both modules identify an interchain account by the
+/// connection on their side of the channel and by the controller port of its owner.
+pub trait Ics27Context: ChannelReader + ChannelKeeper + PortKeeper + Clone {
+    /// Returns the channel over which the interchain account of the controller port `port_id` is
+    /// reachable through connection `connection_id`, if any.
+    fn get_active_channel_id(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<ChannelId>;
+
+    /// Returns whether the active channel of the controller port `port_id` over connection
+    /// `connection_id` is open. That channel is bound to `local_port_id` on this chain, i.e., to
+    /// the controller port itself on the controller chain, and to the host port on the host
+    /// chain. Once the channel closed, e.g., after a packet timed out, the owner may open a new
+    /// one to reach the same interchain account.
+    fn is_active_channel_open(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+        local_port_id: &PortId,
+    ) -> bool {
+        self.get_active_channel_id(connection_id, port_id)
+            .and_then(|channel_id| self.channel_end(&(local_port_id.clone(), channel_id)))
+            .map_or(false, |channel_end| channel_end.state_matches(&State::Open))
+    }
+
+    fn store_active_channel_id(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(), Error>;
+
+    /// Returns the address of the interchain account of the controller port `port_id` over
+    /// connection `connection_id`, if it was registered.
+    fn get_interchain_account_address(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<Signer>;
+
+    fn store_interchain_account_address(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        address: Signer,
+    ) -> Result<(), Error>;
+}
+
+/// Captures the additional dependencies of the ICS27 host module, which creates interchain
+/// accounts and executes transactions on their behalf.
+pub trait Ics27HostContext: Ics27Context {
+    /// Formats the raw `address` of a new interchain account (see
+    /// `account::derive_account_address`) as an account address of the host chain.
+    fn format_account_address(&self, address: &[u8]) -> Signer;
+
+    /// Executes `msg` on behalf of the interchain account `account`, and returns the data of the
+    /// response. Implementations must check that `account` is the only signer of `msg`, and
+    /// should reject the message types which the host chain does not allow interchain accounts
+    /// to execute.
+    fn execute_msg(&mut self, account: &Signer, msg: &Any) -> Result<Vec<u8>, Error>;
+}
@@ -0,0 +1,4 @@
+//! The ICS27 controller module, through which account owners register interchain accounts on
+//! host chains and send them transactions to execute.
+pub mod handler;
+pub mod module;
@@ -0,0 +1,226 @@
+//! This module implements the processing logic for the messages of the ICS27 controller module.
+
+use std::time::Duration;
+
+use crate::application::ics27_interchain_accounts::account::{controller_port_id, host_port_id};
+use crate::application::ics27_interchain_accounts::context::Ics27Context;
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::metadata::Metadata;
+use crate::application::ics27_interchain_accounts::msgs::register_interchain_account::MsgRegisterInterchainAccount;
+use crate::application::ics27_interchain_accounts::msgs::send_tx::MsgSendTx;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+use crate::ics04_channel::handler::send_packet::send_packet;
+use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::ics04_channel::packet::{Packet, PacketResult};
+use crate::ics05_port::capabilities::CapabilityName;
+use crate::ics05_port::context::ScopedCapabilityKeeper;
+use crate::Height;
+
+/// Binds the controller port of the owner, unless it is already bound, and returns the
+/// `ChanOpenInit` message which opens an ordered channel to the host module over the connection
+/// of `msg`. The routing module processes this message like any other, so that the controller
+/// module validates the handshake in `on_chan_open_init`. The controller module claims the port
+/// in its `scope`.
+pub(crate) fn register_interchain_account<Ctx>(
+    ctx: &mut Ctx,
+    scope: &ScopedCapabilityKeeper,
+    msg: MsgRegisterInterchainAccount,
+) -> Result<MsgChannelOpenInit, Error>
+where
+    Ctx: Ics27Context,
+{
+    let port_id = controller_port_id(&msg.owner)?;
+
+    if ctx.lookup_module_by_port(&port_id).is_none() {
+        let port_cap = ctx
+            .bind_port(&port_id)
+            .map_err(|e| Kind::PortBindingFailed(port_id.clone()).context(e))?;
+
+        // The controller module owns the port alongside the IBC module.
+        scope
+            .claim_capability(ctx, CapabilityName::port(&port_id), port_cap)
+            .map_err(|e| Kind::PortBindingFailed(port_id.clone()).context(e))?;
+    }
+
+    let version = if msg.version.is_empty() {
+        let connection_end = ctx
+            .connection_end(&msg.connection_id)
+            .ok_or_else(|| Kind::ConnectionNotFound(msg.connection_id.clone()))?;
+        let host_connection_id = connection_end
+            .counterparty()
+            .connection_id()
+            .cloned()
+            .ok_or_else(|| Kind::CounterpartyConnectionNotFound(msg.connection_id.clone()))?;
+
+        Metadata::new(msg.connection_id.clone(), host_connection_id).to_string()
+    } else {
+        msg.version
+    };
+
+    let channel_end = ChannelEnd::new(
+        State::Init,
+        Order::Ordered,
+        Counterparty::new(host_port_id(), None),
+        vec![msg.connection_id],
+        version,
+    );
+
+    Ok(MsgChannelOpenInit::new(port_id, channel_end, msg.owner))
+}
+
+/// Sends the transaction of `msg` over the active channel of the interchain account, in a packet
+/// which times out `msg.relative_timeout` nanoseconds after the current block timestamp. The
+/// controller module presents the capability which it holds in `scope` for that channel.
+pub(crate) fn send_tx<Ctx>(
+    ctx: &mut Ctx,
+    scope: &ScopedCapabilityKeeper,
+    msg: MsgSendTx,
+) -> Result<HandlerOutput<PacketResult>, Error>
+where
+    Ctx: Ics27Context,
+{
+    let port_id = controller_port_id(&msg.owner)?;
+
+    let channel_id = ctx
+        .get_active_channel_id(&msg.connection_id, &port_id)
+        .ok_or_else(|| Kind::ActiveChannelNotFound(msg.connection_id.clone(), port_id.clone()))?;
+
+    let channel_end = ctx
+        .channel_end(&(port_id.clone(), channel_id.clone()))
+        .ok_or_else(|| Kind::ChannelNotFound(port_id.clone(), channel_id.clone()))?;
+
+    let destination_port = channel_end.counterparty().port_id().clone();
+    let destination_channel = channel_end
+        .counterparty()
+        .channel_id()
+        .cloned()
+        .ok_or_else(|| Kind::DestinationChannelNotFound(port_id.clone(), channel_id.clone()))?;
+
+    let sequence = ctx
+        .get_next_sequence_send(&(port_id.clone(), channel_id.clone()))
+        .ok_or_else(|| Kind::SequenceSendNotFound(port_id.clone(), channel_id.clone()))?;
+
+    let timeout_timestamp = (ctx.host_timestamp() + Duration::from_nanos(msg.relative_timeout))
+        .map_err(|e| Kind::TimeoutOverflow.context(e))?;
+
+    let packet = Packet {
+        sequence,
+        source_port: port_id,
+        source_channel: channel_id,
+        destination_port,
+        destination_channel,
+        data: msg.packet_data.to_json_bytes(),
+        timeout_height: Height::zero(),
+        timeout_timestamp,
+    };
+
+    let channel_cap = scope
+        .get_capability(
+            ctx,
+            &CapabilityName::channel(&packet.source_port, &packet.source_channel),
+        )
+        .ok_or_else(|| {
+            Kind::ChannelCapabilityNotFound(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+            )
+        })?;
+
+    let mut handler_output =
+        send_packet(ctx, &channel_cap, packet).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+    handler_output.log.push(format!(
+        "success: sent a transaction to the interchain account of {} over connection {}",
+        msg.owner, msg.connection_id
+    ));
+
+    Ok(handler_output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics27_interchain_accounts::account::{
+        controller_port_id, host_port_id,
+    };
+    use crate::application::ics27_interchain_accounts::controller::handler::{
+        register_interchain_account, send_tx,
+    };
+    use crate::application::ics27_interchain_accounts::controller::module::test_util::get_dummy_controller_context;
+    use crate::application::ics27_interchain_accounts::metadata::Metadata;
+    use crate::application::ics27_interchain_accounts::msgs::register_interchain_account::test_util::get_dummy_raw_msg_register_interchain_account;
+    use crate::application::ics27_interchain_accounts::msgs::register_interchain_account::MsgRegisterInterchainAccount;
+    use crate::application::ics27_interchain_accounts::msgs::send_tx::test_util::get_dummy_raw_msg_send_tx;
+    use crate::application::ics27_interchain_accounts::msgs::send_tx::MsgSendTx;
+    use crate::application::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+    use crate::application::ics27_interchain_accounts::CONTROLLER_MODULE_ID;
+    use crate::ics04_channel::channel::Order;
+    use crate::ics04_channel::packet::PacketResult;
+    use crate::ics05_port::capabilities::CapabilityName;
+    use crate::ics05_port::context::PortReader;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId};
+    use crate::signer::Signer;
+    use crate::timestamp::Timestamp;
+
+    #[test]
+    fn register_interchain_account_processing() {
+        let msg =
+            MsgRegisterInterchainAccount::try_from(get_dummy_raw_msg_register_interchain_account())
+                .unwrap();
+        let port_id = controller_port_id(&msg.owner).unwrap();
+
+        let mut ctx = get_dummy_transfer_context();
+        let scope = ctx.scope_to_module(CONTROLLER_MODULE_ID).unwrap();
+        let chan_msg = register_interchain_account(&mut ctx, &scope, msg.clone()).unwrap();
+        assert!(ctx.lookup_module_by_port(&port_id).is_some());
+        assert_eq!(
+            scope.get_capability(&ctx, &CapabilityName::port(&port_id)),
+            ctx.lookup_module_by_port(&port_id)
+        );
+        assert_eq!(chan_msg.port_id, port_id);
+        assert_eq!(chan_msg.channel.ordering(), &Order::Ordered);
+        assert_eq!(chan_msg.channel.counterparty().port_id(), &host_port_id());
+        assert_eq!(
+            chan_msg.channel.version(),
+            Metadata::new(ConnectionId::default(), ConnectionId::default()).to_string()
+        );
+
+        // The controller port of the owner is bound already.
+        assert!(register_interchain_account(&mut ctx, &scope, msg.clone()).is_ok());
+
+        let unknown_connection = MsgRegisterInterchainAccount {
+            connection_id: ConnectionId::new(7),
+            ..msg
+        };
+        assert!(register_interchain_account(&mut ctx, &scope, unknown_connection).is_err());
+    }
+
+    #[test]
+    fn send_tx_processing() {
+        let account = Signer::new("cosmos1interchainaccount");
+        let msg = MsgSendTx::try_from(get_dummy_raw_msg_send_tx(&account)).unwrap();
+
+        // The owner did not register an interchain account.
+        let mut ctx = get_dummy_transfer_context();
+        let scope = ctx.scope_to_module(CONTROLLER_MODULE_ID).unwrap();
+        assert!(send_tx(&mut ctx, &scope, msg.clone()).is_err());
+
+        let mut ctx = get_dummy_controller_context().with_timestamp(Timestamp::from_nanoseconds(1));
+        let output = send_tx(&mut ctx, &scope, msg.clone()).unwrap();
+        match output.result {
+            PacketResult::Send(res) => {
+                assert_eq!(res.channel_id, ChannelId::new(1));
+                assert_eq!(
+                    res.timeout_timestamp,
+                    Timestamp::from_nanoseconds(1 + msg.relative_timeout)
+                );
+                let data = InterchainAccountPacketData::from_json_bytes(&res.data).unwrap();
+                assert_eq!(data, msg.packet_data);
+            }
+            _ => panic!("unexpected packet result"),
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! The ICS27 controller application module, which plugs the controller logic into the ICS26
+//! routing module. It owns all the ports prefixed with `CONTROLLER_PORT_PREFIX`.
+
+use prost_types::Any;
+use tendermint_proto::Protobuf;
+
+use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+use crate::application::ics27_interchain_accounts::account::{host_port_id, is_controller_port};
+use crate::application::ics27_interchain_accounts::context::Ics27Context;
+use crate::application::ics27_interchain_accounts::controller::handler::{
+    register_interchain_account, send_tx,
+};
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::metadata::{connection_pair, Metadata};
+use crate::application::ics27_interchain_accounts::msgs::{
+    register_interchain_account as register_msg, send_tx as send_tx_msg, InterchainAccountMsg,
+};
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::{ChannelEnd, Order};
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::context::ScopedCapabilityKeeper;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::{Error as Ics26Error, Kind as Ics26Kind};
+use crate::ics26_routing::module::{Module, MsgResult};
+use crate::signer::Signer;
+
+/// The ICS27 controller application. Opens ordered channels to the host module, at most one open
+/// channel per owner and connection, and records the address of the interchain account once the
+/// host acknowledged the channel.
+///
+/// The controller module also processes the messages of the owners, which register interchain
+/// accounts and send transactions to them.
+#[derive(Clone, Debug)]
+pub struct Ics27ControllerModule {
+    scope: ScopedCapabilityKeeper,
+}
+
+impl Ics27ControllerModule {
+    /// Creates the controller module, which holds the capabilities of the controller ports and
+    /// of their channels in `scope`, the scope of `CONTROLLER_MODULE_ID`.
+    pub fn new(scope: ScopedCapabilityKeeper) -> Self {
+        Self { scope }
+    }
+}
+
+impl<Ctx> Module<Ctx> for Ics27ControllerModule
+where
+    Ctx: Ics27Context,
+{
+    fn on_chan_open_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        validate_channel_open_init(ctx, port_id, channel_end)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        claim_channel_capability(&self.scope, ctx, port_id, channel_id, channel_cap)
+    }
+
+    fn on_chan_open_try(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _channel_cap: &Capability,
+        _channel_end: &ChannelEnd,
+        _counterparty_version: &str,
+    ) -> Result<String, Ics26Error> {
+        Err(Ics26Kind::ModuleRaisedError
+            .context(Kind::ChannelOpenNotAllowed(
+                "controller".to_string(),
+                port_id.clone(),
+            ))
+            .into())
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &str,
+    ) -> Result<(), Ics26Error> {
+        register_account(ctx, port_id, channel_id, counterparty_version)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+    }
+
+    fn on_chan_close_init(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        Err(Ics26Kind::ModuleRaisedError
+            .context(Kind::ChannelClosingNotAllowed(
+                port_id.clone(),
+                channel_id.clone(),
+            ))
+            .into())
+    }
+
+    fn on_recv_packet(
+        &self,
+        _ctx: &mut Ctx,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        let ack = Acknowledgement::from_error(Kind::PacketReceiveNotAllowed);
+
+        HandlerOutput::builder().with_result(Some(ack.to_json_bytes()))
+    }
+
+    fn on_msg(&self, ctx: &mut Ctx, msg: &Any) -> Result<HandlerOutput<MsgResult>, Ics26Error> {
+        match decode_msg(msg)? {
+            // Registering an interchain account opens a channel to the host chain, whose first
+            // step is processed like any `ChanOpenInit` message, on behalf of the owner.
+            InterchainAccountMsg::RegisterInterchainAccount(msg) => {
+                let chan_msg = register_interchain_account(ctx, &self.scope, msg)
+                    .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+                Ok(HandlerOutput::builder().with_result(MsgResult::ChannelOpenInit(chan_msg)))
+            }
+            InterchainAccountMsg::SendTx(msg) => {
+                let output = send_tx(ctx, &self.scope, msg)
+                    .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+                Ok(HandlerOutput::builder()
+                    .with_log(output.log)
+                    .with_events(output.events)
+                    .with_result(MsgResult::SendPacket(output.result)))
+            }
+        }
+    }
+}
+
+fn decode_msg(msg: &Any) -> Result<InterchainAccountMsg, Ics26Error> {
+    match msg.type_url.as_str() {
+        register_msg::TYPE_URL => Ok(InterchainAccountMsg::RegisterInterchainAccount(
+            register_msg::MsgRegisterInterchainAccount::decode_vec(&msg.value)
+                .map_err(|e| Ics26Kind::MalformedMessageBytes.context(e))?,
+        )),
+        send_tx_msg::TYPE_URL => Ok(InterchainAccountMsg::SendTx(
+            send_tx_msg::MsgSendTx::decode_vec(&msg.value)
+                .map_err(|e| Ics26Kind::MalformedMessageBytes.context(e))?,
+        )),
+        _ => Err(Ics26Kind::UnknownMessageTypeUrl(msg.type_url.clone()).into()),
+    }
+}
+
+/// Claims the capability `channel_cap` of the channel end identified by `port_id` and
+/// `channel_id`, in the scope of the controller module.
+fn claim_channel_capability<Ctx: Ics27Context>(
+    scope: &ScopedCapabilityKeeper,
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    channel_cap: &Capability,
+) -> Result<(), Ics26Error> {
+    scope
+        .claim_capability(
+            ctx,
+            CapabilityName::channel(port_id, channel_id),
+            channel_cap.clone(),
+        )
+        .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+}
+
+fn validate_channel_open_init<Ctx>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_end: &ChannelEnd,
+) -> Result<(), Error>
+where
+    Ctx: Ics27Context,
+{
+    if !is_controller_port(port_id) {
+        return Err(Kind::InvalidControllerPort(port_id.clone()).into());
+    }
+    if channel_end.ordering() != &Order::Ordered {
+        return Err(Kind::InvalidChannelOrder(Order::Ordered, *channel_end.ordering()).into());
+    }
+    if channel_end.counterparty().port_id() != &host_port_id() {
+        return Err(Kind::InvalidHostPort(channel_end.counterparty().port_id().clone()).into());
+    }
+
+    let (connection_id, host_connection_id) = connection_pair(ctx, channel_end)?;
+    let metadata: Metadata = channel_end.version().parse()?;
+    metadata.validate(&connection_id, &host_connection_id)?;
+
+    // A new channel may only be opened once the previous one, if any, closed after a timeout.
+    if ctx.is_active_channel_open(&connection_id, port_id, port_id) {
+        return Err(Kind::ActiveChannelAlreadySet(connection_id, port_id.clone()).into());
+    }
+
+    Ok(())
+}
+
+/// Records the interchain account address, which the host module chose, along with the channel
+/// over which the account is now reachable.
+fn register_account<Ctx>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty_version: &str,
+) -> Result<(), Error>
+where
+    Ctx: Ics27Context,
+{
+    let channel_end = ctx
+        .channel_end(&(port_id.clone(), channel_id.clone()))
+        .ok_or_else(|| Kind::ChannelNotFound(port_id.clone(), channel_id.clone()))?;
+
+    let (connection_id, host_connection_id) = connection_pair(ctx, &channel_end)?;
+    let metadata: Metadata = counterparty_version.parse()?;
+    metadata.validate(&connection_id, &host_connection_id)?;
+
+    if metadata.address.trim().is_empty() {
+        return Err(Kind::EmptyAccountAddress.into());
+    }
+
+    ctx.store_active_channel_id(connection_id.clone(), port_id.clone(), channel_id.clone())?;
+    ctx.store_interchain_account_address(connection_id, port_id.clone(), metadata.address.into())
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics27_interchain_accounts::account::{
+        controller_port_id, host_port_id,
+    };
+    use crate::application::ics27_interchain_accounts::context::Ics27Context;
+    use crate::application::ics27_interchain_accounts::metadata::Metadata;
+    use crate::application::ics27_interchain_accounts::CONTROLLER_MODULE_ID;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics05_port::capabilities::ModuleId;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId};
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns the channel end, in state `state`, which the controller port of the dummy owner
+    /// opens to the host module over the default connection.
+    pub fn get_dummy_controller_channel_end(state: State, address: &str) -> ChannelEnd {
+        let metadata = Metadata {
+            address: address.to_string(),
+            ..Metadata::new(ConnectionId::default(), ConnectionId::default())
+        };
+
+        ChannelEnd::new(
+            state,
+            Order::Ordered,
+            Counterparty::new(host_port_id(), Some(ChannelId::new(1))),
+            vec![ConnectionId::default()],
+            metadata.to_string(),
+        )
+    }
+
+    /// Returns a context in which the dummy owner reaches its interchain account on the host
+    /// chain over the open channel `channel-1` of its controller port.
+    pub fn get_dummy_controller_context() -> MockContext {
+        let port_id = controller_port_id(&Signer::new(get_dummy_bech32_account())).unwrap();
+        let account = Signer::new("cosmos1interchainaccount");
+
+        let mut ctx = get_dummy_transfer_context()
+            .with_port_capability(port_id.clone())
+            .with_channel(
+                port_id.clone(),
+                ChannelId::new(1),
+                get_dummy_controller_channel_end(State::Open, account.as_str()),
+            )
+            .with_channel_capability_claimed_by(
+                ModuleId::new(CONTROLLER_MODULE_ID),
+                port_id.clone(),
+                ChannelId::new(1),
+            )
+            .with_send_sequence(port_id.clone(), ChannelId::new(1), 1.into());
+        ctx.store_active_channel_id(ConnectionId::default(), port_id.clone(), ChannelId::new(1))
+            .unwrap();
+        ctx.store_interchain_account_address(ConnectionId::default(), port_id, account)
+            .unwrap();
+        ctx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics27_interchain_accounts::account::controller_port_id;
+    use crate::application::ics27_interchain_accounts::context::Ics27Context;
+    use crate::application::ics27_interchain_accounts::controller::module::test_util::{
+        get_dummy_controller_channel_end, get_dummy_controller_context,
+    };
+    use crate::application::ics27_interchain_accounts::controller::module::Ics27ControllerModule;
+    use crate::application::ics27_interchain_accounts::CONTROLLER_MODULE_ID;
+    use crate::ics04_channel::channel::{Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelKeeper;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::ics26_routing::module::Module;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    #[test]
+    fn channel_handshake_callbacks() {
+        let mut ctx = get_dummy_transfer_context();
+        let module = Ics27ControllerModule::new(ctx.scope_to_module(CONTROLLER_MODULE_ID).unwrap());
+        let port_id = controller_port_id(&Signer::new(get_dummy_bech32_account())).unwrap();
+        let channel_id = ChannelId::new(1);
+        let channel_end = get_dummy_controller_channel_end(State::Init, "");
+        let channel_cap = ctx
+            .new_channel_capability((port_id.clone(), channel_id.clone()))
+            .unwrap();
+
+        assert!(module
+            .on_chan_open_init(&mut ctx, &port_id, &channel_id, &channel_cap, &channel_end)
+            .is_ok());
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &PortId::default(),
+                &channel_id,
+                &channel_cap,
+                &channel_end
+            )
+            .is_err());
+
+        let mut unordered = channel_end.clone();
+        unordered.ordering = Order::Unordered;
+        assert!(module
+            .on_chan_open_init(&mut ctx, &port_id, &channel_id, &channel_cap, &unordered)
+            .is_err());
+
+        let mut not_to_host = channel_end.clone();
+        not_to_host.remote = Counterparty::new(PortId::default(), None);
+        assert!(module
+            .on_chan_open_init(&mut ctx, &port_id, &channel_id, &channel_cap, &not_to_host)
+            .is_err());
+
+        let mut bad_version = channel_end.clone();
+        bad_version.set_version("ics27-1".to_string());
+        assert!(module
+            .on_chan_open_init(&mut ctx, &port_id, &channel_id, &channel_cap, &bad_version)
+            .is_err());
+
+        // The controller never continues a handshake which the counterparty initiated.
+        assert!(module
+            .on_chan_open_try(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end,
+                &channel_end.version()
+            )
+            .is_err());
+
+        // The host must provide the address of the interchain account.
+        let mut ctx = ctx.with_channel(port_id.clone(), channel_id.clone(), channel_end.clone());
+        assert!(module
+            .on_chan_open_ack(&mut ctx, &port_id, &channel_id, &channel_end.version())
+            .is_err());
+        let host_version =
+            get_dummy_controller_channel_end(State::TryOpen, "cosmos1account").version();
+        assert!(module
+            .on_chan_open_ack(&mut ctx, &port_id, &channel_id, &host_version)
+            .is_ok());
+        assert_eq!(
+            ctx.get_interchain_account_address(&ConnectionId::default(), &port_id),
+            Some(Signer::new("cosmos1account"))
+        );
+        assert_eq!(
+            ctx.get_active_channel_id(&ConnectionId::default(), &port_id),
+            Some(channel_id.clone())
+        );
+
+        assert!(module
+            .on_chan_close_init(&mut ctx, &port_id, &channel_id)
+            .is_err());
+
+        // No other channel can be opened while the active channel is open.
+        let mut ctx = get_dummy_controller_context();
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &ChannelId::new(2),
+                &channel_cap,
+                &channel_end
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn packets_are_not_received() {
+        let mut ctx = get_dummy_controller_context();
+        let packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+        let module = Ics27ControllerModule::new(ctx.scope_to_module(CONTROLLER_MODULE_ID).unwrap());
+        let output =
+            module.on_recv_packet(&mut ctx, &packet, &Signer::new(get_dummy_bech32_account()));
+
+        let ack = Acknowledgement::from_json_bytes(&output.result.unwrap()).unwrap();
+        assert!(!ack.is_successful());
+    }
+}
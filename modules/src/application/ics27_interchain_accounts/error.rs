@@ -0,0 +1,124 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::ics04_channel::channel::Order;
+use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::signer::Signer;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum Kind {
+    #[error("error raised by message handler")]
+    HandlerRaisedError,
+
+    #[error("identifier error")]
+    IdentifierError,
+
+    #[error("the interchain account owner cannot be empty")]
+    EmptyOwner,
+
+    #[error("port {0} is not an interchain accounts controller port")]
+    InvalidControllerPort(PortId),
+
+    #[error("failed to bind the controller port {0}")]
+    PortBindingFailed(PortId),
+
+    #[error("port {0} is not the interchain accounts host port")]
+    InvalidHostPort(PortId),
+
+    #[error("expected {0} channel, got {1}")]
+    InvalidChannelOrder(Order, Order),
+
+    #[error("invalid interchain accounts version metadata {0}")]
+    InvalidMetadata(String),
+
+    #[error("expected version {0}, got {1}")]
+    InvalidVersion(String, String),
+
+    #[error("unsupported encoding {0}")]
+    UnsupportedEncoding(String),
+
+    #[error("unsupported transaction type {0}")]
+    UnsupportedTxType(String),
+
+    #[error("expected connection {0} in the version metadata, got {1}")]
+    ConnectionMismatch(ConnectionId, ConnectionId),
+
+    #[error("the counterparty did not provide the interchain account address")]
+    EmptyAccountAddress,
+
+    #[error("the channel of an interchain account must have a single connection hop")]
+    InvalidConnectionHops,
+
+    #[error("Missing connection {0}")]
+    ConnectionNotFound(ConnectionId),
+
+    #[error("connection {0} has no counterparty connection identifier")]
+    CounterpartyConnectionNotFound(ConnectionId),
+
+    #[error("Missing channel for port_id {0} and channel_id {1} ")]
+    ChannelNotFound(PortId, ChannelId),
+
+    #[error("an active channel is already open for port {1} on connection {0}")]
+    ActiveChannelAlreadySet(ConnectionId, PortId),
+
+    #[error("no active channel for port {1} on connection {0}")]
+    ActiveChannelNotFound(ConnectionId, PortId),
+
+    #[error(
+        "Destination channel not found in the counterparty of port_id {0} and channel_id {1} "
+    )]
+    DestinationChannelNotFound(PortId, ChannelId),
+
+    #[error("Sending sequence number not found for port {0} and channel {1}")]
+    SequenceSendNotFound(PortId, ChannelId),
+
+    #[error("the interchain accounts module holds no capability for port {0} and channel {1}")]
+    ChannelCapabilityNotFound(PortId, ChannelId),
+
+    #[error("the interchain accounts {0} module does not open channels on port {1}")]
+    ChannelOpenNotAllowed(String, PortId),
+
+    #[error("channel {1} on port {0} cannot be closed by users")]
+    ChannelClosingNotAllowed(PortId, ChannelId),
+
+    #[error("the interchain accounts controller module does not receive packets")]
+    PacketReceiveNotAllowed,
+
+    #[error("invalid interchain account packet data")]
+    InvalidPacketData,
+
+    #[error("the interchain account packet data cannot be empty")]
+    EmptyPacketData,
+
+    #[error("unsupported interchain account packet type {0}")]
+    UnsupportedPacketType(i32),
+
+    #[error("invalid transaction in the interchain account packet data")]
+    InvalidCosmosTx,
+
+    #[error("the relative timeout must be non-zero")]
+    ZeroTimeout,
+
+    #[error("the timeout overflows the timestamp of the host chain")]
+    TimeoutOverflow,
+
+    #[error("no interchain account for port {1} on connection {0}")]
+    InterchainAccountNotFound(ConnectionId, PortId),
+
+    #[error("the interchain account {0} is not the signer of the message")]
+    UnauthorizedSigner(Signer),
+
+    #[error("the host chain does not allow the execution of messages of type {0}")]
+    MessageNotAllowed(String),
+
+    #[error("the execution of a message of type {0} failed")]
+    MessageExecutionFailed(String),
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
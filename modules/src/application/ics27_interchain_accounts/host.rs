@@ -0,0 +1,4 @@
+//! The ICS27 host module, which creates the interchain accounts of the owners on controller
+//! chains, and executes the transactions they send.
+pub mod module;
+pub mod relay_application_logic;
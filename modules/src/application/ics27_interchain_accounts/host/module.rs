@@ -0,0 +1,412 @@
+//! The ICS27 host application module, which plugs the host logic into the ICS26 routing module.
+//! It is bound to the port `HOST_PORT_ID`.
+
+use crate::application::ics27_interchain_accounts::account::{
+    derive_account_address, host_port_id, is_controller_port,
+};
+use crate::application::ics27_interchain_accounts::context::Ics27HostContext;
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::host::relay_application_logic::on_recv_packet;
+use crate::application::ics27_interchain_accounts::metadata::{connection_pair, Metadata};
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::{ChannelEnd, Order};
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::context::ScopedCapabilityKeeper;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::{Error as Ics26Error, Kind as Ics26Kind};
+use crate::ics26_routing::module::Module;
+use crate::signer::Signer;
+
+/// The ICS27 host application. Accepts the ordered channels which controllers open, picks the
+/// interchain account of the owner when the handshake is tried, and executes the transactions
+/// which the owner sends over the channel.
+#[derive(Clone, Debug)]
+pub struct Ics27HostModule {
+    scope: ScopedCapabilityKeeper,
+}
+
+impl Ics27HostModule {
+    /// Creates the host module, which holds the capabilities of its channels in `scope`, the
+    /// scope of `HOST_MODULE_ID`.
+    pub fn new(scope: ScopedCapabilityKeeper) -> Self {
+        Self { scope }
+    }
+}
+
+impl<Ctx> Module<Ctx> for Ics27HostModule
+where
+    Ctx: Ics27HostContext,
+{
+    fn on_chan_open_init(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _channel_cap: &Capability,
+        _channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        Err(Ics26Kind::ModuleRaisedError
+            .context(Kind::ChannelOpenNotAllowed(
+                "host".to_string(),
+                port_id.clone(),
+            ))
+            .into())
+    }
+
+    fn on_chan_open_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+    ) -> Result<String, Ics26Error> {
+        let metadata = register_account(ctx, port_id, channel_end, counterparty_version)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        claim_channel_capability(&self.scope, ctx, port_id, channel_id, channel_cap)?;
+        Ok(metadata.to_string())
+    }
+
+    fn on_chan_open_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        activate_channel(ctx, port_id, channel_id)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+    }
+
+    fn on_chan_close_init(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        Err(Ics26Kind::ModuleRaisedError
+            .context(Kind::ChannelClosingNotAllowed(
+                port_id.clone(),
+                channel_id.clone(),
+            ))
+            .into())
+    }
+
+    fn on_recv_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        let ack = on_recv_packet(ctx, packet);
+
+        HandlerOutput::builder().with_result(Some(ack.to_json_bytes()))
+    }
+}
+
+/// Claims the capability `channel_cap` of the channel end identified by `port_id` and
+/// `channel_id`, in the scope of the host module.
+fn claim_channel_capability<Ctx: Ics27HostContext>(
+    scope: &ScopedCapabilityKeeper,
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    channel_cap: &Capability,
+) -> Result<(), Ics26Error> {
+    scope
+        .claim_capability(
+            ctx,
+            CapabilityName::channel(port_id, channel_id),
+            channel_cap.clone(),
+        )
+        .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+}
+
+/// Validates the channel which the controller proposes, and returns the version metadata of the
+/// host, which carries the address of the interchain account of the owner. The account is
+/// created upon the first channel of the owner, and reached again by any later channel.
+fn register_account<Ctx>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_end: &ChannelEnd,
+    counterparty_version: &str,
+) -> Result<Metadata, Error>
+where
+    Ctx: Ics27HostContext,
+{
+    if port_id != &host_port_id() {
+        return Err(Kind::InvalidHostPort(port_id.clone()).into());
+    }
+    if channel_end.ordering() != &Order::Ordered {
+        return Err(Kind::InvalidChannelOrder(Order::Ordered, *channel_end.ordering()).into());
+    }
+
+    let controller_port_id = channel_end.counterparty().port_id();
+    if !is_controller_port(controller_port_id) {
+        return Err(Kind::InvalidControllerPort(controller_port_id.clone()).into());
+    }
+
+    let (connection_id, controller_connection_id) = connection_pair(ctx, channel_end)?;
+    let mut metadata: Metadata = counterparty_version.parse()?;
+    metadata.validate(&controller_connection_id, &connection_id)?;
+
+    if ctx.is_active_channel_open(&connection_id, controller_port_id, port_id) {
+        return Err(
+            Kind::ActiveChannelAlreadySet(connection_id, controller_port_id.clone()).into(),
+        );
+    }
+
+    let address = match ctx.get_interchain_account_address(&connection_id, controller_port_id) {
+        Some(address) => address,
+        None => {
+            let raw_address = derive_account_address(&connection_id, controller_port_id);
+            let address = ctx.format_account_address(&raw_address);
+            ctx.store_interchain_account_address(
+                connection_id,
+                controller_port_id.clone(),
+                address.clone(),
+            )?;
+            address
+        }
+    };
+
+    metadata.address = address.to_string();
+    Ok(metadata)
+}
+
+/// Records the channel identified by `port_id` and `channel_id`, now open, as the one over which
+/// the owner reaches its interchain account.
+fn activate_channel<Ctx>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<(), Error>
+where
+    Ctx: Ics27HostContext,
+{
+    let channel_end = ctx
+        .channel_end(&(port_id.clone(), channel_id.clone()))
+        .ok_or_else(|| Kind::ChannelNotFound(port_id.clone(), channel_id.clone()))?;
+    let connection_id = channel_end
+        .connection_hops()
+        .first()
+        .cloned()
+        .ok_or(Kind::InvalidConnectionHops)?;
+
+    ctx.store_active_channel_id(
+        connection_id,
+        channel_end.counterparty().port_id().clone(),
+        channel_id.clone(),
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::context::BankKeeper;
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics20_fungible_token_transfer::MODULE_ID;
+    use crate::application::ics27_interchain_accounts::account::{
+        controller_port_id, host_port_id,
+    };
+    use crate::application::ics27_interchain_accounts::context::Ics27Context;
+    use crate::application::ics27_interchain_accounts::metadata::Metadata;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns the channel end of the host module, in state `state`, for the channel which the
+    /// controller port `port_id` opened over the default connection.
+    pub fn get_dummy_host_channel_end(state: State, port_id: PortId) -> ChannelEnd {
+        ChannelEnd::new(
+            state,
+            Order::Ordered,
+            Counterparty::new(port_id, Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Metadata::new(ConnectionId::default(), ConnectionId::default()).to_string(),
+        )
+    }
+
+    /// Returns a context in which the dummy owner reaches its interchain account, holding 100
+    /// `uatom`, over channel `channel-1` of the host port. The context also has an open ICS20
+    /// channel on the default port and channel identifiers. Returns the controller port of the
+    /// owner and the interchain account along with the context.
+    pub fn get_dummy_host_context() -> (MockContext, PortId, Signer) {
+        let port_id = controller_port_id(&Signer::new(get_dummy_bech32_account())).unwrap();
+        let account = Signer::new("cosmos1interchainaccount");
+
+        let mut ctx = get_dummy_transfer_context()
+            .with_port_capability(host_port_id())
+            .with_channel(
+                host_port_id(),
+                ChannelId::new(1),
+                get_dummy_host_channel_end(State::Open, port_id.clone()),
+            );
+        ctx.store_active_channel_id(ConnectionId::default(), port_id.clone(), ChannelId::new(1))
+            .unwrap();
+        ctx.store_interchain_account_address(
+            ConnectionId::default(),
+            port_id.clone(),
+            account.clone(),
+        )
+        .unwrap();
+        ctx.mint_coins(&account, "uatom", Amount::from(100))
+            .unwrap();
+
+        // The interchain account transfers tokens through the ICS20 module.
+        let scope = ctx.scope_to_module(MODULE_ID).unwrap();
+        ctx.add_msg_route(transfer::TYPE_URL, Ics20Module::new(scope))
+            .unwrap();
+
+        (ctx, port_id, account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics27_interchain_accounts::account::{
+        controller_port_id, host_port_id,
+    };
+    use crate::application::ics27_interchain_accounts::context::Ics27Context;
+    use crate::application::ics27_interchain_accounts::host::module::test_util::get_dummy_host_channel_end;
+    use crate::application::ics27_interchain_accounts::host::module::Ics27HostModule;
+    use crate::application::ics27_interchain_accounts::metadata::Metadata;
+    use crate::application::ics27_interchain_accounts::HOST_MODULE_ID;
+    use crate::ics04_channel::channel::{Order, State};
+    use crate::ics04_channel::context::ChannelKeeper;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::ics26_routing::module::Module;
+    use crate::signer::Signer;
+
+    #[test]
+    fn channel_handshake_callbacks() {
+        let mut ctx = get_dummy_transfer_context();
+        let module = Ics27HostModule::new(ctx.scope_to_module(HOST_MODULE_ID).unwrap());
+        let host_port = host_port_id();
+        let channel_id = ChannelId::new(1);
+        let owner_port = controller_port_id(&Signer::new("owner")).unwrap();
+        let channel_end = get_dummy_host_channel_end(State::TryOpen, owner_port.clone());
+        let proposed = Metadata::new(ConnectionId::default(), ConnectionId::default()).to_string();
+        let channel_cap = ctx
+            .new_channel_capability((host_port.clone(), channel_id.clone()))
+            .unwrap();
+        let other_cap = ctx
+            .new_channel_capability((host_port.clone(), ChannelId::new(2)))
+            .unwrap();
+
+        // The host never initiates the handshake.
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &host_port,
+                &channel_id,
+                &channel_cap,
+                &channel_end
+            )
+            .is_err());
+
+        // The channel must be ordered and bound to the host port.
+        let mut unordered = channel_end.clone();
+        unordered.ordering = Order::Unordered;
+        assert!(module
+            .on_chan_open_try(
+                &mut ctx,
+                &host_port,
+                &channel_id,
+                &channel_cap,
+                &unordered,
+                &proposed
+            )
+            .is_err());
+        assert!(module
+            .on_chan_open_try(
+                &mut ctx,
+                &PortId::default(),
+                &channel_id,
+                &channel_end,
+                &proposed
+            )
+            .is_err());
+        assert!(module
+            .on_chan_open_try(
+                &mut ctx,
+                &host_port,
+                &channel_id,
+                &channel_cap,
+                &channel_end,
+                "ics27-1"
+            )
+            .is_err());
+
+        // The host picks the address of the interchain account.
+        let version = module
+            .on_chan_open_try(
+                &mut ctx,
+                &host_port,
+                &channel_id,
+                &channel_cap,
+                &channel_end,
+                &proposed,
+            )
+            .unwrap();
+        let metadata: Metadata = version.parse().unwrap();
+        let account = ctx
+            .get_interchain_account_address(&ConnectionId::default(), &owner_port)
+            .unwrap();
+        assert_eq!(metadata.address, account.to_string());
+
+        // Every owner gets its own account.
+        let other_port = controller_port_id(&Signer::new("other")).unwrap();
+        let other_version = module
+            .on_chan_open_try(
+                &mut ctx,
+                &host_port,
+                &ChannelId::new(2),
+                &other_cap,
+                &get_dummy_host_channel_end(State::TryOpen, other_port),
+                &proposed,
+            )
+            .unwrap();
+        assert_ne!(other_version, version);
+
+        assert!(module
+            .on_chan_open_confirm(&mut ctx, &host_port, &channel_id)
+            .is_err());
+        let mut ctx = ctx.with_channel(host_port.clone(), channel_id.clone(), channel_end);
+        assert!(module
+            .on_chan_open_confirm(&mut ctx, &host_port, &channel_id)
+            .is_ok());
+        assert_eq!(
+            ctx.get_active_channel_id(&ConnectionId::default(), &owner_port),
+            Some(channel_id.clone())
+        );
+
+        // The owner cannot open another channel while the active one is open.
+        let mut ctx = ctx.with_channel(
+            host_port.clone(),
+            channel_id.clone(),
+            get_dummy_host_channel_end(State::Open, owner_port.clone()),
+        );
+        assert!(module
+            .on_chan_open_try(
+                &mut ctx,
+                &host_port,
+                &ChannelId::new(3),
+                &channel_cap,
+                &get_dummy_host_channel_end(State::TryOpen, owner_port),
+                &proposed
+            )
+            .is_err());
+
+        assert!(module
+            .on_chan_close_init(&mut ctx, &host_port, &channel_id)
+            .is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! This module implements the execution of the transactions which the host module receives.
+
+use ibc_proto::cosmos::base::abci::v1beta1::{MsgData, TxMsgData};
+
+use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+use crate::application::ics27_interchain_accounts::context::Ics27HostContext;
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+use crate::ics04_channel::packet::Packet;
+
+/// Executes the transaction which `packet` carries, and returns the acknowledgement of the
+/// packet. Upon success, the acknowledgement carries the protobuf-encoded `TxMsgData` with the
+/// responses to the messages of the transaction.
+pub fn on_recv_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Acknowledgement
+where
+    Ctx: Ics27HostContext,
+{
+    match execute_tx(ctx, packet) {
+        Ok(result) => Acknowledgement::Result(result),
+        Err(e) => Acknowledgement::from_error(e),
+    }
+}
+
+/// Executes the messages of the transaction in `packet` with the interchain account which the
+/// source port of the packet controls. Either all the messages execute, or none does.
+fn execute_tx<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<Vec<u8>, Error>
+where
+    Ctx: Ics27HostContext,
+{
+    let data = InterchainAccountPacketData::from_json_bytes(&packet.data)?;
+    data.validate_basic()?;
+    let messages = data.messages()?;
+
+    let channel_end = ctx
+        .channel_end(&(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        ))
+        .ok_or_else(|| {
+            Kind::ChannelNotFound(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+            )
+        })?;
+    let connection_id = channel_end
+        .connection_hops()
+        .first()
+        .ok_or(Kind::InvalidConnectionHops)?;
+
+    let account = ctx
+        .get_interchain_account_address(connection_id, &packet.source_port)
+        .ok_or_else(|| {
+            Kind::InterchainAccountNotFound(connection_id.clone(), packet.source_port.clone())
+        })?;
+
+    let mut ctx_interim = ctx.clone();
+    let mut responses = Vec::with_capacity(messages.len());
+    for msg in messages {
+        let data = ctx_interim.execute_msg(&account, &msg)?;
+        responses.push(MsgData {
+            msg_type: msg.type_url,
+            data,
+        });
+    }
+    *ctx = ctx_interim;
+
+    let mut result = Vec::new();
+    prost::Message::encode(&TxMsgData { data: responses }, &mut result).unwrap();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::cosmos::base::abci::v1beta1::TxMsgData;
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics27_interchain_accounts::account::{
+        controller_port_id, host_port_id,
+    };
+    use crate::application::ics27_interchain_accounts::host::module::test_util::get_dummy_host_context;
+    use crate::application::ics27_interchain_accounts::host::relay_application_logic::on_recv_packet;
+    use crate::application::ics27_interchain_accounts::packet::test_util::get_dummy_packet_data;
+    use crate::application::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::signer::Signer;
+    use crate::timestamp::Timestamp;
+    use crate::tx_msg::Msg;
+    use crate::Height;
+
+    fn get_dummy_ica_packet(port_id: PortId, data: &InterchainAccountPacketData) -> Packet {
+        Packet {
+            sequence: 1.into(),
+            source_port: port_id,
+            source_channel: ChannelId::default(),
+            destination_port: host_port_id(),
+            destination_channel: ChannelId::new(1),
+            data: data.to_json_bytes(),
+            timeout_height: Height::zero(),
+            timeout_timestamp: Timestamp::none(),
+        }
+    }
+
+    #[test]
+    fn execute_tx_processing() {
+        let (mut ctx, port_id, account) = get_dummy_host_context();
+        let escrow_address =
+            ctx.get_channel_escrow_address(&PortId::default(), &ChannelId::default());
+
+        // The interchain account transfers 10 `uatom` over the ICS20 channel.
+        let data = get_dummy_packet_data(&account);
+        let ack = on_recv_packet(&mut ctx, &get_dummy_ica_packet(port_id.clone(), &data));
+        match ack {
+            Acknowledgement::Result(result) => {
+                let responses: TxMsgData = prost::Message::decode(result.as_ref()).unwrap();
+                assert_eq!(responses.data.len(), 1);
+                assert_eq!(responses.data[0].msg_type, transfer::TYPE_URL);
+            }
+            Acknowledgement::Error(e) => panic!("unexpected error acknowledgement {}", e),
+        }
+        assert_eq!(ctx.balance(&account, "uatom"), Amount::from(90));
+        assert_eq!(ctx.balance(&escrow_address, "uatom"), Amount::from(10));
+
+        // The interchain account cannot act on behalf of other accounts.
+        let foreign = get_dummy_packet_data(&Signer::new("other"));
+        let ack = on_recv_packet(&mut ctx, &get_dummy_ica_packet(port_id.clone(), &foreign));
+        assert!(!ack.is_successful());
+
+        // A failing message reverts the whole transaction.
+        let mut msg = get_dummy_msg_transfer(10);
+        msg.sender = account.clone();
+        let mut overdraft = msg.clone();
+        overdraft.token = Some(Coin {
+            denom: "uatom".to_string(),
+            amount: "1000".to_string(),
+        });
+        let data = InterchainAccountPacketData::execute_tx(
+            vec![msg.to_any(), overdraft.to_any()],
+            String::new(),
+        );
+        let ack = on_recv_packet(&mut ctx, &get_dummy_ica_packet(port_id, &data));
+        assert!(!ack.is_successful());
+        assert_eq!(ctx.balance(&account, "uatom"), Amount::from(90));
+
+        // Owners without an interchain account cannot execute transactions.
+        let other_port = controller_port_id(&Signer::new("other")).unwrap();
+        let ack = on_recv_packet(&mut ctx, &get_dummy_ica_packet(other_port, &data));
+        assert!(!ack.is_successful());
+    }
+}
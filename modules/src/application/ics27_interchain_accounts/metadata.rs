@@ -0,0 +1,152 @@
+//! The version metadata of ICS27 channels. Rather than a plain version string, both channel ends
+//! negotiate a JSON-encoded `Metadata`, which also carries the connections underlying the
+//! channel, and, once the host accepted the channel, the address of the interchain account.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::VERSION;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics24_host::identifier::ConnectionId;
+
+/// The encoding of the messages in the transactions which interchain accounts execute.
+pub const ENCODING_PROTOBUF: &str = "proto3";
+
+/// The type of transactions which interchain accounts execute: Cosmos SDK transactions carrying
+/// any number of messages.
+pub const TX_TYPE_SDK_MULTI_MSG: &str = "sdk_multi_msg";
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Metadata {
+    pub version: String,
+    pub controller_connection_id: ConnectionId,
+    pub host_connection_id: ConnectionId,
+    /// The address of the interchain account, empty until the host chain registered it.
+    pub address: String,
+    pub encoding: String,
+    pub tx_type: String,
+}
+
+impl Metadata {
+    /// The metadata which the controller proposes by default for a channel over connection
+    /// `controller_connection_id`, whose counterparty is `host_connection_id`.
+    pub fn new(controller_connection_id: ConnectionId, host_connection_id: ConnectionId) -> Self {
+        Self {
+            version: VERSION.to_string(),
+            controller_connection_id,
+            host_connection_id,
+            address: String::new(),
+            encoding: ENCODING_PROTOBUF.to_string(),
+            tx_type: TX_TYPE_SDK_MULTI_MSG.to_string(),
+        }
+    }
+
+    /// Checks that the metadata describes a channel over the given pair of connections, with a
+    /// version, encoding and transaction type which this implementation supports.
+    pub fn validate(
+        &self,
+        controller_connection_id: &ConnectionId,
+        host_connection_id: &ConnectionId,
+    ) -> Result<(), Error> {
+        if self.version != VERSION {
+            return Err(Kind::InvalidVersion(VERSION.to_string(), self.version.clone()).into());
+        }
+        if self.encoding != ENCODING_PROTOBUF {
+            return Err(Kind::UnsupportedEncoding(self.encoding.clone()).into());
+        }
+        if self.tx_type != TX_TYPE_SDK_MULTI_MSG {
+            return Err(Kind::UnsupportedTxType(self.tx_type.clone()).into());
+        }
+        if &self.controller_connection_id != controller_connection_id {
+            return Err(Kind::ConnectionMismatch(
+                controller_connection_id.clone(),
+                self.controller_connection_id.clone(),
+            )
+            .into());
+        }
+        if &self.host_connection_id != host_connection_id {
+            return Err(Kind::ConnectionMismatch(
+                host_connection_id.clone(),
+                self.host_connection_id.clone(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Metadata {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(s).map_err(|e| Kind::InvalidMetadata(s.to_string()).context(e))?)
+    }
+}
+
+impl fmt::Display for Metadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).unwrap())
+    }
+}
+
+/// Returns the connection underlying `channel_end` on the host chain of `ctx`, along with its
+/// counterparty connection.
+pub(crate) fn connection_pair(
+    ctx: &dyn ChannelReader,
+    channel_end: &ChannelEnd,
+) -> Result<(ConnectionId, ConnectionId), Error> {
+    let connection_id = channel_end
+        .connection_hops()
+        .first()
+        .cloned()
+        .ok_or(Kind::InvalidConnectionHops)?;
+
+    let connection_end = ctx
+        .connection_end(&connection_id)
+        .ok_or_else(|| Kind::ConnectionNotFound(connection_id.clone()))?;
+
+    let counterparty_connection_id = connection_end
+        .counterparty()
+        .connection_id()
+        .cloned()
+        .ok_or_else(|| Kind::CounterpartyConnectionNotFound(connection_id.clone()))?;
+
+    Ok((connection_id, counterparty_connection_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::ics27_interchain_accounts::metadata::Metadata;
+    use crate::ics24_host::identifier::ConnectionId;
+
+    #[test]
+    fn metadata_json_encoding() {
+        let metadata = Metadata::new(ConnectionId::new(0), ConnectionId::new(1));
+        let encoded = metadata.to_string();
+        assert_eq!(
+            encoded,
+            r#"{"version":"ics27-1","controller_connection_id":"connection-0","host_connection_id":"connection-1","address":"","encoding":"proto3","tx_type":"sdk_multi_msg"}"#
+        );
+        assert_eq!(encoded.parse::<Metadata>().unwrap(), metadata);
+        assert!("ics27-1".parse::<Metadata>().is_err());
+
+        assert!(metadata
+            .validate(&ConnectionId::new(0), &ConnectionId::new(1))
+            .is_ok());
+        assert!(metadata
+            .validate(&ConnectionId::new(1), &ConnectionId::new(0))
+            .is_err());
+
+        let unsupported = Metadata {
+            encoding: "json".to_string(),
+            ..metadata
+        };
+        assert!(unsupported
+            .validate(&ConnectionId::new(0), &ConnectionId::new(1))
+            .is_err());
+    }
+}
@@ -0,0 +1,30 @@
+//! ICS 27: Interchain Accounts implementation, which lets an account owner on a controller chain
+//! execute transactions with an account it controls on a host chain.
+pub mod account;
+pub mod context;
+pub mod controller;
+pub mod error;
+pub mod host;
+pub mod metadata;
+pub mod msgs;
+pub mod packet;
+
+/// The name of the interchain accounts module, from which the address of the module account, and in
+/// turn those of the interchain accounts, are derived.
+pub const MODULE_NAME: &str = "interchainaccounts";
+
+/// The identifier of the controller module, in whose scope it claims the capabilities of its ports
+/// and channels.
+pub const CONTROLLER_MODULE_ID: &str = "icacontroller";
+
+/// The identifier of the host module, in whose scope it claims the capabilities of its channels.
+pub const HOST_MODULE_ID: &str = "icahost";
+
+/// The prefix of the ports which the controller module binds, one for each account owner.
+pub const CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+
+/// The port identifier to which the host module is conventionally bound.
+pub const HOST_PORT_ID: &str = "icahost";
+
+/// The version negotiated by channels carrying ICS27 packets, within the version metadata.
+pub const VERSION: &str = "ics27-1";
@@ -0,0 +1,16 @@
+//! Message definitions for the ICS27 controller module.
+
+use crate::application::ics27_interchain_accounts::msgs::{
+    register_interchain_account::MsgRegisterInterchainAccount, send_tx::MsgSendTx,
+};
+
+pub mod register_interchain_account;
+pub mod send_tx;
+
+/// Enumeration of all possible messages that the ICS27 controller module is capable of
+/// processing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InterchainAccountMsg {
+    RegisterInterchainAccount(MsgRegisterInterchainAccount),
+    SendTx(MsgSendTx),
+}
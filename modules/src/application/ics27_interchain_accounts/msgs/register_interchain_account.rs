@@ -0,0 +1,168 @@
+use std::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::interchain_accounts::controller::v1::MsgRegisterInterchainAccount as RawMsgRegisterInterchainAccount;
+
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::ics24_host::identifier::ConnectionId;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str =
+    "/ibc.applications.interchain_accounts.controller.v1.MsgRegisterInterchainAccount";
+
+///
+/// Message definition for the registration of an interchain account, which opens a channel from
+/// the controller port of `owner` to the host chain at the other end of `connection_id`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgRegisterInterchainAccount {
+    /// the owner of the interchain account, which signs this message
+    pub owner: Signer,
+    /// the connection to the host chain
+    pub connection_id: ConnectionId,
+    /// the version metadata proposed for the channel, or empty for the default metadata
+    pub version: String,
+}
+
+impl MsgRegisterInterchainAccount {
+    pub fn new(owner: Signer, connection_id: ConnectionId, version: String) -> Self {
+        Self {
+            owner,
+            connection_id,
+            version,
+        }
+    }
+}
+
+impl Msg for MsgRegisterInterchainAccount {
+    type ValidationError = Error;
+    type Raw = RawMsgRegisterInterchainAccount;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgRegisterInterchainAccount> for MsgRegisterInterchainAccount {}
+
+impl TryFrom<RawMsgRegisterInterchainAccount> for MsgRegisterInterchainAccount {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgRegisterInterchainAccount) -> Result<Self, Self::Error> {
+        if raw_msg.owner.trim().is_empty() {
+            return Err(Kind::EmptyOwner.into());
+        }
+
+        Ok(MsgRegisterInterchainAccount {
+            owner: raw_msg.owner.into(),
+            connection_id: raw_msg
+                .connection_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            version: raw_msg.version,
+        })
+    }
+}
+
+impl From<MsgRegisterInterchainAccount> for RawMsgRegisterInterchainAccount {
+    fn from(domain_msg: MsgRegisterInterchainAccount) -> Self {
+        RawMsgRegisterInterchainAccount {
+            owner: domain_msg.owner.to_string(),
+            connection_id: domain_msg.connection_id.to_string(),
+            version: domain_msg.version,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::apps::interchain_accounts::controller::v1::MsgRegisterInterchainAccount as RawMsgRegisterInterchainAccount;
+
+    use crate::ics24_host::identifier::ConnectionId;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgRegisterInterchainAccount`, for testing only!
+    pub fn get_dummy_raw_msg_register_interchain_account() -> RawMsgRegisterInterchainAccount {
+        RawMsgRegisterInterchainAccount {
+            owner: get_dummy_bech32_account(),
+            connection_id: ConnectionId::default().to_string(),
+            version: String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::interchain_accounts::controller::v1::MsgRegisterInterchainAccount as RawMsgRegisterInterchainAccount;
+
+    use crate::application::ics27_interchain_accounts::msgs::register_interchain_account::test_util::get_dummy_raw_msg_register_interchain_account;
+    use crate::application::ics27_interchain_accounts::msgs::register_interchain_account::MsgRegisterInterchainAccount;
+
+    #[test]
+    fn parse_register_interchain_account_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgRegisterInterchainAccount,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_register_interchain_account();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad connection, name too short".to_string(),
+                raw: RawMsgRegisterInterchainAccount {
+                    connection_id: "con".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Empty owner".to_string(),
+                raw: RawMsgRegisterInterchainAccount {
+                    owner: " ".to_string(),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgRegisterInterchainAccount::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgRegisterInterchainAccount::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_register_interchain_account();
+        let msg = MsgRegisterInterchainAccount::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgRegisterInterchainAccount::from(msg.clone());
+        let msg_back = MsgRegisterInterchainAccount::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,181 @@
+use std::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::interchain_accounts::controller::v1::MsgSendTx as RawMsgSendTx;
+
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+use crate::application::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+use crate::ics24_host::identifier::ConnectionId;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.interchain_accounts.controller.v1.MsgSendTx";
+
+///
+/// Message definition for sending a transaction to the interchain account which `owner` controls
+/// over `connection_id`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgSendTx {
+    /// the owner of the interchain account, which signs this message
+    pub owner: Signer,
+    /// the connection to the host chain
+    pub connection_id: ConnectionId,
+    /// the transaction for the interchain account to execute
+    pub packet_data: InterchainAccountPacketData,
+    /// the timeout of the packet, in nanoseconds relative to the current block timestamp
+    pub relative_timeout: u64,
+}
+
+impl Msg for MsgSendTx {
+    type ValidationError = Error;
+    type Raw = RawMsgSendTx;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgSendTx> for MsgSendTx {}
+
+impl TryFrom<RawMsgSendTx> for MsgSendTx {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgSendTx) -> Result<Self, Self::Error> {
+        if raw_msg.owner.trim().is_empty() {
+            return Err(Kind::EmptyOwner.into());
+        }
+        if raw_msg.relative_timeout == 0 {
+            return Err(Kind::ZeroTimeout.into());
+        }
+
+        Ok(MsgSendTx {
+            owner: raw_msg.owner.into(),
+            connection_id: raw_msg
+                .connection_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            packet_data: raw_msg
+                .packet_data
+                .ok_or(Kind::EmptyPacketData)?
+                .try_into()?,
+            relative_timeout: raw_msg.relative_timeout,
+        })
+    }
+}
+
+impl From<MsgSendTx> for RawMsgSendTx {
+    fn from(domain_msg: MsgSendTx) -> Self {
+        RawMsgSendTx {
+            owner: domain_msg.owner.to_string(),
+            connection_id: domain_msg.connection_id.to_string(),
+            packet_data: Some(domain_msg.packet_data.into()),
+            relative_timeout: domain_msg.relative_timeout,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::apps::interchain_accounts::controller::v1::MsgSendTx as RawMsgSendTx;
+
+    use crate::application::ics27_interchain_accounts::packet::test_util::get_dummy_packet_data;
+    use crate::ics24_host::identifier::ConnectionId;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgSendTx`, whose transaction is signed by `account`, for testing
+    /// only!
+    pub fn get_dummy_raw_msg_send_tx(account: &Signer) -> RawMsgSendTx {
+        RawMsgSendTx {
+            owner: get_dummy_bech32_account(),
+            connection_id: ConnectionId::default().to_string(),
+            packet_data: Some(get_dummy_packet_data(account).into()),
+            relative_timeout: 600_000_000_000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::interchain_accounts::controller::v1::MsgSendTx as RawMsgSendTx;
+
+    use crate::application::ics27_interchain_accounts::msgs::send_tx::test_util::get_dummy_raw_msg_send_tx;
+    use crate::application::ics27_interchain_accounts::msgs::send_tx::MsgSendTx;
+    use crate::signer::Signer;
+
+    #[test]
+    fn parse_send_tx_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgSendTx,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_send_tx(&Signer::new("account"));
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Empty owner".to_string(),
+                raw: RawMsgSendTx {
+                    owner: "".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing packet data".to_string(),
+                raw: RawMsgSendTx {
+                    packet_data: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Zero timeout".to_string(),
+                raw: RawMsgSendTx {
+                    relative_timeout: 0,
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgSendTx::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgSendTx::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_send_tx(&Signer::new("account"));
+        let msg = MsgSendTx::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgSendTx::from(msg.clone());
+        let msg_back = MsgSendTx::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
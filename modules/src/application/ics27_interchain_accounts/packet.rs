@@ -0,0 +1,195 @@
+//! The data of the packets which the controller module sends to the host module: a transaction,
+//! i.e., a list of arbitrary messages, to be executed by the interchain account.
+
+use std::convert::TryFrom;
+
+use prost_types::Any;
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_proto::ibc::apps::interchain_accounts::v1::{
+    CosmosTx as RawCosmosTx, InterchainAccountPacketData as RawInterchainAccountPacketData,
+    Type as RawType,
+};
+
+use crate::application::ics20_fungible_token_transfer::acknowledgement::base64_bytes;
+use crate::application::ics27_interchain_accounts::error::{Error, Kind};
+
+/// The maximum length of the memo of a packet.
+pub const MAX_MEMO_LEN: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Type {
+    #[serde(rename = "TYPE_UNSPECIFIED")]
+    Unspecified,
+
+    /// The packet carries a transaction for the interchain account to execute.
+    #[serde(rename = "TYPE_EXECUTE_TX")]
+    ExecuteTx,
+}
+
+impl TryFrom<i32> for Type {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match RawType::from_i32(value) {
+            Some(RawType::Unspecified) => Ok(Type::Unspecified),
+            Some(RawType::ExecuteTx) => Ok(Type::ExecuteTx),
+            None => Err(Kind::UnsupportedPacketType(value).into()),
+        }
+    }
+}
+
+impl From<Type> for i32 {
+    fn from(value: Type) -> Self {
+        match value {
+            Type::Unspecified => RawType::Unspecified as i32,
+            Type::ExecuteTx => RawType::ExecuteTx as i32,
+        }
+    }
+}
+
+/// The data of an ICS27 packet. As in ibc-go, packets carry the JSON encoding of this structure,
+/// in which `data` is the protobuf-encoded `CosmosTx` with the messages to execute.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct InterchainAccountPacketData {
+    #[serde(rename = "type")]
+    pub packet_type: Type,
+    #[serde(with = "base64_bytes")]
+    pub data: Vec<u8>,
+    pub memo: String,
+}
+
+impl InterchainAccountPacketData {
+    /// Builds the data of a packet asking the interchain account to execute `messages`.
+    pub fn execute_tx(messages: Vec<Any>, memo: String) -> Self {
+        Self {
+            packet_type: Type::ExecuteTx,
+            data: serialize_cosmos_tx(messages),
+            memo,
+        }
+    }
+
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.packet_type == Type::Unspecified {
+            return Err(Kind::UnsupportedPacketType(self.packet_type.into()).into());
+        }
+        if self.data.is_empty() {
+            return Err(Kind::EmptyPacketData.into());
+        }
+        if self.memo.len() > MAX_MEMO_LEN {
+            return Err(Kind::InvalidPacketData.into());
+        }
+        Ok(())
+    }
+
+    /// Returns the messages of the transaction which the packet carries.
+    pub fn messages(&self) -> Result<Vec<Any>, Error> {
+        deserialize_cosmos_tx(&self.data)
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(serde_json::from_slice(bytes).map_err(|e| Kind::InvalidPacketData.context(e))?)
+    }
+}
+
+impl TryFrom<RawInterchainAccountPacketData> for InterchainAccountPacketData {
+    type Error = Error;
+
+    fn try_from(raw: RawInterchainAccountPacketData) -> Result<Self, Self::Error> {
+        let data = InterchainAccountPacketData {
+            packet_type: Type::try_from(raw.r#type)?,
+            data: raw.data,
+            memo: raw.memo,
+        };
+        data.validate_basic()?;
+        Ok(data)
+    }
+}
+
+impl From<InterchainAccountPacketData> for RawInterchainAccountPacketData {
+    fn from(data: InterchainAccountPacketData) -> Self {
+        RawInterchainAccountPacketData {
+            r#type: data.packet_type.into(),
+            data: data.data,
+            memo: data.memo,
+        }
+    }
+}
+
+/// Encodes `messages` as the protobuf `CosmosTx` which the host chain executes.
+pub fn serialize_cosmos_tx(messages: Vec<Any>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    prost::Message::encode(&RawCosmosTx { messages }, &mut buf).unwrap();
+    buf
+}
+
+pub fn deserialize_cosmos_tx(bytes: &[u8]) -> Result<Vec<Any>, Error> {
+    let tx: RawCosmosTx =
+        prost::Message::decode(bytes).map_err(|e| Kind::InvalidCosmosTx.context(e))?;
+    Ok(tx.messages)
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use prost_types::Any;
+
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+    use crate::signer::Signer;
+    use crate::tx_msg::Msg;
+
+    /// Returns packet data asking the interchain account `account` to transfer 10 `uatom` over
+    /// the default channel, for testing only!
+    pub fn get_dummy_packet_data(account: &Signer) -> InterchainAccountPacketData {
+        let mut msg = get_dummy_msg_transfer(10);
+        msg.sender = account.clone();
+        let messages: Vec<Any> = vec![msg.to_any()];
+        InterchainAccountPacketData::execute_tx(messages, String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::interchain_accounts::v1::InterchainAccountPacketData as RawInterchainAccountPacketData;
+
+    use crate::application::ics27_interchain_accounts::packet::test_util::get_dummy_packet_data;
+    use crate::application::ics27_interchain_accounts::packet::{
+        InterchainAccountPacketData, Type,
+    };
+    use crate::signer::Signer;
+
+    #[test]
+    fn packet_data_encoding() {
+        let data = get_dummy_packet_data(&Signer::new("account"));
+        assert_eq!(data.messages().unwrap().len(), 1);
+
+        let json = String::from_utf8(data.to_json_bytes()).unwrap();
+        assert!(json.starts_with(r#"{"type":"TYPE_EXECUTE_TX","data":""#));
+        assert_eq!(
+            InterchainAccountPacketData::from_json_bytes(json.as_bytes()).unwrap(),
+            data
+        );
+
+        let raw = RawInterchainAccountPacketData::from(data.clone());
+        assert_eq!(InterchainAccountPacketData::try_from(raw).unwrap(), data);
+
+        let unspecified = InterchainAccountPacketData {
+            packet_type: Type::Unspecified,
+            ..data.clone()
+        };
+        assert!(unspecified.validate_basic().is_err());
+
+        let empty = InterchainAccountPacketData {
+            data: vec![],
+            ..data
+        };
+        assert!(empty.validate_basic().is_err());
+        assert!(InterchainAccountPacketData::from_json_bytes(b"{}").is_err());
+    }
+}
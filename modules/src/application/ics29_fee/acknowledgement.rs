@@ -0,0 +1,86 @@
+//! Acknowledgements written by the fee middleware when a fee-enabled channel receives a packet.
+//! These wrap the acknowledgement of the underlying application, and tell the sending chain where
+//! to pay the receive fee, e.g.,
+//! `{"app_acknowledgement":"eyJyZXN1bHQiOiJBUT09In0=","forward_relayer_address":"cosmos1...",
+//! "underlying_app_success":true}`.
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::application::ics20_fungible_token_transfer::acknowledgement::{
+    base64_bytes, Acknowledgement,
+};
+use crate::application::ics29_fee::error::{Error, Kind};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncentivizedAcknowledgement {
+    /// The acknowledgement written by the underlying application.
+    #[serde(with = "base64_bytes")]
+    pub app_acknowledgement: Vec<u8>,
+
+    /// The address on the sending chain to which the receive fee is paid. This is empty if the
+    /// relayer which delivered the packet did not register a counterparty payee.
+    pub forward_relayer_address: String,
+
+    /// Whether the underlying application processed the packet successfully.
+    pub underlying_app_success: bool,
+}
+
+impl IncentivizedAcknowledgement {
+    /// Wraps the acknowledgement `app_acknowledgement` of the underlying application. The
+    /// application is deemed to have failed if its acknowledgement follows the JSON encoding of
+    /// the ICS04 `Acknowledgement` type and carries an error.
+    pub fn new(app_acknowledgement: Vec<u8>, forward_relayer_address: String) -> Self {
+        let underlying_app_success = !matches!(
+            Acknowledgement::from_json_bytes(&app_acknowledgement),
+            Ok(Acknowledgement::Error(_))
+        );
+
+        IncentivizedAcknowledgement {
+            app_acknowledgement,
+            forward_relayer_address,
+            underlying_app_success,
+        }
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(serde_json::from_slice(bytes).map_err(|e| Kind::InvalidAcknowledgement.context(e))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IncentivizedAcknowledgement;
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+
+    #[test]
+    fn incentivized_acknowledgement_json_encoding() {
+        let ack = IncentivizedAcknowledgement::new(
+            Acknowledgement::success().to_json_bytes(),
+            "payee".to_string(),
+        );
+        assert!(ack.underlying_app_success);
+        assert_eq!(
+            ack.to_json_bytes(),
+            br#"{"app_acknowledgement":"eyJyZXN1bHQiOiJBUT09In0=","forward_relayer_address":"payee","underlying_app_success":true}"#.to_vec()
+        );
+        assert_eq!(
+            IncentivizedAcknowledgement::from_json_bytes(&ack.to_json_bytes()).unwrap(),
+            ack
+        );
+
+        let failed = IncentivizedAcknowledgement::new(
+            Acknowledgement::from_error("insufficient funds").to_json_bytes(),
+            String::new(),
+        );
+        assert!(!failed.underlying_app_success);
+
+        assert!(IncentivizedAcknowledgement::from_json_bytes(
+            &Acknowledgement::success().to_json_bytes()
+        )
+        .is_err());
+    }
+}
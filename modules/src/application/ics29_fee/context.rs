@@ -0,0 +1,49 @@
+use crate::application::ics20_fungible_token_transfer::context::BankKeeper;
+use crate::application::ics29_fee::error::Error;
+use crate::application::ics29_fee::fee::PacketFee;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::PacketId;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+
+/// Captures all the dependencies which the ICS29 module requires to be able to dispatch and
+/// process IBC messages. Fees are moved between accounts through the `BankKeeper`.
+pub trait Ics29Context: ChannelReader + BankKeeper + Clone {
+    /// Returns the account holding the fees escrowed for packets until they are paid out to the
+    /// relayers or refunded.
+    fn get_fee_escrow_address(&self) -> Signer;
+
+    /// Returns whether the channel end identified by `port_id` and `channel_id` negotiated the
+    /// ICS29 fee version, i.e., whether its packets can be incentivized.
+    fn is_fee_enabled(&self, port_id: &PortId, channel_id: &ChannelId) -> bool;
+
+    /// Records whether the channel end identified by `port_id` and `channel_id` is fee-enabled.
+    fn store_fee_enabled(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        enabled: bool,
+    ) -> Result<(), Error>;
+
+    /// Returns the address on the counterparty chain to which the receive fees earned by
+    /// `relayer` for the packets it delivers on channel `channel_id` are paid, if any.
+    fn get_counterparty_payee(&self, channel_id: &ChannelId, relayer: &Signer) -> Option<Signer>;
+
+    fn store_counterparty_payee(
+        &mut self,
+        channel_id: &ChannelId,
+        relayer: &Signer,
+        counterparty_payee: &Signer,
+    ) -> Result<(), Error>;
+
+    /// Returns the fees escrowed for the packet identified by `packet_id`.
+    fn get_fees_in_escrow(&self, packet_id: &PacketId) -> Vec<PacketFee>;
+
+    fn store_fees_in_escrow(
+        &mut self,
+        packet_id: PacketId,
+        packet_fees: Vec<PacketFee>,
+    ) -> Result<(), Error>;
+
+    fn delete_fees_in_escrow(&mut self, packet_id: &PacketId) -> Result<(), Error>;
+}
@@ -0,0 +1,64 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::ics04_channel::packet::{PacketId, Sequence};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum Kind {
+    #[error("identifier error")]
+    IdentifierError,
+
+    #[error("Missing channel for port_id {0} and channel_id {1} ")]
+    ChannelNotFound(PortId, ChannelId),
+
+    #[error("fees are not enabled on channel {1} on port {0}")]
+    FeeNotEnabled(PortId, ChannelId),
+
+    #[error("Sending sequence number not found for port {0} and channel {1}")]
+    SequenceSendNotFound(PortId, ChannelId),
+
+    #[error("no packet with sequence {2} is in flight on channel {1} on port {0}")]
+    PacketNotFound(PortId, ChannelId, Sequence),
+
+    #[error("the message does not specify a fee")]
+    MissingFee,
+
+    #[error("the fee must not be empty")]
+    EmptyFee,
+
+    #[error("invalid fee amount {0}")]
+    InvalidFeeAmount(String),
+
+    #[error("invalid fee denomination {0}")]
+    InvalidFeeDenom(String),
+
+    #[error("paying fees to a restricted set of relayers is not supported")]
+    RelayersNotSupported,
+
+    #[error("the relayer address must not be empty")]
+    EmptyRelayerAddress,
+
+    #[error("the counterparty payee address must not be empty")]
+    EmptyCounterpartyPayee,
+
+    #[error("expected fee version {0}, got {1}")]
+    InvalidFeeVersion(String, String),
+
+    #[error("invalid incentivized acknowledgement")]
+    InvalidAcknowledgement,
+
+    #[error("cannot escrow the fee for packet {0}")]
+    FeeEscrowFailed(PacketId),
+
+    #[error("cannot distribute the fee of packet {0}")]
+    FeeDistributionFailed(PacketId),
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
@@ -0,0 +1,109 @@
+//! Types for the IBC events emitted by the ICS29 (fee middleware) module.
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::application::ics29_fee::fee::{coins_to_string, Coin, Fee};
+use crate::events::IbcEvent;
+use crate::ics02_client::height::Height;
+use crate::ics04_channel::packet::PacketId;
+use crate::ics24_host::identifier::ChannelId;
+use crate::signer::Signer;
+
+/// Signals that a fee was escrowed to incentivize the relaying of a packet.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct IncentivizedPacket {
+    pub height: Height,
+    pub packet_id: PacketId,
+    pub fee: Fee,
+}
+
+impl IncentivizedPacket {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<IncentivizedPacket> for IbcEvent {
+    fn from(v: IncentivizedPacket) -> Self {
+        IbcEvent::IncentivizedPacket(v)
+    }
+}
+
+impl std::fmt::Display for IncentivizedPacket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "h:{}, {} {}", self.height, self.packet_id, self.fee)
+    }
+}
+
+/// Signals that a relayer registered the address on the counterparty chain to which its receive
+/// fees are paid.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RegisterCounterpartyPayee {
+    pub height: Height,
+    pub channel_id: ChannelId,
+    pub relayer: Signer,
+    pub counterparty_payee: Signer,
+}
+
+impl RegisterCounterpartyPayee {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<RegisterCounterpartyPayee> for IbcEvent {
+    fn from(v: RegisterCounterpartyPayee) -> Self {
+        IbcEvent::RegisterCounterpartyPayee(v)
+    }
+}
+
+impl std::fmt::Display for RegisterCounterpartyPayee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, {} {}->{}",
+            self.height, self.channel_id, self.relayer, self.counterparty_payee
+        )
+    }
+}
+
+/// Signals that (part of) an escrowed fee was paid out to a relayer, or refunded.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DistributeFee {
+    pub height: Height,
+    pub receiver: Signer,
+    pub fee: Vec<Coin>,
+}
+
+impl DistributeFee {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<DistributeFee> for IbcEvent {
+    fn from(v: DistributeFee) -> Self {
+        IbcEvent::DistributeFee(v)
+    }
+}
+
+impl std::fmt::Display for DistributeFee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, {} ->{}",
+            self.height,
+            coins_to_string(&self.fee),
+            self.receiver
+        )
+    }
+}
@@ -0,0 +1,246 @@
+//! Domain types for the fees which users escrow to incentivize the relaying of their packets.
+
+use std::convert::{TryFrom, TryInto};
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+use ibc_proto::ibc::apps::fee::v1::{Fee as RawFee, PacketFee as RawPacketFee};
+
+use crate::application::ics20_fungible_token_transfer::amount::Amount;
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::signer::Signer;
+
+/// An amount of tokens of a single denomination.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: Amount,
+}
+
+impl TryFrom<RawCoin> for Coin {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw: RawCoin) -> Result<Self, Self::Error> {
+        if raw.denom.trim().is_empty() {
+            return Err(Kind::InvalidFeeDenom(raw.denom).into());
+        }
+
+        let amount = raw
+            .amount
+            .parse()
+            .map_err(|e| Kind::InvalidFeeAmount(raw.amount.clone()).context(e))?;
+
+        Ok(Coin {
+            denom: raw.denom,
+            amount,
+        })
+    }
+}
+
+impl From<Coin> for RawCoin {
+    fn from(coin: Coin) -> Self {
+        RawCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Coin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+/// Formats `coins` as a comma separated list, e.g., `10uatom,5stake`.
+pub fn coins_to_string(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The fees paid for relaying a packet. The receive fee goes to the relayer which delivered the
+/// packet to the counterparty chain, and the acknowledgement fee to the relayer which delivered
+/// the acknowledgement back. If the packet times out instead, the timeout fee goes to the relayer
+/// which delivered the timeout.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Fee {
+    pub recv_fee: Vec<Coin>,
+    pub ack_fee: Vec<Coin>,
+    pub timeout_fee: Vec<Coin>,
+}
+
+impl Fee {
+    pub fn is_empty(&self) -> bool {
+        self.recv_fee.is_empty() && self.ack_fee.is_empty() && self.timeout_fee.is_empty()
+    }
+
+    /// Returns all the coins of this fee, i.e., the amount that is escrowed for the packet.
+    pub fn total(&self) -> Vec<Coin> {
+        self.recv_fee
+            .iter()
+            .chain(self.ack_fee.iter())
+            .chain(self.timeout_fee.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+impl TryFrom<RawFee> for Fee {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw: RawFee) -> Result<Self, Self::Error> {
+        let fee = Fee {
+            recv_fee: coins_try_from(raw.recv_fee)?,
+            ack_fee: coins_try_from(raw.ack_fee)?,
+            timeout_fee: coins_try_from(raw.timeout_fee)?,
+        };
+
+        if fee.is_empty() {
+            return Err(Kind::EmptyFee.into());
+        }
+
+        Ok(fee)
+    }
+}
+
+impl From<Fee> for RawFee {
+    fn from(fee: Fee) -> Self {
+        RawFee {
+            recv_fee: fee.recv_fee.into_iter().map(Into::into).collect(),
+            ack_fee: fee.ack_fee.into_iter().map(Into::into).collect(),
+            timeout_fee: fee.timeout_fee.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for Fee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "recv:[{}], ack:[{}], timeout:[{}]",
+            coins_to_string(&self.recv_fee),
+            coins_to_string(&self.ack_fee),
+            coins_to_string(&self.timeout_fee)
+        )
+    }
+}
+
+fn coins_try_from(raw_coins: Vec<RawCoin>) -> Result<Vec<Coin>, Error> {
+    raw_coins.into_iter().map(TryInto::try_into).collect()
+}
+
+/// A fee escrowed for a packet, together with the account to which any unspent part of the fee
+/// is refunded.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PacketFee {
+    pub fee: Fee,
+    pub refund_address: Signer,
+}
+
+impl PacketFee {
+    pub fn new(fee: Fee, refund_address: Signer) -> Self {
+        PacketFee {
+            fee,
+            refund_address,
+        }
+    }
+}
+
+impl TryFrom<RawPacketFee> for PacketFee {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw: RawPacketFee) -> Result<Self, Self::Error> {
+        // Restricting the relayers which may be paid is not supported yet, as in ibc-go.
+        if !raw.relayers.is_empty() {
+            return Err(Kind::RelayersNotSupported.into());
+        }
+
+        Ok(PacketFee {
+            fee: raw.fee.ok_or(Kind::MissingFee)?.try_into()?,
+            refund_address: raw.refund_address.into(),
+        })
+    }
+}
+
+impl From<PacketFee> for RawPacketFee {
+    fn from(packet_fee: PacketFee) -> Self {
+        RawPacketFee {
+            fee: Some(packet_fee.fee.into()),
+            refund_address: packet_fee.refund_address.to_string(),
+            relayers: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+    use ibc_proto::ibc::apps::fee::v1::Fee as RawFee;
+
+    /// Returns a dummy `RawFee` of 10, 20 and 30 `uatom` for receiving, acknowledging and timing
+    /// out a packet, respectively, for testing only!
+    pub fn get_dummy_raw_fee() -> RawFee {
+        let coins = |amount: &str| {
+            vec![RawCoin {
+                denom: "uatom".to_string(),
+                amount: amount.to_string(),
+            }]
+        };
+
+        RawFee {
+            recv_fee: coins("10"),
+            ack_fee: coins("20"),
+            timeout_fee: coins("30"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin as RawCoin;
+    use ibc_proto::ibc::apps::fee::v1::{Fee as RawFee, PacketFee as RawPacketFee};
+
+    use super::test_util::get_dummy_raw_fee;
+    use super::{Fee, PacketFee};
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+
+    #[test]
+    fn fee_try_from_raw() {
+        let fee = Fee::try_from(get_dummy_raw_fee()).unwrap();
+        assert_eq!(
+            fee.total()
+                .iter()
+                .try_fold(Amount::default(), |sum, c| sum.checked_add(c.amount)),
+            Some(Amount::from(60))
+        );
+        assert_eq!(RawFee::from(fee), get_dummy_raw_fee());
+
+        assert!(Fee::try_from(RawFee::default()).is_err());
+        assert!(Fee::try_from(RawFee {
+            recv_fee: vec![RawCoin {
+                denom: "uatom".to_string(),
+                amount: "-1".to_string(),
+            }],
+            ..get_dummy_raw_fee()
+        })
+        .is_err());
+
+        let raw_packet_fee = RawPacketFee {
+            fee: Some(get_dummy_raw_fee()),
+            refund_address: "refund".to_string(),
+            relayers: vec![],
+        };
+        assert!(PacketFee::try_from(raw_packet_fee.clone()).is_ok());
+        assert!(PacketFee::try_from(RawPacketFee {
+            relayers: vec!["relayer".to_string()],
+            ..raw_packet_fee
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! This module implements the processing logic for ICS29 (fee middleware) messages.
+
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::events::IncentivizedPacket;
+use crate::application::ics29_fee::fee::PacketFee;
+use crate::application::ics29_fee::msgs::FeeMsg;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::PacketId;
+
+pub mod pay_packet_fee;
+pub mod pay_packet_fee_async;
+pub mod register_counterparty_payee;
+
+/// General entry point for processing any type of message related to the ICS29 fee middleware.
+pub(crate) fn dispatch<Ctx>(ctx: &mut Ctx, msg: FeeMsg) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    match msg {
+        FeeMsg::RegisterCounterpartyPayee(msg) => register_counterparty_payee::process(ctx, msg),
+        FeeMsg::PayPacketFee(msg) => pay_packet_fee::process(ctx, msg),
+        FeeMsg::PayPacketFeeAsync(msg) => pay_packet_fee_async::process(ctx, msg),
+    }
+}
+
+/// Moves `packet_fee` from the account of its payer (i.e., its refund address) to the fee escrow
+/// account, and adds it to the fees escrowed for the packet identified by `packet_id`.
+fn escrow_packet_fee<Ctx>(
+    ctx: &mut Ctx,
+    packet_id: PacketId,
+    packet_fee: PacketFee,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let escrow_address = ctx.get_fee_escrow_address();
+    for coin in packet_fee.fee.total() {
+        ctx.send_coins(
+            &packet_fee.refund_address,
+            &escrow_address,
+            &coin.denom,
+            coin.amount,
+        )
+        .map_err(|e| Kind::FeeEscrowFailed(packet_id.clone()).context(e))?;
+    }
+
+    output.log(format!(
+        "success: escrowed fee {} for packet {}",
+        packet_fee.fee, packet_id
+    ));
+    output.emit(IbcEvent::IncentivizedPacket(IncentivizedPacket {
+        height: Default::default(),
+        packet_id: packet_id.clone(),
+        fee: packet_fee.fee.clone(),
+    }));
+
+    let mut packet_fees = ctx.get_fees_in_escrow(&packet_id);
+    packet_fees.push(packet_fee);
+    ctx.store_fees_in_escrow(packet_id, packet_fees)?;
+
+    Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::context::BankKeeper;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::test_utils::get_dummy_account_id;
+
+    /// Returns a context with an open, fee-enabled channel on the default port and channel
+    /// identifiers, in which the dummy account holds 100 `uatom` to pay fees with.
+    pub fn get_dummy_fee_context() -> MockContext {
+        let mut ctx = get_dummy_transfer_context();
+        ctx.store_fee_enabled(&PortId::default(), &ChannelId::default(), true)
+            .unwrap();
+        ctx.mint_coins(&get_dummy_account_id(), "uatom", Amount::from(100))
+            .unwrap();
+        ctx
+    }
+}
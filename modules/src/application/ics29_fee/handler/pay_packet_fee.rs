@@ -0,0 +1,121 @@
+//! Protocol logic specific to the processing of `PayPacketFee` messages.
+
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::fee::PacketFee;
+use crate::application::ics29_fee::handler::escrow_packet_fee;
+use crate::application::ics29_fee::msgs::pay_packet_fee::MsgPayPacketFee;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::PacketId;
+
+pub(crate) fn process<Ctx>(ctx: &mut Ctx, msg: MsgPayPacketFee) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    let port_channel_id = (msg.source_port_id.clone(), msg.source_channel_id.clone());
+
+    ctx.channel_end(&port_channel_id).ok_or_else(|| {
+        Kind::ChannelNotFound(msg.source_port_id.clone(), msg.source_channel_id.clone())
+    })?;
+
+    if !ctx.is_fee_enabled(&msg.source_port_id, &msg.source_channel_id) {
+        return Err(Kind::FeeNotEnabled(msg.source_port_id, msg.source_channel_id).into());
+    }
+
+    // The fee is paid for the next packet sent on the channel.
+    let sequence = ctx
+        .get_next_sequence_send(&port_channel_id)
+        .ok_or_else(|| {
+            Kind::SequenceSendNotFound(msg.source_port_id.clone(), msg.source_channel_id.clone())
+        })?;
+
+    escrow_packet_fee(
+        ctx,
+        PacketId::new(msg.source_port_id, msg.source_channel_id, sequence),
+        PacketFee::new(msg.fee, msg.signer),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::application::ics29_fee::handler::dispatch;
+    use crate::application::ics29_fee::handler::test_util::get_dummy_fee_context;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::test_util::get_dummy_raw_msg_pay_packet_fee;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::MsgPayPacketFee;
+    use crate::application::ics29_fee::msgs::FeeMsg;
+    use crate::events::IbcEvent;
+    use crate::ics04_channel::packet::PacketId;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn pay_packet_fee_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            want_pass: bool,
+        }
+
+        let msg = MsgPayPacketFee::try_from(get_dummy_raw_msg_pay_packet_fee()).unwrap();
+
+        let mut fee_disabled_ctx = get_dummy_fee_context();
+        fee_disabled_ctx
+            .store_fee_enabled(&PortId::default(), &ChannelId::default(), false)
+            .unwrap();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because no channel exists in the context".to_string(),
+                ctx: MockContext::default(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because fees are not enabled on the channel".to_string(),
+                ctx: fee_disabled_ctx,
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: get_dummy_fee_context(),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for mut test in tests {
+            let res = dispatch(&mut test.ctx, FeeMsg::PayPacketFee(msg.clone()));
+
+            assert_eq!(
+                test.want_pass,
+                res.is_ok(),
+                "pay_packet_fee: test {} failed with error {:?}",
+                test.name,
+                res.err(),
+            );
+        }
+
+        // The fee is escrowed for the next packet sent on the channel.
+        let mut ctx = get_dummy_fee_context();
+        let output = dispatch(&mut ctx, FeeMsg::PayPacketFee(msg.clone())).unwrap();
+        let packet_id = PacketId::new(PortId::default(), ChannelId::default(), 1.into());
+        assert_eq!(ctx.get_fees_in_escrow(&packet_id).len(), 1);
+        assert_eq!(ctx.balance(&msg.signer, "uatom"), Amount::from(40));
+        assert_eq!(
+            ctx.balance(&ctx.get_fee_escrow_address(), "uatom"),
+            Amount::from(60)
+        );
+        assert!(matches!(
+            output.events.as_slice(),
+            [IbcEvent::IncentivizedPacket(_)]
+        ));
+
+        // Another fee cannot be escrowed once the payer ran out of funds.
+        assert!(dispatch(&mut ctx, FeeMsg::PayPacketFee(msg)).is_err());
+        assert_eq!(ctx.get_fees_in_escrow(&packet_id).len(), 1);
+    }
+}
@@ -0,0 +1,83 @@
+//! Protocol logic specific to the processing of `PayPacketFeeAsync` messages.
+
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::handler::escrow_packet_fee;
+use crate::application::ics29_fee::msgs::pay_packet_fee_async::MsgPayPacketFeeAsync;
+use crate::handler::HandlerOutput;
+
+pub(crate) fn process<Ctx>(
+    ctx: &mut Ctx,
+    msg: MsgPayPacketFeeAsync,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    let packet_id = msg.packet_id;
+
+    ctx.channel_end(&(packet_id.port_id.clone(), packet_id.channel_id.clone()))
+        .ok_or_else(|| {
+            Kind::ChannelNotFound(packet_id.port_id.clone(), packet_id.channel_id.clone())
+        })?;
+
+    if !ctx.is_fee_enabled(&packet_id.port_id, &packet_id.channel_id) {
+        return Err(Kind::FeeNotEnabled(packet_id.port_id, packet_id.channel_id).into());
+    }
+
+    // Only packets which are still in flight, i.e., whose commitment was not yet deleted upon
+    // acknowledgement or timeout, can be incentivized.
+    if ctx
+        .get_packet_commitment(&(
+            packet_id.port_id.clone(),
+            packet_id.channel_id.clone(),
+            packet_id.sequence,
+        ))
+        .is_none()
+    {
+        return Err(Kind::PacketNotFound(
+            packet_id.port_id,
+            packet_id.channel_id,
+            packet_id.sequence,
+        )
+        .into());
+    }
+
+    escrow_packet_fee(ctx, packet_id, msg.packet_fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::application::ics29_fee::handler::dispatch;
+    use crate::application::ics29_fee::handler::test_util::get_dummy_fee_context;
+    use crate::application::ics29_fee::msgs::pay_packet_fee_async::test_util::get_dummy_raw_msg_pay_packet_fee_async;
+    use crate::application::ics29_fee::msgs::pay_packet_fee_async::MsgPayPacketFeeAsync;
+    use crate::application::ics29_fee::msgs::FeeMsg;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+
+    #[test]
+    fn pay_packet_fee_async_processing() {
+        let msg =
+            MsgPayPacketFeeAsync::try_from(get_dummy_raw_msg_pay_packet_fee_async(1)).unwrap();
+
+        // No packet with this sequence is in flight.
+        let mut ctx = get_dummy_fee_context();
+        assert!(dispatch(&mut ctx, FeeMsg::PayPacketFeeAsync(msg.clone())).is_err());
+
+        let mut ctx = get_dummy_fee_context().with_packet_commitment(
+            PortId::default(),
+            ChannelId::default(),
+            1.into(),
+            vec![0],
+        );
+        assert!(dispatch(&mut ctx, FeeMsg::PayPacketFeeAsync(msg.clone())).is_ok());
+        assert_eq!(ctx.get_fees_in_escrow(&msg.packet_id).len(), 1);
+        assert_eq!(
+            ctx.balance(&ctx.get_fee_escrow_address(), "uatom"),
+            Amount::from(60)
+        );
+    }
+}
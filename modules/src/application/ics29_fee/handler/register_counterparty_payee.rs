@@ -0,0 +1,80 @@
+//! Protocol logic specific to the processing of `RegisterCounterpartyPayee` messages.
+
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::events::RegisterCounterpartyPayee;
+use crate::application::ics29_fee::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+
+pub(crate) fn process<Ctx>(
+    ctx: &mut Ctx,
+    msg: MsgRegisterCounterpartyPayee,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    ctx.channel_end(&(msg.port_id.clone(), msg.channel_id.clone()))
+        .ok_or_else(|| Kind::ChannelNotFound(msg.port_id.clone(), msg.channel_id.clone()))?;
+
+    if !ctx.is_fee_enabled(&msg.port_id, &msg.channel_id) {
+        return Err(Kind::FeeNotEnabled(msg.port_id, msg.channel_id).into());
+    }
+
+    ctx.store_counterparty_payee(&msg.channel_id, &msg.relayer, &msg.counterparty_payee)?;
+
+    output.log(format!(
+        "success: registered counterparty payee {} for relayer {}",
+        msg.counterparty_payee, msg.relayer
+    ));
+    output.emit(IbcEvent::RegisterCounterpartyPayee(
+        RegisterCounterpartyPayee {
+            height: Default::default(),
+            channel_id: msg.channel_id,
+            relayer: msg.relayer,
+            counterparty_payee: msg.counterparty_payee,
+        },
+    ));
+
+    Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::application::ics29_fee::handler::dispatch;
+    use crate::application::ics29_fee::handler::test_util::get_dummy_fee_context;
+    use crate::application::ics29_fee::msgs::register_counterparty_payee::test_util::get_dummy_raw_msg_register_counterparty_payee;
+    use crate::application::ics29_fee::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+    use crate::application::ics29_fee::msgs::FeeMsg;
+    use crate::events::IbcEvent;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+
+    #[test]
+    fn register_counterparty_payee_processing() {
+        let msg =
+            MsgRegisterCounterpartyPayee::try_from(get_dummy_raw_msg_register_counterparty_payee())
+                .unwrap();
+
+        let mut ctx = get_dummy_fee_context();
+        let output = dispatch(&mut ctx, FeeMsg::RegisterCounterpartyPayee(msg.clone())).unwrap();
+        assert_eq!(
+            ctx.get_counterparty_payee(&msg.channel_id, &msg.relayer),
+            Some(msg.counterparty_payee.clone())
+        );
+        assert!(matches!(
+            output.events.as_slice(),
+            [IbcEvent::RegisterCounterpartyPayee(_)]
+        ));
+
+        // The payee cannot be registered while fees are not enabled on the channel.
+        let mut ctx = get_dummy_fee_context();
+        ctx.store_fee_enabled(&PortId::default(), &ChannelId::default(), false)
+            .unwrap();
+        assert!(dispatch(&mut ctx, FeeMsg::RegisterCounterpartyPayee(msg)).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! ICS 29: Fee Middleware implementation, which lets users incentivize the relaying of the
+//! packets sent by the application it wraps.
+pub mod acknowledgement;
+pub mod context;
+pub mod error;
+pub mod events;
+pub mod fee;
+pub mod handler;
+pub mod module;
+pub mod msgs;
+pub mod relay_application_logic;
@@ -0,0 +1,569 @@
+//! The ICS29 fee middleware, which sits between the ICS26 routing module and the application
+//! module bound to a port, and incentivizes the relaying of the packets of that application.
+
+use std::str::FromStr;
+
+use prost_types::Any;
+use tendermint_proto::Protobuf;
+
+use crate::application::ics29_fee::acknowledgement::IncentivizedAcknowledgement;
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::Kind;
+use crate::application::ics29_fee::handler::dispatch;
+use crate::application::ics29_fee::msgs::{
+    pay_packet_fee, pay_packet_fee_async, register_counterparty_payee, FeeMsg,
+};
+use crate::application::ics29_fee::relay_application_logic::{
+    on_acknowledgement_packet, on_timeout_packet,
+};
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::packet::{Packet, PacketId};
+use crate::ics04_channel::version::{FeeMetadata, FEE_VERSION};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::{Error as Ics26Error, Kind as Ics26Kind};
+use crate::ics26_routing::module::{Module, MsgResult};
+use crate::signer::Signer;
+
+/// Wraps the application module `app`, and negotiates fee-enabled channels on its behalf: if the
+/// version proposed for a channel is a `FeeMetadata`, the fee middleware strips it down to the
+/// version of `app` before handing the handshake over to `app`. Otherwise, the channel is not
+/// fee-enabled and the middleware stays out of the way.
+///
+/// On fee-enabled channels, the acknowledgements of `app` are wrapped into an
+/// `IncentivizedAcknowledgement`, and the fees escrowed for a packet are paid out once the packet
+/// is acknowledged or times out. Note that acknowledgements which `app` writes asynchronously are
+/// not wrapped, so `app` must acknowledge the packets of fee-enabled channels synchronously.
+///
+/// The fee middleware also processes the ICS29 messages, and hands the other messages over to
+/// `app`. Routing the type URLs of the ICS29 messages to a fee middleware therefore enables users
+/// to escrow fees and relayers to register their payees.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeeModule<M> {
+    app: M,
+}
+
+impl<M> FeeModule<M> {
+    pub fn new(app: M) -> Self {
+        FeeModule { app }
+    }
+}
+
+impl<Ctx, M> Module<Ctx> for FeeModule<M>
+where
+    Ctx: Ics29Context,
+    M: Module<Ctx>,
+{
+    fn on_chan_open_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        match unwrap_version(&channel_end.version())? {
+            Some(app_version) => {
+                let mut app_channel_end = channel_end.clone();
+                app_channel_end.set_version(app_version);
+                self.app.on_chan_open_init(
+                    ctx,
+                    port_id,
+                    channel_id,
+                    channel_cap,
+                    &app_channel_end,
+                )?;
+
+                store_fee_enabled(ctx, port_id, channel_id, true)
+            }
+            None => self
+                .app
+                .on_chan_open_init(ctx, port_id, channel_id, channel_cap, channel_end),
+        }
+    }
+
+    fn on_chan_open_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+    ) -> Result<String, Ics26Error> {
+        match unwrap_version(counterparty_version)? {
+            Some(counterparty_app_version) => {
+                let app_version = self.app.on_chan_open_try(
+                    ctx,
+                    port_id,
+                    channel_id,
+                    channel_cap,
+                    &app_channel_end(channel_end)?,
+                    &counterparty_app_version,
+                )?;
+
+                store_fee_enabled(ctx, port_id, channel_id, true)?;
+                Ok(FeeMetadata::new(app_version).to_string())
+            }
+            None => self.app.on_chan_open_try(
+                ctx,
+                port_id,
+                channel_id,
+                channel_cap,
+                channel_end,
+                counterparty_version,
+            ),
+        }
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &str,
+    ) -> Result<(), Ics26Error> {
+        if !ctx.is_fee_enabled(port_id, channel_id) {
+            return self
+                .app
+                .on_chan_open_ack(ctx, port_id, channel_id, counterparty_version);
+        }
+
+        // This end proposed a fee-enabled channel, so the counterparty must have accepted it.
+        let counterparty_app_version = unwrap_version(counterparty_version)?.ok_or_else(|| {
+            Ics26Kind::ModuleRaisedError.context(Kind::InvalidFeeVersion(
+                FEE_VERSION.to_string(),
+                counterparty_version.to_string(),
+            ))
+        })?;
+
+        self.app
+            .on_chan_open_ack(ctx, port_id, channel_id, &counterparty_app_version)
+    }
+
+    fn on_chan_open_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_open_confirm(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_close_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_close_init(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_close_confirm(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_upgrade_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_upgrade_init(
+            ctx,
+            port_id,
+            channel_id,
+            &app_channel_end(proposed_channel_end)?,
+        )
+    }
+
+    fn on_chan_upgrade_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_upgrade_try(
+            ctx,
+            port_id,
+            channel_id,
+            &app_channel_end(proposed_channel_end)?,
+        )
+    }
+
+    fn on_chan_upgrade_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_upgrade_ack(ctx, port_id, channel_id)?;
+        store_upgraded_fee_enabled(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_upgrade_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_upgrade_confirm(ctx, port_id, channel_id)?;
+        store_upgraded_fee_enabled(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_upgrade_cancel(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        self.app.on_chan_upgrade_cancel(ctx, port_id, channel_id)
+    }
+
+    fn on_send_packet(&self, ctx: &mut Ctx, packet: &Packet) -> Result<(), Ics26Error> {
+        self.app.on_send_packet(ctx, packet)
+    }
+
+    fn on_recv_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        let output = self.app.on_recv_packet(ctx, packet, relayer);
+
+        if !ctx.is_fee_enabled(&packet.destination_port, &packet.destination_channel) {
+            return output;
+        }
+
+        // The receive fee is paid on the sending chain, to the counterparty payee which the
+        // relayer registered on this chain, if any.
+        let ack = output.result.map(|app_ack| {
+            let forward_relayer = ctx
+                .get_counterparty_payee(&packet.destination_channel, relayer)
+                .map(|payee| payee.to_string())
+                .unwrap_or_default();
+
+            IncentivizedAcknowledgement::new(app_ack, forward_relayer).to_json_bytes()
+        });
+
+        HandlerOutput::builder()
+            .with_log(output.log)
+            .with_events(output.events)
+            .with_result(ack)
+    }
+
+    fn on_acknowledgement_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        acknowledgement: &[u8],
+        relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Ics26Error> {
+        if !ctx.is_fee_enabled(&packet.source_port, &packet.source_channel) {
+            return self
+                .app
+                .on_acknowledgement_packet(ctx, packet, acknowledgement, relayer);
+        }
+
+        let ack = IncentivizedAcknowledgement::from_json_bytes(acknowledgement)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+        let forward_relayer = Some(Signer::from(ack.forward_relayer_address))
+            .filter(|forward_relayer| !forward_relayer.as_str().is_empty());
+
+        let fee_output =
+            on_acknowledgement_packet(ctx, &packet_id(packet), forward_relayer, relayer)
+                .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        let app_output =
+            self.app
+                .on_acknowledgement_packet(ctx, packet, &ack.app_acknowledgement, relayer)?;
+
+        Ok(HandlerOutput::builder()
+            .with_log(fee_output.log)
+            .with_events(fee_output.events)
+            .with_log(app_output.log)
+            .with_events(app_output.events)
+            .with_result(()))
+    }
+
+    fn on_timeout_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Ics26Error> {
+        if !ctx.is_fee_enabled(&packet.source_port, &packet.source_channel) {
+            return self.app.on_timeout_packet(ctx, packet, relayer);
+        }
+
+        let fee_output = on_timeout_packet(ctx, &packet_id(packet), relayer)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        let app_output = self.app.on_timeout_packet(ctx, packet, relayer)?;
+
+        Ok(HandlerOutput::builder()
+            .with_log(fee_output.log)
+            .with_events(fee_output.events)
+            .with_log(app_output.log)
+            .with_events(app_output.events)
+            .with_result(()))
+    }
+
+    fn on_msg(&self, ctx: &mut Ctx, msg: &Any) -> Result<HandlerOutput<MsgResult>, Ics26Error> {
+        let fee_msg = match decode_fee_msg(msg)? {
+            Some(fee_msg) => fee_msg,
+            None => return self.app.on_msg(ctx, msg),
+        };
+
+        let output = dispatch(ctx, fee_msg).map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        Ok(HandlerOutput::builder()
+            .with_log(output.log)
+            .with_events(output.events)
+            .with_result(MsgResult::Done))
+    }
+}
+
+/// Decodes `msg` if it is an ICS29 message, and returns `None` otherwise.
+fn decode_fee_msg(msg: &Any) -> Result<Option<FeeMsg>, Ics26Error> {
+    let fee_msg = match msg.type_url.as_str() {
+        register_counterparty_payee::TYPE_URL => FeeMsg::RegisterCounterpartyPayee(
+            register_counterparty_payee::MsgRegisterCounterpartyPayee::decode_vec(&msg.value)
+                .map_err(|e| Ics26Kind::MalformedMessageBytes.context(e))?,
+        ),
+        pay_packet_fee::TYPE_URL => FeeMsg::PayPacketFee(
+            pay_packet_fee::MsgPayPacketFee::decode_vec(&msg.value)
+                .map_err(|e| Ics26Kind::MalformedMessageBytes.context(e))?,
+        ),
+        pay_packet_fee_async::TYPE_URL => FeeMsg::PayPacketFeeAsync(
+            pay_packet_fee_async::MsgPayPacketFeeAsync::decode_vec(&msg.value)
+                .map_err(|e| Ics26Kind::MalformedMessageBytes.context(e))?,
+        ),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(fee_msg))
+}
+
+/// Returns the version of the underlying application if `version` is the version of a
+/// fee-enabled channel, and `None` if `version` belongs to the underlying application itself.
+fn unwrap_version(version: &str) -> Result<Option<String>, Ics26Error> {
+    match FeeMetadata::from_str(version) {
+        Ok(metadata) if metadata.fee_version == FEE_VERSION => Ok(Some(metadata.app_version)),
+        Ok(metadata) => Err(Ics26Kind::ModuleRaisedError
+            .context(Kind::InvalidFeeVersion(
+                FEE_VERSION.to_string(),
+                metadata.fee_version,
+            ))
+            .into()),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Returns `channel_end` as seen by the underlying application, i.e., with the version of the
+/// underlying application.
+fn app_channel_end(channel_end: &ChannelEnd) -> Result<ChannelEnd, Ics26Error> {
+    let mut app_channel_end = channel_end.clone();
+    if let Some(app_version) = unwrap_version(&channel_end.version())? {
+        app_channel_end.set_version(app_version);
+    }
+    Ok(app_channel_end)
+}
+
+fn store_fee_enabled<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    enabled: bool,
+) -> Result<(), Ics26Error> {
+    ctx.store_fee_enabled(port_id, channel_id, enabled)
+        .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+}
+
+/// Enables or disables fees on a channel whose upgrade completes, depending on the version of
+/// the upgraded channel end. The upgrade in progress is still stored at this point of the upgrade
+/// handshake, and replaces the channel end only once the callbacks succeed.
+fn store_upgraded_fee_enabled<Ctx: Ics29Context>(
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<(), Ics26Error> {
+    let enabled = match ctx.channel_upgrade(&(port_id.clone(), channel_id.clone())) {
+        Some(channel_upgrade) => {
+            unwrap_version(&channel_upgrade.channel_end().version())?.is_some()
+        }
+        None => false,
+    };
+    store_fee_enabled(ctx, port_id, channel_id, enabled)
+}
+
+fn packet_id(packet: &Packet) -> PacketId {
+    PacketId::new(
+        packet.source_port.clone(),
+        packet.source_channel.clone(),
+        packet.sequence,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::{TryFrom, TryInto};
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
+    use crate::application::ics20_fungible_token_transfer::{MODULE_ID, VERSION};
+    use crate::application::ics29_fee::acknowledgement::IncentivizedAcknowledgement;
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::application::ics29_fee::handler::dispatch;
+    use crate::application::ics29_fee::handler::test_util::get_dummy_fee_context;
+    use crate::application::ics29_fee::module::FeeModule;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::test_util::get_dummy_raw_msg_pay_packet_fee;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::MsgPayPacketFee;
+    use crate::application::ics29_fee::msgs::FeeMsg;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelKeeper;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics04_channel::version::FeeMetadata;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::ics26_routing::module::Module;
+    use crate::mock::context::MockContext;
+    use crate::mock::module::MockModule;
+    use crate::signer::Signer;
+
+    #[test]
+    fn channel_handshake_callbacks() {
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+        let channel_end = |version: &str| {
+            ChannelEnd::new(
+                State::Init,
+                Order::Unordered,
+                Counterparty::new(PortId::default(), None),
+                vec![ConnectionId::default()],
+                version.to_string(),
+            )
+        };
+        let fee_version = FeeMetadata::new(VERSION).to_string();
+
+        let scope = MockContext::default().scope_to_module(MODULE_ID).unwrap();
+        let module = FeeModule::new(Ics20Module::new(scope));
+
+        // Each context holds the capability of the channel end, which the ICS20 module claims.
+        let new_ctx = || {
+            let mut ctx = MockContext::default();
+            let channel_cap = ctx
+                .new_channel_capability((port_id.clone(), channel_id.clone()))
+                .unwrap();
+            (ctx, channel_cap)
+        };
+
+        // A fee-enabled channel is proposed, and the underlying application accepts its version.
+        let (mut ctx, channel_cap) = new_ctx();
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(&fee_version)
+            )
+            .is_ok());
+        assert!(ctx.is_fee_enabled(&port_id, &channel_id));
+        assert!(module
+            .on_chan_open_ack(&mut ctx, &port_id, &channel_id, &fee_version)
+            .is_ok());
+        assert!(module
+            .on_chan_open_ack(&mut ctx, &port_id, &channel_id, VERSION)
+            .is_err());
+
+        // A channel without fees is left to the underlying application.
+        let (mut ctx, channel_cap) = new_ctx();
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(VERSION)
+            )
+            .is_ok());
+        assert!(!ctx.is_fee_enabled(&port_id, &channel_id));
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(&FeeMetadata::new("ics20-2").to_string()),
+            )
+            .is_err());
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(r#"{"fee_version":"ics29-2","app_version":"ics20-1"}"#),
+            )
+            .is_err());
+
+        // The version picked on the try side is wrapped as well.
+        let (mut ctx, channel_cap) = new_ctx();
+        let version = module
+            .on_chan_open_try(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(""),
+                &fee_version,
+            )
+            .unwrap();
+        assert_eq!(version, fee_version);
+        assert!(ctx.is_fee_enabled(&port_id, &channel_id));
+    }
+
+    #[test]
+    fn packet_callbacks() {
+        let module = FeeModule::new(MockModule);
+        let relayer = Signer::new("relayer");
+        let payee = Signer::new("payee");
+
+        let packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+
+        // The acknowledgement written on the receiving chain names the payee of the relayer.
+        let mut ctx = get_dummy_fee_context();
+        ctx.store_counterparty_payee(&packet.destination_channel, &relayer, &payee)
+            .unwrap();
+        let ack = module
+            .on_recv_packet(&mut ctx, &packet, &relayer)
+            .result
+            .unwrap();
+        let incentivized_ack = IncentivizedAcknowledgement::from_json_bytes(&ack).unwrap();
+        assert_eq!(incentivized_ack.app_acknowledgement, vec![1]);
+        assert_eq!(incentivized_ack.forward_relayer_address, payee.to_string());
+
+        // The sending chain pays the fees escrowed for the packet upon its acknowledgement.
+        let msg = MsgPayPacketFee::try_from(get_dummy_raw_msg_pay_packet_fee()).unwrap();
+        let mut ctx = get_dummy_fee_context();
+        dispatch(&mut ctx, FeeMsg::PayPacketFee(msg)).unwrap();
+        assert!(module
+            .on_acknowledgement_packet(&mut ctx, &packet, &[1], &relayer)
+            .is_err());
+        assert!(module
+            .on_acknowledgement_packet(&mut ctx, &packet, &ack, &relayer)
+            .is_ok());
+        assert_eq!(ctx.balance(&payee, "uatom"), Amount::from(10));
+        assert_eq!(ctx.balance(&relayer, "uatom"), Amount::from(20));
+    }
+}
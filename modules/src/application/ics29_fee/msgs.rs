@@ -0,0 +1,18 @@
+//! Message definitions for the ICS29 fee middleware.
+
+use crate::application::ics29_fee::msgs::{
+    pay_packet_fee::MsgPayPacketFee, pay_packet_fee_async::MsgPayPacketFeeAsync,
+    register_counterparty_payee::MsgRegisterCounterpartyPayee,
+};
+
+pub mod pay_packet_fee;
+pub mod pay_packet_fee_async;
+pub mod register_counterparty_payee;
+
+/// Enumeration of all possible messages that the ICS29 module is capable of processing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FeeMsg {
+    RegisterCounterpartyPayee(MsgRegisterCounterpartyPayee),
+    PayPacketFee(MsgPayPacketFee),
+    PayPacketFeeAsync(MsgPayPacketFeeAsync),
+}
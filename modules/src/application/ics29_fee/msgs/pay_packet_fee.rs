@@ -0,0 +1,184 @@
+use std::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::fee::v1::MsgPayPacketFee as RawMsgPayPacketFee;
+
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::fee::Fee;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgPayPacketFee";
+
+///
+/// Message definition for escrowing a fee for the next packet sent on a channel. This message is
+/// meant to be submitted in the same transaction as, and right before, the message which sends
+/// the packet (e.g., a `MsgTransfer`).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgPayPacketFee {
+    pub fee: Fee,
+    pub source_port_id: PortId,
+    pub source_channel_id: ChannelId,
+    /// the account paying the fee, to which any unspent part of the fee is refunded
+    pub signer: Signer,
+}
+
+impl Msg for MsgPayPacketFee {
+    type ValidationError = Error;
+    type Raw = RawMsgPayPacketFee;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgPayPacketFee> for MsgPayPacketFee {}
+
+impl TryFrom<RawMsgPayPacketFee> for MsgPayPacketFee {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgPayPacketFee) -> Result<Self, Self::Error> {
+        // Restricting the relayers which may be paid is not supported yet, as in ibc-go.
+        if !raw_msg.relayers.is_empty() {
+            return Err(Kind::RelayersNotSupported.into());
+        }
+
+        Ok(MsgPayPacketFee {
+            fee: raw_msg.fee.ok_or(Kind::MissingFee)?.try_into()?,
+            source_port_id: raw_msg
+                .source_port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            source_channel_id: raw_msg
+                .source_channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            signer: raw_msg.signer.into(),
+        })
+    }
+}
+
+impl From<MsgPayPacketFee> for RawMsgPayPacketFee {
+    fn from(domain_msg: MsgPayPacketFee) -> Self {
+        RawMsgPayPacketFee {
+            fee: Some(domain_msg.fee.into()),
+            source_port_id: domain_msg.source_port_id.to_string(),
+            source_channel_id: domain_msg.source_channel_id.to_string(),
+            signer: domain_msg.signer.to_string(),
+            relayers: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::apps::fee::v1::MsgPayPacketFee as RawMsgPayPacketFee;
+
+    use crate::application::ics29_fee::fee::test_util::get_dummy_raw_fee;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_account_id;
+
+    /// Returns a dummy `RawMsgPayPacketFee`, for testing only!
+    pub fn get_dummy_raw_msg_pay_packet_fee() -> RawMsgPayPacketFee {
+        RawMsgPayPacketFee {
+            fee: Some(get_dummy_raw_fee()),
+            source_port_id: PortId::default().to_string(),
+            source_channel_id: ChannelId::default().to_string(),
+            signer: get_dummy_account_id().to_string(),
+            relayers: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::fee::v1::MsgPayPacketFee as RawMsgPayPacketFee;
+
+    use crate::application::ics29_fee::msgs::pay_packet_fee::test_util::get_dummy_raw_msg_pay_packet_fee;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::MsgPayPacketFee;
+
+    #[test]
+    fn parse_pay_packet_fee_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgPayPacketFee,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_pay_packet_fee();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Missing fee".to_string(),
+                raw: RawMsgPayPacketFee {
+                    fee: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Empty fee".to_string(),
+                raw: RawMsgPayPacketFee {
+                    fee: Some(Default::default()),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Bad port, name too short".to_string(),
+                raw: RawMsgPayPacketFee {
+                    source_port_id: "p".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Restricted relayers".to_string(),
+                raw: RawMsgPayPacketFee {
+                    relayers: vec!["relayer".to_string()],
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgPayPacketFee::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgPayPacketFee::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_pay_packet_fee();
+        let msg = MsgPayPacketFee::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgPayPacketFee::from(msg.clone());
+        let msg_back = MsgPayPacketFee::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,176 @@
+use std::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::fee::v1::MsgPayPacketFeeAsync as RawMsgPayPacketFeeAsync;
+
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::fee::PacketFee;
+use crate::ics04_channel::packet::PacketId;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgPayPacketFeeAsync";
+
+///
+/// Message definition for escrowing a fee for a packet which was already sent, and which is still
+/// in flight. The fee is paid by the refund address of `packet_fee`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgPayPacketFeeAsync {
+    pub packet_id: PacketId,
+    pub packet_fee: PacketFee,
+}
+
+impl Msg for MsgPayPacketFeeAsync {
+    type ValidationError = Error;
+    type Raw = RawMsgPayPacketFeeAsync;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgPayPacketFeeAsync> for MsgPayPacketFeeAsync {}
+
+impl TryFrom<RawMsgPayPacketFeeAsync> for MsgPayPacketFeeAsync {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgPayPacketFeeAsync) -> Result<Self, Self::Error> {
+        let packet_id: PacketId = raw_msg
+            .packet_id
+            .ok_or(Kind::IdentifierError)?
+            .try_into()
+            .map_err(|e| Kind::IdentifierError.context(e))?;
+
+        if packet_id.sequence.is_zero() {
+            return Err(Kind::PacketNotFound(
+                packet_id.port_id,
+                packet_id.channel_id,
+                packet_id.sequence,
+            )
+            .into());
+        }
+
+        Ok(MsgPayPacketFeeAsync {
+            packet_id,
+            packet_fee: raw_msg.packet_fee.ok_or(Kind::MissingFee)?.try_into()?,
+        })
+    }
+}
+
+impl From<MsgPayPacketFeeAsync> for RawMsgPayPacketFeeAsync {
+    fn from(domain_msg: MsgPayPacketFeeAsync) -> Self {
+        RawMsgPayPacketFeeAsync {
+            packet_id: Some(domain_msg.packet_id.into()),
+            packet_fee: Some(domain_msg.packet_fee.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::apps::fee::v1::{
+        MsgPayPacketFeeAsync as RawMsgPayPacketFeeAsync, PacketFee as RawPacketFee,
+    };
+    use ibc_proto::ibc::core::channel::v1::PacketId as RawPacketId;
+
+    use crate::application::ics29_fee::fee::test_util::get_dummy_raw_fee;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_account_id;
+
+    /// Returns a dummy `RawMsgPayPacketFeeAsync` for the packet with sequence `sequence`, for
+    /// testing only!
+    pub fn get_dummy_raw_msg_pay_packet_fee_async(sequence: u64) -> RawMsgPayPacketFeeAsync {
+        RawMsgPayPacketFeeAsync {
+            packet_id: Some(RawPacketId {
+                port_id: PortId::default().to_string(),
+                channel_id: ChannelId::default().to_string(),
+                sequence,
+            }),
+            packet_fee: Some(RawPacketFee {
+                fee: Some(get_dummy_raw_fee()),
+                refund_address: get_dummy_account_id().to_string(),
+                relayers: vec![],
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::fee::v1::MsgPayPacketFeeAsync as RawMsgPayPacketFeeAsync;
+
+    use crate::application::ics29_fee::msgs::pay_packet_fee_async::test_util::get_dummy_raw_msg_pay_packet_fee_async;
+    use crate::application::ics29_fee::msgs::pay_packet_fee_async::MsgPayPacketFeeAsync;
+
+    #[test]
+    fn parse_pay_packet_fee_async_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgPayPacketFeeAsync,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_pay_packet_fee_async(1);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Missing packet id".to_string(),
+                raw: RawMsgPayPacketFeeAsync {
+                    packet_id: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Zero packet sequence".to_string(),
+                raw: get_dummy_raw_msg_pay_packet_fee_async(0),
+                want_pass: false,
+            },
+            Test {
+                name: "Missing packet fee".to_string(),
+                raw: RawMsgPayPacketFeeAsync {
+                    packet_fee: None,
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgPayPacketFeeAsync::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgPayPacketFeeAsync::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_pay_packet_fee_async(1);
+        let msg = MsgPayPacketFeeAsync::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgPayPacketFeeAsync::from(msg.clone());
+        let msg_back = MsgPayPacketFeeAsync::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,190 @@
+use std::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::fee::v1::MsgRegisterCounterpartyPayee as RawMsgRegisterCounterpartyPayee;
+
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.fee.v1.MsgRegisterCounterpartyPayee";
+
+///
+/// Message definition for the registration of the address on the counterparty chain to which the
+/// receive fees earned by a relayer on a channel are paid.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgRegisterCounterpartyPayee {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    /// the relayer address, which signs this message
+    pub relayer: Signer,
+    /// the payee address on the counterparty chain
+    pub counterparty_payee: Signer,
+}
+
+impl MsgRegisterCounterpartyPayee {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        relayer: Signer,
+        counterparty_payee: Signer,
+    ) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            relayer,
+            counterparty_payee,
+        }
+    }
+}
+
+impl Msg for MsgRegisterCounterpartyPayee {
+    type ValidationError = Error;
+    type Raw = RawMsgRegisterCounterpartyPayee;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgRegisterCounterpartyPayee> for MsgRegisterCounterpartyPayee {}
+
+impl TryFrom<RawMsgRegisterCounterpartyPayee> for MsgRegisterCounterpartyPayee {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgRegisterCounterpartyPayee) -> Result<Self, Self::Error> {
+        if raw_msg.relayer.trim().is_empty() {
+            return Err(Kind::EmptyRelayerAddress.into());
+        }
+        if raw_msg.counterparty_payee.trim().is_empty() {
+            return Err(Kind::EmptyCounterpartyPayee.into());
+        }
+
+        Ok(MsgRegisterCounterpartyPayee {
+            port_id: raw_msg
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            relayer: raw_msg.relayer.into(),
+            counterparty_payee: raw_msg.counterparty_payee.into(),
+        })
+    }
+}
+
+impl From<MsgRegisterCounterpartyPayee> for RawMsgRegisterCounterpartyPayee {
+    fn from(domain_msg: MsgRegisterCounterpartyPayee) -> Self {
+        RawMsgRegisterCounterpartyPayee {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            relayer: domain_msg.relayer.to_string(),
+            counterparty_payee: domain_msg.counterparty_payee.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::apps::fee::v1::MsgRegisterCounterpartyPayee as RawMsgRegisterCounterpartyPayee;
+
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgRegisterCounterpartyPayee`, for testing only!
+    pub fn get_dummy_raw_msg_register_counterparty_payee() -> RawMsgRegisterCounterpartyPayee {
+        RawMsgRegisterCounterpartyPayee {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            relayer: get_dummy_bech32_account(),
+            counterparty_payee: "cosmos1payee".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::fee::v1::MsgRegisterCounterpartyPayee as RawMsgRegisterCounterpartyPayee;
+
+    use crate::application::ics29_fee::msgs::register_counterparty_payee::test_util::get_dummy_raw_msg_register_counterparty_payee;
+    use crate::application::ics29_fee::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+
+    #[test]
+    fn parse_register_counterparty_payee_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgRegisterCounterpartyPayee,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_register_counterparty_payee();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgRegisterCounterpartyPayee {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Empty relayer".to_string(),
+                raw: RawMsgRegisterCounterpartyPayee {
+                    relayer: "".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Empty counterparty payee".to_string(),
+                raw: RawMsgRegisterCounterpartyPayee {
+                    counterparty_payee: " ".to_string(),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgRegisterCounterpartyPayee::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgRegisterCounterpartyPayee::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_register_counterparty_payee();
+        let msg = MsgRegisterCounterpartyPayee::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgRegisterCounterpartyPayee::from(msg.clone());
+        let msg_back = MsgRegisterCounterpartyPayee::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,213 @@
+//! This module implements the payment of the fees escrowed for a packet, once the counterparty
+//! chain acknowledged the packet, or the packet timed out.
+
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::{Error, Kind};
+use crate::application::ics29_fee::events::DistributeFee;
+use crate::application::ics29_fee::fee::Coin;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerOutputBuilder};
+use crate::ics04_channel::packet::PacketId;
+use crate::signer::Signer;
+
+/// Pays the fees escrowed for the acknowledged packet `packet_id`. The receive fees go to
+/// `forward_relayer`, i.e., to the counterparty payee of the relayer which delivered the packet,
+/// or back to the payer if that relayer did not register one. The acknowledgement fees go to
+/// `reverse_relayer`, which delivered the acknowledgement, and the timeout fees are refunded.
+pub fn on_acknowledgement_packet<Ctx>(
+    ctx: &mut Ctx,
+    packet_id: &PacketId,
+    forward_relayer: Option<Signer>,
+    reverse_relayer: &Signer,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    for packet_fee in ctx.get_fees_in_escrow(packet_id) {
+        let recv_fee_receiver = forward_relayer
+            .as_ref()
+            .unwrap_or(&packet_fee.refund_address);
+
+        distribute_fee(
+            ctx,
+            &mut output,
+            packet_id,
+            recv_fee_receiver,
+            &packet_fee.fee.recv_fee,
+        )?;
+        distribute_fee(
+            ctx,
+            &mut output,
+            packet_id,
+            reverse_relayer,
+            &packet_fee.fee.ack_fee,
+        )?;
+        distribute_fee(
+            ctx,
+            &mut output,
+            packet_id,
+            &packet_fee.refund_address,
+            &packet_fee.fee.timeout_fee,
+        )?;
+    }
+
+    ctx.delete_fees_in_escrow(packet_id)?;
+
+    output.log(format!(
+        "success: distributed the fees of acknowledged packet {}",
+        packet_id
+    ));
+
+    Ok(output.with_result(()))
+}
+
+/// Pays the fees escrowed for the packet `packet_id`, which timed out. The timeout fees go to
+/// `timeout_relayer`, which delivered the timeout, and the other fees are refunded.
+pub fn on_timeout_packet<Ctx>(
+    ctx: &mut Ctx,
+    packet_id: &PacketId,
+    timeout_relayer: &Signer,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics29Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    for packet_fee in ctx.get_fees_in_escrow(packet_id) {
+        let refund: Vec<Coin> = packet_fee
+            .fee
+            .recv_fee
+            .iter()
+            .chain(packet_fee.fee.ack_fee.iter())
+            .cloned()
+            .collect();
+
+        distribute_fee(
+            ctx,
+            &mut output,
+            packet_id,
+            timeout_relayer,
+            &packet_fee.fee.timeout_fee,
+        )?;
+        distribute_fee(
+            ctx,
+            &mut output,
+            packet_id,
+            &packet_fee.refund_address,
+            &refund,
+        )?;
+    }
+
+    ctx.delete_fees_in_escrow(packet_id)?;
+
+    output.log(format!(
+        "success: distributed the fees of timed out packet {}",
+        packet_id
+    ));
+
+    Ok(output.with_result(()))
+}
+
+/// Pays `coins` out of the fee escrow account to `receiver`.
+fn distribute_fee<Ctx>(
+    ctx: &mut Ctx,
+    output: &mut HandlerOutputBuilder<()>,
+    packet_id: &PacketId,
+    receiver: &Signer,
+    coins: &[Coin],
+) -> Result<(), Error>
+where
+    Ctx: Ics29Context,
+{
+    if coins.is_empty() {
+        return Ok(());
+    }
+
+    let escrow_address = ctx.get_fee_escrow_address();
+    for coin in coins {
+        ctx.send_coins(&escrow_address, receiver, &coin.denom, coin.amount)
+            .map_err(|e| Kind::FeeDistributionFailed(packet_id.clone()).context(e))?;
+    }
+
+    output.emit(IbcEvent::DistributeFee(DistributeFee {
+        height: Default::default(),
+        receiver: receiver.clone(),
+        fee: coins.to_vec(),
+    }));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::application::ics29_fee::handler::dispatch;
+    use crate::application::ics29_fee::handler::test_util::get_dummy_fee_context;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::test_util::get_dummy_raw_msg_pay_packet_fee;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::MsgPayPacketFee;
+    use crate::application::ics29_fee::msgs::FeeMsg;
+    use crate::application::ics29_fee::relay_application_logic::{
+        on_acknowledgement_packet, on_timeout_packet,
+    };
+    use crate::ics04_channel::packet::PacketId;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+
+    /// Returns a context in which fees of 10, 20 and 30 `uatom` are escrowed for the packet with
+    /// sequence 1 on the default channel, along with the account which paid them.
+    fn get_dummy_incentivized_context() -> (MockContext, PacketId, Signer) {
+        let msg = MsgPayPacketFee::try_from(get_dummy_raw_msg_pay_packet_fee()).unwrap();
+        let mut ctx = get_dummy_fee_context();
+        dispatch(&mut ctx, FeeMsg::PayPacketFee(msg.clone())).unwrap();
+
+        let packet_id = PacketId::new(PortId::default(), ChannelId::default(), 1.into());
+        (ctx, packet_id, msg.signer)
+    }
+
+    #[test]
+    fn acknowledgement_fee_distribution() {
+        let payee = Signer::new("payee");
+        let relayer = Signer::new("relayer");
+
+        let (mut ctx, packet_id, payer) = get_dummy_incentivized_context();
+        let output =
+            on_acknowledgement_packet(&mut ctx, &packet_id, Some(payee.clone()), &relayer).unwrap();
+        assert_eq!(ctx.balance(&payee, "uatom"), Amount::from(10));
+        assert_eq!(ctx.balance(&relayer, "uatom"), Amount::from(20));
+        assert_eq!(ctx.balance(&payer, "uatom"), Amount::from(70));
+        assert_eq!(
+            ctx.balance(&ctx.get_fee_escrow_address(), "uatom"),
+            Amount::from(0)
+        );
+        assert!(ctx.get_fees_in_escrow(&packet_id).is_empty());
+        assert_eq!(output.events.len(), 3);
+
+        // Without a counterparty payee, the receive fee is refunded.
+        let (mut ctx, packet_id, payer) = get_dummy_incentivized_context();
+        on_acknowledgement_packet(&mut ctx, &packet_id, None, &relayer).unwrap();
+        assert_eq!(ctx.balance(&relayer, "uatom"), Amount::from(20));
+        assert_eq!(ctx.balance(&payer, "uatom"), Amount::from(80));
+    }
+
+    #[test]
+    fn timeout_fee_distribution() {
+        let relayer = Signer::new("relayer");
+
+        let (mut ctx, packet_id, payer) = get_dummy_incentivized_context();
+        on_timeout_packet(&mut ctx, &packet_id, &relayer).unwrap();
+        assert_eq!(ctx.balance(&relayer, "uatom"), Amount::from(30));
+        assert_eq!(ctx.balance(&payer, "uatom"), Amount::from(70));
+        assert!(ctx.get_fees_in_escrow(&packet_id).is_empty());
+
+        // Packets without escrowed fees leave all balances untouched.
+        let output = on_timeout_packet(&mut ctx, &packet_id, &relayer).unwrap();
+        assert!(output.events.is_empty());
+        assert_eq!(ctx.balance(&relayer, "uatom"), Amount::from(30));
+    }
+}
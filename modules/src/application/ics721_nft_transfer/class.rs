@@ -0,0 +1,113 @@
+//! Class tracing for NFTs moved over ICS721 channels. An NFT which leaves the chain it originates
+//! from is represented on the receiving chain by a voucher NFT, with the same token identifier,
+//! of a class whose identifier carries the destination port and channel as a prefix (e.g.,
+//! `nft-transfer/channel-0/kitties`). Sending the voucher back over the same channel strips this
+//! prefix again.
+
+use crate::application::ics721_nft_transfer::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
+/// Returns the prefix that a class identifier receives when its NFTs cross the channel end
+/// identified by `port_id` and `channel_id`.
+pub fn class_prefix(port_id: &PortId, channel_id: &ChannelId) -> String {
+    format!("{}/{}/", port_id, channel_id)
+}
+
+/// Prefixes `class_id` with the given port and channel identifiers.
+pub fn prefix_class_id(port_id: &PortId, channel_id: &ChannelId, class_id: &str) -> String {
+    format!("{}{}", class_prefix(port_id, channel_id), class_id)
+}
+
+/// Strips the prefix of the given port and channel identifiers from `class_id`. Returns `None`
+/// if `class_id` does not carry this prefix.
+pub fn unprefix_class_id<'a>(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    class_id: &'a str,
+) -> Option<&'a str> {
+    let prefix = class_prefix(port_id, channel_id);
+    if class_id.len() > prefix.len() && class_id.starts_with(&prefix) {
+        Some(&class_id[prefix.len()..])
+    } else {
+        None
+    }
+}
+
+/// Returns true if the NFTs of class `class_id`, sent over `source_port` and `source_channel`,
+/// are not returning to the chain they came from. The sending chain must then escrow the NFTs,
+/// instead of burning the vouchers it holds.
+pub fn sender_chain_is_source(
+    source_port: &PortId,
+    source_channel: &ChannelId,
+    class_id: &str,
+) -> bool {
+    unprefix_class_id(source_port, source_channel, class_id).is_none()
+}
+
+/// Checks that `class_id` is a non-empty sequence of `/`-separated, non-empty path segments,
+/// none of which contains whitespace.
+pub fn validate_class_id(class_id: &str) -> Result<(), Error> {
+    if class_id
+        .split('/')
+        .any(|segment| segment.is_empty() || segment.contains(char::is_whitespace))
+    {
+        return Err(Kind::InvalidClassId(class_id.to_string()).into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `token_id` is non-empty and does not contain whitespace.
+pub fn validate_token_id(token_id: &str) -> Result<(), Error> {
+    if token_id.is_empty() || token_id.contains(char::is_whitespace) {
+        return Err(Kind::InvalidTokenId(token_id.to_string()).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_id_prefixing() {
+        let port_id: PortId = "nft-transfer".parse().unwrap();
+        let channel_id = ChannelId::new(0);
+
+        let prefixed = prefix_class_id(&port_id, &channel_id, "kitties");
+        assert_eq!(prefixed, "nft-transfer/channel-0/kitties");
+        assert_eq!(
+            unprefix_class_id(&port_id, &channel_id, &prefixed),
+            Some("kitties")
+        );
+
+        // Only the outermost prefix is stripped.
+        let twice = prefix_class_id(&port_id, &ChannelId::new(1), &prefixed);
+        assert_eq!(
+            unprefix_class_id(&port_id, &ChannelId::new(1), &twice),
+            Some(prefixed.as_str())
+        );
+        assert_eq!(unprefix_class_id(&port_id, &channel_id, &twice), None);
+
+        assert!(sender_chain_is_source(&port_id, &channel_id, "kitties"));
+        assert!(!sender_chain_is_source(&port_id, &channel_id, &prefixed));
+    }
+
+    #[test]
+    fn identifier_validation() {
+        assert!(validate_class_id("kitties").is_ok());
+        assert!(validate_class_id("nft-transfer/channel-0/kitties").is_ok());
+        for class_id in &["", "/kitties", "nft-transfer//kitties", "kit ties"] {
+            assert!(
+                validate_class_id(class_id).is_err(),
+                "class {:?} should be invalid",
+                class_id
+            );
+        }
+
+        assert!(validate_token_id("kitty-1").is_ok());
+        assert!(validate_token_id("").is_err());
+        assert!(validate_token_id("kitty 1").is_err());
+    }
+}
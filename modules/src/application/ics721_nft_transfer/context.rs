@@ -0,0 +1,57 @@
+use crate::application::ics721_nft_transfer::error::Error;
+use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::ics05_port::context::CapabilityKeeper;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+
+/// Captures the NFT-module-like functionality through which the ICS721 module moves NFTs between
+/// accounts of the host chain, and creates or destroys vouchers for NFTs of foreign origin. NFTs
+/// are identified by their class and token identifiers.
+pub trait NftKeeper {
+    /// Returns the URI of class `class_id`, or `None` if the class does not exist.
+    fn get_class_uri(&self, class_id: &str) -> Option<String>;
+
+    /// Creates class `class_id` with URI `class_uri`, or updates the URI of the class if it
+    /// already exists.
+    fn save_class(&mut self, class_id: &str, class_uri: &str) -> Result<(), Error>;
+
+    /// Returns the owner of token `token_id` of class `class_id`, or `None` if the token does not
+    /// exist.
+    fn get_owner(&self, class_id: &str, token_id: &str) -> Option<Signer>;
+
+    /// Returns the URI of token `token_id` of class `class_id`, or `None` if the token does not
+    /// exist.
+    fn get_token_uri(&self, class_id: &str, token_id: &str) -> Option<String>;
+
+    /// Transfers token `token_id` of class `class_id` from its owner `from` to account `to`.
+    fn transfer_nft(
+        &mut self,
+        class_id: &str,
+        token_id: &str,
+        from: &Signer,
+        to: &Signer,
+    ) -> Result<(), Error>;
+
+    /// Creates token `token_id` with URI `token_uri` in the existing class `class_id`, owned by
+    /// `receiver`.
+    fn mint_nft(
+        &mut self,
+        class_id: &str,
+        token_id: &str,
+        token_uri: &str,
+        receiver: &Signer,
+    ) -> Result<(), Error>;
+
+    /// Destroys token `token_id` of class `class_id`, which `owner` holds.
+    fn burn_nft(&mut self, class_id: &str, token_id: &str, owner: &Signer) -> Result<(), Error>;
+}
+
+/// Captures all the dependencies which the ICS721 module requires to be able to dispatch and
+/// process IBC messages.
+pub trait Ics721Context:
+    ChannelReader + ChannelKeeper + CapabilityKeeper + NftKeeper + Clone
+{
+    /// Returns the account holding the NFTs escrowed for transfers over the channel identified
+    /// by `port_id` and `channel_id`.
+    fn get_nft_escrow_address(&self, port_id: &PortId, channel_id: &ChannelId) -> Signer;
+}
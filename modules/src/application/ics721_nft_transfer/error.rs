@@ -0,0 +1,82 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::ics04_channel::channel::Order;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum Kind {
+    #[error("error raised by message handler")]
+    HandlerRaisedError,
+
+    #[error("identifier error")]
+    IdentifierError,
+
+    #[error("invalid timeout height")]
+    InvalidTimeoutHeight,
+
+    #[error("Sending sequence number not found for port {0} and channel {1}")]
+    SequenceSendNotFound(PortId, ChannelId),
+
+    #[error("Missing channel for port_id {0} and channel_id {1} ")]
+    ChannelNotFound(PortId, ChannelId),
+
+    #[error(
+        "Destination channel not found in the counterparty of port_id {0} and channel_id {1} "
+    )]
+    DestinationChannelNotFound(PortId, ChannelId),
+
+    #[error("the NFT transfer module holds no capability for port_id {0} and channel_id {1}")]
+    ChannelCapabilityNotFound(PortId, ChannelId),
+
+    #[error("invalid class identifier {0}")]
+    InvalidClassId(String),
+
+    #[error("invalid token identifier {0}")]
+    InvalidTokenId(String),
+
+    #[error("the transfer does not specify any token")]
+    EmptyTokenIds,
+
+    #[error("token {0} is transferred more than once")]
+    DuplicateTokenId(String),
+
+    #[error("expected {0} token URIs, got {1}")]
+    TokenUrisMismatch(usize, usize),
+
+    #[error("invalid non-fungible token packet data")]
+    InvalidPacketData,
+
+    #[error("invalid acknowledgement")]
+    InvalidAcknowledgement,
+
+    #[error("class {0} does not exist")]
+    ClassNotFound(String),
+
+    #[error("token {1} of class {0} does not exist")]
+    TokenNotFound(String, String),
+
+    #[error("token {1} of class {0} already exists")]
+    TokenAlreadyExists(String, String),
+
+    #[error("account {2} does not own token {1} of class {0}")]
+    UnauthorizedOwner(String, String, Signer),
+
+    #[error("expected {0} channel, got {1}")]
+    InvalidChannelOrder(Order, Order),
+
+    #[error("expected version {0}, got {1}")]
+    InvalidVersion(String, String),
+
+    #[error("channel {1} on port {0} cannot be closed by users")]
+    ChannelClosingNotAllowed(PortId, ChannelId),
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
@@ -0,0 +1,123 @@
+//! Types for the IBC events emitted by the ICS721 (non-fungible token transfer) module.
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::events::IbcEvent;
+use crate::ics02_client::height::Height;
+use crate::signer::Signer;
+
+/// Signals that NFTs were escrowed or burned for a transfer to another chain.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NftTransfer {
+    pub height: Height,
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub class_id: String,
+    pub token_ids: Vec<String>,
+}
+
+impl NftTransfer {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<NftTransfer> for IbcEvent {
+    fn from(v: NftTransfer) -> Self {
+        IbcEvent::NftTransfer(v)
+    }
+}
+
+impl std::fmt::Display for NftTransfer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, {}[{}] {}->{}",
+            self.height,
+            self.class_id,
+            self.token_ids.join(","),
+            self.sender,
+            self.receiver
+        )
+    }
+}
+
+/// Signals the processing of an incoming ICS721 packet, or of the acknowledgement for an
+/// outgoing one. The `success` flag indicates whether the transfer went through.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NonFungibleTokenPacket {
+    pub height: Height,
+    pub receiver: Signer,
+    pub class_id: String,
+    pub token_ids: Vec<String>,
+    pub success: bool,
+}
+
+impl NonFungibleTokenPacket {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<NonFungibleTokenPacket> for IbcEvent {
+    fn from(v: NonFungibleTokenPacket) -> Self {
+        IbcEvent::NonFungibleTokenPacket(v)
+    }
+}
+
+impl std::fmt::Display for NonFungibleTokenPacket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, {}[{}] ->{}, success:{}",
+            self.height,
+            self.class_id,
+            self.token_ids.join(","),
+            self.receiver,
+            self.success
+        )
+    }
+}
+
+/// Signals that the NFTs of an ICS721 packet which timed out were refunded to their sender.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NftTransferTimeout {
+    pub height: Height,
+    pub refund_receiver: Signer,
+    pub refund_class_id: String,
+    pub refund_token_ids: Vec<String>,
+}
+
+impl NftTransferTimeout {
+    pub fn height(&self) -> Height {
+        self.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.height = height;
+    }
+}
+
+impl From<NftTransferTimeout> for IbcEvent {
+    fn from(v: NftTransferTimeout) -> Self {
+        IbcEvent::NftTransferTimeout(v)
+    }
+}
+
+impl std::fmt::Display for NftTransferTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "h:{}, refund {}[{}] ->{}",
+            self.height,
+            self.refund_class_id,
+            self.refund_token_ids.join(","),
+            self.refund_receiver
+        )
+    }
+}
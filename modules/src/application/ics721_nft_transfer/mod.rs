@@ -0,0 +1,19 @@
+//! ICS 721: IBC Non-Fungible Token Transfer implementation
+pub mod class;
+pub mod context;
+pub mod error;
+pub mod events;
+pub mod module;
+pub mod msgs;
+pub mod packet;
+pub mod relay_application_logic;
+
+/// The identifier of the ICS721 module, in whose scope it claims the capabilities of its
+/// channels.
+pub const MODULE_ID: &str = "nonfungibletokentransfer";
+
+/// The port identifier to which the ICS721 application is conventionally bound.
+pub const PORT_ID: &str = "nft-transfer";
+
+/// The version string negotiated by channels carrying ICS721 packets.
+pub const VERSION: &str = "ics721-1";
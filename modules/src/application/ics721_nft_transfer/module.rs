@@ -0,0 +1,309 @@
+//! The ICS721 application module, which plugs the non-fungible token transfer logic into the
+//! ICS26 routing module.
+
+use prost_types::Any;
+use tendermint_proto::Protobuf;
+
+use crate::application::ics721_nft_transfer::context::Ics721Context;
+use crate::application::ics721_nft_transfer::error::{Error, Kind};
+use crate::application::ics721_nft_transfer::msgs::transfer;
+use crate::application::ics721_nft_transfer::relay_application_logic::{
+    on_acknowledgement_packet::on_acknowledgement_packet, on_recv_packet::on_recv_packet,
+    on_timeout_packet::on_timeout_packet, send_transfer::send_transfer,
+};
+use crate::application::ics721_nft_transfer::VERSION;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::{ChannelEnd, Order};
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::{Capability, CapabilityName};
+use crate::ics05_port::context::ScopedCapabilityKeeper;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::{Error as Ics26Error, Kind as Ics26Kind};
+use crate::ics26_routing::module::{Module, MsgResult};
+use crate::signer::Signer;
+
+/// The ICS721 application. Accepts only unordered channels with the version `VERSION`, and never
+/// lets users close a channel, since that would strand the NFTs in flight. The module also
+/// processes the transfers which users send.
+#[derive(Clone, Debug)]
+pub struct Ics721Module {
+    scope: ScopedCapabilityKeeper,
+}
+
+impl Ics721Module {
+    /// Creates the ICS721 module, which holds the capabilities of its channels in `scope`, the
+    /// scope of `MODULE_ID`.
+    pub fn new(scope: ScopedCapabilityKeeper) -> Self {
+        Self { scope }
+    }
+}
+
+impl<Ctx> Module<Ctx> for Ics721Module
+where
+    Ctx: Ics721Context,
+{
+    fn on_chan_open_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        validate_order(channel_end.ordering())
+            .and_then(|_| validate_version(&channel_end.version()))
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        claim_channel_capability(&self.scope, ctx, port_id, channel_id, channel_cap)
+    }
+
+    fn on_chan_open_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+    ) -> Result<String, Ics26Error> {
+        validate_order(channel_end.ordering())
+            .and_then(|_| validate_version(counterparty_version))
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        claim_channel_capability(&self.scope, ctx, port_id, channel_id, channel_cap)?;
+        Ok(VERSION.to_string())
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        counterparty_version: &str,
+    ) -> Result<(), Ics26Error> {
+        validate_version(counterparty_version)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+    }
+
+    fn on_chan_upgrade_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        validate_order(proposed_channel_end.ordering())
+            .and_then(|_| validate_version(&proposed_channel_end.version()))
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+    }
+
+    fn on_chan_upgrade_try(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Ics26Error> {
+        validate_order(proposed_channel_end.ordering())
+            .and_then(|_| validate_version(&proposed_channel_end.version()))
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+    }
+
+    fn on_chan_close_init(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Ics26Error> {
+        Err(Ics26Kind::ModuleRaisedError
+            .context(Kind::ChannelClosingNotAllowed(
+                port_id.clone(),
+                channel_id.clone(),
+            ))
+            .into())
+    }
+
+    fn on_recv_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        let output = on_recv_packet(ctx, packet);
+
+        HandlerOutput::builder()
+            .with_log(output.log)
+            .with_events(output.events)
+            .with_result(Some(output.result.to_json_bytes()))
+    }
+
+    fn on_acknowledgement_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        acknowledgement: &[u8],
+        _relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Ics26Error> {
+        Ok(on_acknowledgement_packet(ctx, packet, acknowledgement)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?)
+    }
+
+    fn on_timeout_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Ics26Error> {
+        Ok(on_timeout_packet(ctx, packet).map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?)
+    }
+
+    fn on_msg(&self, ctx: &mut Ctx, msg: &Any) -> Result<HandlerOutput<MsgResult>, Ics26Error> {
+        if msg.type_url != transfer::TYPE_URL {
+            return Err(Ics26Kind::UnknownMessageTypeUrl(msg.type_url.clone()).into());
+        }
+
+        let msg = transfer::MsgTransfer::decode_vec(&msg.value)
+            .map_err(|e| Ics26Kind::MalformedMessageBytes.context(e))?;
+        let output = send_transfer(ctx, &self.scope, msg)
+            .map_err(|e| Ics26Kind::ModuleRaisedError.context(e))?;
+
+        Ok(HandlerOutput::builder()
+            .with_log(output.log)
+            .with_events(output.events)
+            .with_result(MsgResult::SendPacket(output.result)))
+    }
+}
+
+/// Claims the capability `channel_cap` of the channel end identified by `port_id` and
+/// `channel_id`, in the scope of the ICS721 module.
+fn claim_channel_capability<Ctx: Ics721Context>(
+    scope: &ScopedCapabilityKeeper,
+    ctx: &mut Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    channel_cap: &Capability,
+) -> Result<(), Ics26Error> {
+    scope
+        .claim_capability(
+            ctx,
+            CapabilityName::channel(port_id, channel_id),
+            channel_cap.clone(),
+        )
+        .map_err(|e| Ics26Kind::ModuleRaisedError.context(e).into())
+}
+
+fn validate_order(order: &Order) -> Result<(), Error> {
+    if order != &Order::Unordered {
+        return Err(Kind::InvalidChannelOrder(Order::Unordered, *order).into());
+    }
+    Ok(())
+}
+
+fn validate_version(version: &str) -> Result<(), Error> {
+    if version != VERSION {
+        return Err(Kind::InvalidVersion(VERSION.to_string(), version.to_string()).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::ics721_nft_transfer::module::Ics721Module;
+    use crate::application::ics721_nft_transfer::{MODULE_ID, VERSION};
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::ChannelKeeper;
+    use crate::ics05_port::capabilities::CapabilityName;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::ics26_routing::module::Module;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn channel_handshake_callbacks() {
+        let mut ctx = MockContext::default();
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+        let channel_cap = ctx
+            .new_channel_capability((port_id.clone(), channel_id.clone()))
+            .unwrap();
+        let channel_end = |order, version: &str| {
+            ChannelEnd::new(
+                State::Init,
+                order,
+                Counterparty::new(PortId::default(), None),
+                vec![ConnectionId::default()],
+                version.to_string(),
+            )
+        };
+
+        let scope = ctx.scope_to_module(MODULE_ID).unwrap();
+        let module = Ics721Module::new(scope.clone());
+
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(Order::Unordered, VERSION),
+            )
+            .is_ok());
+        assert_eq!(
+            scope.get_capability(&ctx, &CapabilityName::channel(&port_id, &channel_id)),
+            Some(channel_cap.clone())
+        );
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(Order::Ordered, VERSION),
+            )
+            .is_err());
+        assert!(module
+            .on_chan_open_init(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(Order::Unordered, "ics721-2"),
+            )
+            .is_err());
+
+        let version = module
+            .on_chan_open_try(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(Order::Unordered, ""),
+                VERSION,
+            )
+            .unwrap();
+        assert_eq!(version, VERSION);
+        assert!(module
+            .on_chan_open_try(
+                &mut ctx,
+                &port_id,
+                &channel_id,
+                &channel_cap,
+                &channel_end(Order::Unordered, VERSION),
+                "ics721-2",
+            )
+            .is_err());
+
+        assert!(module
+            .on_chan_open_ack(&mut ctx, &port_id, &channel_id, VERSION)
+            .is_ok());
+        assert!(module
+            .on_chan_open_ack(&mut ctx, &port_id, &channel_id, "ics721-2")
+            .is_err());
+
+        assert!(module
+            .on_chan_close_init(&mut ctx, &port_id, &channel_id)
+            .is_err());
+        assert!(module
+            .on_chan_close_confirm(&mut ctx, &port_id, &channel_id)
+            .is_ok());
+    }
+}
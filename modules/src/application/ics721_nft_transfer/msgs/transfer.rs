@@ -0,0 +1,152 @@
+//! This is the definition of the NFT transfer message that an application submits to a chain.
+
+use std::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::apps::nft_transfer::v1::MsgTransfer as RawMsgTransfer;
+
+use crate::application::ics721_nft_transfer::error::{Error, Kind};
+use crate::ics02_client::height::Height;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::timestamp::Timestamp;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.applications.nft_transfer.v1.MsgTransfer";
+
+///
+/// Message definition for the transfer of NFTs of a class to another chain.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgTransfer {
+    /// the port on which the packet will be sent
+    pub source_port: PortId,
+    /// the channel by which the packet will be sent
+    pub source_channel: ChannelId,
+    /// the class to which the NFTs to be transferred belong
+    pub class_id: String,
+    /// the identifiers of the NFTs to be transferred
+    pub token_ids: Vec<String>,
+    /// the sender address
+    pub sender: Signer,
+    /// the recipient address on the destination chain
+    pub receiver: Signer,
+    /// Timeout height relative to the current block height.
+    /// The timeout is disabled when set to 0.
+    pub timeout_height: Height,
+    /// Timeout timestamp relative to the current block timestamp.
+    /// The timeout is disabled when there is no timestamp.
+    pub timeout_timestamp: Timestamp,
+}
+
+impl Msg for MsgTransfer {
+    type ValidationError = Error;
+    type Raw = RawMsgTransfer;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgTransfer> for MsgTransfer {}
+
+impl TryFrom<RawMsgTransfer> for MsgTransfer {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgTransfer) -> Result<Self, Self::Error> {
+        Ok(MsgTransfer {
+            source_port: raw_msg
+                .source_port
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            source_channel: raw_msg
+                .source_channel
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            class_id: raw_msg.class_id,
+            token_ids: raw_msg.token_ids,
+            sender: raw_msg.sender.into(),
+            receiver: raw_msg.receiver.into(),
+            timeout_height: raw_msg
+                .timeout_height
+                .map(TryInto::try_into)
+                .transpose()
+                .map_err(|e| Kind::InvalidTimeoutHeight.context(e))?
+                .unwrap_or_else(Height::zero),
+            timeout_timestamp: Timestamp::from_nanoseconds(raw_msg.timeout_timestamp),
+        })
+    }
+}
+
+impl From<MsgTransfer> for RawMsgTransfer {
+    fn from(domain_msg: MsgTransfer) -> Self {
+        RawMsgTransfer {
+            source_port: domain_msg.source_port.to_string(),
+            source_channel: domain_msg.source_channel.to_string(),
+            class_id: domain_msg.class_id,
+            token_ids: domain_msg.token_ids,
+            sender: domain_msg.sender.to_string(),
+            receiver: domain_msg.receiver.to_string(),
+            timeout_height: Some(domain_msg.timeout_height.into()),
+            timeout_timestamp: domain_msg.timeout_timestamp.as_nanoseconds(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    use super::MsgTransfer;
+
+    /// Returns a dummy `MsgTransfer` of the NFT `kitty-1` of class `kitties`, over the default
+    /// port and channel, for testing only!
+    pub fn get_dummy_msg_nft_transfer(height: u64) -> MsgTransfer {
+        let id = get_dummy_account_id();
+
+        MsgTransfer {
+            source_port: PortId::default(),
+            source_channel: ChannelId::default(),
+            class_id: "kitties".to_string(),
+            token_ids: vec!["kitty-1".to_string()],
+            sender: id.clone(),
+            receiver: id,
+            timeout_timestamp: Timestamp::from_nanoseconds(10),
+            timeout_height: Height {
+                revision_number: 0,
+                revision_height: height,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::apps::nft_transfer::v1::MsgTransfer as RawMsgTransfer;
+
+    use crate::application::ics721_nft_transfer::msgs::transfer::test_util::get_dummy_msg_nft_transfer;
+    use crate::application::ics721_nft_transfer::msgs::transfer::MsgTransfer;
+
+    #[test]
+    fn to_and_from() {
+        let msg = get_dummy_msg_nft_transfer(10);
+        let raw = RawMsgTransfer::from(msg.clone());
+        assert_eq!(MsgTransfer::try_from(raw.clone()).unwrap(), msg);
+
+        let invalid_port = RawMsgTransfer {
+            source_port: "p".to_string(),
+            ..raw
+        };
+        assert!(MsgTransfer::try_from(invalid_port).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! The payload of the packets exchanged by the ICS721 module, together with its JSON encoding.
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_proto::ibc::apps::nft_transfer::v1::NonFungibleTokenPacketData as RawNonFungibleTokenPacketData;
+
+use crate::application::ics721_nft_transfer::class::{validate_class_id, validate_token_id};
+use crate::application::ics721_nft_transfer::error::{Error, Kind};
+use crate::signer::Signer;
+
+/// The data carried by an ICS721 packet. Packets carry this data JSON-encoded, with camel-cased
+/// fields in alphabetical order, so that the packet commitments match those computed by other
+/// ICS721 implementations.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonFungibleTokenPacketData {
+    /// The class of the NFTs, including its trace prefixes.
+    pub class_id: String,
+    /// The URI of the class, which the receiving chain records for the voucher class.
+    #[serde(default)]
+    pub class_uri: String,
+    /// The recipient address on the destination chain.
+    pub receiver: Signer,
+    /// The sender address on the source chain.
+    pub sender: Signer,
+    /// The identifiers of the NFTs to be transferred.
+    pub token_ids: Vec<String>,
+    /// The URIs of the NFTs, in the order of `token_ids`.
+    #[serde(default)]
+    pub token_uris: Vec<String>,
+}
+
+impl NonFungibleTokenPacketData {
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        validate_class_id(&self.class_id)?;
+
+        if self.token_ids.is_empty() {
+            return Err(Kind::EmptyTokenIds.into());
+        }
+
+        let mut token_ids = HashSet::new();
+        for token_id in &self.token_ids {
+            validate_token_id(token_id)?;
+            if !token_ids.insert(token_id) {
+                return Err(Kind::DuplicateTokenId(token_id.clone()).into());
+            }
+        }
+
+        if self.token_uris.len() != self.token_ids.len() {
+            return Err(
+                Kind::TokenUrisMismatch(self.token_ids.len(), self.token_uris.len()).into(),
+            );
+        }
+
+        if self.sender.as_str().trim().is_empty() {
+            return Err(Kind::InvalidPacketData.context("empty sender").into());
+        }
+
+        if self.receiver.as_str().trim().is_empty() {
+            return Err(Kind::InvalidPacketData.context("empty receiver").into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the identifier of each NFT of the packet along with its URI.
+    pub fn tokens(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.token_ids
+            .iter()
+            .map(String::as_str)
+            .zip(self.token_uris.iter().map(String::as_str))
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap()
+    }
+
+    /// Decodes and validates the JSON-encoded data of an ICS721 packet.
+    pub fn from_json_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let data: NonFungibleTokenPacketData =
+            serde_json::from_slice(bytes).map_err(|e| Kind::InvalidPacketData.context(e))?;
+        data.validate_basic()?;
+        Ok(data)
+    }
+}
+
+impl TryFrom<RawNonFungibleTokenPacketData> for NonFungibleTokenPacketData {
+    type Error = Error;
+
+    fn try_from(raw: RawNonFungibleTokenPacketData) -> Result<Self, Self::Error> {
+        let data = NonFungibleTokenPacketData {
+            class_id: raw.class_id,
+            class_uri: raw.class_uri,
+            receiver: raw.receiver.into(),
+            sender: raw.sender.into(),
+            token_ids: raw.token_ids,
+            token_uris: raw.token_uris,
+        };
+        data.validate_basic()?;
+        Ok(data)
+    }
+}
+
+impl From<NonFungibleTokenPacketData> for RawNonFungibleTokenPacketData {
+    fn from(data: NonFungibleTokenPacketData) -> Self {
+        RawNonFungibleTokenPacketData {
+            class_id: data.class_id,
+            class_uri: data.class_uri,
+            token_ids: data.token_ids,
+            token_uris: data.token_uris,
+            sender: data.sender.to_string(),
+            receiver: data.receiver.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonFungibleTokenPacketData;
+
+    #[test]
+    fn packet_data_json_encoding() {
+        let data = NonFungibleTokenPacketData {
+            class_id: "nft-transfer/channel-0/kitties".to_string(),
+            class_uri: "https://kitties.example/class".to_string(),
+            receiver: "bob".parse().unwrap(),
+            sender: "alice".parse().unwrap(),
+            token_ids: vec!["kitty-1".to_string(), "kitty-2".to_string()],
+            token_uris: vec!["ipfs://kitty-1".to_string(), "ipfs://kitty-2".to_string()],
+        };
+
+        let bytes = data.to_json_bytes();
+        assert_eq!(
+            String::from_utf8(bytes.clone()).unwrap(),
+            r#"{"classId":"nft-transfer/channel-0/kitties","classUri":"https://kitties.example/class","receiver":"bob","sender":"alice","tokenIds":["kitty-1","kitty-2"],"tokenUris":["ipfs://kitty-1","ipfs://kitty-2"]}"#
+        );
+        assert_eq!(
+            NonFungibleTokenPacketData::from_json_bytes(&bytes).unwrap(),
+            data
+        );
+
+        let invalid: [&[u8]; 5] = [
+            // Not JSON.
+            &[0],
+            // No tokens.
+            br#"{"classId":"kitties","receiver":"bob","sender":"alice","tokenIds":[],"tokenUris":[]}"#,
+            // The same token twice.
+            br#"{"classId":"kitties","receiver":"bob","sender":"alice","tokenIds":["k","k"],"tokenUris":["",""]}"#,
+            // A token without URI.
+            br#"{"classId":"kitties","receiver":"bob","sender":"alice","tokenIds":["k"],"tokenUris":[]}"#,
+            // Missing receiver.
+            br#"{"classId":"kitties","sender":"alice","tokenIds":["k"],"tokenUris":[""]}"#,
+        ];
+        for bytes in invalid.iter() {
+            assert!(NonFungibleTokenPacketData::from_json_bytes(bytes).is_err());
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! This module implements the processing logic for ICS721 (NFT transfer) messages.
+
+use crate::application::ics721_nft_transfer::class::sender_chain_is_source;
+use crate::application::ics721_nft_transfer::context::Ics721Context;
+use crate::application::ics721_nft_transfer::error::Error;
+use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+use crate::ics04_channel::packet::Packet;
+
+pub mod on_acknowledgement_packet;
+pub mod on_recv_packet;
+pub mod on_timeout_packet;
+pub mod send_transfer;
+
+/// Gives the NFTs of a packet which could not be delivered back to their sender. This reverts
+/// the effect of `send_transfer`: NFTs which were escrowed are released from the escrow account,
+/// and vouchers which were burned are minted again. Either all the NFTs are refunded, or none is.
+fn refund_packet_tokens<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    data: &NonFungibleTokenPacketData,
+) -> Result<(), Error>
+where
+    Ctx: Ics721Context,
+{
+    let mut ctx_interim = ctx.clone();
+
+    if sender_chain_is_source(&packet.source_port, &packet.source_channel, &data.class_id) {
+        let escrow_address =
+            ctx_interim.get_nft_escrow_address(&packet.source_port, &packet.source_channel);
+        for token_id in &data.token_ids {
+            ctx_interim.transfer_nft(&data.class_id, token_id, &escrow_address, &data.sender)?;
+        }
+    } else {
+        for (token_id, token_uri) in data.tokens() {
+            ctx_interim.mint_nft(&data.class_id, token_id, token_uri, &data.sender)?;
+        }
+    }
+
+    *ctx = ctx_interim;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics721_nft_transfer::context::NftKeeper;
+    use crate::application::ics721_nft_transfer::{MODULE_ID, VERSION};
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics05_port::capabilities::ModuleId;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::test_utils::get_dummy_account_id;
+
+    /// Returns a context with an open ICS721 channel on the default port and channel identifiers,
+    /// in which the dummy account owns the NFT `kitty-1` of class `kitties`.
+    pub fn get_dummy_nft_transfer_context() -> MockContext {
+        let channel_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            VERSION.to_string(),
+        );
+
+        let mut ctx = get_dummy_transfer_context()
+            .with_channel(PortId::default(), ChannelId::default(), channel_end)
+            .with_channel_capability_claimed_by(
+                ModuleId::new(MODULE_ID),
+                PortId::default(),
+                ChannelId::default(),
+            );
+        ctx.save_class("kitties", "https://kitties.example/class")
+            .unwrap();
+        ctx.mint_nft(
+            "kitties",
+            "kitty-1",
+            "ipfs://kitty-1",
+            &get_dummy_account_id(),
+        )
+        .unwrap();
+        ctx
+    }
+}
@@ -0,0 +1,97 @@
+use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+use crate::application::ics721_nft_transfer::context::Ics721Context;
+use crate::application::ics721_nft_transfer::error::{Error, Kind};
+use crate::application::ics721_nft_transfer::events::NonFungibleTokenPacket;
+use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+use crate::application::ics721_nft_transfer::relay_application_logic::refund_packet_tokens;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes the acknowledgement which the receiving chain wrote for an ICS721 packet sent by the
+/// host chain. If the acknowledgement signals an error, the NFTs are refunded to the sender.
+pub fn on_acknowledgement_packet<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    acknowledgement: &[u8],
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics721Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let data = NonFungibleTokenPacketData::from_json_bytes(&packet.data)?;
+    let ack = Acknowledgement::from_json_bytes(acknowledgement)
+        .map_err(|e| Kind::InvalidAcknowledgement.context(e))?;
+
+    if let Acknowledgement::Error(e) = &ack {
+        refund_packet_tokens(ctx, packet, &data)?;
+        output.log(format!(
+            "success: refunded {}[{}] after error acknowledgement: {}",
+            data.class_id,
+            data.token_ids.join(","),
+            e
+        ));
+    }
+
+    output.emit(IbcEvent::NonFungibleTokenPacket(NonFungibleTokenPacket {
+        height: Default::default(),
+        receiver: data.receiver,
+        class_id: data.class_id,
+        token_ids: data.token_ids,
+        success: ack.is_successful(),
+    }));
+
+    Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics721_nft_transfer::context::{Ics721Context, NftKeeper};
+    use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+    use crate::application::ics721_nft_transfer::relay_application_logic::on_acknowledgement_packet::on_acknowledgement_packet;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn acknowledgement_packet_processing() {
+        let mut ctx = MockContext::default();
+        let escrow_address = ctx.get_nft_escrow_address(&PortId::default(), &ChannelId::default());
+        ctx.save_class("kitties", "").unwrap();
+        ctx.mint_nft("kitties", "kitty-1", "", &escrow_address)
+            .unwrap();
+
+        let data = NonFungibleTokenPacketData {
+            class_id: "kitties".to_string(),
+            class_uri: String::new(),
+            receiver: "receiver".parse().unwrap(),
+            sender: "sender".parse().unwrap(),
+            token_ids: vec!["kitty-1".to_string()],
+            token_uris: vec![String::new()],
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+        packet.data = data.to_json_bytes();
+
+        // A malformed acknowledgement is rejected.
+        assert!(on_acknowledgement_packet(&mut ctx, &packet, &[1]).is_err());
+
+        // A successful acknowledgement leaves the NFTs in escrow.
+        let ack = Acknowledgement::success().to_json_bytes();
+        assert!(on_acknowledgement_packet(&mut ctx, &packet, &ack).is_ok());
+        assert_eq!(
+            ctx.get_owner("kitties", "kitty-1"),
+            Some(escrow_address.clone())
+        );
+
+        // An error acknowledgement refunds the sender.
+        let ack = Acknowledgement::from_error("failure").to_json_bytes();
+        let output = on_acknowledgement_packet(&mut ctx, &packet, &ack).unwrap();
+        assert_eq!(ctx.get_owner("kitties", "kitty-1"), Some(data.sender));
+        assert!(!output.events.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+use crate::application::ics721_nft_transfer::class::{prefix_class_id, unprefix_class_id};
+use crate::application::ics721_nft_transfer::context::Ics721Context;
+use crate::application::ics721_nft_transfer::error::Error;
+use crate::application::ics721_nft_transfer::events::NonFungibleTokenPacket;
+use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes an ICS721 packet received by the host chain, and returns the acknowledgement to be
+/// written for it. NFTs which return to this chain are released from escrow, whereas NFTs
+/// originating from other chains are represented by freshly minted vouchers. This function does
+/// not fail: any error is reported to the sending chain through an error acknowledgement, in
+/// which case none of the NFTs of the packet is received.
+pub fn on_recv_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> HandlerOutput<Acknowledgement>
+where
+    Ctx: Ics721Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let data = match NonFungibleTokenPacketData::from_json_bytes(&packet.data) {
+        Ok(data) => data,
+        Err(e) => {
+            output.log(format!("failure: cannot decode packet data: {}", e));
+            return output.with_result(Acknowledgement::from_error(e));
+        }
+    };
+
+    let mut ctx_interim = ctx.clone();
+    let (ack, class_id) = match receive_tokens(&mut ctx_interim, packet, &data) {
+        Ok(class_id) => {
+            *ctx = ctx_interim;
+            output.log(format!(
+                "success: received {}[{}]",
+                class_id,
+                data.token_ids.join(",")
+            ));
+            (Acknowledgement::success(), class_id)
+        }
+        Err(e) => {
+            output.log(format!("failure: cannot receive NFTs: {}", e));
+            (Acknowledgement::from_error(e), data.class_id)
+        }
+    };
+
+    output.emit(IbcEvent::NonFungibleTokenPacket(NonFungibleTokenPacket {
+        height: Default::default(),
+        receiver: data.receiver,
+        class_id,
+        token_ids: data.token_ids,
+        success: ack.is_successful(),
+    }));
+
+    output.with_result(ack)
+}
+
+/// Gives the NFTs of `packet` to their receiver. Returns the class of the NFTs on the host chain.
+fn receive_tokens<Ctx>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+    data: &NonFungibleTokenPacketData,
+) -> Result<String, Error>
+where
+    Ctx: Ics721Context,
+{
+    match unprefix_class_id(&packet.source_port, &packet.source_channel, &data.class_id) {
+        // The sending chain prefixed the class when it first received the NFTs over this channel,
+        // so they originate from this chain and are held in escrow here.
+        Some(class_id) => {
+            let escrow_address =
+                ctx.get_nft_escrow_address(&packet.destination_port, &packet.destination_channel);
+            for token_id in &data.token_ids {
+                ctx.transfer_nft(class_id, token_id, &escrow_address, &data.receiver)?;
+            }
+            Ok(class_id.to_string())
+        }
+        // Otherwise, the NFTs are represented by vouchers of a class tracing the channel they
+        // arrived on.
+        None => {
+            let class_id = prefix_class_id(
+                &packet.destination_port,
+                &packet.destination_channel,
+                &data.class_id,
+            );
+            ctx.save_class(&class_id, &data.class_uri)?;
+            for (token_id, token_uri) in data.tokens() {
+                ctx.mint_nft(&class_id, token_id, token_uri, &data.receiver)?;
+            }
+            Ok(class_id)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics721_nft_transfer::class::prefix_class_id;
+    use crate::application::ics721_nft_transfer::context::{Ics721Context, NftKeeper};
+    use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+    use crate::application::ics721_nft_transfer::relay_application_logic::on_recv_packet::on_recv_packet;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn recv_packet_processing() {
+        let mut ctx = MockContext::default();
+        let escrow_address = ctx.get_nft_escrow_address(&PortId::default(), &ChannelId::default());
+        let voucher_class = prefix_class_id(&PortId::default(), &ChannelId::default(), "kitties");
+
+        let mut data = NonFungibleTokenPacketData {
+            class_id: "kitties".to_string(),
+            class_uri: "https://kitties.example/class".to_string(),
+            receiver: "receiver".parse().unwrap(),
+            sender: "sender".parse().unwrap(),
+            token_ids: vec!["kitty-1".to_string(), "kitty-2".to_string()],
+            token_uris: vec!["ipfs://kitty-1".to_string(), "ipfs://kitty-2".to_string()],
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+
+        // Undecodable packet data yields an error acknowledgement.
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert!(!output.result.is_successful());
+
+        // NFTs from the sending chain are represented by vouchers.
+        packet.data = data.to_json_bytes();
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert_eq!(output.result, Acknowledgement::success());
+        assert_eq!(
+            ctx.get_class_uri(&voucher_class),
+            Some(data.class_uri.clone())
+        );
+        assert_eq!(
+            ctx.get_owner(&voucher_class, "kitty-2"),
+            Some(data.receiver.clone())
+        );
+        assert_eq!(
+            ctx.get_token_uri(&voucher_class, "kitty-2"),
+            Some("ipfs://kitty-2".to_string())
+        );
+
+        // NFTs returning to this chain must be held in escrow, and none is received otherwise.
+        data.class_id = voucher_class;
+        packet.data = data.to_json_bytes();
+        ctx.save_class("kitties", "").unwrap();
+        ctx.mint_nft("kitties", "kitty-1", "", &escrow_address)
+            .unwrap();
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert!(!output.result.is_successful());
+        assert_eq!(
+            ctx.get_owner("kitties", "kitty-1"),
+            Some(escrow_address.clone())
+        );
+
+        ctx.mint_nft("kitties", "kitty-2", "", &escrow_address)
+            .unwrap();
+        let output = on_recv_packet(&mut ctx, &packet);
+        assert_eq!(output.result, Acknowledgement::success());
+        assert_eq!(
+            ctx.get_owner("kitties", "kitty-1"),
+            Some(data.receiver.clone())
+        );
+        assert_eq!(ctx.get_owner("kitties", "kitty-2"), Some(data.receiver));
+        assert!(!output.events.is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::application::ics721_nft_transfer::context::Ics721Context;
+use crate::application::ics721_nft_transfer::error::Error;
+use crate::application::ics721_nft_transfer::events::NftTransferTimeout;
+use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+use crate::application::ics721_nft_transfer::relay_application_logic::refund_packet_tokens;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::packet::Packet;
+
+/// Processes an ICS721 packet sent by the host chain which timed out before being received, by
+/// refunding its NFTs to the sender.
+pub fn on_timeout_packet<Ctx>(ctx: &mut Ctx, packet: &Packet) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: Ics721Context,
+{
+    let mut output = HandlerOutput::builder();
+
+    let data = NonFungibleTokenPacketData::from_json_bytes(&packet.data)?;
+
+    refund_packet_tokens(ctx, packet, &data)?;
+
+    output.log(format!(
+        "success: refunded {}[{}] after timeout",
+        data.class_id,
+        data.token_ids.join(",")
+    ));
+    output.emit(IbcEvent::NftTransferTimeout(NftTransferTimeout {
+        height: Default::default(),
+        refund_receiver: data.sender,
+        refund_class_id: data.class_id,
+        refund_token_ids: data.token_ids,
+    }));
+
+    Ok(output.with_result(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use crate::application::ics721_nft_transfer::class::prefix_class_id;
+    use crate::application::ics721_nft_transfer::context::NftKeeper;
+    use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+    use crate::application::ics721_nft_transfer::relay_application_logic::on_timeout_packet::on_timeout_packet;
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn timeout_packet_processing() {
+        let mut ctx = MockContext::default();
+
+        // The sender transferred vouchers, which were burned and must be minted again.
+        let voucher_class = prefix_class_id(&PortId::default(), &ChannelId::default(), "kitties");
+        ctx.save_class(&voucher_class, "").unwrap();
+        let data = NonFungibleTokenPacketData {
+            class_id: voucher_class.clone(),
+            class_uri: String::new(),
+            receiver: "receiver".parse().unwrap(),
+            sender: "sender".parse().unwrap(),
+            token_ids: vec!["kitty-1".to_string()],
+            token_uris: vec!["ipfs://kitty-1".to_string()],
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+
+        // The packet data cannot be decoded.
+        assert!(on_timeout_packet(&mut ctx, &packet).is_err());
+
+        packet.data = data.to_json_bytes();
+        let output = on_timeout_packet(&mut ctx, &packet).unwrap();
+        assert_eq!(ctx.get_owner(&voucher_class, "kitty-1"), Some(data.sender));
+        assert_eq!(
+            ctx.get_token_uri(&voucher_class, "kitty-1"),
+            Some("ipfs://kitty-1".to_string())
+        );
+        assert!(!output.events.is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::application::ics721_nft_transfer::class::sender_chain_is_source;
+use crate::application::ics721_nft_transfer::context::Ics721Context;
+use crate::application::ics721_nft_transfer::error::{Error, Kind};
+use crate::application::ics721_nft_transfer::events::NftTransfer;
+use crate::application::ics721_nft_transfer::msgs::transfer::MsgTransfer;
+use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+use crate::events::IbcEvent;
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::handler::send_packet::send_packet;
+use crate::ics04_channel::packet::{Packet, PacketResult};
+use crate::ics05_port::capabilities::CapabilityName;
+use crate::ics05_port::context::ScopedCapabilityKeeper;
+
+/// Sends the NFTs of `msg` in a packet over the channel of `msg`, presenting the capability which
+/// the ICS721 module holds in `scope` for that channel.
+pub(crate) fn send_transfer<Ctx>(
+    ctx: &mut Ctx,
+    scope: &ScopedCapabilityKeeper,
+    msg: MsgTransfer,
+) -> Result<HandlerOutput<PacketResult>, Error>
+where
+    Ctx: Ics721Context,
+{
+    let source_channel_end = ctx
+        .channel_end(&(msg.source_port.clone(), msg.source_channel.clone()))
+        .ok_or_else(|| {
+            Kind::ChannelNotFound(msg.source_port.clone(), msg.source_channel.clone())
+        })?;
+
+    let destination_port = source_channel_end.counterparty().port_id().clone();
+    let destination_channel = source_channel_end
+        .counterparty()
+        .channel_id()
+        .ok_or_else(|| {
+            Kind::DestinationChannelNotFound(msg.source_port.clone(), msg.source_channel.clone())
+        })?;
+
+    let sequence = ctx
+        .get_next_sequence_send(&(msg.source_port.clone(), msg.source_channel.clone()))
+        .ok_or_else(|| {
+            Kind::SequenceSendNotFound(msg.source_port.clone(), msg.source_channel.clone())
+        })?;
+
+    // The packet carries the URIs of the class and of the NFTs, so that the receiving chain can
+    // record them for the vouchers.
+    let class_uri = ctx
+        .get_class_uri(&msg.class_id)
+        .ok_or_else(|| Kind::ClassNotFound(msg.class_id.clone()))?;
+    let token_uris = msg
+        .token_ids
+        .iter()
+        .map(|token_id| {
+            let owner = ctx
+                .get_owner(&msg.class_id, token_id)
+                .ok_or_else(|| Kind::TokenNotFound(msg.class_id.clone(), token_id.clone()))?;
+            if owner != msg.sender {
+                return Err(Kind::UnauthorizedOwner(
+                    msg.class_id.clone(),
+                    token_id.clone(),
+                    msg.sender.clone(),
+                ));
+            }
+
+            ctx.get_token_uri(&msg.class_id, token_id)
+                .ok_or_else(|| Kind::TokenNotFound(msg.class_id.clone(), token_id.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let data = NonFungibleTokenPacketData {
+        class_id: msg.class_id,
+        class_uri,
+        receiver: msg.receiver,
+        sender: msg.sender,
+        token_ids: msg.token_ids,
+        token_uris,
+    };
+    data.validate_basic()?;
+
+    let packet = Packet {
+        sequence,
+        source_port: msg.source_port,
+        source_channel: msg.source_channel,
+        destination_port,
+        destination_channel: destination_channel.clone(),
+        data: data.to_json_bytes(),
+        timeout_height: msg.timeout_height,
+        timeout_timestamp: msg.timeout_timestamp,
+    };
+
+    let channel_cap = scope
+        .get_capability(
+            ctx,
+            &CapabilityName::channel(&packet.source_port, &packet.source_channel),
+        )
+        .ok_or_else(|| {
+            Kind::ChannelCapabilityNotFound(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+            )
+        })?;
+
+    let mut handler_output = send_packet(ctx, &channel_cap, packet.clone())
+        .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+    // The packet is valid, so the NFTs can now leave the sender's account. If the class
+    // originates from this chain, the NFTs are held in escrow until they return (or are
+    // refunded). Otherwise, the sender holds vouchers, which are burned.
+    if sender_chain_is_source(&packet.source_port, &packet.source_channel, &data.class_id) {
+        let escrow_address =
+            ctx.get_nft_escrow_address(&packet.source_port, &packet.source_channel);
+        for token_id in &data.token_ids {
+            ctx.transfer_nft(&data.class_id, token_id, &data.sender, &escrow_address)?;
+        }
+    } else {
+        for token_id in &data.token_ids {
+            ctx.burn_nft(&data.class_id, token_id, &data.sender)?;
+        }
+    }
+
+    handler_output.log.push(format!(
+        "success: escrowed or burned {}[{}]",
+        data.class_id,
+        data.token_ids.join(",")
+    ));
+    handler_output
+        .events
+        .push(IbcEvent::NftTransfer(NftTransfer {
+            height: Default::default(),
+            sender: data.sender,
+            receiver: data.receiver,
+            class_id: data.class_id,
+            token_ids: data.token_ids,
+        }));
+
+    Ok(handler_output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::application::ics721_nft_transfer::class::prefix_class_id;
+    use crate::application::ics721_nft_transfer::context::{Ics721Context, NftKeeper};
+    use crate::application::ics721_nft_transfer::msgs::transfer::test_util::get_dummy_msg_nft_transfer;
+    use crate::application::ics721_nft_transfer::packet::NonFungibleTokenPacketData;
+    use crate::application::ics721_nft_transfer::relay_application_logic::send_transfer::send_transfer;
+    use crate::application::ics721_nft_transfer::relay_application_logic::test_util::get_dummy_nft_transfer_context;
+    use crate::application::ics721_nft_transfer::MODULE_ID;
+    use crate::events::IbcEvent;
+    use crate::ics04_channel::packet::PacketResult;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::signer::Signer;
+
+    #[test]
+    fn send_transfer_processing() {
+        let mut context = get_dummy_nft_transfer_context();
+        let scope = context.scope_to_module(MODULE_ID).unwrap();
+        let msg = get_dummy_msg_nft_transfer(1);
+        let sender = msg.sender.clone();
+        let escrow_address =
+            context.get_nft_escrow_address(&PortId::default(), &ChannelId::default());
+
+        // The sender does not own the NFT.
+        let mut msg_not_owner = msg.clone();
+        msg_not_owner.sender = Signer::new("thief");
+        assert!(send_transfer(&mut context.clone(), &scope, msg_not_owner).is_err());
+
+        // The NFT does not exist.
+        let mut msg_unknown = msg.clone();
+        msg_unknown.token_ids = vec!["kitty-2".to_string()];
+        assert!(send_transfer(&mut context.clone(), &scope, msg_unknown).is_err());
+
+        // NFTs native to the host chain are escrowed.
+        let mut ctx = context.clone();
+        let output = send_transfer(&mut ctx, &scope, msg.clone()).unwrap();
+        assert_eq!(ctx.get_owner("kitties", "kitty-1"), Some(escrow_address));
+        assert!(output
+            .events
+            .iter()
+            .any(|e| matches!(e, IbcEvent::NftTransfer(_))));
+        match output.result {
+            PacketResult::Send(res) => {
+                let data = NonFungibleTokenPacketData::from_json_bytes(&res.data).unwrap();
+                assert_eq!(data.class_id, "kitties");
+                assert_eq!(data.class_uri, "https://kitties.example/class");
+                assert_eq!(data.token_uris, vec!["ipfs://kitty-1".to_string()]);
+                assert_eq!(data.sender, sender);
+            }
+            _ => panic!("unexpected packet result"),
+        }
+
+        // Vouchers for NFTs which arrived over the same channel are burned.
+        let voucher_class = prefix_class_id(&PortId::default(), &ChannelId::default(), "kitties");
+        let mut ctx = context;
+        ctx.save_class(&voucher_class, "").unwrap();
+        ctx.mint_nft(&voucher_class, "kitty-9", "", &sender)
+            .unwrap();
+        let mut msg_voucher = msg;
+        msg_voucher.class_id = voucher_class.clone();
+        msg_voucher.token_ids = vec!["kitty-9".to_string()];
+        send_transfer(&mut ctx, &scope, msg_voucher).unwrap();
+        assert_eq!(ctx.get_owner(&voucher_class, "kitty-9"), None);
+    }
+}
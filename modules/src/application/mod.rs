@@ -1 +1,4 @@
 pub mod ics20_fungible_token_transfer;
+pub mod ics27_interchain_accounts;
+pub mod ics29_fee;
+pub mod ics721_nft_transfer;
@@ -3,6 +3,9 @@ use std::collections::HashMap;
 use anomaly::BoxError;
 use serde_derive::{Deserialize, Serialize};
 
+use crate::application::ics20_fungible_token_transfer::events as TransferEvents;
+use crate::application::ics29_fee::events as FeeEvents;
+use crate::application::ics721_nft_transfer::events as NftTransferEvents;
 use crate::ics02_client::events as ClientEvents;
 use crate::ics02_client::events::NewBlock;
 use crate::ics03_connection::events as ConnectionEvents;
@@ -52,6 +55,11 @@ pub enum IbcEvent {
     OpenConfirmChannel(ChannelEvents::OpenConfirm),
     CloseInitChannel(ChannelEvents::CloseInit),
     CloseConfirmChannel(ChannelEvents::CloseConfirm),
+    UpgradeInitChannel(ChannelEvents::UpgradeInit),
+    UpgradeTryChannel(ChannelEvents::UpgradeTry),
+    UpgradeAckChannel(ChannelEvents::UpgradeAck),
+    UpgradeConfirmChannel(ChannelEvents::UpgradeConfirm),
+    UpgradeCancelChannel(ChannelEvents::UpgradeCancel),
 
     SendPacket(ChannelEvents::SendPacket),
     ReceivePacket(ChannelEvents::ReceivePacket),
@@ -60,6 +68,18 @@ pub enum IbcEvent {
     TimeoutPacket(ChannelEvents::TimeoutPacket),
     TimeoutOnClosePacket(ChannelEvents::TimeoutOnClosePacket),
 
+    Transfer(TransferEvents::Transfer),
+    FungibleTokenPacket(TransferEvents::FungibleTokenPacket),
+    TransferTimeout(TransferEvents::TransferTimeout),
+
+    NftTransfer(NftTransferEvents::NftTransfer),
+    NonFungibleTokenPacket(NftTransferEvents::NonFungibleTokenPacket),
+    NftTransferTimeout(NftTransferEvents::NftTransferTimeout),
+
+    IncentivizedPacket(FeeEvents::IncentivizedPacket),
+    RegisterCounterpartyPayee(FeeEvents::RegisterCounterpartyPayee),
+    DistributeFee(FeeEvents::DistributeFee),
+
     Empty(String),      // Special event, signifying empty response
     ChainError(String), // Special event, signifying an error on CheckTx or DeliverTx
 }
@@ -97,6 +117,11 @@ impl fmt::Display for IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => write!(f, "OpenConfirmChannelEv({:?})", ev),
             IbcEvent::CloseInitChannel(ev) => write!(f, "CloseInitChannelEv({})", ev),
             IbcEvent::CloseConfirmChannel(ev) => write!(f, "CloseConfirmChannelEv({:?})", ev),
+            IbcEvent::UpgradeInitChannel(ev) => write!(f, "UpgradeInitChannelEv({:?})", ev),
+            IbcEvent::UpgradeTryChannel(ev) => write!(f, "UpgradeTryChannelEv({:?})", ev),
+            IbcEvent::UpgradeAckChannel(ev) => write!(f, "UpgradeAckChannelEv({:?})", ev),
+            IbcEvent::UpgradeConfirmChannel(ev) => write!(f, "UpgradeConfirmChannelEv({:?})", ev),
+            IbcEvent::UpgradeCancelChannel(ev) => write!(f, "UpgradeCancelChannelEv({:?})", ev),
 
             IbcEvent::SendPacket(ev) => write!(f, "SendPacketEv({})", ev),
             IbcEvent::ReceivePacket(ev) => write!(f, "ReceivePacketEv({})", ev),
@@ -105,6 +130,18 @@ impl fmt::Display for IbcEvent {
             IbcEvent::TimeoutPacket(ev) => write!(f, "TimeoutPacketEv({})", ev),
             IbcEvent::TimeoutOnClosePacket(ev) => write!(f, "TimeoutOnClosePacketEv({})", ev),
 
+            IbcEvent::Transfer(ev) => write!(f, "TransferEv({})", ev),
+            IbcEvent::FungibleTokenPacket(ev) => write!(f, "FungibleTokenPacketEv({})", ev),
+            IbcEvent::TransferTimeout(ev) => write!(f, "TransferTimeoutEv({})", ev),
+            IbcEvent::NftTransfer(ev) => write!(f, "NftTransferEv({})", ev),
+            IbcEvent::NonFungibleTokenPacket(ev) => write!(f, "NonFungibleTokenPacketEv({})", ev),
+            IbcEvent::NftTransferTimeout(ev) => write!(f, "NftTransferTimeoutEv({})", ev),
+            IbcEvent::IncentivizedPacket(ev) => write!(f, "IncentivizedPacketEv({})", ev),
+            IbcEvent::RegisterCounterpartyPayee(ev) => {
+                write!(f, "RegisterCounterpartyPayeeEv({})", ev)
+            }
+            IbcEvent::DistributeFee(ev) => write!(f, "DistributeFeeEv({})", ev),
+
             IbcEvent::Empty(ev) => write!(f, "EmptyEv({})", ev),
             IbcEvent::ChainError(ev) => write!(f, "ChainErrorEv({})", ev),
         }
@@ -149,11 +186,25 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => ev.height(),
             IbcEvent::CloseInitChannel(ev) => ev.height(),
             IbcEvent::CloseConfirmChannel(ev) => ev.height(),
+            IbcEvent::UpgradeInitChannel(ev) => ev.height(),
+            IbcEvent::UpgradeTryChannel(ev) => ev.height(),
+            IbcEvent::UpgradeAckChannel(ev) => ev.height(),
+            IbcEvent::UpgradeConfirmChannel(ev) => ev.height(),
+            IbcEvent::UpgradeCancelChannel(ev) => ev.height(),
             IbcEvent::SendPacket(ev) => ev.height(),
             IbcEvent::ReceivePacket(ev) => ev.height(),
             IbcEvent::WriteAcknowledgement(ev) => ev.height(),
             IbcEvent::AcknowledgePacket(ev) => ev.height(),
             IbcEvent::TimeoutPacket(ev) => ev.height(),
+            IbcEvent::Transfer(ev) => ev.height(),
+            IbcEvent::FungibleTokenPacket(ev) => ev.height(),
+            IbcEvent::TransferTimeout(ev) => ev.height(),
+            IbcEvent::NftTransfer(ev) => ev.height(),
+            IbcEvent::NonFungibleTokenPacket(ev) => ev.height(),
+            IbcEvent::NftTransferTimeout(ev) => ev.height(),
+            IbcEvent::IncentivizedPacket(ev) => ev.height(),
+            IbcEvent::RegisterCounterpartyPayee(ev) => ev.height(),
+            IbcEvent::DistributeFee(ev) => ev.height(),
             _ => unimplemented!(),
         }
     }
@@ -175,11 +226,25 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(ev) => ev.set_height(height),
             IbcEvent::CloseInitChannel(ev) => ev.set_height(height),
             IbcEvent::CloseConfirmChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeInitChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeTryChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeAckChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeConfirmChannel(ev) => ev.set_height(height),
+            IbcEvent::UpgradeCancelChannel(ev) => ev.set_height(height),
             IbcEvent::SendPacket(ev) => ev.set_height(height),
             IbcEvent::ReceivePacket(ev) => ev.set_height(height),
             IbcEvent::WriteAcknowledgement(ev) => ev.set_height(height),
             IbcEvent::AcknowledgePacket(ev) => ev.set_height(height),
             IbcEvent::TimeoutPacket(ev) => ev.set_height(height),
+            IbcEvent::Transfer(ev) => ev.set_height(height),
+            IbcEvent::FungibleTokenPacket(ev) => ev.set_height(height),
+            IbcEvent::TransferTimeout(ev) => ev.set_height(height),
+            IbcEvent::NftTransfer(ev) => ev.set_height(height),
+            IbcEvent::NonFungibleTokenPacket(ev) => ev.set_height(height),
+            IbcEvent::NftTransferTimeout(ev) => ev.set_height(height),
+            IbcEvent::IncentivizedPacket(ev) => ev.set_height(height),
+            IbcEvent::RegisterCounterpartyPayee(ev) => ev.set_height(height),
+            IbcEvent::DistributeFee(ev) => ev.set_height(height),
             _ => unimplemented!(),
         }
     }
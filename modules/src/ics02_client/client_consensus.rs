@@ -1,7 +1,6 @@
 use core::marker::{Send, Sync};
 use std::convert::{TryFrom, TryInto};
 
-use chrono::{DateTime, Utc};
 use prost_types::Any;
 use serde::Serialize;
 use tendermint_proto::Protobuf;
@@ -12,15 +11,24 @@ use crate::events::IbcEventType;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::{Error, Kind};
 use crate::ics02_client::height::Height;
+use crate::ics06_solomachine::consensus_state::ConsensusState as SoloMachineConsensusState;
 use crate::ics07_tendermint::consensus_state;
+use crate::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
 use crate::ics23_commitment::commitment::CommitmentRoot;
 use crate::ics24_host::identifier::ClientId;
 #[cfg(any(test, feature = "mocks"))]
 use crate::mock::client_state::MockConsensusState;
+use crate::timestamp::Timestamp;
 
 pub const TENDERMINT_CONSENSUS_STATE_TYPE_URL: &str =
     "/ibc.lightclients.tendermint.v1.ConsensusState";
 
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v1.ConsensusState";
+
+/// Localhost consensus states carry only an (empty) commitment root.
+pub const LOCALHOST_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.core.commitment.v1.MerkleRoot";
+
 pub const MOCK_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.mock.ConsensusState";
 
 #[dyn_clonable::clonable]
@@ -42,29 +50,35 @@ pub trait ConsensusState: Clone + std::fmt::Debug + Send + Sync {
 #[serde(tag = "type")]
 pub enum AnyConsensusState {
     Tendermint(consensus_state::ConsensusState),
+    SoloMachine(SoloMachineConsensusState),
+    Localhost(LocalhostConsensusState),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockConsensusState),
 }
 
 impl AnyConsensusState {
-    pub fn timestamp(&self) -> Result<u64, Kind> {
+    pub fn timestamp(&self) -> Result<Timestamp, Kind> {
         match self {
-            Self::Tendermint(cs_state) => {
-                let date: DateTime<Utc> = cs_state.timestamp.into();
-                let value = date.timestamp();
-                u64::try_from(value)
-                    .map_err(|_| Kind::NegativeConsensusStateTimestamp(value.to_string()))
-            }
+            Self::Tendermint(cs_state) => Timestamp::try_from(cs_state.timestamp)
+                .map_err(|_| Kind::NegativeConsensusStateTimestamp(cs_state.timestamp.to_string())),
+
+            Self::SoloMachine(cs_state) => Ok(Timestamp::from_nanoseconds(cs_state.timestamp)),
+
+            // Localhost clients do not track the time of the host chain, hence packets sent
+            // through them can time out only on height.
+            Self::Localhost(_cs_state) => Ok(Timestamp::none()),
 
             #[cfg(any(test, feature = "mocks"))]
-            Self::Mock(mock_state) => Ok(mock_state.timestamp()),
+            Self::Mock(mock_state) => Ok(Timestamp::from_nanoseconds(mock_state.timestamp())),
         }
     }
 
     pub fn client_type(&self) -> ClientType {
         match self {
             AnyConsensusState::Tendermint(_cs) => ClientType::Tendermint,
+            AnyConsensusState::SoloMachine(_cs) => ClientType::SoloMachine,
+            AnyConsensusState::Localhost(_cs) => ClientType::Localhost,
 
             #[cfg(any(test, feature = "mocks"))]
             AnyConsensusState::Mock(_cs) => ClientType::Mock,
@@ -86,6 +100,16 @@ impl TryFrom<Any> for AnyConsensusState {
                     .map_err(|e| Kind::InvalidRawConsensusState.context(e))?,
             )),
 
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::SoloMachine(
+                SoloMachineConsensusState::decode_vec(&value.value)
+                    .map_err(|e| Kind::InvalidRawConsensusState.context(e))?,
+            )),
+
+            LOCALHOST_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Localhost(
+                LocalhostConsensusState::decode_vec(&value.value)
+                    .map_err(|e| Kind::InvalidRawConsensusState.context(e))?,
+            )),
+
             #[cfg(any(test, feature = "mocks"))]
             MOCK_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::Mock(
                 MockConsensusState::decode_vec(&value.value)
@@ -104,6 +128,14 @@ impl From<AnyConsensusState> for Any {
                 type_url: TENDERMINT_CONSENSUS_STATE_TYPE_URL.to_string(),
                 value: value.encode_vec().unwrap(),
             },
+            AnyConsensusState::SoloMachine(value) => Any {
+                type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+                value: value.encode_vec().unwrap(),
+            },
+            AnyConsensusState::Localhost(value) => Any {
+                type_url: LOCALHOST_CONSENSUS_STATE_TYPE_URL.to_string(),
+                value: value.encode_vec().unwrap(),
+            },
             #[cfg(any(test, feature = "mocks"))]
             AnyConsensusState::Mock(value) => Any {
                 type_url: MOCK_CONSENSUS_STATE_TYPE_URL.to_string(),
@@ -154,7 +186,14 @@ impl ConsensusState for AnyConsensusState {
     }
 
     fn root(&self) -> &CommitmentRoot {
-        todo!()
+        match self {
+            Self::Tendermint(cs_state) => cs_state.root(),
+            Self::SoloMachine(cs_state) => cs_state.root(),
+            Self::Localhost(cs_state) => cs_state.root(),
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(mock_state) => mock_state.root(),
+        }
     }
 
     fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
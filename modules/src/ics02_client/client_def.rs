@@ -1,13 +1,22 @@
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
 use crate::downcast;
 use crate::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
 use crate::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::ics02_client::client_type::ClientType;
+use crate::ics02_client::context::ClientReader;
 use crate::ics02_client::error::Kind;
 use crate::ics02_client::header::{AnyHeader, Header};
+use crate::ics02_client::misbehaviour::{AnyMisbehaviour, Misbehaviour};
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
+use crate::ics06_solomachine::client_def::SoloMachineClient;
 use crate::ics07_tendermint::client_def::TendermintClient;
+use crate::ics09_localhost::client_def::LocalhostClient;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::Height;
@@ -19,17 +28,52 @@ pub trait ClientDef: Clone {
     type Header: Header;
     type ClientState: ClientState;
     type ConsensusState: ConsensusState;
+    type Misbehaviour: Misbehaviour;
 
-    /// TODO
+    /// Validates an incoming `header` against the client state and the consensus states the host
+    /// chain stores for client `client_id`. On success, returns the updated client state and the
+    /// consensus state derived from the header, which are to be persisted by the keeper.
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>>;
 
+    /// Verifies the evidence of `misbehaviour` against the consensus states the host chain stores
+    /// for client `client_id`. On success, returns the client state frozen at the height of the
+    /// misbehaviour, which is to be persisted by the keeper.
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
+
+    /// Verifies the proofs that the counterparty chain committed, ahead of a planned upgrade, to
+    /// the `upgraded_client_state` and `upgraded_consensus_state` of client `client_id`. On
+    /// success, returns the client state and the consensus state that replace the current ones.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_upgrade_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: &Self::ClientState,
+        upgraded_client_state: Self::ClientState,
+        upgraded_consensus_state: Self::ConsensusState,
+        proof_upgrade_client: MerkleProof,
+        proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>>;
+
     /// Verification functions as specified in:
     /// https://github.com/cosmos/ics/tree/master/spec/ics-002-client-semantics
     ///
+    /// On success, each of them returns the client state after the verification, against which
+    /// any subsequent proof must be verified and which is to be persisted by the host. Most
+    /// clients return `client_state` as is, whereas solo machine clients advance their sequence.
+    ///
     /// Verify a `proof` that the consensus state of a given client (at height `consensus_height`)
     /// matches the input `consensus_state`. The parameter `counterparty_height` represent the
     /// height of the counterparty chain that this proof assumes (i.e., the height at which this
@@ -37,43 +81,67 @@ pub trait ClientDef: Clone {
     #[allow(clippy::too_many_arguments)]
     fn verify_client_consensus_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a connection state matches that of the input `connection_end`.
+    #[allow(clippy::too_many_arguments)]
     fn verify_connection_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         connection_id: Option<&ConnectionId>,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a channel state matches that of the input `channel_end`.
     #[allow(clippy::too_many_arguments)]
     fn verify_channel_state(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
+
+    /// Verify a `proof` that the upgrade in progress of a channel end matches the input
+    /// `channel_upgrade`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_channel_upgrade(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_upgrade: &ChannelUpgrade,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify the client state for this chain that it is stored on the counterparty chain.
     #[allow(clippy::too_many_arguments)]
     fn verify_client_full_state(
         &self,
+        ctx: &dyn ConnectionReader,
         _client_state: &Self::ClientState,
         height: Height,
         root: &CommitmentRoot,
@@ -81,62 +149,76 @@ pub trait ClientDef: Clone {
         client_id: &ClientId,
         proof: &CommitmentProofBytes,
         client_state: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a packet has been commited.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_data(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        commitment: String,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a packet has been commited.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_acknowledgement(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
         ack: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that of the next_seq_received.
     #[allow(clippy::too_many_arguments)]
     fn verify_next_sequence_recv(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 
     /// Verify a `proof` that a packet has not been received.
     #[allow(clippy::too_many_arguments)]
     fn verify_packet_receipt_absence(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>>;
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>>;
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AnyClient {
     Tendermint(TendermintClient),
+    SoloMachine(SoloMachineClient),
+    Localhost(LocalhostClient),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockClient),
@@ -146,6 +228,8 @@ impl AnyClient {
     pub fn from_client_type(client_type: ClientType) -> AnyClient {
         match client_type {
             ClientType::Tendermint => Self::Tendermint(TendermintClient),
+            ClientType::SoloMachine => Self::SoloMachine(SoloMachineClient),
+            ClientType::Localhost => Self::Localhost(LocalhostClient),
 
             #[cfg(any(test, feature = "mocks"))]
             ClientType::Mock => Self::Mock(MockClient),
@@ -158,10 +242,13 @@ impl ClientDef for AnyClient {
     type Header = AnyHeader;
     type ClientState = AnyClientState;
     type ConsensusState = AnyConsensusState;
+    type Misbehaviour = AnyMisbehaviour;
 
     /// Validates an incoming `header` against the latest consensus state of this client.
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: AnyClientState,
         header: AnyHeader,
     ) -> Result<(AnyClientState, AnyConsensusState), Box<dyn std::error::Error>> {
@@ -174,7 +261,7 @@ impl ClientDef for AnyClient {
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
                 let (new_state, new_consensus) =
-                    client.check_header_and_update_state(client_state, header)?;
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
 
                 Ok((
                     AnyClientState::Tendermint(new_state),
@@ -182,6 +269,38 @@ impl ClientDef for AnyClient {
                 ))
             }
 
+            Self::SoloMachine(client) => {
+                let (client_state, header) = downcast!(
+                    client_state => AnyClientState::SoloMachine,
+                    header => AnyHeader::SoloMachine,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                let (new_state, new_consensus) =
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::SoloMachine(new_state),
+                    AnyConsensusState::SoloMachine(new_consensus),
+                ))
+            }
+
+            Self::Localhost(client) => {
+                let (client_state, header) = downcast!(
+                    client_state => AnyClientState::Localhost,
+                    header => AnyHeader::Localhost,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                let (new_state, new_consensus) =
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::Localhost(new_state),
+                    AnyConsensusState::Localhost(new_consensus),
+                ))
+            }
+
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(client) => {
                 let (client_state, header) = downcast!(
@@ -191,7 +310,197 @@ impl ClientDef for AnyClient {
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
                 let (new_state, new_consensus) =
-                    client.check_header_and_update_state(client_state, header)?;
+                    client.check_header_and_update_state(ctx, client_id, client_state, header)?;
+
+                Ok((
+                    AnyClientState::Mock(new_state),
+                    AnyConsensusState::Mock(new_consensus),
+                ))
+            }
+        }
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: AnyClientState,
+        misbehaviour: AnyMisbehaviour,
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
+        match self {
+            Self::Tendermint(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Tendermint,
+                    misbehaviour => AnyMisbehaviour::Tendermint,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Tendermint(new_state))
+            }
+
+            Self::SoloMachine(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::SoloMachine,
+                    misbehaviour => AnyMisbehaviour::SoloMachine,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::SoloMachine(new_state))
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Localhost(new_state))
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let (client_state, misbehaviour) = downcast!(
+                    client_state => AnyClientState::Mock,
+                    misbehaviour => AnyMisbehaviour::Mock,
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
+
+                let new_state = client.check_misbehaviour_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    misbehaviour,
+                )?;
+
+                Ok(AnyClientState::Mock(new_state))
+            }
+        }
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: &AnyClientState,
+        upgraded_client_state: AnyClientState,
+        upgraded_consensus_state: AnyConsensusState,
+        proof_upgrade_client: MerkleProof,
+        proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(AnyClientState, AnyConsensusState), Box<dyn std::error::Error>> {
+        match self {
+            Self::Tendermint(client) => {
+                let (client_state, upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    client_state => AnyClientState::Tendermint,
+                    upgraded_client_state => AnyClientState::Tendermint,
+                    upgraded_consensus_state => AnyConsensusState::Tendermint,
+                )
+                .map(|(cs, (ucs, ucons))| (cs, ucs, ucons))
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
+
+                let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                )?;
+
+                Ok((
+                    AnyClientState::Tendermint(new_state),
+                    AnyConsensusState::Tendermint(new_consensus),
+                ))
+            }
+
+            Self::SoloMachine(client) => {
+                let (client_state, upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    client_state => AnyClientState::SoloMachine,
+                    upgraded_client_state => AnyClientState::SoloMachine,
+                    upgraded_consensus_state => AnyConsensusState::SoloMachine,
+                )
+                .map(|(cs, (ucs, ucons))| (cs, ucs, ucons))
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                )?;
+
+                Ok((
+                    AnyClientState::SoloMachine(new_state),
+                    AnyConsensusState::SoloMachine(new_consensus),
+                ))
+            }
+
+            Self::Localhost(client) => {
+                let (client_state, upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    client_state => AnyClientState::Localhost,
+                    upgraded_client_state => AnyClientState::Localhost,
+                    upgraded_consensus_state => AnyConsensusState::Localhost,
+                )
+                .map(|(cs, (ucs, ucons))| (cs, ucs, ucons))
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                )?;
+
+                Ok((
+                    AnyClientState::Localhost(new_state),
+                    AnyConsensusState::Localhost(new_consensus),
+                ))
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let (client_state, upgraded_client_state, upgraded_consensus_state) = downcast!(
+                    client_state => AnyClientState::Mock,
+                    upgraded_client_state => AnyClientState::Mock,
+                    upgraded_consensus_state => AnyConsensusState::Mock,
+                )
+                .map(|(cs, (ucs, ucons))| (cs, ucs, ucons))
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
+
+                let (new_state, new_consensus) = client.verify_upgrade_and_update_state(
+                    ctx,
+                    client_id,
+                    client_state,
+                    upgraded_client_state,
+                    upgraded_consensus_state,
+                    proof_upgrade_client,
+                    proof_upgrade_consensus_state,
+                )?;
 
                 Ok((
                     AnyClientState::Mock(new_state),
@@ -203,14 +512,16 @@ impl ClientDef for AnyClient {
 
     fn verify_client_consensus_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         client_id: &ClientId,
         consensus_height: Height,
         expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -218,15 +529,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_client_consensus_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    client_id,
-                    consensus_height,
-                    expected_consensus_state,
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -236,41 +593,87 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_client_consensus_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    client_id,
-                    consensus_height,
-                    expected_consensus_state,
-                )
+                client
+                    .verify_client_consensus_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        client_id,
+                        consensus_height,
+                        expected_consensus_state,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_connection_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &AnyClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         connection_id: Option<&ConnectionId>,
         expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(client_state => AnyClientState::Tendermint)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_connection_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    connection_id,
-                    expected_connection_end,
-                )
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::SoloMachine)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -278,42 +681,90 @@ impl ClientDef for AnyClient {
                 let client_state = downcast!(client_state => AnyClientState::Mock)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_connection_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    connection_id,
-                    expected_connection_end,
-                )
+                client
+                    .verify_connection_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        connection_id,
+                        expected_connection_end,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_channel_state(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &AnyClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(client_state => AnyClientState::Tendermint)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_channel_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    port_id,
-                    channel_id,
-                    expected_channel_end,
-                )
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::SoloMachine)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -321,21 +772,118 @@ impl ClientDef for AnyClient {
                 let client_state = downcast!(client_state => AnyClientState::Mock)
                     .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_channel_state(
-                    client_state,
-                    height,
-                    prefix,
-                    proof,
-                    port_id,
-                    channel_id,
-                    expected_channel_end,
-                )
+                client
+                    .verify_channel_state(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_end,
+                    )
+                    .map(AnyClientState::Mock)
+            }
+        }
+    }
+
+    fn verify_channel_upgrade(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &AnyClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_upgrade: &ChannelUpgrade,
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
+        match self {
+            Self::Tendermint(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Tendermint)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
+
+                client
+                    .verify_channel_upgrade(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_upgrade,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(client_state => AnyClientState::SoloMachine)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_channel_upgrade(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_upgrade,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Localhost)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_channel_upgrade(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_upgrade,
+                    )
+                    .map(AnyClientState::Localhost)
+            }
+
+            #[cfg(any(test, feature = "mocks"))]
+            Self::Mock(client) => {
+                let client_state = downcast!(client_state => AnyClientState::Mock)
+                    .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
+
+                client
+                    .verify_channel_upgrade(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        expected_channel_upgrade,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_client_full_state(
         &self,
+        ctx: &dyn ConnectionReader,
         client_state: &Self::ClientState,
         height: Height,
         root: &CommitmentRoot,
@@ -343,7 +891,7 @@ impl ClientDef for AnyClient {
         client_id: &ClientId,
         proof: &CommitmentProofBytes,
         client_state_on_counterparty: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -351,15 +899,58 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_client_full_state(
-                    client_state,
-                    height,
-                    root,
-                    prefix,
-                    client_id,
-                    proof,
-                    client_state_on_counterparty,
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -369,28 +960,34 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_client_full_state(
-                    client_state,
-                    height,
-                    root,
-                    prefix,
-                    client_id,
-                    proof,
-                    client_state_on_counterparty,
-                )
+                client
+                    .verify_client_full_state(
+                        ctx,
+                        client_state,
+                        height,
+                        root,
+                        prefix,
+                        client_id,
+                        proof,
+                        client_state_on_counterparty,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
     fn verify_packet_data(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-        commitment: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        commitment: Vec<u8>,
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -398,15 +995,64 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_data(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    commitment,
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -416,29 +1062,37 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_packet_data(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    commitment,
-                )
+                client
+                    .verify_packet_data(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        commitment,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_packet_acknowledgement(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
         ack: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -446,15 +1100,64 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_acknowledgement(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    ack,
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -464,28 +1167,36 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_packet_acknowledgement(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                    ack,
-                )
+                client
+                    .verify_packet_acknowledgement(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                        ack,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
 
     fn verify_next_sequence_recv(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -493,14 +1204,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_next_sequence_recv(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -510,26 +1268,34 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_next_sequence_recv(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                )
+                client
+                    .verify_next_sequence_recv(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
     fn verify_packet_receipt_absence(
         &self,
+        ctx: &dyn ChannelReader,
         client_state: &Self::ClientState,
         height: Height,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
         port_id: &PortId,
         channel_id: &ChannelId,
         seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<AnyClientState, Box<dyn std::error::Error>> {
         match self {
             Self::Tendermint(client) => {
                 let client_state = downcast!(
@@ -537,14 +1303,61 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Tendermint))?;
 
-                client.verify_packet_receipt_absence(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Tendermint)
+            }
+
+            Self::SoloMachine(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::SoloMachine
                 )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::SoloMachine))?;
+
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::SoloMachine)
+            }
+
+            Self::Localhost(client) => {
+                let client_state = downcast!(
+                    client_state => AnyClientState::Localhost
+                )
+                .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Localhost))?;
+
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Localhost)
             }
 
             #[cfg(any(test, feature = "mocks"))]
@@ -554,14 +1367,19 @@ impl ClientDef for AnyClient {
                 )
                 .ok_or_else(|| Kind::ClientArgsTypeMismatch(ClientType::Mock))?;
 
-                client.verify_packet_receipt_absence(
-                    client_state,
-                    height,
-                    proof,
-                    port_id,
-                    channel_id,
-                    seq,
-                )
+                client
+                    .verify_packet_receipt_absence(
+                        ctx,
+                        client_state,
+                        height,
+                        prefix,
+                        proof,
+                        root,
+                        port_id,
+                        channel_id,
+                        seq,
+                    )
+                    .map(AnyClientState::Mock)
             }
         }
     }
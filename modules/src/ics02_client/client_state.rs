@@ -8,13 +8,17 @@ use tendermint_proto::Protobuf;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::{Error, Kind};
 
+use crate::ics06_solomachine::client_state::ClientState as SoloMachineClientState;
 use crate::ics07_tendermint::client_state;
+use crate::ics09_localhost::client_state::ClientState as LocalhostClientState;
 use crate::ics24_host::identifier::ChainId;
 #[cfg(any(test, feature = "mocks"))]
 use crate::mock::client_state::MockClientState;
 use crate::Height;
 
 pub const TENDERMINT_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.ClientState";
+pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v1.ClientState";
+pub const LOCALHOST_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.localhost.v1.ClientState";
 pub const MOCK_CLIENT_STATE_TYPE_URL: &str = "/ibc.mock.ClientState";
 
 #[dyn_clonable::clonable]
@@ -40,6 +44,8 @@ pub trait ClientState: Clone + std::fmt::Debug + Send + Sync {
 #[serde(tag = "type")]
 pub enum AnyClientState {
     Tendermint(client_state::ClientState),
+    SoloMachine(SoloMachineClientState),
+    Localhost(LocalhostClientState),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockClientState),
@@ -49,6 +55,8 @@ impl AnyClientState {
     pub fn latest_height(&self) -> Height {
         match self {
             Self::Tendermint(tm_state) => tm_state.latest_height(),
+            Self::SoloMachine(sm_state) => sm_state.latest_height(),
+            Self::Localhost(lh_state) => lh_state.latest_height(),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(mock_state) => mock_state.latest_height(),
@@ -58,6 +66,8 @@ impl AnyClientState {
     pub fn client_type(&self) -> ClientType {
         match self {
             Self::Tendermint(state) => state.client_type(),
+            Self::SoloMachine(state) => state.client_type(),
+            Self::Localhost(state) => state.client_type(),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(state) => state.client_type(),
@@ -79,6 +89,16 @@ impl TryFrom<Any> for AnyClientState {
                     .map_err(|e| Kind::InvalidRawClientState.context(e))?,
             )),
 
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::SoloMachine(
+                SoloMachineClientState::decode_vec(&raw.value)
+                    .map_err(|e| Kind::InvalidRawClientState.context(e))?,
+            )),
+
+            LOCALHOST_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Localhost(
+                LocalhostClientState::decode_vec(&raw.value)
+                    .map_err(|e| Kind::InvalidRawClientState.context(e))?,
+            )),
+
             #[cfg(any(test, feature = "mocks"))]
             MOCK_CLIENT_STATE_TYPE_URL => Ok(AnyClientState::Mock(
                 MockClientState::decode_vec(&raw.value)
@@ -97,6 +117,14 @@ impl From<AnyClientState> for Any {
                 type_url: TENDERMINT_CLIENT_STATE_TYPE_URL.to_string(),
                 value: value.encode_vec().unwrap(),
             },
+            AnyClientState::SoloMachine(value) => Any {
+                type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+                value: value.encode_vec().unwrap(),
+            },
+            AnyClientState::Localhost(value) => Any {
+                type_url: LOCALHOST_CLIENT_STATE_TYPE_URL.to_string(),
+                value: value.encode_vec().unwrap(),
+            },
             #[cfg(any(test, feature = "mocks"))]
             AnyClientState::Mock(value) => Any {
                 type_url: MOCK_CLIENT_STATE_TYPE_URL.to_string(),
@@ -110,6 +138,8 @@ impl ClientState for AnyClientState {
     fn chain_id(&self) -> ChainId {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.chain_id(),
+            AnyClientState::SoloMachine(sm_state) => sm_state.chain_id(),
+            AnyClientState::Localhost(lh_state) => lh_state.chain_id(),
 
             #[cfg(any(test, feature = "mocks"))]
             AnyClientState::Mock(mock_state) => mock_state.chain_id(),
@@ -127,6 +157,8 @@ impl ClientState for AnyClientState {
     fn is_frozen(&self) -> bool {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.is_frozen(),
+            AnyClientState::SoloMachine(sm_state) => sm_state.is_frozen(),
+            AnyClientState::Localhost(lh_state) => lh_state.is_frozen(),
 
             #[cfg(any(test, feature = "mocks"))]
             AnyClientState::Mock(mock_state) => mock_state.is_frozen(),
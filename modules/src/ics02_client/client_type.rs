@@ -8,6 +8,8 @@ use super::error;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ClientType {
     Tendermint = 1,
+    SoloMachine = 6,
+    Localhost = 9,
 
     #[cfg(any(test, feature = "mocks"))]
     Mock = 9999,
@@ -17,7 +19,9 @@ impl ClientType {
     /// Yields the identifier of this client type as a string
     pub fn as_string(&self) -> &'static str {
         match self {
+            Self::SoloMachine => "06-solomachine",
             Self::Tendermint => "07-tendermint",
+            Self::Localhost => "09-localhost",
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock => "9999-mock",
@@ -36,10 +40,12 @@ impl std::str::FromStr for ClientType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            "06-solomachine" => Ok(Self::SoloMachine),
             "07-tendermint" => Ok(Self::Tendermint),
+            "09-localhost" => Ok(Self::Localhost),
 
             #[cfg(any(test, feature = "mocks"))]
-            "mock" => Ok(Self::Mock),
+            "9999-mock" | "mock" => Ok(Self::Mock),
 
             _ => Err(error::Kind::UnknownClientType(s.to_string()).into()),
         }
@@ -52,6 +58,16 @@ mod tests {
 
     use super::ClientType;
 
+    #[test]
+    fn parse_solomachine_client_type() {
+        let client_type = ClientType::from_str("06-solomachine");
+
+        match client_type {
+            Ok(ClientType::SoloMachine) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
     #[test]
     fn parse_tendermint_client_type() {
         let client_type = ClientType::from_str("07-tendermint");
@@ -62,6 +78,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_localhost_client_type() {
+        let client_type = ClientType::from_str("09-localhost");
+
+        match client_type {
+            Ok(ClientType::Localhost) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
     #[test]
     fn parse_mock_client_type() {
         let client_type = ClientType::from_str("mock");
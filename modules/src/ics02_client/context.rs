@@ -2,11 +2,13 @@
 //! that any host chain must implement to be able to process any `ClientMsg`. See
 //! "ADR 003: IBC protocol implementation" for more details.
 
+use tendermint::Time;
+
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::Error;
-use crate::ics02_client::handler::ClientResult::{self, Create, Update, Upgrade};
+use crate::ics02_client::handler::ClientResult::{self, Create, Misbehaviour, Update, Upgrade};
 use crate::ics24_host::identifier::ClientId;
 use crate::Height;
 
@@ -16,6 +18,13 @@ pub trait ClientReader {
     fn client_state(&self, client_id: &ClientId) -> Option<AnyClientState>;
     fn consensus_state(&self, client_id: &ClientId, height: Height) -> Option<AnyConsensusState>;
 
+    /// Returns the current time of the host chain, against which headers are checked for expiry
+    /// of the trusting period and for clock drift.
+    fn host_time(&self) -> Time;
+
+    /// Returns the latest height of the host chain, up to which localhost clients can be updated.
+    fn host_height(&self) -> Height;
+
     /// Returns a natural number, counting how many clients have been created thus far.
     /// The value of this counter should increase only via method `ClientKeeper::increase_client_counter`.
     fn client_counter(&self) -> u64;
@@ -47,7 +56,19 @@ pub trait ClientKeeper {
                 )?;
                 Ok(())
             }
-            Upgrade(_) => unimplemented!(),
+            Upgrade(res) => {
+                self.store_client_state(res.client_id.clone(), res.client_state.clone())?;
+                self.store_consensus_state(
+                    res.client_id.clone(),
+                    res.client_state.latest_height(),
+                    res.consensus_state,
+                )?;
+                Ok(())
+            }
+            Misbehaviour(res) => {
+                self.store_client_state(res.client_id, res.client_state)?;
+                Ok(())
+            }
         }
     }
 
@@ -58,14 +79,15 @@ pub trait ClientKeeper {
         client_type: ClientType,
     ) -> Result<(), Error>;
 
-    /// Called upon successful client creation and update
+    /// Called upon successful client creation, update, upgrade and misbehaviour handling, as well
+    /// as after the verification of the proofs of a connection, channel or packet message
     fn store_client_state(
         &mut self,
         client_id: ClientId,
         client_state: AnyClientState,
     ) -> Result<(), Error>;
 
-    /// Called upon successful client creation and update
+    /// Called upon successful client creation, update and upgrade
     fn store_consensus_state(
         &mut self,
         client_id: ClientId,
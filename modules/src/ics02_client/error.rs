@@ -38,6 +38,15 @@ pub enum Kind {
     #[error("header verification failed")]
     HeaderVerificationFailure,
 
+    #[error("upgraded client height {1} must be higher than the client latest height {0}")]
+    LowUpgradeHeight(Height, Height),
+
+    #[error("client upgrade verification failed")]
+    UpgradeVerificationFailure,
+
+    #[error("misbehaviour verification failed")]
+    MisbehaviourVerificationFailure,
+
     #[error("unknown client state type: {0}")]
     UnknownClientStateType(String),
 
@@ -267,6 +267,12 @@ impl ClientMisbehaviour {
     }
 }
 
+impl From<Attributes> for ClientMisbehaviour {
+    fn from(attrs: Attributes) -> Self {
+        ClientMisbehaviour(attrs)
+    }
+}
+
 impl TryFrom<RawObject> for ClientMisbehaviour {
     type Error = BoxError;
     fn try_from(obj: RawObject) -> Result<Self, Self::Error> {
@@ -291,6 +297,12 @@ impl From<ClientMisbehaviour> for IbcEvent {
 pub struct UpgradeClient(Attributes);
 
 impl UpgradeClient {
+    pub fn client_id(&self) -> &ClientId {
+        &self.0.client_id
+    }
+    pub fn height(&self) -> Height {
+        self.0.height
+    }
     pub fn set_height(&mut self, height: Height) {
         self.0.height = height;
     }
@@ -86,8 +86,8 @@ mod tests {
         let height = Height::new(0, 42);
 
         let msg = MsgCreateAnyClient::new(
-            MockClientState(MockHeader::new(height)).into(),
-            MockConsensusState(MockHeader::new(height)).into(),
+            MockClientState::new(MockHeader::new(height)).into(),
+            MockConsensusState::new(MockHeader::new(height)).into(),
             signer,
         )
         .unwrap();
@@ -132,12 +132,12 @@ mod tests {
 
         let create_client_msgs: Vec<MsgCreateAnyClient> = vec![
             MsgCreateAnyClient::new(
-                MockClientState(MockHeader::new(Height {
+                MockClientState::new(MockHeader::new(Height {
                     revision_height: 42,
                     ..height
                 }))
                 .into(),
-                MockConsensusState(MockHeader::new(Height {
+                MockConsensusState::new(MockHeader::new(Height {
                     revision_height: 42,
                     ..height
                 }))
@@ -146,12 +146,12 @@ mod tests {
             )
             .unwrap(),
             MsgCreateAnyClient::new(
-                MockClientState(MockHeader::new(Height {
+                MockClientState::new(MockHeader::new(Height {
                     revision_height: 42,
                     ..height
                 }))
                 .into(),
-                MockConsensusState(MockHeader::new(Height {
+                MockConsensusState::new(MockHeader::new(Height {
                     revision_height: 42,
                     ..height
                 }))
@@ -160,12 +160,12 @@ mod tests {
             )
             .unwrap(),
             MsgCreateAnyClient::new(
-                MockClientState(MockHeader::new(Height {
+                MockClientState::new(MockHeader::new(Height {
                     revision_height: 50,
                     ..height
                 }))
                 .into(),
-                MockConsensusState(MockHeader::new(Height {
+                MockConsensusState::new(MockHeader::new(Height {
                     revision_height: 50,
                     ..height
                 }))
@@ -0,0 +1,349 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgSubmitAnyMisbehaviour`.
+
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_def::{AnyClient, ClientDef};
+use crate::ics02_client::client_state::{AnyClientState, ClientState};
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::error::{Error, Kind};
+use crate::ics02_client::events::Attributes;
+use crate::ics02_client::handler::ClientResult;
+use crate::ics02_client::misbehaviour::Misbehaviour;
+use crate::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+use crate::ics24_host::identifier::ClientId;
+
+/// The result following the successful processing of a `MsgSubmitAnyMisbehaviour` message.
+/// This data type should be used with a qualified name `misbehaviour::Result` to avoid ambiguity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Result {
+    pub client_id: ClientId,
+    pub client_state: AnyClientState,
+}
+
+pub fn process(
+    ctx: &dyn ClientReader,
+    msg: MsgSubmitAnyMisbehaviour,
+) -> HandlerResult<ClientResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    let MsgSubmitAnyMisbehaviour {
+        client_id,
+        misbehaviour,
+        signer: _,
+    } = msg;
+
+    // Read client state from the host chain store.
+    let client_state = ctx
+        .client_state(&client_id)
+        .ok_or_else(|| Kind::ClientNotFound(client_id.clone()))?;
+
+    if client_state.is_frozen() {
+        return Err(Kind::ClientFrozen(client_id).into());
+    }
+
+    let client_def = AnyClient::from_client_type(client_state.client_type());
+
+    // Verify the evidence of misbehaviour against the consensus states of the client. On success,
+    // this returns the client state frozen at the height of the misbehaviour.
+    let misbehaviour_height = misbehaviour.height();
+    let new_client_state = client_def
+        .check_misbehaviour_and_update_state(ctx, client_id.clone(), client_state, misbehaviour)
+        .map_err(|e| Kind::MisbehaviourVerificationFailure.context(e.to_string()))?;
+
+    let event_attributes = Attributes {
+        client_id: client_id.clone(),
+        client_type: new_client_state.client_type(),
+        consensus_height: misbehaviour_height,
+        ..Default::default()
+    };
+
+    let result = ClientResult::Misbehaviour(Result {
+        client_id,
+        client_state: new_client_state,
+    });
+
+    output.emit(IbcEvent::ClientMisbehaviour(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::ics02_client::client_state::ClientState;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics02_client::context::ClientReader;
+    use crate::ics02_client::error::Kind;
+    use crate::ics02_client::handler::dispatch;
+    use crate::ics02_client::handler::ClientResult::Misbehaviour;
+    use crate::ics02_client::header::AnyHeader;
+    use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+    use crate::ics02_client::msgs::misbehavior::MsgSubmitAnyMisbehaviour;
+    use crate::ics02_client::msgs::ClientMsg;
+    use crate::ics07_tendermint::header::Header as TendermintHeader;
+    use crate::ics07_tendermint::misbehaviour::Misbehaviour as TendermintMisbehaviour;
+    use crate::ics18_relayer::context::Ics18Context;
+    use crate::ics24_host::identifier::{ChainId, ClientId};
+    use crate::ics26_routing::msgs::Ics26Envelope;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::host::{HostBlock, HostType};
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::{downcast, Height};
+
+    /// Returns a message with two conflicting mock headers at `height`.
+    fn get_dummy_misbehaviour_msg(client_id: ClientId, height: Height) -> MsgSubmitAnyMisbehaviour {
+        MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Mock(MockMisbehaviour {
+                client_id,
+                header1: MockHeader {
+                    height,
+                    timestamp: 1,
+                },
+                header2: MockHeader {
+                    height,
+                    timestamp: 2,
+                },
+            }),
+            signer: get_dummy_account_id(),
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_ok() {
+        let client_id = ClientId::default();
+        let misbehaviour_height = Height::new(0, 43);
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_misbehaviour_msg(client_id.clone(), misbehaviour_height);
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg.clone()));
+
+        match output {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log,
+            }) => {
+                assert_eq!(events.len(), 1);
+                let event = events.pop().unwrap();
+                assert!(
+                    matches!(event, IbcEvent::ClientMisbehaviour(e) if e.client_id() == &msg.client_id)
+                );
+                assert!(log.is_empty());
+                // Check the result
+                match result {
+                    Misbehaviour(res) => {
+                        assert_eq!(res.client_id, client_id);
+                        assert!(res.client_state.is_frozen());
+                    }
+                    _ => panic!("misbehaviour handler result has incorrect type"),
+                }
+            }
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_freezes_client() {
+        let client_id = ClientId::default();
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_misbehaviour_msg(client_id.clone(), Height::new(0, 43));
+
+        let res = ctx.deliver(Ics26Envelope::Ics2Msg(ClientMsg::Misbehaviour(msg.clone())));
+        assert!(res.is_ok(), "unexpected error: {:?}", res);
+        assert!(ctx.client_state(&client_id).unwrap().is_frozen());
+
+        // A frozen client cannot be frozen again.
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::ClientFrozen(client_id));
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_nonexisting_client() {
+        let client_id = ClientId::from_str("mockclient1").unwrap();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_misbehaviour_msg(
+            ClientId::from_str("nonexistingclient").unwrap(),
+            Height::new(0, 43),
+        );
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg.clone()));
+
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::ClientNotFound(msg.client_id));
+            }
+        }
+    }
+
+    /// Returns the latest header of a synthetic Tendermint chain `chain_name` at height
+    /// `header_height`, with the trusted height of this header set to `trusted_height`.
+    fn get_synthetic_tendermint_header(
+        chain_name: &str,
+        trusted_height: Height,
+        header_height: Height,
+    ) -> TendermintHeader {
+        let ctx = MockContext::new(
+            ChainId::new(chain_name.to_string(), 1),
+            HostType::SyntheticTendermint,
+            5,
+            trusted_height,
+        )
+        .with_height(header_height);
+
+        let mut header =
+            downcast!(ctx.query_latest_header().unwrap() => AnyHeader::Tendermint).unwrap();
+        header.trusted_height = trusted_height;
+        header
+    }
+
+    /// Returns a header which conflicts with `header`: it is signed by the same validators, for
+    /// the same chain and height, but at an earlier time.
+    fn get_conflicting_tendermint_header(header: &TendermintHeader) -> TendermintHeader {
+        let light_block = HostBlock::generate_tm_block(
+            ChainId::from(header.signed_header.header.chain_id.clone()),
+            header.height().revision_height,
+            header.time() - Duration::from_secs(1),
+        );
+
+        let mut conflicting_header = TendermintHeader::from(light_block);
+        conflicting_header.trusted_height = header.trusted_height;
+        conflicting_header
+    }
+
+    /// Returns a context on chain A with a Tendermint client at height `client_height` for the
+    /// synthetic chain `mockgaiaB`.
+    fn get_context_with_tendermint_client(
+        client_id: &ClientId,
+        client_height: Height,
+    ) -> MockContext {
+        // The host clock should run ahead of the synthetic chain, so that its headers are not
+        // seen as coming from the future.
+        MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1),
+        )
+        .with_client_parametrized_with_chain_id(
+            ChainId::new("mockgaiaB".to_string(), 1),
+            client_id,
+            client_height,
+            Some(ClientType::Tendermint),
+            Some(client_height),
+        )
+        .with_height(Height::new(1, 3))
+    }
+
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_fork() {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+        let misbehaviour_height = Height::new(1, 22);
+
+        let ctx = get_context_with_tendermint_client(&client_id, client_height);
+
+        // The same validators sign two different blocks at the same height of the same chain.
+        let header1 =
+            get_synthetic_tendermint_header("mockgaiaB", client_height, misbehaviour_height);
+        let header2 = get_conflicting_tendermint_header(&header1);
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Tendermint(TendermintMisbehaviour {
+                client_id: client_id.clone(),
+                header1: header1.clone(),
+                header2,
+            }),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+
+        match output {
+            Ok(HandlerOutput { result, .. }) => match result {
+                Misbehaviour(res) => {
+                    assert_eq!(res.client_id, client_id);
+                    assert!(res.client_state.is_frozen());
+                }
+                _ => panic!("misbehaviour handler result has incorrect type"),
+            },
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+
+        // Two copies of the same header are no evidence of misbehaviour.
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Tendermint(TendermintMisbehaviour {
+                client_id,
+                header1: header1.clone(),
+                header2: header1,
+            }),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::MisbehaviourVerificationFailure);
+            }
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_other_chain() {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+        let misbehaviour_height = Height::new(1, 22);
+
+        let ctx = get_context_with_tendermint_client(&client_id, client_height);
+
+        // Conflicting headers of another chain are no evidence against this client.
+        let header1 =
+            get_synthetic_tendermint_header("mockgaiaC", client_height, misbehaviour_height);
+        let header2 = get_conflicting_tendermint_header(&header1);
+
+        let msg = MsgSubmitAnyMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: AnyMisbehaviour::Tendermint(TendermintMisbehaviour {
+                client_id,
+                header1,
+                header2,
+            }),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::Misbehaviour(msg));
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::MisbehaviourVerificationFailure);
+            }
+        }
+    }
+}
@@ -53,7 +53,7 @@ pub fn process(
     // This function will return the new client_state (its latest_height changed) and a
     // consensus_state obtained from header. These will be later persisted by the keeper.
     let (new_client_state, new_consensus_state) = client_def
-        .check_header_and_update_state(client_state, header)
+        .check_header_and_update_state(ctx, client_id.clone(), client_state, header)
         .map_err(|e| Kind::HeaderVerificationFailure.context(e.to_string()))?;
 
     let result = ClientResult::Update(Result {
@@ -74,22 +74,27 @@ pub fn process(
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use std::time::Duration;
 
     use crate::events::IbcEvent;
     use crate::handler::HandlerOutput;
     use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics02_client::client_type::ClientType;
     use crate::ics02_client::error::Kind;
     use crate::ics02_client::handler::dispatch;
     use crate::ics02_client::handler::ClientResult::Update;
-    use crate::ics02_client::header::Header;
+    use crate::ics02_client::header::{AnyHeader, Header};
     use crate::ics02_client::msgs::update_client::MsgUpdateAnyClient;
     use crate::ics02_client::msgs::ClientMsg;
-    use crate::ics24_host::identifier::ClientId;
+    use crate::ics07_tendermint::header::Header as TendermintHeader;
+    use crate::ics18_relayer::context::Ics18Context;
+    use crate::ics24_host::identifier::{ChainId, ClientId};
     use crate::mock::client_state::MockClientState;
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::mock::host::HostType;
     use crate::test_utils::get_dummy_account_id;
-    use crate::Height;
+    use crate::{downcast, Height};
 
     #[test]
     fn test_update_client_ok() {
@@ -123,7 +128,7 @@ mod tests {
                         assert_eq!(upd_res.client_id, client_id);
                         assert_eq!(
                             upd_res.client_state,
-                            AnyClientState::Mock(MockClientState(MockHeader::new(
+                            AnyClientState::Mock(MockClientState::new(MockHeader::new(
                                 msg.header.height()
                             )))
                         )
@@ -207,4 +212,105 @@ mod tests {
             }
         }
     }
+
+    /// Returns a context on chain A with a Tendermint client for chain B at height
+    /// `client_height`, and a synthetic Tendermint context for chain B whose latest header is at
+    /// height `header_height`, with the trusted height of this header set to `client_height`.
+    fn get_synthetic_tendermint_update(
+        client_id: &ClientId,
+        client_height: Height,
+        header_height: Height,
+    ) -> (MockContext, TendermintHeader) {
+        // The host clock of chain A should run ahead of chain B, so that the header of B is not
+        // seen as coming from the future when the update is processed.
+        let ctx_a = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1),
+        )
+        .with_client_parametrized_with_chain_id(
+            ChainId::new("mockgaiaB".to_string(), 1),
+            client_id,
+            client_height,
+            Some(ClientType::Tendermint),
+            Some(client_height),
+        )
+        .with_height(Height::new(1, 3));
+
+        let ctx_b = MockContext::new(
+            ChainId::new("mockgaiaB".to_string(), 1),
+            HostType::SyntheticTendermint,
+            5,
+            client_height,
+        )
+        .with_height(header_height);
+
+        let mut header =
+            downcast!(ctx_b.query_latest_header().unwrap() => AnyHeader::Tendermint).unwrap();
+        header.trusted_height = client_height;
+
+        (ctx_a, header)
+    }
+
+    #[test]
+    fn test_update_synthetic_tendermint_client_ok() {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+        let update_height = Height::new(1, 22);
+
+        let (ctx, header) =
+            get_synthetic_tendermint_update(&client_id, client_height, update_height);
+
+        let msg = MsgUpdateAnyClient {
+            client_id: client_id.clone(),
+            header: header.wrap_any(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg));
+
+        match output {
+            Ok(HandlerOutput { result, .. }) => match result {
+                Update(upd_res) => {
+                    assert_eq!(upd_res.client_id, client_id);
+                    assert_eq!(upd_res.client_state.latest_height(), update_height);
+                }
+                _ => panic!("update handler result has incorrect type"),
+            },
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_synthetic_tendermint_client_forged_header() {
+        let client_id = ClientId::new(ClientType::Tendermint, 0).unwrap();
+        let client_height = Height::new(1, 20);
+        let update_height = Height::new(1, 22);
+
+        let (ctx, mut header) =
+            get_synthetic_tendermint_update(&client_id, client_height, update_height);
+
+        // Tampering with the header invalidates the commit signed by the validators.
+        header.signed_header.header.time = header.time() - Duration::from_secs(1);
+
+        let msg = MsgUpdateAnyClient {
+            client_id,
+            header: header.wrap_any(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg));
+
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::HeaderVerificationFailure);
+            }
+        }
+    }
 }
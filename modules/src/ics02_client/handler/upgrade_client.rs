@@ -1,11 +1,14 @@
 //! Protocol logic specific to processing ICS2 messages of type `MsgUpgradeAnyClient`.
 //!
-use crate::handler::HandlerResult;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
 use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::{AnyClient, ClientDef};
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::client_state::ClientState;
 use crate::ics02_client::context::ClientReader;
 use crate::ics02_client::error::{Error, Kind};
+use crate::ics02_client::events::Attributes;
 use crate::ics02_client::handler::ClientResult;
 use crate::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
 use crate::ics24_host::identifier::ClientId;
@@ -23,7 +26,16 @@ pub fn process(
     ctx: &dyn ClientReader,
     msg: MsgUpgradeAnyClient,
 ) -> HandlerResult<ClientResult, Error> {
-    let MsgUpgradeAnyClient { client_id, .. } = msg;
+    let mut output = HandlerOutput::builder();
+
+    let MsgUpgradeAnyClient {
+        client_id,
+        client_state: upgraded_client_state,
+        consensus_state: upgraded_consensus_state,
+        proof_upgrade_client,
+        proof_upgrade_consensus_state,
+        signer: _,
+    } = msg;
 
     // Read client state from the host chain store.
     let client_state = ctx
@@ -34,20 +46,180 @@ pub fn process(
         return Err(Kind::ClientFrozen(client_id).into());
     }
 
-    // Not implemented yet: https://github.com/informalsystems/ibc-rs/issues/722
-    todo!()
-
-    // let result = ClientResult::Upgrade(Result {
-    //     client_id: client_id.clone(),
-    //     client_state: new_client_state,
-    //     consensus_state: new_consensus_state,
-    // });
-    //
-    // let event_attributes = Attributes {
-    //     client_id,
-    //     ..Default::default()
-    // };
-    // output.emit(IbcEvent::UpgradeClient(event_attributes.into()));
-    //
-    // Ok(output.with_result(result))
+    // The upgraded client must be at a height higher than that of the current client.
+    if upgraded_client_state.latest_height() <= client_state.latest_height() {
+        return Err(Kind::LowUpgradeHeight(
+            client_state.latest_height(),
+            upgraded_client_state.latest_height(),
+        )
+        .into());
+    }
+
+    let client_def = AnyClient::from_client_type(client_state.client_type());
+
+    // Verify the proofs of the upgraded client and consensus states against the latest consensus
+    // state of the client. This returns the client and consensus states that replace the current
+    // ones, to be persisted by the keeper.
+    let (new_client_state, new_consensus_state) = client_def
+        .verify_upgrade_and_update_state(
+            ctx,
+            client_id.clone(),
+            &client_state,
+            upgraded_client_state,
+            upgraded_consensus_state,
+            proof_upgrade_client,
+            proof_upgrade_consensus_state,
+        )
+        .map_err(|e| Kind::UpgradeVerificationFailure.context(e.to_string()))?;
+
+    let event_attributes = Attributes {
+        client_id: client_id.clone(),
+        client_type: new_client_state.client_type(),
+        consensus_height: new_client_state.latest_height(),
+        ..Default::default()
+    };
+
+    let result = ClientResult::Upgrade(Result {
+        client_id,
+        client_state: new_client_state,
+        consensus_state: new_consensus_state,
+    });
+
+    output.emit(IbcEvent::UpgradeClient(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics02_client::context::ClientReader;
+    use crate::ics02_client::error::Kind;
+    use crate::ics02_client::handler::dispatch;
+    use crate::ics02_client::handler::ClientResult::Upgrade;
+    use crate::ics02_client::msgs::upgrade_client::MsgUpgradeAnyClient;
+    use crate::ics02_client::msgs::ClientMsg;
+    use crate::ics24_host::identifier::ClientId;
+    use crate::ics26_routing::msgs::Ics26Envelope;
+    use crate::mock::client_state::{MockClientState, MockConsensusState};
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+
+    fn get_dummy_upgrade_msg(client_id: ClientId, upgrade_height: Height) -> MsgUpgradeAnyClient {
+        MsgUpgradeAnyClient {
+            client_id,
+            client_state: MockClientState::new(MockHeader::new(upgrade_height)).into(),
+            consensus_state: MockConsensusState::new(MockHeader::new(upgrade_height)).into(),
+            proof_upgrade_client: MerkleProof::default(),
+            proof_upgrade_consensus_state: MerkleProof::default(),
+            signer: get_dummy_account_id(),
+        }
+    }
+
+    #[test]
+    fn test_upgrade_client_ok() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_upgrade_msg(client_id.clone(), Height::new(1, 26));
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg.clone()));
+
+        match output {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log,
+            }) => {
+                assert_eq!(events.len(), 1);
+                let event = events.pop().unwrap();
+                assert!(
+                    matches!(event, IbcEvent::UpgradeClient(e) if e.client_id() == &msg.client_id)
+                );
+                assert!(log.is_empty());
+                // Check the result
+                match result {
+                    Upgrade(upg_res) => {
+                        assert_eq!(upg_res.client_id, client_id);
+                        assert_eq!(
+                            upg_res.client_state,
+                            AnyClientState::Mock(MockClientState::new(MockHeader::new(
+                                Height::new(1, 26)
+                            )))
+                        )
+                    }
+                    _ => panic!("upgrade handler result has incorrect type"),
+                }
+            }
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_upgrade_client_stored() {
+        let client_id = ClientId::default();
+        let upgrade_height = Height::new(1, 26);
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_upgrade_msg(client_id.clone(), upgrade_height);
+
+        let res = ctx.deliver(Ics26Envelope::Ics2Msg(ClientMsg::UpgradeClient(msg)));
+        assert!(res.is_ok(), "unexpected error: {:?}", res);
+
+        assert_eq!(
+            ctx.client_state(&client_id).map(|cs| cs.latest_height()),
+            Some(upgrade_height)
+        );
+        assert!(ctx.consensus_state(&client_id, upgrade_height).is_some());
+    }
+
+    #[test]
+    fn test_upgrade_nonexisting_client() {
+        let client_id = ClientId::from_str("mockclient1").unwrap();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_upgrade_msg(
+            ClientId::from_str("nonexistingclient").unwrap(),
+            Height::new(1, 26),
+        );
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg.clone()));
+
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(err.kind(), &Kind::ClientNotFound(msg.client_id));
+            }
+        }
+    }
+
+    #[test]
+    fn test_upgrade_client_low_height() {
+        let client_id = ClientId::default();
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42));
+        let msg = get_dummy_upgrade_msg(client_id, Height::new(0, 26));
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg));
+
+        match output {
+            Ok(_) => {
+                panic!("unexpected success (expected error)");
+            }
+            Err(err) => {
+                assert_eq!(
+                    err.kind(),
+                    &Kind::LowUpgradeHeight(Height::new(0, 42), Height::new(0, 26))
+                );
+            }
+        }
+    }
 }
@@ -6,12 +6,17 @@ use tendermint_proto::Protobuf;
 
 use crate::ics02_client::client_type::ClientType;
 use crate::ics02_client::error::{Error, Kind};
+use crate::ics06_solomachine::header::Header as SoloMachineHeader;
 use crate::ics07_tendermint::header::Header as TendermintHeader;
+use crate::ics09_localhost::header::Header as LocalhostHeader;
 #[cfg(any(test, feature = "mocks"))]
 use crate::mock::header::MockHeader;
 use crate::Height;
 
 pub const TENDERMINT_HEADER_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.Header";
+pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v1.Header";
+/// Localhost headers carry only the height of the host chain.
+pub const LOCALHOST_HEADER_TYPE_URL: &str = "/ibc.core.client.v1.Height";
 pub const MOCK_HEADER_TYPE_URL: &str = "/ibc.mock.Header";
 
 /// Abstract of consensus state update information
@@ -31,6 +36,8 @@ pub trait Header: Clone + std::fmt::Debug + Send + Sync {
 #[allow(clippy::large_enum_variant)]
 pub enum AnyHeader {
     Tendermint(TendermintHeader),
+    SoloMachine(SoloMachineHeader),
+    Localhost(LocalhostHeader),
 
     #[cfg(any(test, feature = "mocks"))]
     Mock(MockHeader),
@@ -40,6 +47,8 @@ impl Header for AnyHeader {
     fn client_type(&self) -> ClientType {
         match self {
             Self::Tendermint(header) => header.client_type(),
+            Self::SoloMachine(header) => header.client_type(),
+            Self::Localhost(header) => header.client_type(),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(header) => header.client_type(),
@@ -49,6 +58,8 @@ impl Header for AnyHeader {
     fn height(&self) -> Height {
         match self {
             Self::Tendermint(header) => header.height(),
+            Self::SoloMachine(header) => header.height(),
+            Self::Localhost(header) => header.height(),
 
             #[cfg(any(test, feature = "mocks"))]
             Self::Mock(header) => header.height(),
@@ -72,6 +83,16 @@ impl TryFrom<Any> for AnyHeader {
                     .map_err(|e| Kind::InvalidRawHeader.context(e))?,
             )),
 
+            SOLOMACHINE_HEADER_TYPE_URL => Ok(AnyHeader::SoloMachine(
+                SoloMachineHeader::decode_vec(&raw.value)
+                    .map_err(|e| Kind::InvalidRawHeader.context(e))?,
+            )),
+
+            LOCALHOST_HEADER_TYPE_URL => Ok(AnyHeader::Localhost(
+                LocalhostHeader::decode_vec(&raw.value)
+                    .map_err(|e| Kind::InvalidRawHeader.context(e))?,
+            )),
+
             #[cfg(any(test, feature = "mocks"))]
             MOCK_HEADER_TYPE_URL => Ok(AnyHeader::Mock(
                 MockHeader::decode_vec(&raw.value)
@@ -90,6 +111,14 @@ impl From<AnyHeader> for Any {
                 type_url: TENDERMINT_HEADER_TYPE_URL.to_string(),
                 value: header.encode_vec().unwrap(),
             },
+            AnyHeader::SoloMachine(header) => Any {
+                type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+                value: header.encode_vec().unwrap(),
+            },
+            AnyHeader::Localhost(header) => Any {
+                type_url: LOCALHOST_HEADER_TYPE_URL.to_string(),
+                value: header.encode_vec().unwrap(),
+            },
             #[cfg(any(test, feature = "mocks"))]
             AnyHeader::Mock(header) => Any {
                 type_url: MOCK_HEADER_TYPE_URL.to_string(),
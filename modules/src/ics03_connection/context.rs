@@ -40,7 +40,9 @@ pub trait ConnectionReader {
     fn host_consensus_state(&self, height: Height) -> Option<AnyConsensusState>;
 
     /// Function required by ICS 03. Returns the list of all possible versions that the connection
-    /// handshake protocol supports.
+    /// handshake protocol supports. Host chains override this method to configure the versions
+    /// they support, and thereby the features, e.g., the channel orderings, which the connections
+    /// they negotiate support.
     fn get_compatible_versions(&self) -> Vec<Version> {
         get_compatible_versions()
     }
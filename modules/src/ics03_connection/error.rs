@@ -94,6 +94,9 @@ pub enum Kind {
 
     #[error("the client state proof verification failed for client id: {0}")]
     ClientStateVerificationFailure(ClientId),
+
+    #[error("implementation specific")]
+    ImplementationSpecific,
 }
 
 impl Kind {
@@ -1,11 +1,12 @@
 //! This module implements the processing logic for ICS3 (connection open handshake) messages.
 
 use crate::handler::HandlerOutput;
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics03_connection::context::ConnectionReader;
 use crate::ics03_connection::error::Error;
 use crate::ics03_connection::msgs::ConnectionMsg;
-use crate::ics24_host::identifier::ConnectionId;
+use crate::ics24_host::identifier::{ClientId, ConnectionId};
 
 pub mod conn_open_ack;
 pub mod conn_open_confirm;
@@ -37,6 +38,11 @@ pub struct ConnectionResult {
 
     /// The connection end, which the handler produced as a result of processing the message.
     pub connection_end: ConnectionEnd,
+
+    /// The state of the client which verified the proofs of the message, as the verification
+    /// left it (e.g., a solo machine client advances its sequence), or `None` if the handler
+    /// verified no proof.
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
 /// General entry point for processing any type of message related to the ICS3 connection open
@@ -24,9 +24,13 @@ pub(crate) fn process(
         // A connection end must exist and must be Init or TryOpen; otherwise we return an error.
         Some(old_conn_end) => {
             // Check if the connection state is either Init or TryOpen and message version
-            // is compatible.
+            // is compatible. In the Init state, the counterparty picked the version among ours,
+            // possibly with a subset of its features.
             let state_is_consistent = old_conn_end.state_matches(&State::Init)
-                && old_conn_end.versions().contains(msg.version())
+                && old_conn_end
+                    .versions()
+                    .iter()
+                    .any(|version| version.is_compatible(msg.version()))
                 || old_conn_end.state_matches(&State::TryOpen)
                     && old_conn_end.versions().get(0).eq(&Some(msg.version()));
 
@@ -72,7 +76,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         msg.client_state(),
         &new_conn_end,
@@ -88,6 +92,7 @@ pub(crate) fn process(
     let result = ConnectionResult {
         connection_id: msg.connection_id().clone(),
         connection_id_state: ConnectionIdState::Reused,
+        client_state: Some((new_conn_end.client_id().clone(), client_state)),
         connection_end: new_conn_end,
     };
 
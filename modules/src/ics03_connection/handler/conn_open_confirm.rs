@@ -52,7 +52,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(ctx, None, &new_conn_end, &expected_conn, msg.proofs())?;
+    let client_state = verify_proofs(ctx, None, &new_conn_end, &expected_conn, msg.proofs())?;
 
     output.log("success: connection verification passed");
 
@@ -62,6 +62,7 @@ pub(crate) fn process(
     let result = ConnectionResult {
         connection_id: msg.connection_id().clone(),
         connection_id_state: ConnectionIdState::Reused,
+        client_state: Some((new_conn_end.client_id().clone(), client_state)),
         connection_end: new_conn_end,
     };
 
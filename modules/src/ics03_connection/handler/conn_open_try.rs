@@ -79,7 +79,7 @@ pub(crate) fn process(
     );
 
     // 2. Pass the details to the verification function.
-    verify_proofs(
+    let client_state = verify_proofs(
         ctx,
         msg.client_state(),
         &new_connection_end,
@@ -107,6 +107,7 @@ pub(crate) fn process(
         } else {
             ConnectionIdState::Reused
         },
+        client_state: Some((new_connection_end.client_id().clone(), client_state)),
         connection_end: new_connection_end,
     };
 
@@ -129,6 +130,7 @@ mod tests {
     use crate::ics03_connection::msgs::conn_open_try::test_util::get_dummy_raw_msg_conn_open_try;
     use crate::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
     use crate::ics03_connection::msgs::ConnectionMsg;
+    use crate::ics03_connection::version::Version;
     use crate::ics24_host::identifier::ChainId;
     use crate::mock::context::MockContext;
     use crate::mock::host::HostType;
@@ -215,10 +217,16 @@ mod tests {
                 msg: ConnectionMsg::ConnectionOpenTry(Box::new(msg_conn_try.clone())),
                 want_pass: false,
             },
+            Test {
+                name: "Processing fails because the host supports none of the counterparty versions".to_string(),
+                ctx: context.clone().with_client(msg_conn_try.client_id(), Height::new(0, client_consensus_state_height)).with_connection_versions(vec![Version::new("2".to_string(), vec!["ORDER_UNORDERED".to_string()])]),
+                msg: ConnectionMsg::ConnectionOpenTry(Box::new(msg_conn_try.clone().with_previous_connection_id(None))),
+                want_pass: false,
+            },
             Test {
                 name: "Good parameters".to_string(),
-                ctx: context.with_client(msg_conn_try.client_id(), Height::new(0, client_consensus_state_height)),
-                msg: ConnectionMsg::ConnectionOpenTry(Box::new(msg_conn_try.with_previous_connection_id(None))),
+                ctx: context.clone().with_client(msg_conn_try.client_id(), Height::new(0, client_consensus_state_height)),
+                msg: ConnectionMsg::ConnectionOpenTry(Box::new(msg_conn_try.clone().with_previous_connection_id(None))),
                 want_pass: true,
             },
         ]
@@ -261,5 +269,22 @@ mod tests {
                 }
             }
         }
+
+        // A host which supports only unordered channels negotiates connections with that feature.
+        let unordered = Version::new("1".to_string(), vec!["ORDER_UNORDERED".to_string()]);
+        let ctx = context
+            .with_client(
+                msg_conn_try.client_id(),
+                Height::new(0, client_consensus_state_height),
+            )
+            .with_connection_versions(vec![unordered.clone()]);
+        let output = dispatch(
+            &ctx,
+            ConnectionMsg::ConnectionOpenTry(Box::new(
+                msg_conn_try.with_previous_connection_id(None),
+            )),
+        )
+        .unwrap();
+        assert_eq!(output.result.connection_end.versions(), vec![unordered]);
     }
 }
@@ -10,16 +10,22 @@ use crate::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::proofs::{ConsensusProof, Proofs};
 use crate::Height;
 
-/// Entry point for verifying all proofs bundled in any ICS3 message.
+/// Entry point for verifying all proofs bundled in any ICS3 message. On success, returns the state
+/// of the client of `connection_end` after the verification, which the host is to persist.
 pub fn verify_proofs(
     ctx: &dyn ConnectionReader,
-    client_state: Option<AnyClientState>,
+    expected_client_state: Option<AnyClientState>,
     connection_end: &ConnectionEnd,
     expected_conn: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    verify_connection_proof(
+) -> Result<AnyClientState, Error> {
+    // Each verification may advance the client state, e.g., the sequence of a solo machine
+    // client, hence every proof is verified against the state the previous one left.
+    let client_state = connection_client_state(ctx, connection_end)?;
+
+    let client_state = verify_connection_proof(
         ctx,
+        client_state,
         connection_end,
         expected_conn,
         proofs.height(),
@@ -27,9 +33,10 @@ pub fn verify_proofs(
     )?;
 
     // If the message includes a client state, then verify the proof for that state.
-    if let Some(expected_client_state) = client_state {
-        verify_client_proof(
+    let client_state = match expected_client_state {
+        Some(expected_client_state) => verify_client_proof(
             ctx,
+            client_state,
             connection_end,
             expected_client_state,
             proofs.height(),
@@ -37,65 +44,68 @@ pub fn verify_proofs(
                 .client_proof()
                 .as_ref()
                 .ok_or(Kind::NullClientProof)?,
-        )?;
-    }
+        )?,
+        None => client_state,
+    };
 
     // If a consensus proof is attached to the message, then verify it.
-    if let Some(proof) = proofs.consensus_proof() {
-        Ok(verify_consensus_proof(
-            ctx,
-            connection_end,
-            proofs.height(),
-            &proof,
-        )?)
-    } else {
-        Ok(())
+    match proofs.consensus_proof() {
+        Some(proof) => {
+            verify_consensus_proof(ctx, client_state, connection_end, proofs.height(), &proof)
+        }
+        None => Ok(client_state),
     }
 }
 
-/// Verifies the authenticity and semantic correctness of a commitment `proof`. The commitment
-/// claims to prove that an object of type connection exists on the source chain (i.e., the chain
-/// which created this proof). This object must match the state of `expected_conn`.
-pub fn verify_connection_proof(
+/// Fetches the state of the client of `connection_end` (IBC client on the local/host chain),
+/// which must not be frozen.
+fn connection_client_state(
     ctx: &dyn ConnectionReader,
     connection_end: &ConnectionEnd,
-    expected_conn: &ConnectionEnd,
-    proof_height: Height,
-    proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local/host chain).
+) -> Result<AnyClientState, Error> {
     let client_state = ctx
         .client_state(connection_end.client_id())
         .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
 
-    // The client must not be frozen.
     if client_state.is_frozen() {
         return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
     }
 
+    Ok(client_state)
+}
+
+/// Verifies the authenticity and semantic correctness of a commitment `proof`. The commitment
+/// claims to prove that an object of type connection exists on the source chain (i.e., the chain
+/// which created this proof). This object must match the state of `expected_conn`. On success,
+/// returns the client state after the verification.
+pub fn verify_connection_proof(
+    ctx: &dyn ConnectionReader,
+    client_state: AnyClientState,
+    connection_end: &ConnectionEnd,
+    expected_conn: &ConnectionEnd,
+    proof_height: Height,
+    proof: &CommitmentProofBytes,
+) -> Result<AnyClientState, Error> {
     // The client must have the consensus state for the height where this proof was created.
-    if ctx
+    let consensus_state = ctx
         .client_consensus_state(connection_end.client_id(), proof_height)
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(
-            proof_height,
-            connection_end.client_id().clone(),
-        )
-        .into());
-    }
+        .ok_or_else(|| {
+            Kind::MissingClientConsensusState(proof_height, connection_end.client_id().clone())
+        })?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
     // Verify the proof for the connection state against the expected connection end.
-    // A counterparty connection id of None causes `unwrap()` below and indicates an internal
+    // A counterparty connection id of None fails the verification and indicates an internal
     // error as this is the connection id on the counterparty chain that must always be present.
     Ok(client_def
         .verify_connection_state(
+            ctx,
             &client_state,
             proof_height,
             connection_end.counterparty().prefix(),
             proof,
+            consensus_state.root(),
             connection_end.counterparty().connection_id(),
             expected_conn,
         )
@@ -108,23 +118,15 @@ pub fn verify_connection_proof(
 /// is running on the counterparty chain (the chain which sent this message). This method does a
 /// complete verification: that the client state the counterparty stores is valid (i.e., not frozen,
 /// at the same revision as the current chain, with matching chain identifiers, etc) and that the
-/// `proof` is correct.
+/// `proof` is correct. On success, returns the local client state after the verification.
 pub fn verify_client_proof(
     ctx: &dyn ConnectionReader,
+    client_state: AnyClientState,
     connection_end: &ConnectionEnd,
     expected_client_state: AnyClientState,
     proof_height: Height,
     proof: &CommitmentProofBytes,
-) -> Result<(), Error> {
-    // Fetch the local client state (IBC client running on the host chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
-
+) -> Result<AnyClientState, Error> {
     let consensus_state = ctx
         .client_consensus_state(connection_end.client_id(), proof_height)
         .ok_or_else(|| {
@@ -135,6 +137,7 @@ pub fn verify_client_proof(
 
     Ok(client_def
         .verify_client_full_state(
+            ctx,
             &client_state,
             proof_height,
             consensus_state.root(),
@@ -151,18 +154,17 @@ pub fn verify_client_proof(
 
 pub fn verify_consensus_proof(
     ctx: &dyn ConnectionReader,
+    client_state: AnyClientState,
     connection_end: &ConnectionEnd,
     proof_height: Height,
     proof: &ConsensusProof,
-) -> Result<(), Error> {
-    // Fetch the client state (IBC client on the local chain).
-    let client_state = ctx
-        .client_state(connection_end.client_id())
-        .ok_or_else(|| Kind::MissingClient(connection_end.client_id().clone()))?;
-
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
-    }
+) -> Result<AnyClientState, Error> {
+    // The client must have the consensus state for the height where this proof was created.
+    let consensus_state = ctx
+        .client_consensus_state(connection_end.client_id(), proof_height)
+        .ok_or_else(|| {
+            Kind::MissingClientConsensusState(proof_height, connection_end.client_id().clone())
+        })?;
 
     // Fetch the expected consensus state from the historical (local) header data.
     let expected_consensus = ctx
@@ -173,10 +175,12 @@ pub fn verify_consensus_proof(
 
     Ok(client
         .verify_client_consensus_state(
+            ctx,
             &client_state,
             proof_height,
             connection_end.counterparty().prefix(),
             proof.proof(),
+            consensus_state.root(),
             connection_end.counterparty().client_id(),
             proof.height(),
             &expected_consensus,
@@ -17,10 +17,37 @@ pub struct Version {
 }
 
 impl Version {
+    pub fn new(identifier: String, features: Vec<String>) -> Self {
+        Version {
+            identifier,
+            features,
+        }
+    }
+
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
     /// Checks whether or not the given feature is supported in this versin
     pub fn is_supported_feature(&self, feature: String) -> bool {
         self.features.contains(&feature)
     }
+
+    /// Checks whether the version `proposed` by the counterparty, which picked it among the
+    /// versions of this chain, is compatible with this version: the identifiers must match, and
+    /// each feature of `proposed` must be one of the features of this version.
+    pub fn is_compatible(&self, proposed: &Version) -> bool {
+        self.identifier == proposed.identifier
+            && !proposed.features.is_empty()
+            && proposed
+                .features
+                .iter()
+                .all(|feature| self.features.contains(feature))
+    }
 }
 
 impl Protobuf<RawVersion> for Version {}
@@ -70,7 +97,9 @@ pub fn get_compatible_versions() -> Vec<Version> {
     vec![Version::default()]
 }
 
-/// Selects a version from the intersection of locally supported and counterparty versions.
+/// Selects a version from the intersection of locally supported and counterparty versions. The
+/// features of the selected version are those which both chains support for its identifier, and
+/// versions for which the chains have no feature in common are not selected.
 pub fn pick_version(
     supported_versions: Vec<Version>,
     counterparty_versions: Vec<Version>,
@@ -81,8 +110,11 @@ pub fn pick_version(
             if c.identifier != s.identifier {
                 continue;
             }
-            // TODO - perform feature intersection and error if empty
-            intersection.append(&mut vec![s.clone()]);
+            let features = get_feature_set_intersection(&s.features, &c.features);
+            if features.is_empty() {
+                continue;
+            }
+            intersection.push(Version::new(s.identifier.clone(), features));
         }
     }
     intersection.sort_by(|a, b| a.identifier.cmp(&b.identifier));
@@ -92,6 +124,19 @@ pub fn pick_version(
     Some(intersection[0].clone())
 }
 
+/// Returns the features of `supported_features` which are also in `counterparty_features`, in the
+/// order of `supported_features`.
+fn get_feature_set_intersection(
+    supported_features: &[String],
+    counterparty_features: &[String],
+) -> Vec<String> {
+    supported_features
+        .iter()
+        .filter(|feature| counterparty_features.contains(feature))
+        .cloned()
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::{TryFrom, TryInto};
@@ -136,11 +181,11 @@ mod tests {
                 Version::default(),
                 Version {
                     identifier: "3".to_string(),
-                    features: vec![],
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
                 Version {
                     identifier: "4".to_string(),
-                    features: vec![],
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
             ]
             .into_iter()
@@ -148,15 +193,15 @@ mod tests {
             vec![
                 Version {
                     identifier: "2".to_string(),
-                    features: vec![],
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
                 Version {
                     identifier: "4".to_string(),
-                    features: vec![],
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
                 Version {
                     identifier: "3".to_string(),
-                    features: vec![],
+                    features: vec!["ORDER_ORDERED".to_string(), "ORDER_UNORDERED".to_string()],
                 },
             ]
             .into_iter()
@@ -164,11 +209,39 @@ mod tests {
             // Should pick version 3 as it's the lowest of the intersection {3, 4}
             Version {
                 identifier: "3".to_string(),
-                features: vec![],
+                features: vec!["ORDER_ORDERED".to_string()],
+            },
+        )
+    }
+
+    fn intersecting_features() -> (Vec<Version>, Vec<Version>, Version) {
+        (
+            get_compatible_versions(),
+            vec![Version {
+                identifier: "1".to_string(),
+                features: vec!["ORDER_UNORDERED".to_string(), "ORDER_DAG".to_string()],
+            }],
+            // Only the features supported by both chains are picked.
+            Version {
+                identifier: "1".to_string(),
+                features: vec!["ORDER_UNORDERED".to_string()],
             },
         )
     }
 
+    fn disjoint_features() -> (Vec<Version>, Vec<Version>) {
+        (
+            vec![Version {
+                identifier: "1".to_string(),
+                features: vec!["ORDER_ORDERED".to_string()],
+            }],
+            vec![Version {
+                identifier: "1".to_string(),
+                features: vec!["ORDER_UNORDERED".to_string()],
+            }],
+        )
+    }
+
     fn disjoint() -> (Vec<Version>, Vec<Version>) {
         (
             vec![Version {
@@ -268,6 +341,20 @@ mod tests {
                 picked: None,
                 want_pass: false,
             },
+            Test {
+                name: "Intersecting features".to_string(),
+                supported: intersecting_features().0,
+                counterparty: intersecting_features().1,
+                picked: Some(intersecting_features().2),
+                want_pass: true,
+            },
+            Test {
+                name: "Disjoint features".to_string(),
+                supported: disjoint_features().0,
+                counterparty: disjoint_features().1,
+                picked: None,
+                want_pass: false,
+            },
         ];
 
         for test in tests {
@@ -285,6 +372,21 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn compatible() {
+        let supported = Version::default();
+        let unordered = Version::new("1".to_string(), vec!["ORDER_UNORDERED".to_string()]);
+
+        assert!(supported.is_compatible(&supported));
+        assert!(supported.is_compatible(&unordered));
+        assert!(!unordered.is_compatible(&supported));
+        assert!(!supported.is_compatible(&Version::new("1".to_string(), vec![])));
+        assert!(!supported.is_compatible(&Version::new(
+            "2".to_string(),
+            vec!["ORDER_UNORDERED".to_string()]
+        )));
+    }
+
     #[test]
     fn serialize() {
         let def = Version::default();
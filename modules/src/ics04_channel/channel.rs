@@ -145,7 +145,7 @@ impl ChannelEnd {
     }
 
     pub fn validate_basic(&self) -> Result<(), Error> {
-        if self.connection_hops.len() != 1 {
+        if self.connection_hops.is_empty() {
             return Err(
                 Kind::InvalidConnectionHopsLength(1, self.connection_hops.len())
                     .context("validate channel")
@@ -250,7 +250,7 @@ impl From<Counterparty> for RawCounterparty {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Order {
     None = 0,
     Unordered,
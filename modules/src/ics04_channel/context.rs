@@ -2,14 +2,19 @@
 //! the interface that any host chain must implement to be able to process any `ChannelMsg`.
 //!
 
+use sha2::{Digest, Sha256};
+
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_state::AnyClientState;
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::ics04_channel::upgrade::ChannelUpgrade;
 use crate::ics04_channel::{error::Error, packet::Receipt};
 use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::timestamp::Timestamp;
 use crate::Height;
 
 use super::packet::{PacketResult, Sequence};
@@ -19,6 +24,9 @@ pub trait ChannelReader {
     /// Returns the ChannelEnd for the given `port_id` and `chan_id`.
     fn channel_end(&self, port_channel_id: &(PortId, ChannelId)) -> Option<ChannelEnd>;
 
+    /// Returns the upgrade in progress of the channel end identified by `port_channel_id`, if any.
+    fn channel_upgrade(&self, port_channel_id: &(PortId, ChannelId)) -> Option<ChannelUpgrade>;
+
     /// Returns the ConnectionState for the given identifier `connection_id`.
     fn connection_end(&self, connection_id: &ConnectionId) -> Option<ConnectionEnd>;
 
@@ -34,7 +42,26 @@ pub trait ChannelReader {
         height: Height,
     ) -> Option<AnyConsensusState>;
 
-    fn authenticated_capability(&self, port_id: &PortId) -> Result<Capability, Error>;
+    /// Returns the connection-level view of the host chain, with which the connection ends and
+    /// consensus states stored on the intermediate chains of a multi-hop channel are verified.
+    fn connection_reader(&self) -> &dyn ConnectionReader;
+
+    /// Checks that `capability` is the capability of the port `port_id`, i.e., that its holder
+    /// may open channels on this port.
+    fn authenticate_port_capability(
+        &self,
+        port_id: &PortId,
+        capability: &Capability,
+    ) -> Result<(), Error>;
+
+    /// Checks that `capability` is the capability of the channel end identified by
+    /// `port_channel_id`, i.e., that its holder may act on this channel end, e.g., send packets
+    /// over the channel.
+    fn authenticate_channel_capability(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        capability: &Capability,
+    ) -> Result<(), Error>;
 
     fn get_next_sequence_send(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
@@ -42,20 +69,41 @@ pub trait ChannelReader {
 
     fn get_next_sequence_ack(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence>;
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String>;
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>>;
 
     fn get_packet_receipt(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Receipt>;
 
-    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String>;
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>>;
 
-    /// A hashing function for packet commitments  
-    fn hash(&self, value: String) -> String;
+    /// Computes the commitment to a packet, as stored by the sending chain and proven to the
+    /// receiving chain. The commitment is the SHA-256 hash of the big-endian timeout timestamp,
+    /// timeout revision number and timeout revision height, followed by the SHA-256 hash of the
+    /// packet data, which matches the commitments of the Cosmos SDK.
+    fn packet_commitment(
+        &self,
+        timeout_timestamp: Timestamp,
+        timeout_height: Height,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut input = timeout_timestamp.as_nanoseconds().to_be_bytes().to_vec();
+        input.extend(&timeout_height.revision_number.to_be_bytes());
+        input.extend(&timeout_height.revision_height.to_be_bytes());
+        input.extend(Sha256::digest(data));
+
+        Sha256::digest(&input).to_vec()
+    }
+
+    /// Computes the commitment to an acknowledgement, as stored by the receiving chain and proven
+    /// to the sending chain, i.e., the SHA-256 hash of the acknowledgement.
+    fn ack_commitment(&self, ack: &[u8]) -> Vec<u8> {
+        Sha256::digest(ack).to_vec()
+    }
 
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Height;
 
     /// Returns the current timestamp of the local chain.
-    fn host_timestamp(&self) -> u64;
+    fn host_timestamp(&self) -> Timestamp;
 
     /// Returns a counter on the number of channel ids have been created thus far.
     /// The value of this counter should increase only via method
@@ -73,6 +121,17 @@ pub trait ChannelKeeper {
             &result.channel_end,
         )?;
 
+        // Any upgrade of the channel end in progress is stored apart from the channel end.
+        match &result.channel_upgrade {
+            Some(channel_upgrade) => self.store_channel_upgrade(
+                (result.port_id.clone(), result.channel_id.clone()),
+                channel_upgrade,
+            )?,
+            None => {
+                self.delete_channel_upgrade((result.port_id.clone(), result.channel_id.clone()))?
+            }
+        }
+
         // The channel identifier was freshly brewed.
         // Increase counter & initialize seq. nrs.
         if matches!(result.channel_id_state, ChannelIdState::Generated) {
@@ -139,20 +198,12 @@ pub trait ChannelKeeper {
                 )?;
             }
             PacketResult::Ack(res) => {
-                match res.seq_number {
-                    Some(s) => {
-                        //Ordered Channel
-                        self.store_next_sequence_ack((res.port_id.clone(), res.channel_id), s)?;
-                    }
-                    None => {
-                        //Unordered Channel
-                        self.delete_packet_acknowledgement((
-                            res.port_id.clone(),
-                            res.channel_id.clone(),
-                            res.seq,
-                        ))?;
-                    }
+                if let Some(s) = res.seq_number {
+                    //Ordered Channel
+                    self.store_next_sequence_ack((res.port_id.clone(), res.channel_id.clone()), s)?;
                 }
+                // The packet is acknowledged, so its commitment is no longer needed.
+                self.delete_packet_commitment((res.port_id, res.channel_id, res.seq))?;
             }
             PacketResult::Timeout(res) => {
                 if let Some(c) = res.channel {
@@ -169,10 +220,18 @@ pub trait ChannelKeeper {
         Ok(())
     }
 
+    /// Creates the capability of the channel end identified by `port_channel_id`, owned by the IBC
+    /// module. The application module which owns the port claims this capability as well, to act
+    /// on the channel end, e.g., to send packets over the channel.
+    fn new_channel_capability(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<Capability, Error>;
+
     fn store_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
-        timestamp: u64,
+        timestamp: Timestamp,
         heigh: Height,
         data: Vec<u8>,
     ) -> Result<(), Error>;
@@ -210,6 +269,19 @@ pub trait ChannelKeeper {
         channel_end: &ChannelEnd,
     ) -> Result<(), Error>;
 
+    /// Stores the upgrade in progress of the channel end identified by `port_channel_id`, apart
+    /// from the channel end itself.
+    fn store_channel_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_upgrade: &ChannelUpgrade,
+    ) -> Result<(), Error>;
+
+    /// Deletes the upgrade of the channel end identified by `port_channel_id`, once the upgrade
+    /// is complete or cancelled. Deleting an upgrade which does not exist is not an error.
+    fn delete_channel_upgrade(&mut self, port_channel_id: (PortId, ChannelId))
+        -> Result<(), Error>;
+
     fn store_next_sequence_send(
         &mut self,
         port_channel_id: (PortId, ChannelId),
@@ -233,3 +305,43 @@ pub trait ChannelKeeper {
     /// Should never fail.
     fn increase_channel_counter(&mut self);
 }
+
+#[cfg(test)]
+mod tests {
+    use subtle_encoding::hex;
+
+    use crate::ics04_channel::context::ChannelReader;
+    use crate::mock::context::MockContext;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    #[test]
+    fn packet_and_ack_commitments() {
+        let ctx = MockContext::default();
+
+        // The expected values are those of `CommitPacket` and `CommitAcknowledgement` in ibc-go.
+        let commitment = ctx.packet_commitment(
+            Timestamp::from_nanoseconds(1_600_000_000_000_000_000),
+            Height::new(1, 10),
+            b"packet data",
+        );
+        assert_eq!(
+            hex::encode(commitment),
+            b"7e8a23fc3bd67176de962200f6296c8a82183e943084e9c9b53b67ceee35609f".to_vec()
+        );
+
+        // A packet without a timeout timestamp.
+        let commitment =
+            ctx.packet_commitment(Timestamp::none(), Height::new(0, 10), b"packet data");
+        assert_eq!(
+            hex::encode(commitment),
+            b"a26caf488c3f082e881748fc3f5fb30bbd56d4428f2db3b4b8cc8d6b77a26229".to_vec()
+        );
+
+        let ack_commitment = ctx.ack_commitment(br#"{"result":"AQ=="}"#);
+        assert_eq!(
+            hex::encode(ack_commitment),
+            b"08f7557ed51826fe18d84512bf24ec75001edbaf2123a477df72a0a9f3640a7c".to_vec()
+        );
+    }
+}
@@ -5,7 +5,9 @@ pub type Error = anomaly::Error<Kind>;
 
 use super::packet::Sequence;
 use crate::ics04_channel::channel::State;
+use crate::ics04_channel::upgrade::UpgradeState;
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::timestamp::Timestamp;
 use crate::{ics02_client, Height};
 
 #[derive(Clone, Debug, Error, Eq, PartialEq)]
@@ -78,6 +80,12 @@ pub enum Kind {
     #[error("the module associated with the port does not have the capability it needs")]
     InvalidPortCapability,
 
+    #[error("the capability does not authorize the use of channel {1} on port {0}")]
+    InvalidChannelCapability(PortId, ChannelId),
+
+    #[error("cannot create the capability of channel {1} on port {0}")]
+    ChannelCapabilityCreationFailed(PortId, ChannelId),
+
     #[error("single version must be negociated on connection before opening channel")]
     InvalidVersionLengthConnection,
 
@@ -122,14 +130,20 @@ pub enum Kind {
     #[error("Receiving chain block height {0} >= packet timeout height {1}")]
     LowPacketHeight(Height, Height),
 
-    #[error("Packet timeout height {0} > chain height {1}")]
-    PacketTimeoutHeightNotReached(Height, Height),
+    #[error(
+        "Packet timeout height {0} > chain height {1} and packet timeout timestamp {2} > \
+        chain timestamp {3}"
+    )]
+    PacketTimeoutNotReached(Height, Height, Timestamp, Timestamp),
 
-    #[error("Packet timeout timestamp {0} > chain timestamp {1}")]
-    PacketTimeoutTimestampNotReached(u64, u64),
+    #[error("Receiving chain block timestamp {0} >= packet timeout timestamp {1}")]
+    LowPacketTimestamp(Timestamp, Timestamp),
 
-    #[error("Receiving chain block timestamp >= packet timeout timestamp")]
-    LowPacketTimestamp,
+    #[error(
+        "packet timeout timestamp {0} cannot be checked, as the consensus states of client {1} \
+        have no timestamp"
+    )]
+    InvalidPacketTimestamp(Timestamp, ClientId),
 
     #[error("Invalid timestamp in consensus state; timestamp must be a positive value")]
     ErrorInvalidConsensusState(ics02_client::error::Kind),
@@ -166,6 +180,45 @@ pub enum Kind {
 
     #[error("Missing sequence number for ack packets")]
     MissingNextAckSeq,
+
+    #[error("channel upgrade state unknown")]
+    UnknownUpgradeState,
+
+    #[error("the upgrade of channel {0} must be in state {1}")]
+    InvalidUpgradeState(ChannelId, UpgradeState),
+
+    #[error("no upgrade of the channel end ({0}, {1}) is in progress")]
+    MissingChannelUpgrade(PortId, ChannelId),
+
+    #[error("an upgrade of channel {0} is already in progress")]
+    ChannelUpgradeInProgress(ChannelId),
+
+    #[error("the channel proposed as the upgrade of channel {0} must be in state {1}")]
+    InvalidUpgradeChannelState(ChannelId, State),
+
+    #[error("the upgrade proposed for channel {0} must keep the counterparty of the channel")]
+    UpgradeCounterpartyMismatch(ChannelId),
+
+    #[error("Handshake proof verification fails at ChannelUpgradeTry")]
+    ChanUpgradeTryProofVerification,
+
+    #[error("Handshake proof verification fails at ChannelUpgradeAck")]
+    ChanUpgradeAckProofVerification,
+
+    #[error("Handshake proof verification fails at ChannelUpgradeConfirm")]
+    ChanUpgradeConfirmProofVerification,
+
+    #[error("invalid multi-hop proof")]
+    InvalidMultihopProof,
+
+    #[error("invalid multi-hop proof: expected proofs on {0} intermediate chains; actual {1}")]
+    InvalidMultihopProofLength(usize, usize),
+
+    #[error("Verification fails for the connection hop {0}")]
+    ConnectionHopVerificationFailed(ConnectionId),
+
+    #[error("implementation specific")]
+    ImplementationSpecific,
 }
 
 impl Kind {
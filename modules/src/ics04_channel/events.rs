@@ -15,6 +15,11 @@ const OPEN_ACK_EVENT_TYPE: &str = "channel_open_ack";
 const OPEN_CONFIRM_EVENT_TYPE: &str = "channel_open_confirm";
 const CLOSE_INIT_EVENT_TYPE: &str = "channel_close_init";
 const CLOSE_CONFIRM_EVENT_TYPE: &str = "channel_close_confirm";
+const UPGRADE_INIT_EVENT_TYPE: &str = "channel_upgrade_init";
+const UPGRADE_TRY_EVENT_TYPE: &str = "channel_upgrade_try";
+const UPGRADE_ACK_EVENT_TYPE: &str = "channel_upgrade_ack";
+const UPGRADE_CONFIRM_EVENT_TYPE: &str = "channel_upgrade_confirm";
+const UPGRADE_CANCEL_EVENT_TYPE: &str = "channel_upgrade_cancel";
 
 /// Channel event attribute keys
 const CONNECTION_ID_ATTRIBUTE_KEY: &str = "connection_id";
@@ -60,6 +65,21 @@ pub fn try_from_tx(event: &tendermint::abci::Event) -> Option<IbcEvent> {
         CLOSE_CONFIRM_EVENT_TYPE => Some(IbcEvent::CloseConfirmChannel(CloseConfirm::from(
             extract_attributes_from_tx(event),
         ))),
+        UPGRADE_INIT_EVENT_TYPE => Some(IbcEvent::UpgradeInitChannel(UpgradeInit::from(
+            extract_attributes_from_tx(event),
+        ))),
+        UPGRADE_TRY_EVENT_TYPE => Some(IbcEvent::UpgradeTryChannel(UpgradeTry::from(
+            extract_attributes_from_tx(event),
+        ))),
+        UPGRADE_ACK_EVENT_TYPE => Some(IbcEvent::UpgradeAckChannel(UpgradeAck::from(
+            extract_attributes_from_tx(event),
+        ))),
+        UPGRADE_CONFIRM_EVENT_TYPE => Some(IbcEvent::UpgradeConfirmChannel(UpgradeConfirm::from(
+            extract_attributes_from_tx(event),
+        ))),
+        UPGRADE_CANCEL_EVENT_TYPE => Some(IbcEvent::UpgradeCancelChannel(UpgradeCancel::from(
+            extract_attributes_from_tx(event),
+        ))),
         SEND_PACKET => {
             let (packet, write_ack) = extract_packet_and_write_ack_from_tx(event);
             // This event should not have a write ack.
@@ -466,6 +486,226 @@ impl From<CloseConfirm> for IbcEvent {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpgradeInit(Attributes);
+
+impl UpgradeInit {
+    pub fn channel_id(&self) -> &Option<ChannelId> {
+        &self.0.channel_id
+    }
+    pub fn height(&self) -> Height {
+        self.0.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.0.height = height;
+    }
+}
+
+impl From<Attributes> for UpgradeInit {
+    fn from(attrs: Attributes) -> Self {
+        UpgradeInit(attrs)
+    }
+}
+
+impl TryFrom<RawObject> for UpgradeInit {
+    type Error = BoxError;
+    fn try_from(obj: RawObject) -> Result<Self, Self::Error> {
+        Ok(UpgradeInit(Attributes {
+            height: obj.height,
+            port_id: attribute!(obj, "channel_upgrade_init.port_id"),
+            channel_id: some_attribute!(obj, "channel_upgrade_init.channel_id"),
+            connection_id: attribute!(obj, "channel_upgrade_init.connection_id"),
+            counterparty_port_id: attribute!(obj, "channel_upgrade_init.counterparty_port_id"),
+            counterparty_channel_id: some_attribute!(
+                obj,
+                "channel_upgrade_init.counterparty_channel_id"
+            ),
+        }))
+    }
+}
+
+impl From<UpgradeInit> for IbcEvent {
+    fn from(v: UpgradeInit) -> Self {
+        IbcEvent::UpgradeInitChannel(v)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpgradeTry(Attributes);
+
+impl UpgradeTry {
+    pub fn channel_id(&self) -> &Option<ChannelId> {
+        &self.0.channel_id
+    }
+    pub fn height(&self) -> Height {
+        self.0.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.0.height = height;
+    }
+}
+
+impl From<Attributes> for UpgradeTry {
+    fn from(attrs: Attributes) -> Self {
+        UpgradeTry(attrs)
+    }
+}
+
+impl TryFrom<RawObject> for UpgradeTry {
+    type Error = BoxError;
+    fn try_from(obj: RawObject) -> Result<Self, Self::Error> {
+        Ok(UpgradeTry(Attributes {
+            height: obj.height,
+            port_id: attribute!(obj, "channel_upgrade_try.port_id"),
+            channel_id: some_attribute!(obj, "channel_upgrade_try.channel_id"),
+            connection_id: attribute!(obj, "channel_upgrade_try.connection_id"),
+            counterparty_port_id: attribute!(obj, "channel_upgrade_try.counterparty_port_id"),
+            counterparty_channel_id: some_attribute!(
+                obj,
+                "channel_upgrade_try.counterparty_channel_id"
+            ),
+        }))
+    }
+}
+
+impl From<UpgradeTry> for IbcEvent {
+    fn from(v: UpgradeTry) -> Self {
+        IbcEvent::UpgradeTryChannel(v)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpgradeAck(Attributes);
+
+impl UpgradeAck {
+    pub fn channel_id(&self) -> &Option<ChannelId> {
+        &self.0.channel_id
+    }
+    pub fn height(&self) -> Height {
+        self.0.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.0.height = height;
+    }
+}
+
+impl From<Attributes> for UpgradeAck {
+    fn from(attrs: Attributes) -> Self {
+        UpgradeAck(attrs)
+    }
+}
+
+impl TryFrom<RawObject> for UpgradeAck {
+    type Error = BoxError;
+    fn try_from(obj: RawObject) -> Result<Self, Self::Error> {
+        Ok(UpgradeAck(Attributes {
+            height: obj.height,
+            port_id: attribute!(obj, "channel_upgrade_ack.port_id"),
+            channel_id: some_attribute!(obj, "channel_upgrade_ack.channel_id"),
+            connection_id: attribute!(obj, "channel_upgrade_ack.connection_id"),
+            counterparty_port_id: attribute!(obj, "channel_upgrade_ack.counterparty_port_id"),
+            counterparty_channel_id: some_attribute!(
+                obj,
+                "channel_upgrade_ack.counterparty_channel_id"
+            ),
+        }))
+    }
+}
+
+impl From<UpgradeAck> for IbcEvent {
+    fn from(v: UpgradeAck) -> Self {
+        IbcEvent::UpgradeAckChannel(v)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpgradeConfirm(Attributes);
+
+impl UpgradeConfirm {
+    pub fn channel_id(&self) -> &Option<ChannelId> {
+        &self.0.channel_id
+    }
+    pub fn height(&self) -> Height {
+        self.0.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.0.height = height;
+    }
+}
+
+impl From<Attributes> for UpgradeConfirm {
+    fn from(attrs: Attributes) -> Self {
+        UpgradeConfirm(attrs)
+    }
+}
+
+impl TryFrom<RawObject> for UpgradeConfirm {
+    type Error = BoxError;
+    fn try_from(obj: RawObject) -> Result<Self, Self::Error> {
+        Ok(UpgradeConfirm(Attributes {
+            height: obj.height,
+            port_id: attribute!(obj, "channel_upgrade_confirm.port_id"),
+            channel_id: some_attribute!(obj, "channel_upgrade_confirm.channel_id"),
+            connection_id: attribute!(obj, "channel_upgrade_confirm.connection_id"),
+            counterparty_port_id: attribute!(obj, "channel_upgrade_confirm.counterparty_port_id"),
+            counterparty_channel_id: some_attribute!(
+                obj,
+                "channel_upgrade_confirm.counterparty_channel_id"
+            ),
+        }))
+    }
+}
+
+impl From<UpgradeConfirm> for IbcEvent {
+    fn from(v: UpgradeConfirm) -> Self {
+        IbcEvent::UpgradeConfirmChannel(v)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UpgradeCancel(Attributes);
+
+impl UpgradeCancel {
+    pub fn channel_id(&self) -> &Option<ChannelId> {
+        &self.0.channel_id
+    }
+    pub fn height(&self) -> Height {
+        self.0.height
+    }
+    pub fn set_height(&mut self, height: Height) {
+        self.0.height = height;
+    }
+}
+
+impl From<Attributes> for UpgradeCancel {
+    fn from(attrs: Attributes) -> Self {
+        UpgradeCancel(attrs)
+    }
+}
+
+impl TryFrom<RawObject> for UpgradeCancel {
+    type Error = BoxError;
+    fn try_from(obj: RawObject) -> Result<Self, Self::Error> {
+        Ok(UpgradeCancel(Attributes {
+            height: obj.height,
+            port_id: attribute!(obj, "channel_upgrade_cancel.port_id"),
+            channel_id: some_attribute!(obj, "channel_upgrade_cancel.channel_id"),
+            connection_id: attribute!(obj, "channel_upgrade_cancel.connection_id"),
+            counterparty_port_id: attribute!(obj, "channel_upgrade_cancel.counterparty_port_id"),
+            counterparty_channel_id: some_attribute!(
+                obj,
+                "channel_upgrade_cancel.counterparty_channel_id"
+            ),
+        }))
+    }
+}
+
+impl From<UpgradeCancel> for IbcEvent {
+    fn from(v: UpgradeCancel) -> Self {
+        IbcEvent::UpgradeCancelChannel(v)
+    }
+}
+
 #[macro_export]
 macro_rules! p_attribute {
     ($a:ident, $b:literal) => {{
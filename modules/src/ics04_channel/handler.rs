@@ -1,13 +1,16 @@
 //! This module implements the processing logic for ICS4 (channel) messages.
 
 use crate::handler::HandlerOutput;
-use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics04_channel::channel::{ChannelEnd, Order};
 use crate::ics04_channel::context::ChannelReader;
-use crate::ics04_channel::error::Error;
+use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::msgs::ChannelMsg;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
 use crate::ics04_channel::{msgs::PacketMsg, packet::PacketResult};
 use crate::ics05_port::capabilities::Capability;
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 
 pub mod acknowledgement;
 pub mod chan_close_confirm;
@@ -16,6 +19,11 @@ pub mod chan_open_ack;
 pub mod chan_open_confirm;
 pub mod chan_open_init;
 pub mod chan_open_try;
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_try;
 pub mod recv_packet;
 pub mod send_packet;
 pub mod timeout;
@@ -39,38 +47,124 @@ pub struct ChannelResult {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub channel_id_state: ChannelIdState,
-    pub channel_cap: Capability,
     pub channel_end: ChannelEnd,
+    /// The state of the client which verified the proofs of the message, as the verification
+    /// left it, or `None` if the handler verified no proof.
+    pub client_state: Option<(ClientId, AnyClientState)>,
+    /// The upgrade of the channel end in progress once the message is processed, if any.
+    pub channel_upgrade: Option<ChannelUpgrade>,
 }
 
-/// General entry point for processing any type of message related to the ICS4 channel open and
-/// channel close handshake protocols.
+/// General entry point for processing any type of message related to the ICS4 channel open,
+/// channel close and channel upgrade handshake protocols. The caller presents the capability `cap`
+/// which entitles it to act on the port of the channel, for the `ChanOpenInit` and `ChanOpenTry`
+/// steps, or on the channel end itself, for all the other steps.
 pub fn channel_dispatch<Ctx>(
     ctx: &Ctx,
+    cap: &Capability,
     msg: ChannelMsg,
 ) -> Result<HandlerOutput<ChannelResult>, Error>
 where
     Ctx: ChannelReader,
 {
     match msg {
-        ChannelMsg::ChannelOpenInit(msg) => chan_open_init::process(ctx, msg),
-        ChannelMsg::ChannelOpenTry(msg) => chan_open_try::process(ctx, msg),
-        ChannelMsg::ChannelOpenAck(msg) => chan_open_ack::process(ctx, msg),
-        ChannelMsg::ChannelOpenConfirm(msg) => chan_open_confirm::process(ctx, msg),
-        ChannelMsg::ChannelCloseInit(msg) => chan_close_init::process(ctx, msg),
-        ChannelMsg::ChannelCloseConfirm(msg) => chan_close_confirm::process(ctx, msg),
+        ChannelMsg::ChannelOpenInit(msg) => chan_open_init::process(ctx, cap, msg),
+        ChannelMsg::ChannelOpenTry(msg) => chan_open_try::process(ctx, cap, msg),
+        ChannelMsg::ChannelOpenAck(msg) => chan_open_ack::process(ctx, cap, msg),
+        ChannelMsg::ChannelOpenConfirm(msg) => chan_open_confirm::process(ctx, cap, msg),
+        ChannelMsg::ChannelCloseInit(msg) => chan_close_init::process(ctx, cap, msg),
+        ChannelMsg::ChannelCloseConfirm(msg) => chan_close_confirm::process(ctx, cap, msg),
+        ChannelMsg::ChannelUpgradeInit(msg) => chan_upgrade_init::process(ctx, cap, msg),
+        ChannelMsg::ChannelUpgradeTry(msg) => chan_upgrade_try::process(ctx, cap, msg),
+        ChannelMsg::ChannelUpgradeAck(msg) => chan_upgrade_ack::process(ctx, cap, msg),
+        ChannelMsg::ChannelUpgradeConfirm(msg) => chan_upgrade_confirm::process(ctx, cap, msg),
+        ChannelMsg::ChannelUpgradeCancel(msg) => chan_upgrade_cancel::process(ctx, cap, msg),
     }
 }
 
-/// Dispatcher for processing any type of message related to the ICS4 packet protocols.
-pub fn packet_dispatch<Ctx>(ctx: &Ctx, msg: PacketMsg) -> Result<HandlerOutput<PacketResult>, Error>
+/// Dispatcher for processing any type of message related to the ICS4 packet protocols. The caller
+/// presents the capability `channel_cap` of the channel end which the packet is processed on.
+pub fn packet_dispatch<Ctx>(
+    ctx: &Ctx,
+    channel_cap: &Capability,
+    msg: PacketMsg,
+) -> Result<HandlerOutput<PacketResult>, Error>
 where
     Ctx: ChannelReader,
 {
     match msg {
-        PacketMsg::RecvPacket(msg) => recv_packet::process(ctx, msg),
-        PacketMsg::AckPacket(msg) => acknowledgement::process(ctx, msg),
-        PacketMsg::ToPacket(msg) => timeout::process(ctx, msg),
-        PacketMsg::ToClosePacket(msg) => timeout_on_close::process(ctx, msg),
+        PacketMsg::RecvPacket(msg) => recv_packet::process(ctx, channel_cap, msg),
+        PacketMsg::AckPacket(msg) => acknowledgement::process(ctx, channel_cap, msg),
+        PacketMsg::ToPacket(msg) => timeout::process(ctx, channel_cap, msg),
+        PacketMsg::ToClosePacket(msg) => timeout_on_close::process(ctx, channel_cap, msg),
+    }
+}
+
+/// Checks that the connection `conn`, over which a channel with the given `ordering` is opened or
+/// upgraded, negotiated a single version, and that this version supports the ordering as one of
+/// its features.
+pub(crate) fn verify_connection_features(
+    conn: &ConnectionEnd,
+    ordering: &Order,
+) -> Result<(), Error> {
+    let versions = conn.versions();
+    let version = match versions.as_slice() {
+        [version] => version,
+        _ => return Err(Kind::InvalidVersionLengthConnection.into()),
+    };
+
+    if !version.is_supported_feature(ordering.to_string()) {
+        return Err(Kind::ChannelFeatureNotSuportedByConnection
+            .context(ordering.to_string())
+            .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use crate::ics04_channel::msgs::ChannelMsg;
+    use crate::ics05_port::capabilities::Capability;
+    use crate::ics05_port::context::PortReader;
+    use crate::mock::context::MockContext;
+
+    /// Returns the capability which the IBC module of `ctx` owns for the object that `msg` acts
+    /// upon, i.e., the port for the `ChanOpenInit` and `ChanOpenTry` steps and the channel end for
+    /// the other steps. Falls back on a capability which `ctx` never handed out.
+    pub fn get_channel_msg_capability(ctx: &MockContext, msg: &ChannelMsg) -> Capability {
+        let cap = match msg {
+            ChannelMsg::ChannelOpenInit(msg) => ctx.lookup_module_by_port(msg.port_id()),
+            ChannelMsg::ChannelOpenTry(msg) => ctx.lookup_module_by_port(msg.port_id()),
+            ChannelMsg::ChannelOpenAck(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelOpenConfirm(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelCloseInit(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelCloseConfirm(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelUpgradeInit(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelUpgradeTry(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelUpgradeAck(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelUpgradeConfirm(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+            ChannelMsg::ChannelUpgradeCancel(msg) => {
+                ctx.lookup_module_by_channel(msg.port_id(), msg.channel_id())
+            }
+        };
+
+        cap.unwrap_or_else(|| Capability::new(u64::MAX))
     }
 }
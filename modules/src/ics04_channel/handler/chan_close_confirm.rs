@@ -6,12 +6,16 @@ use crate::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::Attributes;
-use crate::ics04_channel::handler::verify::verify_channel_proofs;
+use crate::ics04_channel::handler::verify::{
+    connection_client_state, counterparty_connection_hops, verify_channel_proofs,
+};
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+use crate::ics05_port::capabilities::Capability;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgChannelCloseConfirm,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -26,11 +30,14 @@ pub(crate) fn process(
         return Err(Kind::ChannelClosed(msg.channel_id().clone()).into());
     }
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(msg.port_id().clone(), msg.channel_id().clone()),
+        channel_cap,
+    )?;
 
     // An OPEN IBC connection running on the local (host) chain should exist.
-    if channel_end.connection_hops().len() != 1 {
+    if channel_end.connection_hops().is_empty() {
         return Err(
             Kind::InvalidConnectionHopsLength(1, channel_end.connection_hops().len()).into(),
         );
@@ -48,12 +55,11 @@ pub(crate) fn process(
     let expected_counterparty =
         Counterparty::new(msg.port_id().clone(), Some(msg.channel_id().clone()));
 
-    let counterparty = conn.counterparty();
-    let ccid = counterparty.connection_id().ok_or_else(|| {
-        Kind::UndefinedConnectionCounterparty(channel_end.connection_hops()[0].clone())
-    })?;
-
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops = counterparty_connection_hops(
+        channel_end.connection_hops(),
+        &conn,
+        msg.proofs().object_proof(),
+    )?;
 
     let expected_channel_end = ChannelEnd::new(
         State::Closed,
@@ -63,8 +69,10 @@ pub(crate) fn process(
         channel_end.version(),
     );
 
-    verify_channel_proofs(
+    let client_state = connection_client_state(ctx, &conn)?;
+    let client_state = verify_channel_proofs(
         ctx,
+        client_state,
         &channel_end,
         &conn,
         &expected_channel_end,
@@ -81,8 +89,9 @@ pub(crate) fn process(
         port_id: msg.port_id().clone(),
         channel_id: msg.channel_id().clone(),
         channel_id_state: ChannelIdState::Reused,
-        channel_cap,
         channel_end,
+        client_state: Some((conn.client_id().clone(), client_state)),
+        channel_upgrade: None,
     };
 
     let event_attributes = Attributes {
@@ -8,9 +8,11 @@ use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::Attributes;
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+use crate::ics05_port::capabilities::Capability;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgChannelCloseInit,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -28,11 +30,14 @@ pub(crate) fn process(
         )));
     }
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(msg.port_id().clone(), msg.channel_id().clone()),
+        channel_cap,
+    )?;
     // An OPEN IBC connection running on the local (host) chain should exist.
 
-    if channel_end.connection_hops().len() != 1 {
+    if channel_end.connection_hops().is_empty() {
         return Err(
             Kind::InvalidConnectionHopsLength(1, channel_end.connection_hops().len()).into(),
         );
@@ -55,8 +60,9 @@ pub(crate) fn process(
         port_id: msg.port_id().clone(),
         channel_id: msg.channel_id().clone(),
         channel_id_state: ChannelIdState::Reused,
-        channel_cap,
         channel_end,
+        client_state: None,
+        channel_upgrade: None,
     };
 
     let event_attributes = Attributes {
@@ -6,12 +6,16 @@ use crate::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::Attributes;
-use crate::ics04_channel::handler::verify::verify_channel_proofs;
+use crate::ics04_channel::handler::verify::{
+    connection_client_state, counterparty_connection_hops, verify_channel_proofs,
+};
 use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::ics05_port::capabilities::Capability;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgChannelOpenConfirm,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -26,11 +30,14 @@ pub(crate) fn process(
         return Err(Kind::InvalidChannelState(msg.channel_id().clone(), channel_end.state).into());
     }
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(msg.port_id().clone(), msg.channel_id().clone()),
+        channel_cap,
+    )?;
 
     // An OPEN IBC connection running on the local (host) chain should exist.
-    if channel_end.connection_hops().len() != 1 {
+    if channel_end.connection_hops().is_empty() {
         return Err(
             Kind::InvalidConnectionHopsLength(1, channel_end.connection_hops().len()).into(),
         );
@@ -50,12 +57,11 @@ pub(crate) fn process(
     let expected_counterparty =
         Counterparty::new(msg.port_id().clone(), Some(msg.channel_id().clone()));
 
-    let connection_counterparty = conn.counterparty();
-    let ccid = connection_counterparty.connection_id().ok_or_else(|| {
-        Kind::UndefinedConnectionCounterparty(channel_end.connection_hops()[0].clone())
-    })?;
-
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops = counterparty_connection_hops(
+        channel_end.connection_hops(),
+        &conn,
+        msg.proofs().object_proof(),
+    )?;
 
     let expected_channel_end = ChannelEnd::new(
         State::Open,
@@ -65,8 +71,10 @@ pub(crate) fn process(
         channel_end.version(),
     );
     //2. Verify proofs
-    verify_channel_proofs(
+    let client_state = connection_client_state(ctx, &conn)?;
+    let client_state = verify_channel_proofs(
         ctx,
+        client_state,
         &channel_end,
         &conn,
         &expected_channel_end,
@@ -83,8 +91,9 @@ pub(crate) fn process(
         port_id: msg.port_id().clone(),
         channel_id: msg.channel_id().clone(),
         channel_id_state: ChannelIdState::Reused,
-        channel_cap,
         channel_end,
+        client_state: Some((conn.client_id().clone(), client_state)),
+        channel_upgrade: None,
     };
 
     let event_attributes = Attributes {
@@ -110,6 +119,7 @@ mod tests {
     use crate::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
     use crate::ics03_connection::version::get_compatible_versions;
     use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::handler::test_util::get_channel_msg_capability;
     use crate::ics04_channel::handler::{channel_dispatch, ChannelResult};
     use crate::ics04_channel::msgs::chan_open_confirm::test_util::get_dummy_raw_msg_chan_open_confirm;
     use crate::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
@@ -175,7 +185,8 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = channel_dispatch(&test.ctx, test.msg.clone());
+            let cap = get_channel_msg_capability(&test.ctx, &test.msg);
+            let res = channel_dispatch(&test.ctx, &cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -6,20 +6,22 @@ use crate::ics04_channel::channel::{ChannelEnd, State};
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::Attributes;
-use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::ics04_channel::handler::{verify_connection_features, ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::ChannelId;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    port_cap: &Capability,
     msg: MsgChannelOpenInit,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the port.
+    ctx.authenticate_port_capability(msg.port_id(), port_cap)?;
 
-    if msg.channel().connection_hops().len() != 1 {
+    if msg.channel().connection_hops().is_empty() {
         return Err(
             Kind::InvalidConnectionHopsLength(1, msg.channel().connection_hops().len()).into(),
         );
@@ -31,16 +33,7 @@ pub(crate) fn process(
     let conn = connection_end
         .ok_or_else(|| Kind::MissingConnection(msg.channel().connection_hops()[0].clone()))?;
 
-    let get_versions = conn.versions();
-    let version = match get_versions.as_slice() {
-        [version] => version,
-        _ => return Err(Kind::InvalidVersionLengthConnection.into()),
-    };
-
-    let channel_feature = msg.channel().ordering().to_string();
-    if !version.is_supported_feature(channel_feature) {
-        return Err(Kind::ChannelFeatureNotSuportedByConnection.into());
-    }
+    verify_connection_features(&conn, msg.channel().ordering())?;
 
     // TODO: Check that `version` is non empty but not necessary coherent
     if msg.channel().version().is_empty() {
@@ -71,7 +64,8 @@ pub(crate) fn process(
         channel_id: chan_id.clone(),
         channel_end: new_channel_end,
         channel_id_state: ChannelIdState::Generated,
-        channel_cap,
+        client_state: None,
+        channel_upgrade: None,
     };
 
     let event_attributes = Attributes {
@@ -92,8 +86,9 @@ mod tests {
     use crate::ics03_connection::connection::State as ConnectionState;
     use crate::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
     use crate::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
-    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics03_connection::version::{get_compatible_versions, Version};
     use crate::ics04_channel::channel::State;
+    use crate::ics04_channel::handler::test_util::get_channel_msg_capability;
     use crate::ics04_channel::handler::{channel_dispatch, ChannelResult};
     use crate::ics04_channel::msgs::chan_open_init::test_util::get_dummy_raw_msg_chan_open_init;
     use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
@@ -126,6 +121,13 @@ mod tests {
             msg_conn_init.delay_period,
         );
 
+        // A connection end which does not support unordered channels, like the dummy one.
+        let mut ordered_conn_end = init_conn_end.clone();
+        ordered_conn_end.set_version(Version::new(
+            "1".to_string(),
+            vec!["ORDER_ORDERED".to_string()],
+        ));
+
         let cid = ConnectionId::default();
 
         let tests: Vec<Test> = vec![
@@ -144,6 +146,16 @@ mod tests {
                 msg: ChannelMsg::ChannelOpenInit(msg_chan_init.clone()),
                 want_pass: false,
             },
+            Test {
+                name: "Processing fails because the connection does not support the ordering"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_connection(cid.clone(), ordered_conn_end)
+                    .with_port_capability(msg_chan_init.port_id().clone()),
+                msg: ChannelMsg::ChannelOpenInit(msg_chan_init.clone()),
+                want_pass: false,
+            },
             Test {
                 name: "Good parameters".to_string(),
                 ctx: context
@@ -162,7 +174,8 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = channel_dispatch(&test.ctx, test.msg.clone());
+            let cap = get_channel_msg_capability(&test.ctx, &test.msg);
+            let res = channel_dispatch(&test.ctx, &cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
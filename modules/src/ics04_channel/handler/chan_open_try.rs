@@ -7,13 +7,17 @@ use crate::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::Attributes;
-use crate::ics04_channel::handler::verify::verify_channel_proofs;
-use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::ics04_channel::handler::verify::{
+    connection_client_state, counterparty_connection_hops, verify_channel_proofs,
+};
+use crate::ics04_channel::handler::{verify_connection_features, ChannelIdState, ChannelResult};
 use crate::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::ChannelId;
 
 pub(crate) fn process(
     ctx: &dyn ChannelReader,
+    port_cap: &Capability,
     msg: MsgChannelOpenTry,
 ) -> HandlerResult<ChannelResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -73,7 +77,7 @@ pub(crate) fn process(
     }?;
 
     // An IBC connection running on the local (host) chain should exist.
-    if msg.channel.connection_hops().len() != 1 {
+    if msg.channel.connection_hops().is_empty() {
         return Err(
             Kind::InvalidConnectionHopsLength(1, msg.channel.connection_hops().len()).into(),
         );
@@ -88,19 +92,10 @@ pub(crate) fn process(
         return Err(Kind::ConnectionNotOpen(msg.channel.connection_hops()[0].clone()).into());
     }
 
-    let get_versions = conn.versions();
-    let version = match get_versions.as_slice() {
-        [version] => version,
-        _ => return Err(Kind::InvalidVersionLengthConnection.into()),
-    };
-
-    let channel_feature = msg.channel().ordering().to_string();
-    if !version.is_supported_feature(channel_feature) {
-        return Err(Kind::ChannelFeatureNotSuportedByConnection.into());
-    }
+    verify_connection_features(&conn, msg.channel().ordering())?;
 
-    // Channel capabilities
-    let channel_cap = ctx.authenticated_capability(&msg.port_id().clone())?;
+    // The caller must own the port.
+    ctx.authenticate_port_capability(msg.port_id(), port_cap)?;
 
     if msg.channel().version().is_empty() {
         return Err(Kind::InvalidVersion.into());
@@ -111,11 +106,11 @@ pub(crate) fn process(
     //      the port should be identical with the port we're using; the channel id should not be set
     //      since the counterparty cannot know yet which ID did we choose.
     let expected_counterparty = Counterparty::new(msg.port_id().clone(), None);
-    let counterparty = conn.counterparty();
-    let ccid = counterparty.connection_id().ok_or_else(|| {
-        Kind::UndefinedConnectionCounterparty(msg.channel().connection_hops()[0].clone())
-    })?;
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops = counterparty_connection_hops(
+        msg.channel().connection_hops(),
+        &conn,
+        msg.proofs().object_proof(),
+    )?;
 
     // The other party should be storing a channel end in this configuration.
     let expected_channel_end = ChannelEnd::new(
@@ -127,8 +122,10 @@ pub(crate) fn process(
     );
 
     // 2. Actual proofs are verified now.
-    verify_channel_proofs(
+    let client_state = connection_client_state(ctx, &conn)?;
+    let client_state = verify_channel_proofs(
         ctx,
+        client_state,
         &new_channel_end,
         &conn,
         &expected_channel_end,
@@ -143,7 +140,6 @@ pub(crate) fn process(
 
     let result = ChannelResult {
         port_id: msg.port_id().clone(),
-        channel_cap,
         channel_id_state: if matches!(msg.previous_channel_id, None) {
             ChannelIdState::Generated
         } else {
@@ -151,6 +147,8 @@ pub(crate) fn process(
         },
         channel_id: channel_id.clone(),
         channel_end: new_channel_end,
+        client_state: Some((conn.client_id().clone(), client_state)),
+        channel_upgrade: None,
     };
 
     let event_attributes = Attributes {
@@ -176,6 +174,7 @@ mod tests {
     use crate::ics03_connection::version::get_compatible_versions;
     use crate::ics04_channel::channel::{ChannelEnd, State};
     use crate::ics04_channel::error::Kind;
+    use crate::ics04_channel::handler::test_util::get_channel_msg_capability;
     use crate::ics04_channel::handler::{channel_dispatch, ChannelResult};
     use crate::ics04_channel::msgs::chan_open_try::test_util::get_dummy_raw_msg_chan_open_try;
     use crate::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
@@ -358,7 +357,8 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = channel_dispatch(&test.ctx, test.msg.clone());
+            let cap = get_channel_msg_capability(&test.ctx, &test.msg);
+            let res = channel_dispatch(&test.ctx, &cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(handler_output) => {
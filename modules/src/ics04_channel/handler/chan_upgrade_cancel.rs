@@ -0,0 +1,185 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeCancel`.
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics04_channel::events::Attributes;
+use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::ics04_channel::upgrade::UpgradeState;
+use crate::ics05_port::capabilities::Capability;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    msg: MsgChannelUpgradeCancel,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    // Unwrap the channel end and validate it against the message.
+    let channel_end = ctx
+        .channel_end(&(msg.port_id().clone(), msg.channel_id().clone()))
+        .ok_or_else(|| Kind::ChannelNotFound(msg.port_id.clone(), msg.channel_id().clone()))?;
+
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(msg.port_id().clone(), msg.channel_id().clone()),
+        channel_cap,
+    )?;
+
+    let channel_upgrade = ctx
+        .channel_upgrade(&(msg.port_id().clone(), msg.channel_id().clone()))
+        .ok_or_else(|| {
+            Kind::MissingChannelUpgrade(msg.port_id().clone(), msg.channel_id().clone())
+        })?;
+
+    // Only the channel end which proposed the upgrade may cancel it. Once the counterparty accepted
+    // an upgrade, it is up to the proposer to either complete or cancel it, otherwise both channel
+    // ends could end up in different versions of the channel.
+    if !channel_upgrade.state_matches(&UpgradeState::Init) {
+        return Err(Kind::InvalidUpgradeState(msg.channel_id().clone(), UpgradeState::Init).into());
+    }
+
+    output.log("success: channel upgrade cancel ");
+
+    // The channel end carries on as it was before the upgrade.
+    let result = ChannelResult {
+        port_id: msg.port_id().clone(),
+        channel_id: msg.channel_id().clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end,
+        channel_upgrade: None,
+        client_state: None,
+    };
+
+    let event_attributes = Attributes {
+        channel_id: Some(msg.channel_id().clone()),
+        ..Default::default()
+    };
+    output.emit(IbcEvent::UpgradeCancelChannel(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::events::IbcEvent;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::handler::test_util::get_channel_msg_capability;
+    use crate::ics04_channel::handler::{channel_dispatch, ChannelResult};
+    use crate::ics04_channel::msgs::chan_upgrade_cancel::test_util::get_dummy_raw_msg_chan_upgrade_cancel;
+    use crate::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+    use crate::ics04_channel::msgs::ChannelMsg;
+    use crate::ics04_channel::upgrade::{ChannelUpgrade, UpgradeState};
+    use crate::ics24_host::identifier::ConnectionId;
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn chan_upgrade_cancel_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+
+        let msg =
+            MsgChannelUpgradeCancel::try_from(get_dummy_raw_msg_chan_upgrade_cancel()).unwrap();
+
+        // The channel before the upgrade: open, unordered and running an older version.
+        let chan_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(msg.port_id().clone(), Some(msg.channel_id().clone())),
+            vec![ConnectionId::new(2)],
+            "ics20-0".to_string(),
+        );
+
+        // The upgrade in progress: ordered and running a newer version.
+        let upgraded_chan_end = ChannelEnd {
+            ordering: Order::Ordered,
+            version: "ics20-1".to_string(),
+            ..chan_end.clone()
+        };
+
+        let ctx = MockContext::default()
+            .with_port_capability(msg.port_id().clone())
+            .with_channel(
+                msg.port_id().clone(),
+                msg.channel_id().clone(),
+                chan_end.clone(),
+            );
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because the channel is not upgrading".to_string(),
+                ctx: ctx.clone(),
+                msg: ChannelMsg::ChannelUpgradeCancel(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel accepted the upgrade".to_string(),
+                ctx: ctx.clone().with_channel_upgrade(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    ChannelUpgrade::new(UpgradeState::Try, upgraded_chan_end.clone()),
+                ),
+                msg: ChannelMsg::ChannelUpgradeCancel(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: ctx.with_channel_upgrade(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    ChannelUpgrade::new(UpgradeState::Init, upgraded_chan_end),
+                ),
+                msg: ChannelMsg::ChannelUpgradeCancel(msg.clone()),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let cap = get_channel_msg_capability(&test.ctx, &test.msg);
+            let res = channel_dispatch(&test.ctx, &cap, test.msg.clone());
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok(proto_output) => {
+                    assert_eq!(
+                        test.want_pass,
+                        true,
+                        "chan_upgrade_cancel: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+                    assert_ne!(proto_output.events.is_empty(), true); // Some events must exist.
+
+                    // The channel end is left as it was, and the upgrade is dropped.
+                    let res: ChannelResult = proto_output.result;
+                    assert_eq!(res.channel_end, chan_end);
+                    assert_eq!(res.channel_upgrade, None);
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeCancelChannel(_)));
+                    }
+                }
+                Err(e) => {
+                    assert_eq!(
+                        test.want_pass,
+                        false,
+                        "chan_upgrade_cancel: did not pass test: {}, \nparams {:?} {:?}\nerror: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
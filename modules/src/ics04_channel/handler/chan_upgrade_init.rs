@@ -0,0 +1,319 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeInit`.
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics03_connection::connection::State as ConnectionState;
+use crate::ics04_channel::channel::State;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics04_channel::events::Attributes;
+use crate::ics04_channel::handler::{verify_connection_features, ChannelIdState, ChannelResult};
+use crate::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::ics04_channel::upgrade::{ChannelUpgrade, UpgradeState};
+use crate::ics05_port::capabilities::Capability;
+
+pub(crate) fn process(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    msg: MsgChannelUpgradeInit,
+) -> HandlerResult<ChannelResult, Error> {
+    let mut output = HandlerOutput::builder();
+
+    // Unwrap the channel end and validate it against the message.
+    let channel_end = ctx
+        .channel_end(&(msg.port_id().clone(), msg.channel_id().clone()))
+        .ok_or_else(|| Kind::ChannelNotFound(msg.port_id.clone(), msg.channel_id().clone()))?;
+
+    // Only an open channel can be upgraded.
+    if !channel_end.state_matches(&State::Open) {
+        return Err(Kind::InvalidChannelState(msg.channel_id().clone(), channel_end.state).into());
+    }
+
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(msg.port_id().clone(), msg.channel_id().clone()),
+        channel_cap,
+    )?;
+
+    // Only one upgrade of the channel end may be in progress at a time.
+    if ctx
+        .channel_upgrade(&(msg.port_id().clone(), msg.channel_id().clone()))
+        .is_some()
+    {
+        return Err(Kind::ChannelUpgradeInProgress(msg.channel_id().clone()).into());
+    }
+
+    // The proposed channel end is the channel end as it will be once upgraded, hence open.
+    let proposed_channel_end = msg.proposed_upgrade_channel().clone();
+
+    if !proposed_channel_end.state_matches(&State::Open) {
+        return Err(Kind::InvalidUpgradeChannelState(msg.channel_id().clone(), State::Open).into());
+    }
+
+    // The upgrade may change the ordering, connection hops and version, but not the counterparty.
+    if !proposed_channel_end.counterparty_matches(channel_end.counterparty()) {
+        return Err(Kind::UpgradeCounterpartyMismatch(msg.channel_id().clone()).into());
+    }
+
+    // An OPEN IBC connection running on the local (host) chain should exist.
+    if proposed_channel_end.connection_hops().is_empty() {
+        return Err(Kind::InvalidConnectionHopsLength(
+            1,
+            proposed_channel_end.connection_hops().len(),
+        )
+        .into());
+    }
+
+    let conn = ctx
+        .connection_end(&proposed_channel_end.connection_hops()[0])
+        .ok_or_else(|| {
+            Kind::MissingConnection(proposed_channel_end.connection_hops()[0].clone())
+        })?;
+
+    if !conn.state_matches(&ConnectionState::Open) {
+        return Err(
+            Kind::ConnectionNotOpen(proposed_channel_end.connection_hops()[0].clone()).into(),
+        );
+    }
+
+    // The connection must support the ordering of the upgraded channel.
+    verify_connection_features(&conn, proposed_channel_end.ordering())?;
+
+    output.log("success: channel upgrade init ");
+
+    // The channel end stays as it is until the upgrade completes.
+    let result = ChannelResult {
+        port_id: msg.port_id().clone(),
+        channel_id: msg.channel_id().clone(),
+        channel_id_state: ChannelIdState::Reused,
+        channel_end,
+        channel_upgrade: Some(ChannelUpgrade::new(
+            UpgradeState::Init,
+            proposed_channel_end,
+        )),
+        client_state: None,
+    };
+
+    let event_attributes = Attributes {
+        channel_id: Some(msg.channel_id().clone()),
+        ..Default::default()
+    };
+    output.emit(IbcEvent::UpgradeInitChannel(event_attributes.into()));
+
+    Ok(output.with_result(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::time::Duration;
+
+    use crate::events::IbcEvent;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics03_connection::connection::ConnectionEnd;
+    use crate::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::ics03_connection::connection::State as ConnectionState;
+    use crate::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::handler::test_util::get_channel_msg_capability;
+    use crate::ics04_channel::handler::{channel_dispatch, ChannelResult};
+    use crate::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+    use crate::ics04_channel::msgs::ChannelMsg;
+    use crate::ics04_channel::upgrade::{ChannelUpgrade, UpgradeState};
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId};
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    #[test]
+    fn chan_upgrade_init_msg_processing() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: ChannelMsg,
+            want_pass: bool,
+        }
+        let client_id = ClientId::new(ClientType::Mock, 24).unwrap();
+        let conn_id = ConnectionId::new(2);
+        let context = MockContext::default();
+
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+
+        let msg = MsgChannelUpgradeInit::try_from(get_dummy_raw_msg_chan_upgrade_init()).unwrap();
+
+        let proposed = msg.proposed_upgrade_channel().clone();
+        let mut proposed_with_conn = proposed.clone();
+        proposed_with_conn.connection_hops = vec![conn_id.clone()];
+        let msg = MsgChannelUpgradeInit {
+            proposed_upgrade_channel: proposed_with_conn.clone(),
+            ..msg
+        };
+
+        // The channel before the upgrade: open, unordered and running an older version.
+        let chan_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            proposed.counterparty().clone(),
+            vec![conn_id.clone()],
+            "ics20-0".to_string(),
+        );
+
+        let ctx = context
+            .with_client(&client_id, Height::new(0, 10))
+            .with_connection(conn_id.clone(), conn_end)
+            .with_port_capability(msg.port_id().clone());
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Processing fails because no channel exists in the context".to_string(),
+                ctx: ctx.clone(),
+                msg: ChannelMsg::ChannelUpgradeInit(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the channel is not open".to_string(),
+                ctx: ctx.clone().with_channel(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    ChannelEnd {
+                        state: State::TryOpen,
+                        ..chan_end.clone()
+                    },
+                ),
+                msg: ChannelMsg::ChannelUpgradeInit(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because an upgrade is already in progress".to_string(),
+                ctx: ctx
+                    .clone()
+                    .with_channel(
+                        msg.port_id().clone(),
+                        msg.channel_id().clone(),
+                        chan_end.clone(),
+                    )
+                    .with_channel_upgrade(
+                        msg.port_id().clone(),
+                        msg.channel_id().clone(),
+                        ChannelUpgrade::new(UpgradeState::Try, proposed_with_conn.clone()),
+                    ),
+                msg: ChannelMsg::ChannelUpgradeInit(msg.clone()),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the proposed channel is not open".to_string(),
+                ctx: ctx.clone().with_channel(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeInit(MsgChannelUpgradeInit {
+                    proposed_upgrade_channel: ChannelEnd {
+                        state: State::Init,
+                        ..proposed_with_conn.clone()
+                    },
+                    ..msg.clone()
+                }),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the upgrade changes the counterparty".to_string(),
+                ctx: ctx.clone().with_channel(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeInit(MsgChannelUpgradeInit {
+                    proposed_upgrade_channel: ChannelEnd {
+                        remote: Counterparty::new(
+                            proposed.counterparty().port_id().clone(),
+                            Some(ChannelId::new(42)),
+                        ),
+                        ..proposed_with_conn.clone()
+                    },
+                    ..msg.clone()
+                }),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the proposed connection does not exist".to_string(),
+                ctx: ctx.clone().with_channel(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeInit(MsgChannelUpgradeInit {
+                    proposed_upgrade_channel: ChannelEnd {
+                        connection_hops: vec![ConnectionId::new(7)],
+                        ..proposed_with_conn.clone()
+                    },
+                    ..msg.clone()
+                }),
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters".to_string(),
+                ctx: ctx.with_channel(
+                    msg.port_id().clone(),
+                    msg.channel_id().clone(),
+                    chan_end.clone(),
+                ),
+                msg: ChannelMsg::ChannelUpgradeInit(msg.clone()),
+                want_pass: true,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let cap = get_channel_msg_capability(&test.ctx, &test.msg);
+            let res = channel_dispatch(&test.ctx, &cap, test.msg.clone());
+            // Additionally check the events and the output objects in the result.
+            match res {
+                Ok(proto_output) => {
+                    assert_eq!(
+                        test.want_pass,
+                        true,
+                        "chan_upgrade_init: test passed but was supposed to fail for test: {}, \nparams {:?} {:?}",
+                        test.name,
+                        test.msg.clone(),
+                        test.ctx.clone()
+                    );
+                    assert_ne!(proto_output.events.is_empty(), true); // Some events must exist.
+
+                    // The channel end is left as it is, and the proposed one is stored apart.
+                    let res: ChannelResult = proto_output.result;
+                    assert_eq!(res.channel_end, chan_end);
+                    assert_eq!(
+                        res.channel_upgrade,
+                        Some(ChannelUpgrade::new(
+                            UpgradeState::Init,
+                            proposed_with_conn.clone()
+                        ))
+                    );
+
+                    for e in proto_output.events.iter() {
+                        assert!(matches!(e, &IbcEvent::UpgradeInitChannel(_)));
+                    }
+                }
+                Err(e) => {
+                    assert_eq!(
+                        test.want_pass,
+                        false,
+                        "chan_upgrade_init: did not pass test: {}, \nparams {:?} {:?}\nerror: {:?}",
+                        test.name,
+                        test.msg,
+                        test.ctx.clone(),
+                        e,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,15 +1,18 @@
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::height::Height;
 use crate::ics03_connection::connection::State as ConnectionState;
 use crate::ics04_channel::channel::{Counterparty, Order, State};
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
 use crate::ics04_channel::events::ReceivePacket;
-use crate::ics04_channel::handler::verify::verify_packet_recv_proofs;
+use crate::ics04_channel::handler::verify::{connection_client_state, verify_packet_recv_proofs};
 use crate::ics04_channel::msgs::recv_packet::MsgRecvPacket;
 use crate::ics04_channel::packet::{PacketResult, Receipt, Sequence};
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
+use crate::timestamp::Expiry;
 
 #[derive(Clone, Debug)]
 pub struct RecvPacketResult {
@@ -18,9 +21,14 @@ pub struct RecvPacketResult {
     pub seq: Sequence,
     pub seq_number: Sequence,
     pub receipt: Option<Receipt>,
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
-pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<PacketResult, Error> {
+pub fn process(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    msg: MsgRecvPacket,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let packet = &msg.packet;
@@ -45,7 +53,14 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         .into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.destination_port)?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        ),
+        channel_cap,
+    )?;
 
     let counterparty = Counterparty::new(
         packet.source_port.clone(),
@@ -68,8 +83,6 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         return Err(Kind::ConnectionNotOpen(dest_channel_end.connection_hops()[0].clone()).into());
     }
 
-    let client_id = connection_end.client_id().clone();
-
     // Check if packet height is newer than the height of the local host chain
     let latest_height = ctx.host_height();
     if (!packet.timeout_height.is_zero()) && (packet.timeout_height <= latest_height) {
@@ -78,15 +91,27 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
 
     // Check if packet timestamp is newer than the local host chain timestamp
     let latest_timestamp = ctx.host_timestamp();
-    if (packet.timeout_timestamp != 0) && (packet.timeout_timestamp <= latest_timestamp) {
-        return Err(Kind::LowPacketTimestamp.into());
+    if let Expiry::Expired = packet.timeout_timestamp.check_expiry(&latest_timestamp) {
+        return Err(Kind::LowPacketTimestamp(latest_timestamp, packet.timeout_timestamp).into());
     }
 
-    verify_packet_recv_proofs(ctx, &packet, client_id, &msg.proofs)?;
+    let client_state = connection_client_state(ctx, &connection_end)?;
+    let client_state = verify_packet_recv_proofs(
+        ctx,
+        client_state,
+        &dest_channel_end,
+        &packet,
+        &connection_end,
+        &msg.proofs,
+    )?;
+    let client_state = Some((connection_end.client_id().clone(), client_state));
 
     let result = if dest_channel_end.order_matches(&Order::Ordered) {
         let next_seq_recv = ctx
-            .get_next_sequence_recv(&(packet.source_port.clone(), packet.source_channel.clone()))
+            .get_next_sequence_recv(&(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+            ))
             .ok_or(Kind::MissingNextRecvSeq)?;
 
         if packet.sequence != next_seq_recv {
@@ -94,16 +119,17 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
         }
 
         PacketResult::Recv(RecvPacketResult {
-            port_id: packet.source_port.clone(),
-            channel_id: packet.source_channel.clone(),
+            port_id: packet.destination_port.clone(),
+            channel_id: packet.destination_channel.clone(),
             seq: packet.sequence,
             seq_number: next_seq_recv.increment(),
             receipt: None,
+            client_state,
         })
     } else {
         let packet_rec = ctx.get_packet_receipt(&(
-            packet.source_port.clone(),
-            packet.source_channel.clone(),
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
             packet.sequence,
         ));
 
@@ -112,11 +138,12 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgRecvPacket) -> HandlerResult<Pac
             None => {
                 // store a receipt that does not contain any data
                 PacketResult::Recv(RecvPacketResult {
-                    port_id: packet.source_port.clone(),
-                    channel_id: packet.source_channel.clone(),
+                    port_id: packet.destination_port.clone(),
+                    channel_id: packet.destination_channel.clone(),
                     seq: packet.sequence,
                     seq_number: 1.into(),
                     receipt: Some(Receipt::Ok),
+                    client_state,
                 })
             }
         }
@@ -145,10 +172,17 @@ mod tests {
     use crate::ics04_channel::handler::recv_packet::process;
     use crate::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
     use crate::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use crate::ics04_channel::multihop::test_util::{
+        get_dummy_hop_connection, get_dummy_multihop_proofs,
+    };
+    use crate::ics05_port::capabilities::Capability;
+    use crate::ics05_port::context::PortReader;
     use crate::ics18_relayer::context::Ics18Context;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::mock::context::MockContext;
+    use crate::proofs::Proofs;
     use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
     use crate::{events::IbcEvent, ics04_channel::packet::Packet};
 
     #[test]
@@ -180,7 +214,7 @@ mod tests {
             destination_channel: ChannelId::default(),
             data: vec![],
             timeout_height: client_height,
-            timeout_timestamp: 1,
+            timeout_timestamp: Timestamp::from_nanoseconds(1),
         };
 
         let msg_packet_old =
@@ -209,7 +243,71 @@ mod tests {
             Duration::from_secs(0),
         );
 
+        // A channel reaching the counterparty chain through an intermediate chain.
+        let multihop_channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(
+                packet.source_port.clone(),
+                Some(packet.source_channel.clone()),
+            ),
+            vec![ConnectionId::default(), ConnectionId::new(1)],
+            "ics20".to_string(),
+        );
+
+        let mut multihop_connection_end = connection_end.clone();
+        multihop_connection_end.set_counterparty(ConnectionCounterparty::new(
+            ClientId::default(),
+            Some(ConnectionId::default()),
+            b"ibc".to_vec().into(),
+        ));
+
+        let multihop_context = context
+            .clone()
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), multihop_connection_end)
+            .with_port_capability(packet.destination_port.clone())
+            .with_channel(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+                multihop_channel_end,
+            )
+            .with_height(host_height)
+            .with_timestamp(Timestamp::from_nanoseconds(1));
+
+        let multihop_msg = |hop_state: ConnectionState| {
+            let mut hop_connection = get_dummy_hop_connection("07-tendermint-1", "connection-7");
+            hop_connection.set_state(hop_state);
+            let proofs = Proofs::new(
+                get_dummy_multihop_proofs(vec![hop_connection]).into(),
+                None,
+                None,
+                None,
+                msg.proofs.height(),
+            )
+            .unwrap();
+            MsgRecvPacket::new(msg.packet.clone(), proofs, get_dummy_account_id())
+        };
+
         let tests: Vec<Test> = vec![
+            Test {
+                name: "Multi-hop channel with proofs through its intermediate chain".to_string(),
+                ctx: multihop_context.clone(),
+                msg: multihop_msg(ConnectionState::Open),
+                want_pass: true,
+            },
+            Test {
+                name: "Multi-hop channel whose intermediate connection is not open".to_string(),
+                ctx: multihop_context.clone(),
+                msg: multihop_msg(ConnectionState::Init),
+                want_pass: false,
+            },
+            Test {
+                name: "Multi-hop channel with a single-hop proof".to_string(),
+                ctx: multihop_context,
+                msg: msg.clone(),
+                want_pass: false,
+            },
             Test {
                 name: "Processing fails because no channel exists in the context".to_string(),
                 ctx: context.clone(),
@@ -245,7 +343,7 @@ mod tests {
                         1.into(),
                     )
                     .with_height(host_height)
-                    .with_timestamp(1)
+                    .with_timestamp(Timestamp::from_nanoseconds(1))
                     // This `with_recv_sequence` is required for ordered channels
                     .with_recv_sequence(
                         packet.destination_port.clone(),
@@ -264,7 +362,7 @@ mod tests {
                     .with_channel(PortId::default(), ChannelId::default(), dest_channel_end)
                     .with_send_sequence(PortId::default(), ChannelId::default(), 1.into())
                     .with_height(host_height)
-                    .with_timestamp(3),
+                    .with_timestamp(Timestamp::from_nanoseconds(3)),
                 msg: msg_packet_old,
                 want_pass: false,
             },
@@ -273,7 +371,15 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            // Fall back on a capability which the context never handed out.
+            let channel_cap = test
+                .ctx
+                .lookup_module_by_channel(
+                    &test.msg.packet.destination_port,
+                    &test.msg.packet.destination_channel,
+                )
+                .unwrap_or_else(|| Capability::new(u64::MAX));
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
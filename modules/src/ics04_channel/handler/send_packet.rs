@@ -6,7 +6,9 @@ use crate::ics04_channel::channel::State;
 use crate::ics04_channel::events::SendPacket;
 use crate::ics04_channel::packet::{PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind, packet::Packet};
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::timestamp::{Expiry, Timestamp};
 use crate::Height;
 
 #[derive(Clone, Debug)]
@@ -16,11 +18,17 @@ pub struct SendPacketResult {
     pub seq: Sequence,
     pub seq_number: Sequence,
     pub timeout_height: Height,
-    pub timeout_timestamp: u64,
+    pub timeout_timestamp: Timestamp,
     pub data: Vec<u8>,
 }
 
-pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<PacketResult, Error> {
+/// Processes a packet which an application module sends over a channel. The module proves that it
+/// may use the channel by presenting the channel capability, `channel_cap`.
+pub fn send_packet(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    packet: Packet,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let source_channel_end = ctx
@@ -34,7 +42,10 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         return Err(Kind::ChannelClosed(packet.source_channel).into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    ctx.authenticate_channel_capability(
+        &(packet.source_port.clone(), packet.source_channel.clone()),
+        channel_cap,
+    )?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -64,26 +75,49 @@ pub fn send_packet(ctx: &dyn ChannelReader, packet: Packet) -> HandlerResult<Pac
         return Err(Kind::FrozenClient(connection_end.client_id().clone()).into());
     }
 
-    // check if packet height is newer than the height of the latest client state on the receiving chain
-    let latest_height = client_state.latest_height();
+    // The client of a single-hop channel tracks the receiving chain, so the timeouts of the
+    // packet can be checked against its latest consensus state. The client of a multi-hop
+    // channel tracks the first intermediate chain instead, and the timeouts are only enforced by
+    // the receiving chain, hence a multi-hop packet must have at least one of them.
     let packet_height = packet.timeout_height;
 
-    if !packet.timeout_height.is_zero() && packet_height <= latest_height {
-        return Err(Kind::LowPacketHeight(latest_height, packet.timeout_height).into());
+    if source_channel_end.connection_hops().len() > 1
+        && packet.timeout_height.is_zero()
+        && !packet.timeout_timestamp.is_set()
+    {
+        return Err(Kind::ZeroPacketTimeout.into());
     }
 
-    //check if packet timestamp is newer than the timestamp of the latest consensus state of the receiving chain
-    let consensus_state = ctx
-        .client_consensus_state(&client_id, latest_height)
-        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), latest_height))?;
+    if source_channel_end.connection_hops().len() == 1 {
+        // check if packet height is newer than the height of the latest client state on the receiving chain
+        let latest_height = client_state.latest_height();
 
-    let latest_timestamp = consensus_state
-        .timestamp()
-        .map_err(Kind::ErrorInvalidConsensusState)?;
+        if !packet.timeout_height.is_zero() && packet_height <= latest_height {
+            return Err(Kind::LowPacketHeight(latest_height, packet.timeout_height).into());
+        }
+
+        //check if packet timestamp is newer than the timestamp of the latest consensus state of the receiving chain
+        let consensus_state = ctx
+            .client_consensus_state(&client_id, latest_height)
+            .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), latest_height))?;
 
-    let packet_timestamp = packet.timeout_timestamp;
-    if packet.timeout_timestamp != 0 && packet_timestamp <= latest_timestamp {
-        return Err(Kind::LowPacketTimestamp.into());
+        let latest_timestamp = consensus_state
+            .timestamp()
+            .map_err(Kind::ErrorInvalidConsensusState)?;
+
+        match packet.timeout_timestamp.check_expiry(&latest_timestamp) {
+            Expiry::Expired => {
+                return Err(
+                    Kind::LowPacketTimestamp(latest_timestamp, packet.timeout_timestamp).into(),
+                );
+            }
+            Expiry::InvalidTimestamp => {
+                return Err(
+                    Kind::InvalidPacketTimestamp(packet.timeout_timestamp, client_id).into(),
+                );
+            }
+            Expiry::NotExpired => {}
+        }
     }
 
     // check sequence number
@@ -121,6 +155,7 @@ mod tests {
     use std::time::Duration;
 
     use crate::events::IbcEvent;
+    use crate::ics02_client::client_type::ClientType;
     use crate::ics02_client::height::Height;
     use crate::ics03_connection::connection::ConnectionEnd;
     use crate::ics03_connection::connection::Counterparty as ConnectionCounterparty;
@@ -130,8 +165,11 @@ mod tests {
     use crate::ics04_channel::handler::send_packet::send_packet;
     use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
     use crate::ics04_channel::packet::Packet;
+    use crate::ics05_port::capabilities::{Capability, CapabilityName, ModuleId};
+    use crate::ics05_port::context::CapabilityReader;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::mock::context::MockContext;
+    use crate::timestamp::Timestamp;
 
     #[test]
     fn send_packet_processing() {
@@ -139,6 +177,8 @@ mod tests {
             name: String,
             ctx: MockContext,
             packet: Packet,
+            // The name of the capability which the sending module presents.
+            cap_name: CapabilityName,
             want_pass: bool,
         }
 
@@ -174,22 +214,42 @@ mod tests {
 
         let client_height = Height::new(0, Height::default().revision_height + 1);
 
+        let mut packet_no_timeout = packet_old.clone();
+        packet_no_timeout.timeout_height = Height::zero();
+        packet_no_timeout.timeout_timestamp = Timestamp::none();
+
+        let multihop_channel_end = ChannelEnd::new(
+            State::Open,
+            Order::default(),
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default(), ConnectionId::new(1)],
+            "ics20".to_string(),
+        );
+
+        let port_cap_name = CapabilityName::port(&PortId::default());
+        let channel_cap_name = CapabilityName::channel(&PortId::default(), &ChannelId::default());
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Processing fails because no channel exists in the context".to_string(),
                 ctx: context.clone(),
                 packet: packet.clone(),
+                cap_name: channel_cap_name.clone(),
                 want_pass: false,
             },
             Test {
-                name: "Processing fails because the port does not have a capability associated"
+                name: "Processing fails because the port capability does not authorize the use \
+                    of the channel"
                     .to_string(),
-                ctx: context.clone().with_channel(
-                    PortId::default(),
-                    ChannelId::default(),
-                    channel_end.clone(),
-                ),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), Height::default())
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_port_capability(PortId::default())
+                    .with_channel(PortId::default(), ChannelId::default(), channel_end.clone())
+                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
                 packet: packet.clone(),
+                cap_name: port_cap_name,
                 want_pass: false,
             },
             Test {
@@ -202,8 +262,65 @@ mod tests {
                     .with_channel(PortId::default(), ChannelId::default(), channel_end.clone())
                     .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
                 packet,
+                cap_name: channel_cap_name.clone(),
                 want_pass: true,
             },
+            Test {
+                name: "Timeouts of multi-hop packets are left to the receiving chain".to_string(),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_port_capability(PortId::default())
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        multihop_channel_end.clone(),
+                    )
+                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
+                packet: packet_old.clone(),
+                cap_name: channel_cap_name.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Processing fails because a multi-hop packet without timeouts would never \
+                    time out"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_port_capability(PortId::default())
+                    .with_channel(
+                        PortId::default(),
+                        ChannelId::default(),
+                        multihop_channel_end,
+                    )
+                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
+                packet: packet_no_timeout,
+                cap_name: channel_cap_name.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the timeout timestamp cannot be checked against \
+                    a client without timestamps"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_client_parametrized(
+                        &ClientId::default(),
+                        Height::default(),
+                        Some(ClientType::Localhost),
+                        None,
+                    )
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_port_capability(PortId::default())
+                    .with_channel(PortId::default(), ChannelId::default(), channel_end.clone())
+                    .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
+                packet: packet.clone(),
+                cap_name: channel_cap_name.clone(),
+                want_pass: false,
+            },
             Test {
                 name: "Packet timeout".to_string(),
                 ctx: context
@@ -213,6 +330,7 @@ mod tests {
                     .with_channel(PortId::default(), ChannelId::default(), channel_end)
                     .with_send_sequence(PortId::default(), ChannelId::default(), 1.into()),
                 packet: packet_old,
+                cap_name: channel_cap_name,
                 want_pass: false,
             },
         ]
@@ -220,7 +338,13 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = send_packet(&test.ctx, test.packet.clone());
+            // Fall back on a capability which the context never handed out.
+            let channel_cap = test
+                .ctx
+                .get_capability(&ModuleId::ibc(), &test.cap_name)
+                .unwrap_or_else(|| Capability::new(u64::MAX));
+
+            let res = send_packet(&test.ctx, &channel_cap, test.packet.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
@@ -1,15 +1,18 @@
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics04_channel::channel::State;
 use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::ics04_channel::events::TimeoutPacket;
 use crate::ics04_channel::handler::verify::{
-    verify_next_sequence_recv, verify_packet_receipt_absence,
+    connection_client_state, counterparty_consensus_state, verify_next_sequence_recv,
+    verify_packet_receipt_absence,
 };
 use crate::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::ics04_channel::packet::{PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind};
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 
 #[derive(Clone, Debug)]
 pub struct TimeoutPacketResult {
@@ -17,9 +20,14 @@ pub struct TimeoutPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub channel: Option<ChannelEnd>,
+    pub client_state: Option<(ClientId, AnyClientState)>,
 }
 
-pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<PacketResult, Error> {
+pub fn process(
+    ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
+    msg: MsgTimeout,
+) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
 
     let packet = &msg.packet;
@@ -35,7 +43,11 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
         return Err(Kind::ChannelClosed(packet.source_channel.clone()).into());
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(packet.source_port.clone(), packet.source_channel.clone()),
+        channel_cap,
+    )?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -54,31 +66,29 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
         .connection_end(&source_channel_end.connection_hops()[0])
         .ok_or_else(|| Kind::MissingConnection(source_channel_end.connection_hops()[0].clone()))?;
 
-    let client_id = connection_end.client_id().clone();
-
-    // check that timeout height or timeout timestamp has passed on the other end
-    let proof_height = msg.proofs.height();
-    let packet_height = packet.timeout_height;
-
-    if (!packet.timeout_height.is_zero()) && packet_height > proof_height {
-        return Err(
-            Kind::PacketTimeoutHeightNotReached(packet.timeout_height, proof_height).into(),
-        );
-    }
-
-    let consensus_state = ctx
-        .client_consensus_state(&client_id, proof_height)
-        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), proof_height))?;
+    // check that timeout height or timeout timestamp has passed on the other end, as witnessed by
+    // the consensus state of the counterparty chain at the proof height
+    let client_state = connection_client_state(ctx, &connection_end)?;
+    let (proof_height, consensus_state) = counterparty_consensus_state(
+        ctx,
+        client_state.clone(),
+        &source_channel_end,
+        &connection_end,
+        &msg.proofs,
+    )?;
 
     let proof_timestamp = consensus_state
         .timestamp()
         .map_err(Kind::ErrorInvalidConsensusState)?;
 
-    let packet_timestamp = packet.timeout_timestamp;
-    if packet.timeout_timestamp != 0 && packet_timestamp > proof_timestamp {
-        return Err(
-            Kind::PacketTimeoutTimestampNotReached(packet_timestamp, proof_timestamp).into(),
-        );
+    if !packet.timed_out(&proof_timestamp, proof_height) {
+        return Err(Kind::PacketTimeoutNotReached(
+            packet.timeout_height,
+            proof_height,
+            packet.timeout_timestamp,
+            proof_timestamp,
+        )
+        .into());
     }
 
     //verify packet commitment
@@ -90,12 +100,13 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
         ))
         .ok_or(Kind::PacketCommitmentNotFound(packet.sequence))?;
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data,
-    );
-
-    if packet_commitment != ChannelReader::hash(ctx, input) {
+    if packet_commitment
+        != ctx.packet_commitment(
+            packet.timeout_timestamp,
+            packet.timeout_height,
+            &packet.data,
+        )
+    {
         return Err(Kind::IncorrectPacketCommitment(packet.sequence).into());
     }
 
@@ -105,9 +116,11 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
                 Kind::InvalidPacketSequence(packet.sequence, msg.next_sequence_recv).into(),
             );
         }
-        verify_next_sequence_recv(
+        let client_state = verify_next_sequence_recv(
             ctx,
-            client_id,
+            client_state,
+            &source_channel_end,
+            &connection_end,
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs.clone(),
@@ -119,15 +132,24 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            client_state: Some((connection_end.client_id().clone(), client_state)),
         })
     } else {
-        verify_packet_receipt_absence(ctx, client_id, packet.clone(), &msg.proofs.clone())?;
+        let client_state = verify_packet_receipt_absence(
+            ctx,
+            client_state,
+            &source_channel_end,
+            &connection_end,
+            packet.clone(),
+            &msg.proofs,
+        )?;
 
         PacketResult::Timeout(TimeoutPacketResult {
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: None,
+            client_state: Some((connection_end.client_id().clone(), client_state)),
         })
     };
 
@@ -145,6 +167,7 @@ pub fn process(ctx: &dyn ChannelReader, msg: MsgTimeout) -> HandlerResult<Packet
 mod tests {
 
     use crate::events::IbcEvent;
+    use crate::ics02_client::context::ClientKeeper;
     use crate::ics02_client::height::Height;
     use crate::ics03_connection::connection::ConnectionEnd;
     use crate::ics03_connection::connection::Counterparty as ConnectionCounterparty;
@@ -155,9 +178,14 @@ mod tests {
     use crate::ics04_channel::handler::timeout::process;
     use crate::ics04_channel::msgs::timeout::test_util::get_dummy_raw_msg_timeout;
     use crate::ics04_channel::msgs::timeout::MsgTimeout;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics05_port::capabilities::Capability;
+    use crate::ics05_port::context::PortReader;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 
+    use crate::mock::client_state::MockConsensusState;
     use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
 
     use std::convert::TryFrom;
     use std::time::Duration;
@@ -185,13 +213,37 @@ mod tests {
         let mut msg_ok = msg.clone();
         msg_ok.packet.timeout_timestamp = Default::default();
 
-        let input = format!(
-            "{:?},{:?},{:?}",
-            msg_ok.packet.timeout_timestamp,
-            msg_ok.packet.timeout_height.clone(),
-            msg_ok.packet.data.clone()
-        );
-        let data = ChannelReader::hash(&context, input);
+        // Neither the timeout height nor the timeout timestamp are reached at the proof height.
+        let mut msg_not_timed_out = msg.clone();
+        msg_not_timed_out.packet.timeout_height = client_height.increment();
+
+        // Only the timeout timestamp is reached at the proof height.
+        let mut msg_timestamp_timeout = msg.clone();
+        msg_timestamp_timeout.packet.timeout_height = Height::zero();
+
+        let commitment = |packet: &Packet| {
+            context.packet_commitment(
+                packet.timeout_timestamp,
+                packet.timeout_height,
+                &packet.data,
+            )
+        };
+        let data = commitment(&msg_ok.packet);
+
+        let timestamped_consensus_state = MockConsensusState::new(MockHeader {
+            height: client_height,
+            timestamp: timeout_timestamp,
+        });
+        let mut context_timestamped = context
+            .clone()
+            .with_client(&ClientId::default(), client_height);
+        context_timestamped
+            .store_consensus_state(
+                ClientId::default(),
+                client_height,
+                timestamped_consensus_state.into(),
+            )
+            .unwrap();
 
         let source_channel_end = ChannelEnd::new(
             State::Open,
@@ -240,7 +292,7 @@ mod tests {
                 want_pass: false,
             },
             Test {
-                name: "Processing fails because the proof's timeout has not been reached "
+                name: "Processing fails because there is no commitment for the packet "
                     .to_string(),
                 ctx: context.clone().with_channel(
                     PortId::default(),
@@ -253,6 +305,45 @@ mod tests {
                 msg,
                 want_pass: false,
             },
+            Test {
+                name: "Processing fails because neither the timeout height nor the timeout timestamp have been reached"
+                    .to_string(),
+                ctx: context.clone().with_channel(
+                    PortId::default(),
+                    ChannelId::default(),
+                    source_channel_end.clone(),
+                )
+                .with_client(&ClientId::default(), client_height)
+                .with_port_capability(packet.destination_port.clone())
+                .with_connection(ConnectionId::default(), connection_end.clone())
+                .with_packet_commitment(
+                    packet.source_port.clone(),
+                    packet.source_channel.clone(),
+                    packet.sequence,
+                    commitment(&msg_not_timed_out.packet),
+                ),
+                msg: msg_not_timed_out,
+                want_pass: false,
+            },
+            Test {
+                name: "Good parameters Unordered channel, timeout on timestamp only".to_string(),
+                ctx: context_timestamped
+                    .with_connection(ConnectionId::default(), connection_end.clone())
+                    .with_port_capability(packet.destination_port.clone())
+                    .with_channel(
+                        packet.source_port.clone(),
+                        packet.source_channel.clone(),
+                        source_channel_end.clone(),
+                    )
+                    .with_packet_commitment(
+                        packet.source_port.clone(),
+                        packet.source_channel.clone(),
+                        packet.sequence,
+                        commitment(&msg_timestamp_timeout.packet),
+                    ),
+                msg: msg_timestamp_timeout,
+                want_pass: true,
+            },
             Test {
                 name: "Good parameters Unordered channel".to_string(),
                 ctx: context.clone()
@@ -303,7 +394,15 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            // Fall back on a capability which the context never handed out.
+            let channel_cap = test
+                .ctx
+                .lookup_module_by_channel(
+                    &test.msg.packet.source_port,
+                    &test.msg.packet.source_channel,
+                )
+                .unwrap_or_else(|| Capability::new(u64::MAX));
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
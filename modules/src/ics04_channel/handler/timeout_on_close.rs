@@ -3,8 +3,8 @@ use crate::handler::{HandlerOutput, HandlerResult};
 use crate::ics04_channel::channel::State;
 use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::ics04_channel::events::TimeoutOnClosePacket;
-use crate::ics04_channel::handler::verify::verify_channel_proofs;
 use crate::ics04_channel::handler::verify::{
+    connection_client_state, counterparty_connection_hops, verify_channel_proofs,
     verify_next_sequence_recv, verify_packet_receipt_absence,
 };
 use crate::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
@@ -12,9 +12,12 @@ use crate::ics04_channel::packet::PacketResult;
 use crate::ics04_channel::{
     context::ChannelReader, error::Error, error::Kind, handler::timeout::TimeoutPacketResult,
 };
+use crate::ics05_port::capabilities::Capability;
+use crate::proofs::Proofs;
 
 pub fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     msg: MsgTimeoutOnClose,
 ) -> HandlerResult<PacketResult, Error> {
     let mut output = HandlerOutput::builder();
@@ -28,7 +31,11 @@ pub fn process(
                 .context(packet.source_channel.to_string())
         })?;
 
-    let _channel_cap = ctx.authenticated_capability(&packet.source_port)?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(packet.source_port.clone(), packet.source_channel.clone()),
+        channel_cap,
+    )?;
 
     let counterparty = Counterparty::new(
         packet.destination_port.clone(),
@@ -47,8 +54,6 @@ pub fn process(
         .connection_end(&source_channel_end.connection_hops()[0])
         .ok_or_else(|| Kind::MissingConnection(source_channel_end.connection_hops()[0].clone()))?;
 
-    let client_id = connection_end.client_id().clone();
-
     //verify the packet was sent, check the store
     let packet_commitment = ctx
         .get_packet_commitment(&(
@@ -58,12 +63,13 @@ pub fn process(
         ))
         .ok_or(Kind::PacketCommitmentNotFound(packet.sequence))?;
 
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data,
-    );
-
-    if packet_commitment != ChannelReader::hash(ctx, input) {
+    if packet_commitment
+        != ctx.packet_commitment(
+            packet.timeout_timestamp,
+            packet.timeout_height,
+            &packet.data,
+        )
+    {
         return Err(Kind::IncorrectPacketCommitment(packet.sequence).into());
     }
 
@@ -72,12 +78,19 @@ pub fn process(
         Some(packet.source_channel.clone()),
     );
 
-    let counterparty = connection_end.counterparty();
-    let ccid = counterparty.connection_id().ok_or_else(|| {
-        Kind::UndefinedConnectionCounterparty(source_channel_end.connection_hops()[0].clone())
-    })?;
+    // The channel closure is proven by `proof_close`, whereas the object proof of the message
+    // proves that the packet was not received.
+    let proof_close = msg
+        .proofs
+        .other_proof()
+        .clone()
+        .ok_or_else(|| Kind::InvalidProof.context("missing proof of channel closure"))?;
 
-    let expected_connection_hops = vec![ccid.clone()];
+    let expected_connection_hops = counterparty_connection_hops(
+        source_channel_end.connection_hops(),
+        &connection_end,
+        &proof_close,
+    )?;
 
     let expected_channel_end = ChannelEnd::new(
         State::Closed,
@@ -87,12 +100,19 @@ pub fn process(
         source_channel_end.version(),
     );
 
-    verify_channel_proofs(
+    let channel_proofs = Proofs::new(proof_close, None, None, None, msg.proofs.height())
+        .map_err(|e| Kind::InvalidProof.context(e))?;
+
+    // The proof of unreceipt is verified against the client state which the verification of the
+    // channel closure left.
+    let client_state = connection_client_state(ctx, &connection_end)?;
+    let client_state = verify_channel_proofs(
         ctx,
+        client_state,
         &source_channel_end,
         &connection_end,
         &expected_channel_end,
-        &msg.proofs.clone(),
+        &channel_proofs,
     )?;
 
     let result = if source_channel_end.order_matches(&Order::Ordered) {
@@ -101,9 +121,11 @@ pub fn process(
                 Kind::InvalidPacketSequence(packet.sequence, msg.next_sequence_recv).into(),
             );
         }
-        verify_next_sequence_recv(
+        let client_state = verify_next_sequence_recv(
             ctx,
-            client_id,
+            client_state,
+            &source_channel_end,
+            &connection_end,
             packet.clone(),
             msg.next_sequence_recv,
             &msg.proofs.clone(),
@@ -114,15 +136,24 @@ pub fn process(
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: Some(source_channel_end),
+            client_state: Some((connection_end.client_id().clone(), client_state)),
         })
     } else {
-        verify_packet_receipt_absence(ctx, client_id, packet.clone(), &msg.proofs.clone())?;
+        let client_state = verify_packet_receipt_absence(
+            ctx,
+            client_state,
+            &source_channel_end,
+            &connection_end,
+            packet.clone(),
+            &msg.proofs,
+        )?;
 
         PacketResult::Timeout(TimeoutPacketResult {
             port_id: packet.source_port.clone(),
             channel_id: packet.source_channel.clone(),
             seq: packet.sequence,
             channel: None,
+            client_state: Some((connection_end.client_id().clone(), client_state)),
         })
     };
 
@@ -150,6 +181,8 @@ mod tests {
     use crate::ics04_channel::handler::timeout_on_close::process;
     use crate::ics04_channel::msgs::timeout_on_close::test_util::get_dummy_raw_msg_timeout_on_close;
     use crate::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+    use crate::ics05_port::capabilities::Capability;
+    use crate::ics05_port::context::PortReader;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 
     use crate::mock::context::MockContext;
@@ -180,13 +213,11 @@ mod tests {
         .unwrap();
         let packet = msg.packet.clone();
 
-        let input = format!(
-            "{:?},{:?},{:?}",
+        let data = context.packet_commitment(
             msg.packet.timeout_timestamp,
-            msg.packet.timeout_height.clone(),
-            msg.packet.data.clone()
+            msg.packet.timeout_height,
+            &msg.packet.data,
         );
-        let data = ChannelReader::hash(&context, input);
 
         let source_channel_end = ChannelEnd::new(
             State::Open,
@@ -257,7 +288,15 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.msg.clone());
+            // Fall back on a capability which the context never handed out.
+            let channel_cap = test
+                .ctx
+                .lookup_module_by_channel(
+                    &test.msg.packet.source_port,
+                    &test.msg.packet.source_channel,
+                )
+                .unwrap_or_else(|| Capability::new(u64::MAX));
+            let res = process(&test.ctx, &channel_cap, test.msg.clone());
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
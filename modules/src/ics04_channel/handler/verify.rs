@@ -1,50 +1,217 @@
-use crate::ics02_client::client_state::ClientState;
+use std::convert::TryFrom;
+
+use crate::ics02_client::client_consensus::{AnyConsensusState, ConsensusState};
+use crate::ics02_client::client_state::{AnyClientState, ClientState};
 use crate::ics02_client::{client_def::AnyClient, client_def::ClientDef};
-use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::connection::{ConnectionEnd, State as ConnectionState};
 use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::error::{Error, Kind};
+use crate::ics04_channel::multihop::MultihopProofs;
 use crate::ics04_channel::packet::{Packet, Sequence};
-use crate::ics24_host::identifier::ClientId;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::ics24_host::identifier::ConnectionId;
 use crate::proofs::Proofs;
+use crate::Height;
 
-/// Entry point for verifying all proofs bundled in any ICS4 message for channel protocols.
-pub fn verify_channel_proofs(
+/// The state of the chain at the other end of a channel, against which the key proof of a
+/// message is verified. For a multi-hop channel, this is the state of the last chain along the
+/// connection hops, as proven by the connection ends and consensus states which the intermediate
+/// chains store.
+struct CounterpartyState {
+    /// The client of the first connection hop, which verifies the proofs on every chain.
+    client_def: AnyClient,
+    client_state: AnyClientState,
+    height: Height,
+    prefix: CommitmentPrefix,
+    consensus_state: AnyConsensusState,
+    key_proof: CommitmentProofBytes,
+}
+
+/// Fetches the state of the client of `connection_end`, which performs the verification of the
+/// proofs of the messages on the channels over this connection. The client must not be frozen.
+/// The verification functions below take this state and return it as the verification left it,
+/// e.g., a solo machine client at its next sequence, for the host to persist.
+pub fn connection_client_state(
     ctx: &dyn ChannelReader,
-    channel_end: &ChannelEnd,
     connection_end: &ConnectionEnd,
-    expected_chan: &ChannelEnd,
-    proofs: &Proofs,
-) -> Result<(), Error> {
-    // This is the client which will perform proof verification.
-    let client_id = connection_end.client_id().clone();
-
+) -> Result<AnyClientState, Error> {
+    let client_id = connection_end.client_id();
     let client_state = ctx
-        .client_state(&client_id)
+        .client_state(client_id)
         .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
 
-    // The client must not be frozen.
     if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
+        return Err(Kind::FrozenClient(client_id.clone()).into());
     }
 
-    if ctx
-        .client_consensus_state(&client_id, proofs.height())
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(client_id, proofs.height()).into());
-    }
+    Ok(client_state)
+}
+
+/// Returns the state of the counterparty chain of `channel_end`, as witnessed by the host chain
+/// at `height`. The `proof` of a multi-hop channel is verified along the connection hops, starting
+/// from `client_state`, the state of the client of `connection_end`.
+fn counterparty_state(
+    ctx: &dyn ChannelReader,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
+    proof: &CommitmentProofBytes,
+    height: Height,
+) -> Result<CounterpartyState, Error> {
+    let client_id = connection_end.client_id();
+    let consensus_state = ctx
+        .client_consensus_state(client_id, height)
+        .ok_or_else(|| Kind::MissingClientConsensusState(client_id.clone(), height))?;
 
     let client_def = AnyClient::from_client_type(client_state.client_type());
 
+    let mut state = CounterpartyState {
+        client_def,
+        client_state,
+        height,
+        prefix: connection_end.counterparty().prefix().clone(),
+        consensus_state,
+        key_proof: proof.clone(),
+    };
+
+    let connection_hops = channel_end.connection_hops();
+    if connection_hops.len() <= 1 {
+        return Ok(state);
+    }
+
+    let multihop_proofs = MultihopProofs::try_from(proof)?;
+    if multihop_proofs.hops().len() != connection_hops.len() - 1 {
+        return Err(Kind::InvalidMultihopProofLength(
+            connection_hops.len() - 1,
+            multihop_proofs.hops().len(),
+        )
+        .into());
+    }
+
+    // Each intermediate chain must store an open connection for the next hop, along with the
+    // consensus state of the next chain under the client of that connection. The root of this
+    // consensus state proves the state of the next chain, and so on until the counterparty chain.
+    for (hop, connection_id) in multihop_proofs.hops().iter().zip(&connection_hops[1..]) {
+        if !hop.connection_end().state_matches(&ConnectionState::Open) {
+            return Err(Kind::ConnectionNotOpen(connection_id.clone()).into());
+        }
+
+        state.client_state = state
+            .client_def
+            .verify_connection_state(
+                ctx.connection_reader(),
+                &state.client_state,
+                state.height,
+                &state.prefix,
+                hop.proof_connection(),
+                state.consensus_state.root(),
+                Some(connection_id),
+                hop.connection_end(),
+            )
+            .map_err(|_| Kind::ConnectionHopVerificationFailed(connection_id.clone()))?;
+
+        state.client_state = state
+            .client_def
+            .verify_client_consensus_state(
+                ctx.connection_reader(),
+                &state.client_state,
+                state.height,
+                &state.prefix,
+                hop.proof_consensus(),
+                state.consensus_state.root(),
+                hop.connection_end().client_id(),
+                hop.consensus_height(),
+                hop.consensus_state(),
+            )
+            .map_err(|_| Kind::ConnectionHopVerificationFailed(connection_id.clone()))?;
+
+        state.height = hop.consensus_height();
+        state.prefix = hop.connection_end().counterparty().prefix().clone();
+        state.consensus_state = hop.consensus_state().clone();
+    }
+
+    state.key_proof = multihop_proofs.key_proof().clone();
+
+    Ok(state)
+}
+
+/// Returns the connection hops which the counterparty end of a channel with the given
+/// `connection_hops` must have, given the connection end of the first hop on the host chain and
+/// the `proof` of a message on this channel. These are the counterparty connections of every hop,
+/// in the reverse order, where the connection ends of the intermediate chains are taken from the
+/// (yet to be verified) multi-hop proof.
+pub fn counterparty_connection_hops(
+    connection_hops: &[ConnectionId],
+    connection_end: &ConnectionEnd,
+    proof: &CommitmentProofBytes,
+) -> Result<Vec<ConnectionId>, Error> {
+    if connection_hops.len() <= 1 {
+        let counterparty_connection_id = connection_end
+            .counterparty()
+            .connection_id()
+            .ok_or_else(|| Kind::UndefinedConnectionCounterparty(connection_hops[0].clone()))?;
+
+        return Ok(vec![counterparty_connection_id.clone()]);
+    }
+
+    MultihopProofs::try_from(proof)?.counterparty_connection_hops(connection_end)
+}
+
+/// Returns the height and the consensus state of the counterparty chain of `channel_end` at which
+/// the key proof of `proofs` is verified. For a multi-hop channel, this is the consensus state of
+/// the last chain along the connection hops, rather than that of the chain tracked by the client of
+/// the first hop. Any update of `client_state` along the hops is discarded, as the hops are
+/// verified again along with the key proof.
+pub fn counterparty_consensus_state(
+    ctx: &dyn ChannelReader,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
+    proofs: &Proofs,
+) -> Result<(Height, AnyConsensusState), Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
+
+    Ok((state.height, state.consensus_state))
+}
+
+/// Entry point for verifying all proofs bundled in any ICS4 message for channel protocols.
+pub fn verify_channel_proofs(
+    ctx: &dyn ChannelReader,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
+    expected_chan: &ChannelEnd,
+    proofs: &Proofs,
+) -> Result<AnyClientState, Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
+
     // Verify the proof for the channel state against the expected channel end.
     // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-    Ok(client_def
+    Ok(state
+        .client_def
         .verify_channel_state(
-            &client_state,
-            proofs.height(),
-            connection_end.counterparty().prefix(),
-            proofs.object_proof(),
+            ctx,
+            &state.client_state,
+            state.height,
+            &state.prefix,
+            &state.key_proof,
+            state.consensus_state.root(),
             &channel_end.counterparty().port_id(),
             &channel_end.counterparty().channel_id().unwrap(),
             expected_chan,
@@ -52,43 +219,76 @@ pub fn verify_channel_proofs(
         .map_err(|_| Kind::InvalidProof)?)
 }
 
+/// Entry point for verifying all proofs bundled in the ICS4 messages of the channel upgrade
+/// handshake, which prove the upgrade in progress on the counterparty channel end.
+pub fn verify_channel_upgrade_proofs(
+    ctx: &dyn ChannelReader,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
+    expected_upgrade: &ChannelUpgrade,
+    proofs: &Proofs,
+) -> Result<AnyClientState, Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
+
+    // Verify the proof for the upgrade against the expected upgrade of the counterparty.
+    Ok(state
+        .client_def
+        .verify_channel_upgrade(
+            ctx,
+            &state.client_state,
+            state.height,
+            &state.prefix,
+            &state.key_proof,
+            state.consensus_state.root(),
+            &channel_end.counterparty().port_id(),
+            &channel_end.counterparty().channel_id().unwrap(),
+            expected_upgrade,
+        )
+        .map_err(|_| Kind::InvalidProof)?)
+}
+
 /// Entry point for verifying all proofs bundled in a ICS4 packet recv. message.
 pub fn verify_packet_recv_proofs(
     ctx: &dyn ChannelReader,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
     packet: &Packet,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+) -> Result<AnyClientState, Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
 
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
-
-    if ctx
-        .client_consensus_state(&client_id, proofs.height())
-        .is_none()
-    {
-        return Err(Kind::MissingClientConsensusState(client_id, proofs.height()).into());
-    }
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
-
-    let input = format!(
-        "{:?},{:?},{:?}",
-        packet.timeout_timestamp, packet.timeout_height, packet.data
+    let commitment = ctx.packet_commitment(
+        packet.timeout_timestamp,
+        packet.timeout_height,
+        &packet.data,
     );
-    let commitment = ctx.hash(input);
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    Ok(state
+        .client_def
         .verify_packet_data(
-            &client_state,
-            proofs.height(),
-            proofs.object_proof(),
+            ctx,
+            &state.client_state,
+            state.height,
+            &state.prefix,
+            &state.key_proof,
+            state.consensus_state.root(),
             &packet.source_port,
             &packet.source_channel,
             &packet.sequence,
@@ -100,30 +300,35 @@ pub fn verify_packet_recv_proofs(
 /// Entry point for verifying all proofs bundled in an ICS4 packet ack message.
 pub fn verify_packet_acknowledgement_proofs(
     ctx: &dyn ChannelReader,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
     packet: &Packet,
     acknowledgement: Vec<u8>,
-    client_id: ClientId,
+    connection_end: &ConnectionEnd,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
+) -> Result<AnyClientState, Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
 
-    // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    // Verify the proof for the packet against the chain store. The acknowledgement is written by
+    // the receiving chain, hence it is stored under the destination port and channel.
+    Ok(state
+        .client_def
         .verify_packet_acknowledgement(
-            &client_state,
-            proofs.height(),
-            proofs.object_proof(),
-            &packet.source_port,
-            &packet.source_channel,
+            ctx,
+            &state.client_state,
+            state.height,
+            &state.prefix,
+            &state.key_proof,
+            state.consensus_state.root(),
+            &packet.destination_port,
+            &packet.destination_channel,
             &packet.sequence,
             acknowledgement,
         )
@@ -133,28 +338,32 @@ pub fn verify_packet_acknowledgement_proofs(
 /// Entry point for verifying all timeout proofs.
 pub fn verify_next_sequence_recv(
     ctx: &dyn ChannelReader,
-    client_id: ClientId,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
     packet: Packet,
     seq: Sequence,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
+) -> Result<AnyClientState, Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    Ok(state
+        .client_def
         .verify_next_sequence_recv(
-            &client_state,
-            proofs.height(),
-            proofs.object_proof(),
+            ctx,
+            &state.client_state,
+            state.height,
+            &state.prefix,
+            &state.key_proof,
+            state.consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             &seq,
@@ -164,27 +373,31 @@ pub fn verify_next_sequence_recv(
 
 pub fn verify_packet_receipt_absence(
     ctx: &dyn ChannelReader,
-    client_id: ClientId,
+    client_state: AnyClientState,
+    channel_end: &ChannelEnd,
+    connection_end: &ConnectionEnd,
     packet: Packet,
     proofs: &Proofs,
-) -> Result<(), Error> {
-    let client_state = ctx
-        .client_state(&client_id)
-        .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
-
-    // The client must not be frozen.
-    if client_state.is_frozen() {
-        return Err(Kind::FrozenClient(client_id).into());
-    }
-
-    let client_def = AnyClient::from_client_type(client_state.client_type());
+) -> Result<AnyClientState, Error> {
+    let state = counterparty_state(
+        ctx,
+        client_state,
+        channel_end,
+        connection_end,
+        proofs.object_proof(),
+        proofs.height(),
+    )?;
 
     // Verify the proof for the packet against the chain store.
-    Ok(client_def
+    Ok(state
+        .client_def
         .verify_packet_receipt_absence(
-            &client_state,
-            proofs.height(),
-            proofs.object_proof(),
+            ctx,
+            &state.client_state,
+            state.height,
+            &state.prefix,
+            &state.key_proof,
+            state.consensus_state.root(),
             &packet.destination_port,
             &packet.destination_channel,
             &packet.sequence,
@@ -2,6 +2,7 @@ use crate::ics04_channel::channel::State;
 use crate::ics04_channel::events::WriteAcknowledgement;
 use crate::ics04_channel::packet::{Packet, PacketResult, Sequence};
 use crate::ics04_channel::{context::ChannelReader, error::Error, error::Kind};
+use crate::ics05_port::capabilities::Capability;
 use crate::ics24_host::identifier::{ChannelId, PortId};
 use crate::{
     events::IbcEvent,
@@ -18,6 +19,7 @@ pub struct WriteAckPacketResult {
 
 pub fn process(
     ctx: &dyn ChannelReader,
+    channel_cap: &Capability,
     packet: Packet,
     ack: Vec<u8>,
 ) -> HandlerResult<PacketResult, Error> {
@@ -41,7 +43,14 @@ pub fn process(
         );
     }
 
-    let _channel_cap = ctx.authenticated_capability(&packet.destination_port)?;
+    // The caller must own the channel end.
+    ctx.authenticate_channel_capability(
+        &(
+            packet.destination_port.clone(),
+            packet.destination_channel.clone(),
+        ),
+        channel_cap,
+    )?;
 
     // NOTE: IBC app modules might have written the acknowledgement synchronously on
     // the OnRecvPacket callback so we need to check if the acknowledgement is already
@@ -61,9 +70,11 @@ pub fn process(
         return Err(Kind::InvalidAcknowledgement.into());
     }
 
+    // The acknowledgement is stored under the channel end of the host chain, i.e., the receiving
+    // end of the packet.
     let result = PacketResult::WriteAck(WriteAckPacketResult {
-        port_id: packet.source_port.clone(),
-        channel_id: packet.source_channel.clone(),
+        port_id: packet.destination_port.clone(),
+        channel_id: packet.destination_channel.clone(),
         seq: packet.sequence,
         ack: ack.clone(),
     });
@@ -92,6 +103,8 @@ mod tests {
     use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
     use crate::ics04_channel::handler::write_acknowledgement::process;
     use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics05_port::capabilities::Capability;
+    use crate::ics05_port::context::PortReader;
     use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::mock::context::MockContext;
     use crate::{events::IbcEvent, ics04_channel::packet::Packet};
@@ -192,7 +205,15 @@ mod tests {
         .collect();
 
         for test in tests {
-            let res = process(&test.ctx, test.packet.clone(), test.ack);
+            // Fall back on a capability which the context never handed out.
+            let channel_cap = test
+                .ctx
+                .lookup_module_by_channel(
+                    &test.packet.destination_port,
+                    &test.packet.destination_channel,
+                )
+                .unwrap_or_else(|| Capability::new(u64::MAX));
+            let res = process(&test.ctx, &channel_cap, test.packet.clone(), test.ack);
             // Additionally check the events and the output objects in the result.
             match res {
                 Ok(proto_output) => {
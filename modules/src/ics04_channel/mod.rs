@@ -7,5 +7,7 @@ pub mod events;
 
 pub mod handler;
 pub mod msgs;
+pub mod multihop;
 pub mod packet;
+pub mod upgrade;
 pub mod version;
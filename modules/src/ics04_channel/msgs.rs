@@ -1,5 +1,5 @@
-//! Message definitions for all ICS4 domain types: channel open & close handshake datagrams, as well
-//! as packets.
+//! Message definitions for all ICS4 domain types: channel open, close & upgrade handshake
+//! datagrams, as well as packets.
 
 use acknowledgement::MsgAcknowledgement;
 
@@ -9,6 +9,11 @@ use crate::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use crate::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use crate::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
 
 use self::{recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose};
 
@@ -22,6 +27,13 @@ pub mod chan_open_try;
 pub mod chan_close_confirm;
 pub mod chan_close_init;
 
+// Upgrade handshake messages.
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_try;
+
 // Packet specific messages.
 pub mod acknowledgement;
 pub mod recv_packet;
@@ -37,6 +49,11 @@ pub enum ChannelMsg {
     ChannelOpenConfirm(MsgChannelOpenConfirm),
     ChannelCloseInit(MsgChannelCloseInit),
     ChannelCloseConfirm(MsgChannelCloseConfirm),
+    ChannelUpgradeInit(MsgChannelUpgradeInit),
+    ChannelUpgradeTry(MsgChannelUpgradeTry),
+    ChannelUpgradeAck(MsgChannelUpgradeAck),
+    ChannelUpgradeConfirm(MsgChannelUpgradeConfirm),
+    ChannelUpgradeCancel(MsgChannelUpgradeCancel),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -0,0 +1,199 @@
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::proofs::Proofs;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeAck as RawMsgChannelUpgradeAck;
+use tendermint_proto::Protobuf;
+
+use std::convert::{TryFrom, TryInto};
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeAck";
+
+///
+/// Message definition for the third step in the channel upgrade handshake (`ChanUpgradeAck`
+/// datagram). The proofs show that the counterparty channel end accepted the upgrade.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeAck {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub proofs: Proofs,
+    pub signer: Signer,
+}
+
+impl MsgChannelUpgradeAck {
+    pub fn new(port_id: PortId, channel_id: ChannelId, proofs: Proofs, signer: Signer) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            proofs,
+            signer,
+        }
+    }
+
+    /// Getter: borrow the `port_id` from this message.
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
+
+    pub fn proofs(&self) -> &Proofs {
+        &self.proofs
+    }
+}
+
+impl Msg for MsgChannelUpgradeAck {
+    type ValidationError = Error;
+    type Raw = RawMsgChannelUpgradeAck;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeAck> for MsgChannelUpgradeAck {}
+
+impl TryFrom<RawMsgChannelUpgradeAck> for MsgChannelUpgradeAck {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeAck) -> Result<Self, Self::Error> {
+        let proofs = Proofs::new(
+            raw_msg.proof_upgrade_try.into(),
+            None,
+            None,
+            None,
+            raw_msg
+                .proof_height
+                .ok_or(Kind::MissingHeight)?
+                .try_into()
+                .map_err(|e| Kind::InvalidProof.context(e))?,
+        )
+        .map_err(|e| Kind::InvalidProof.context(e))?;
+
+        Ok(MsgChannelUpgradeAck {
+            port_id: raw_msg
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            proofs,
+            signer: raw_msg.signer.into(),
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeAck> for RawMsgChannelUpgradeAck {
+    fn from(domain_msg: MsgChannelUpgradeAck) -> Self {
+        RawMsgChannelUpgradeAck {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            proof_upgrade_try: domain_msg.proofs.object_proof().clone().into(),
+            proof_height: Some(domain_msg.proofs.height().into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeAck as RawMsgChannelUpgradeAck;
+    use ibc_proto::ibc::core::client::v1::Height;
+
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeAck`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_ack(proof_height: u64) -> RawMsgChannelUpgradeAck {
+        RawMsgChannelUpgradeAck {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            proof_upgrade_try: get_dummy_proof(),
+            proof_height: Some(Height {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeAck as RawMsgChannelUpgradeAck;
+    use std::convert::TryFrom;
+
+    use crate::ics04_channel::msgs::chan_upgrade_ack::test_util::get_dummy_raw_msg_chan_upgrade_ack;
+    use crate::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+
+    #[test]
+    fn channel_upgrade_ack_from_raw() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeAck,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_ack(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Missing proof height".to_string(),
+                raw: RawMsgChannelUpgradeAck {
+                    proof_height: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing object proof".to_string(),
+                raw: RawMsgChannelUpgradeAck {
+                    proof_upgrade_try: vec![],
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res_msg = MsgChannelUpgradeAck::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                res_msg.is_ok(),
+                "MsgChanUpgradeAck::try_from failed for test {}, \nraw msg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                res_msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_ack(10);
+        let msg = MsgChannelUpgradeAck::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeAck::from(msg.clone());
+        let msg_back = MsgChannelUpgradeAck::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,173 @@
+use std::convert::TryFrom;
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeCancel as RawMsgChannelUpgradeCancel;
+
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeCancel";
+
+///
+/// Message definition for the cancellation of the channel upgrade handshake
+/// (`ChanUpgradeCancel` datagram). Only the channel end which proposed the upgrade can cancel it,
+/// as long as the counterparty has not completed it.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeCancel {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub signer: Signer,
+}
+
+impl MsgChannelUpgradeCancel {
+    pub fn new(port_id: PortId, channel_id: ChannelId, signer: Signer) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            signer,
+        }
+    }
+
+    /// Getter: borrow the `port_id` from this message.
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
+}
+
+impl Msg for MsgChannelUpgradeCancel {
+    type ValidationError = Error;
+    type Raw = RawMsgChannelUpgradeCancel;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeCancel> for MsgChannelUpgradeCancel {}
+
+impl TryFrom<RawMsgChannelUpgradeCancel> for MsgChannelUpgradeCancel {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeCancel) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeCancel {
+            port_id: raw_msg
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            signer: raw_msg.signer.into(),
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeCancel> for RawMsgChannelUpgradeCancel {
+    fn from(domain_msg: MsgChannelUpgradeCancel) -> Self {
+        RawMsgChannelUpgradeCancel {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeCancel as RawMsgChannelUpgradeCancel;
+
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgChannelUpgradeCancel`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_cancel() -> RawMsgChannelUpgradeCancel {
+        RawMsgChannelUpgradeCancel {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeCancel as RawMsgChannelUpgradeCancel;
+
+    use crate::ics04_channel::msgs::chan_upgrade_cancel::test_util::get_dummy_raw_msg_chan_upgrade_cancel;
+    use crate::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+
+    #[test]
+    fn channel_upgrade_cancel_from_raw() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeCancel,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_cancel();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad port, name too short".to_string(),
+                raw: RawMsgChannelUpgradeCancel {
+                    port_id: "p".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeCancel {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res_msg = MsgChannelUpgradeCancel::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                res_msg.is_ok(),
+                "MsgChanUpgradeCancel::try_from failed for test {}, \nraw msg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                res_msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_cancel();
+        let msg = MsgChannelUpgradeCancel::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeCancel::from(msg.clone());
+        let msg_back = MsgChannelUpgradeCancel::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
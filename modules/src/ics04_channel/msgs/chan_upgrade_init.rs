@@ -0,0 +1,195 @@
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeInit as RawMsgChannelUpgradeInit;
+use tendermint_proto::Protobuf;
+
+use std::convert::{TryFrom, TryInto};
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeInit";
+
+///
+/// Message definition for the first step in the channel upgrade handshake (`ChanUpgradeInit`
+/// datagram). The proposed upgrade channel carries the new ordering, connection hops and version
+/// of the channel.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeInit {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub proposed_upgrade_channel: ChannelEnd,
+    pub signer: Signer,
+}
+
+impl MsgChannelUpgradeInit {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        proposed_upgrade_channel: ChannelEnd,
+        signer: Signer,
+    ) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            proposed_upgrade_channel,
+            signer,
+        }
+    }
+
+    /// Getter: borrow the `port_id` from this message.
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
+
+    pub fn proposed_upgrade_channel(&self) -> &ChannelEnd {
+        &self.proposed_upgrade_channel
+    }
+}
+
+impl Msg for MsgChannelUpgradeInit {
+    type ValidationError = Error;
+    type Raw = RawMsgChannelUpgradeInit;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeInit> for MsgChannelUpgradeInit {}
+
+impl TryFrom<RawMsgChannelUpgradeInit> for MsgChannelUpgradeInit {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeInit) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeInit {
+            port_id: raw_msg
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            proposed_upgrade_channel: raw_msg
+                .proposed_upgrade_channel
+                .ok_or(Kind::MissingChannel)?
+                .try_into()?,
+            signer: raw_msg.signer.into(),
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeInit> for RawMsgChannelUpgradeInit {
+    fn from(domain_msg: MsgChannelUpgradeInit) -> Self {
+        RawMsgChannelUpgradeInit {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            proposed_upgrade_channel: Some(domain_msg.proposed_upgrade_channel.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeInit as RawMsgChannelUpgradeInit;
+
+    use crate::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
+    use crate::ics04_channel::channel::State;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgChannelUpgradeInit`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_init() -> RawMsgChannelUpgradeInit {
+        RawMsgChannelUpgradeInit {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            proposed_upgrade_channel: Some(RawChannel {
+                state: State::Open as i32,
+                ..get_dummy_raw_channel_end()
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeInit as RawMsgChannelUpgradeInit;
+    use std::convert::TryFrom;
+
+    use crate::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+
+    #[test]
+    fn channel_upgrade_init_from_raw() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeInit,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_init();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proposed upgrade channel".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    proposed_upgrade_channel: None,
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res_msg = MsgChannelUpgradeInit::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                res_msg.is_ok(),
+                "MsgChanUpgradeInit::try_from failed for test {}, \nraw msg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                res_msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_init();
+        let msg = MsgChannelUpgradeInit::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeInit::from(msg.clone());
+        let msg_back = MsgChannelUpgradeInit::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
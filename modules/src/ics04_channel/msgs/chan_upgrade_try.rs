@@ -0,0 +1,232 @@
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::proofs::Proofs;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeTry as RawMsgChannelUpgradeTry;
+use tendermint_proto::Protobuf;
+
+use std::convert::{TryFrom, TryInto};
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeTry";
+
+///
+/// Message definition for the second step in the channel upgrade handshake (`ChanUpgradeTry`
+/// datagram). The proofs show that the counterparty channel end proposed the same upgrade.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgChannelUpgradeTry {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub proposed_upgrade_channel: ChannelEnd,
+    pub proofs: Proofs,
+    pub signer: Signer,
+}
+
+impl MsgChannelUpgradeTry {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        proposed_upgrade_channel: ChannelEnd,
+        proofs: Proofs,
+        signer: Signer,
+    ) -> Self {
+        Self {
+            port_id,
+            channel_id,
+            proposed_upgrade_channel,
+            proofs,
+            signer,
+        }
+    }
+
+    /// Getter: borrow the `port_id` from this message.
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
+
+    pub fn proposed_upgrade_channel(&self) -> &ChannelEnd {
+        &self.proposed_upgrade_channel
+    }
+
+    pub fn proofs(&self) -> &Proofs {
+        &self.proofs
+    }
+}
+
+impl Msg for MsgChannelUpgradeTry {
+    type ValidationError = Error;
+    type Raw = RawMsgChannelUpgradeTry;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeTry> for MsgChannelUpgradeTry {}
+
+impl TryFrom<RawMsgChannelUpgradeTry> for MsgChannelUpgradeTry {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeTry) -> Result<Self, Self::Error> {
+        let proofs = Proofs::new(
+            raw_msg.proof_upgrade_init.into(),
+            None,
+            None,
+            None,
+            raw_msg
+                .proof_height
+                .ok_or(Kind::MissingHeight)?
+                .try_into()
+                .map_err(|e| Kind::InvalidProof.context(e))?,
+        )
+        .map_err(|e| Kind::InvalidProof.context(e))?;
+
+        Ok(MsgChannelUpgradeTry {
+            port_id: raw_msg
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            proposed_upgrade_channel: raw_msg
+                .proposed_upgrade_channel
+                .ok_or(Kind::MissingChannel)?
+                .try_into()?,
+            proofs,
+            signer: raw_msg.signer.into(),
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeTry> for RawMsgChannelUpgradeTry {
+    fn from(domain_msg: MsgChannelUpgradeTry) -> Self {
+        RawMsgChannelUpgradeTry {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.channel_id.to_string(),
+            proposed_upgrade_channel: Some(domain_msg.proposed_upgrade_channel.into()),
+            proof_upgrade_init: domain_msg.proofs.object_proof().clone().into(),
+            proof_height: Some(domain_msg.proofs.height().into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeTry as RawMsgChannelUpgradeTry;
+    use ibc_proto::ibc::core::client::v1::Height;
+
+    use crate::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
+    use crate::ics04_channel::channel::State;
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeTry`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_try(proof_height: u64) -> RawMsgChannelUpgradeTry {
+        RawMsgChannelUpgradeTry {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            proposed_upgrade_channel: Some(RawChannel {
+                state: State::Open as i32,
+                ..get_dummy_raw_channel_end()
+            }),
+            proof_upgrade_init: get_dummy_proof(),
+            proof_height: Some(Height {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::core::channel::v1::MsgChannelUpgradeTry as RawMsgChannelUpgradeTry;
+    use std::convert::TryFrom;
+
+    use crate::ics04_channel::msgs::chan_upgrade_try::test_util::get_dummy_raw_msg_chan_upgrade_try;
+    use crate::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+
+    #[test]
+    fn channel_upgrade_try_from_raw() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeTry,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_try(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Missing proposed upgrade channel".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    proposed_upgrade_channel: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof height".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    proof_height: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing object proof".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    proof_upgrade_init: vec![],
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res_msg = MsgChannelUpgradeTry::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                res_msg.is_ok(),
+                "MsgChanUpgradeTry::try_from failed for test {}, \nraw msg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                res_msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_try(10);
+        let msg = MsgChannelUpgradeTry::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeTry::from(msg.clone());
+        let msg_back = MsgChannelUpgradeTry::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
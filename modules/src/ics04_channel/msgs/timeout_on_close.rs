@@ -62,7 +62,7 @@ impl TryFrom<RawMsgTimeoutOnClose> for MsgTimeoutOnClose {
             raw_msg.proof_unreceived.into(),
             None,
             None,
-            None,
+            Some(raw_msg.proof_close.into()),
             raw_msg
                 .proof_height
                 .ok_or(Kind::MissingHeight)?
@@ -0,0 +1,313 @@
+//! Proofs for multi-hop channels, i.e., channels whose connection hops traverse intermediate
+//! chains instead of connecting the two channel ends directly.
+//!
+//! A multi-hop channel end on chain `A` with connection hops `[A->B, B->C, C->D]` has its
+//! counterparty on chain `D`. Chain `A` only has a client of `B`, so a key stored on `D` is
+//! proven to `A` through a chain of proofs: `B` stores the connection `B->C` along with a
+//! consensus state of `C`, which in turn stores the connection `C->D` along with a consensus
+//! state of `D`, whose commitment root finally proves the key.
+
+use std::convert::{TryFrom, TryInto};
+
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::MsgMultihopProofs as RawMultihopProofs;
+use ibc_proto::ibc::core::channel::v1::MultihopProof as RawMultihopProof;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics04_channel::error::{Error, Kind};
+use crate::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::ics24_host::identifier::ConnectionId;
+use crate::Height;
+
+/// The proof, on an intermediate chain of a multi-hop channel, of the connection end leading to
+/// the next chain and of the consensus state which the intermediate chain stores for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultihopProof {
+    connection_end: ConnectionEnd,
+    proof_connection: CommitmentProofBytes,
+    consensus_state: AnyConsensusState,
+    proof_consensus: CommitmentProofBytes,
+    consensus_height: Height,
+}
+
+impl MultihopProof {
+    pub fn new(
+        connection_end: ConnectionEnd,
+        proof_connection: CommitmentProofBytes,
+        consensus_state: AnyConsensusState,
+        proof_consensus: CommitmentProofBytes,
+        consensus_height: Height,
+    ) -> Self {
+        Self {
+            connection_end,
+            proof_connection,
+            consensus_state,
+            proof_consensus,
+            consensus_height,
+        }
+    }
+
+    /// Getter for the connection end of this hop, as stored on the intermediate chain.
+    pub fn connection_end(&self) -> &ConnectionEnd {
+        &self.connection_end
+    }
+
+    /// Getter for the proof of the connection end.
+    pub fn proof_connection(&self) -> &CommitmentProofBytes {
+        &self.proof_connection
+    }
+
+    /// Getter for the consensus state of the next chain, as stored by the client of the
+    /// connection end of this hop.
+    pub fn consensus_state(&self) -> &AnyConsensusState {
+        &self.consensus_state
+    }
+
+    /// Getter for the proof of the consensus state.
+    pub fn proof_consensus(&self) -> &CommitmentProofBytes {
+        &self.proof_consensus
+    }
+
+    /// Getter for the height of the consensus state, at which the proofs of the next hop (or the
+    /// key proof) are verified.
+    pub fn consensus_height(&self) -> Height {
+        self.consensus_height
+    }
+}
+
+impl Protobuf<RawMultihopProof> for MultihopProof {}
+
+impl TryFrom<RawMultihopProof> for MultihopProof {
+    type Error = Error;
+
+    fn try_from(raw: RawMultihopProof) -> Result<Self, Self::Error> {
+        let connection_end = raw
+            .connection
+            .ok_or_else(|| Kind::InvalidMultihopProof.context("missing connection end"))?
+            .try_into()
+            .map_err(|e| Kind::InvalidMultihopProof.context(e))?;
+
+        let consensus_state = raw
+            .consensus_state
+            .ok_or_else(|| Kind::InvalidMultihopProof.context("missing consensus state"))?
+            .try_into()
+            .map_err(|e| Kind::InvalidMultihopProof.context(e))?;
+
+        let consensus_height = raw
+            .consensus_height
+            .ok_or(Kind::MissingHeight)?
+            .try_into()
+            .map_err(|e| Kind::InvalidMultihopProof.context(e))?;
+
+        Ok(MultihopProof {
+            connection_end,
+            proof_connection: raw.proof_connection.into(),
+            consensus_state,
+            proof_consensus: raw.proof_consensus.into(),
+            consensus_height,
+        })
+    }
+}
+
+impl From<MultihopProof> for RawMultihopProof {
+    fn from(proof: MultihopProof) -> Self {
+        RawMultihopProof {
+            connection: Some(proof.connection_end.into()),
+            proof_connection: proof.proof_connection.into(),
+            consensus_state: Some(proof.consensus_state.into()),
+            proof_consensus: proof.proof_consensus.into(),
+            consensus_height: Some(proof.consensus_height.into()),
+        }
+    }
+}
+
+/// The proof of a key stored on the counterparty chain of a multi-hop channel. It is encoded in
+/// place of the proof bytes of the channel and packet messages, so that these messages are the
+/// same for single-hop and multi-hop channels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultihopProofs {
+    key_proof: CommitmentProofBytes,
+    hops: Vec<MultihopProof>,
+}
+
+impl MultihopProofs {
+    pub fn new(key_proof: CommitmentProofBytes, hops: Vec<MultihopProof>) -> Self {
+        Self { key_proof, hops }
+    }
+
+    /// Getter for the proof of the key on the counterparty chain.
+    pub fn key_proof(&self) -> &CommitmentProofBytes {
+        &self.key_proof
+    }
+
+    /// Getter for the proofs on the intermediate chains, ordered from the chain reached by the
+    /// first connection hop to the chain preceding the counterparty chain.
+    pub fn hops(&self) -> &[MultihopProof] {
+        &self.hops
+    }
+
+    /// Returns the connection hops of the counterparty channel end, given the connection end of
+    /// the first hop on the host chain. These are the counterparty connections of each hop, in
+    /// the reverse order.
+    pub fn counterparty_connection_hops(
+        &self,
+        connection_end: &ConnectionEnd,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        std::iter::once(connection_end)
+            .chain(self.hops.iter().map(MultihopProof::connection_end))
+            .map(|connection_end| -> Result<ConnectionId, Error> {
+                Ok(connection_end
+                    .counterparty()
+                    .connection_id()
+                    .cloned()
+                    .ok_or_else(|| {
+                        Kind::InvalidMultihopProof.context("undefined counterparty connection")
+                    })?)
+            })
+            .rev()
+            .collect()
+    }
+}
+
+impl Protobuf<RawMultihopProofs> for MultihopProofs {}
+
+impl TryFrom<RawMultihopProofs> for MultihopProofs {
+    type Error = Error;
+
+    fn try_from(raw: RawMultihopProofs) -> Result<Self, Self::Error> {
+        if raw.key_proof.is_empty() {
+            return Err(Kind::InvalidMultihopProof
+                .context("key proof cannot be empty")
+                .into());
+        }
+
+        Ok(MultihopProofs {
+            key_proof: raw.key_proof.into(),
+            hops: raw
+                .hops
+                .into_iter()
+                .map(MultihopProof::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<MultihopProofs> for RawMultihopProofs {
+    fn from(proofs: MultihopProofs) -> Self {
+        RawMultihopProofs {
+            key_proof: proofs.key_proof.into(),
+            hops: proofs.hops.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<&CommitmentProofBytes> for MultihopProofs {
+    type Error = Error;
+
+    fn try_from(proof: &CommitmentProofBytes) -> Result<Self, Self::Error> {
+        MultihopProofs::decode_vec(&Vec::from(proof.clone()))
+            .map_err(|e| Kind::InvalidMultihopProof.context(e).into())
+    }
+}
+
+impl From<MultihopProofs> for CommitmentProofBytes {
+    fn from(proofs: MultihopProofs) -> Self {
+        proofs.encode_vec().unwrap().into()
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use crate::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::multihop::{MultihopProof, MultihopProofs};
+    use crate::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::mock::client_state::MockConsensusState;
+    use crate::mock::header::MockHeader;
+    use crate::Height;
+
+    /// Returns an OPEN connection end tracked by `client_id`, whose counterparty connection is
+    /// `counterparty_connection_id`.
+    pub fn get_dummy_hop_connection(
+        client_id: &str,
+        counterparty_connection_id: &str,
+    ) -> ConnectionEnd {
+        ConnectionEnd::new(
+            State::Open,
+            client_id.parse().unwrap(),
+            Counterparty::new(
+                ClientId::default(),
+                Some(counterparty_connection_id.parse::<ConnectionId>().unwrap()),
+                CommitmentPrefix::from(b"ibc".to_vec()),
+            ),
+            get_compatible_versions(),
+            Default::default(),
+        )
+    }
+
+    /// Returns multi-hop proofs through one intermediate chain per connection end in `hops`.
+    pub fn get_dummy_multihop_proofs(hops: Vec<ConnectionEnd>) -> MultihopProofs {
+        let consensus_height = Height::new(0, 10);
+        MultihopProofs::new(
+            vec![0].into(),
+            hops.into_iter()
+                .map(|connection_end| {
+                    MultihopProof::new(
+                        connection_end,
+                        vec![0].into(),
+                        MockConsensusState::new(MockHeader::new(consensus_height)).into(),
+                        vec![0].into(),
+                        consensus_height,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::ics04_channel::multihop::test_util::{
+        get_dummy_hop_connection, get_dummy_multihop_proofs,
+    };
+    use crate::ics04_channel::multihop::MultihopProofs;
+    use crate::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::ics24_host::identifier::ConnectionId;
+
+    #[test]
+    fn multihop_proofs_encoding() {
+        let proofs = get_dummy_multihop_proofs(vec![
+            get_dummy_hop_connection("07-tendermint-1", "connection-11"),
+            get_dummy_hop_connection("07-tendermint-2", "connection-12"),
+        ]);
+
+        let bytes = CommitmentProofBytes::from(proofs.clone());
+        assert_eq!(MultihopProofs::try_from(&bytes).unwrap(), proofs);
+
+        // Plain commitment proofs are not multi-hop proofs.
+        assert!(MultihopProofs::try_from(&CommitmentProofBytes::from(vec![1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn counterparty_connection_hops() {
+        let proofs = get_dummy_multihop_proofs(vec![get_dummy_hop_connection(
+            "07-tendermint-1",
+            "connection-11",
+        )]);
+        let first = get_dummy_hop_connection("07-tendermint-0", "connection-10");
+
+        let expected: Vec<ConnectionId> = vec![
+            "connection-11".parse().unwrap(),
+            "connection-10".parse().unwrap(),
+        ];
+        assert_eq!(
+            proofs.counterparty_connection_hops(&first).unwrap(),
+            expected
+        );
+    }
+}
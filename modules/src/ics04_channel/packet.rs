@@ -3,9 +3,12 @@ use std::convert::{TryFrom, TryInto};
 use serde_derive::{Deserialize, Serialize};
 
 use ibc_proto::ibc::core::channel::v1::Packet as RawPacket;
+use ibc_proto::ibc::core::channel::v1::PacketId as RawPacketId;
 
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics04_channel::error::Kind;
-use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
+use crate::timestamp::{Expiry, Timestamp};
 use crate::Height;
 
 use super::handler::{
@@ -32,6 +35,19 @@ pub enum PacketResult {
     Timeout(TimeoutPacketResult),
 }
 
+impl PacketResult {
+    /// Returns the state of the client which verified the proofs of the packet message, as the
+    /// verification left it, or `None` if the handler verified no proof.
+    pub fn client_state(&self) -> Option<&(ClientId, AnyClientState)> {
+        match self {
+            PacketResult::Send(_) | PacketResult::WriteAck(_) => None,
+            PacketResult::Recv(result) => result.client_state.as_ref(),
+            PacketResult::Ack(result) => result.client_state.as_ref(),
+            PacketResult::Timeout(result) => result.client_state.as_ref(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Receipt {
     Ok,
@@ -81,6 +97,58 @@ impl std::fmt::Display for Sequence {
     }
 }
 
+/// Identifies a packet by the port and channel on which it was sent, and by its sequence.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct PacketId {
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub sequence: Sequence,
+}
+
+impl PacketId {
+    pub fn new(port_id: PortId, channel_id: ChannelId, sequence: Sequence) -> Self {
+        PacketId {
+            port_id,
+            channel_id,
+            sequence,
+        }
+    }
+}
+
+impl std::fmt::Display for PacketId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}/{}/{}", self.port_id, self.channel_id, self.sequence)
+    }
+}
+
+impl TryFrom<RawPacketId> for PacketId {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(raw: RawPacketId) -> Result<Self, Self::Error> {
+        Ok(PacketId {
+            port_id: raw
+                .port_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            channel_id: raw
+                .channel_id
+                .parse()
+                .map_err(|e| Kind::IdentifierError.context(e))?,
+            sequence: Sequence::from(raw.sequence),
+        })
+    }
+}
+
+impl From<PacketId> for RawPacketId {
+    fn from(packet_id: PacketId) -> Self {
+        RawPacketId {
+            port_id: packet_id.port_id.to_string(),
+            channel_id: packet_id.channel_id.to_string(),
+            sequence: packet_id.sequence.into(),
+        }
+    }
+}
+
 #[derive(PartialEq, Deserialize, Serialize, Hash, Clone)]
 pub struct Packet {
     pub sequence: Sequence,
@@ -91,7 +159,17 @@ pub struct Packet {
     #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
     pub data: Vec<u8>,
     pub timeout_height: Height,
-    pub timeout_timestamp: u64,
+    pub timeout_timestamp: Timestamp,
+}
+
+impl Packet {
+    /// Checks whether the packet timed out on the destination chain, given the height and the
+    /// timestamp of some block of that chain. A packet times out as soon as either its timeout
+    /// height or its timeout timestamp is reached; a zero timeout height never expires.
+    pub fn timed_out(&self, dst_chain_timestamp: &Timestamp, dst_chain_height: Height) -> bool {
+        (!self.timeout_height.is_zero() && self.timeout_height <= dst_chain_height)
+            || self.timeout_timestamp.check_expiry(dst_chain_timestamp) == Expiry::Expired
+    }
 }
 
 impl std::fmt::Debug for Packet {
@@ -131,7 +209,7 @@ impl Default for Packet {
             destination_channel: Default::default(),
             data: vec![],
             timeout_height: Default::default(),
-            timeout_timestamp: 0,
+            timeout_timestamp: Timestamp::none(),
         }
     }
 }
@@ -149,7 +227,9 @@ impl TryFrom<RawPacket> for Packet {
             .try_into()
             .map_err(|e| Kind::InvalidTimeoutHeight.context(e))?;
 
-        if packet_timeout_height.is_zero() && raw_pkt.timeout_timestamp == 0 {
+        let packet_timeout_timestamp = Timestamp::from_nanoseconds(raw_pkt.timeout_timestamp);
+
+        if packet_timeout_height.is_zero() && !packet_timeout_timestamp.is_set() {
             return Err(Kind::ZeroPacketTimeout.into());
         }
         if raw_pkt.data.is_empty() {
@@ -176,7 +256,7 @@ impl TryFrom<RawPacket> for Packet {
                 .map_err(|e| Kind::IdentifierError.context(e))?,
             data: raw_pkt.data,
             timeout_height: packet_timeout_height,
-            timeout_timestamp: raw_pkt.timeout_timestamp,
+            timeout_timestamp: packet_timeout_timestamp,
         })
     }
 }
@@ -191,7 +271,7 @@ impl From<Packet> for RawPacket {
             destination_channel: packet.destination_channel.to_string(),
             data: packet.data,
             timeout_height: Some(packet.timeout_height.into()),
-            timeout_timestamp: packet.timeout_timestamp,
+            timeout_timestamp: packet.timeout_timestamp.as_nanoseconds(),
         }
     }
 }
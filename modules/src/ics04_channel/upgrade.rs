@@ -0,0 +1,131 @@
+//! The upgrade of a channel end in progress. A channel end under upgrade stays open and usable as
+//! it is, whereas the proposed upgrade lives under a separate path of the store until the upgrade
+//! handshake either completes, which replaces the channel end with the upgraded one, or is
+//! cancelled, which leaves the channel end as it was.
+
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+
+use anomaly::fail;
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::channel::v1::ChannelUpgrade as RawChannelUpgrade;
+
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::error::{self, Error, Kind};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ChannelUpgrade {
+    pub state: UpgradeState,
+    pub channel_end: ChannelEnd,
+}
+
+impl ChannelUpgrade {
+    pub fn new(state: UpgradeState, channel_end: ChannelEnd) -> Self {
+        Self { state, channel_end }
+    }
+
+    /// Getter for the step of the upgrade handshake which the channel end reached.
+    pub fn state(&self) -> &UpgradeState {
+        &self.state
+    }
+
+    /// Getter for the channel end once upgraded.
+    pub fn channel_end(&self) -> &ChannelEnd {
+        &self.channel_end
+    }
+
+    /// Helper function to compare the state of this upgrade with a given state.
+    pub fn state_matches(&self, other: &UpgradeState) -> bool {
+        self.state.eq(other)
+    }
+}
+
+impl Protobuf<RawChannelUpgrade> for ChannelUpgrade {}
+
+impl TryFrom<RawChannelUpgrade> for ChannelUpgrade {
+    type Error = anomaly::Error<Kind>;
+
+    fn try_from(value: RawChannelUpgrade) -> Result<Self, Self::Error> {
+        Ok(ChannelUpgrade {
+            state: UpgradeState::from_i32(value.state)?,
+            channel_end: value.channel.ok_or(Kind::MissingChannel)?.try_into()?,
+        })
+    }
+}
+
+impl From<ChannelUpgrade> for RawChannelUpgrade {
+    fn from(value: ChannelUpgrade) -> Self {
+        RawChannelUpgrade {
+            state: value.state as i32,
+            channel: Some(value.channel_end.into()),
+        }
+    }
+}
+
+/// The step of the upgrade handshake which a channel end reached.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Eq)]
+pub enum UpgradeState {
+    /// The channel end proposed the upgrade to the counterparty.
+    Init = 1,
+    /// The channel end accepted the upgrade proposed by the counterparty.
+    Try = 2,
+}
+
+impl UpgradeState {
+    /// Yields the state as a string
+    pub fn as_string(&self) -> &'static str {
+        match self {
+            Self::Init => "INIT",
+            Self::Try => "TRY",
+        }
+    }
+
+    // Parses the UpgradeState out from a i32.
+    pub fn from_i32(s: i32) -> Result<Self, Error> {
+        match s {
+            1 => Ok(Self::Init),
+            2 => Ok(Self::Try),
+            _ => fail!(error::Kind::UnknownUpgradeState, s),
+        }
+    }
+}
+
+/// Provides a `to_string` method.
+impl fmt::Display for UpgradeState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ibc_proto::ibc::core::channel::v1::ChannelUpgrade as RawChannelUpgrade;
+
+    use crate::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
+    use crate::ics04_channel::upgrade::{ChannelUpgrade, UpgradeState};
+
+    #[test]
+    fn channel_upgrade_try_from_raw() {
+        let raw = RawChannelUpgrade {
+            state: UpgradeState::Try as i32,
+            channel: Some(get_dummy_raw_channel_end()),
+        };
+
+        let upgrade = ChannelUpgrade::try_from(raw.clone()).unwrap();
+        assert!(upgrade.state_matches(&UpgradeState::Try));
+        assert_eq!(RawChannelUpgrade::from(upgrade), raw);
+
+        let missing_channel = RawChannelUpgrade {
+            channel: None,
+            ..raw.clone()
+        };
+        assert!(ChannelUpgrade::try_from(missing_channel).is_err());
+
+        let unspecified_state = RawChannelUpgrade { state: 0, ..raw };
+        assert!(ChannelUpgrade::try_from(unspecified_state).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::convert::TryFrom;
 
 use ibc_proto::ibc::core::connection::v1::Version as RawVersion;
+use serde_derive::{Deserialize, Serialize};
 use tendermint_proto::Protobuf;
 
 use crate::ics04_channel::error::{Error, Kind};
@@ -124,3 +125,62 @@ pub fn validate_version(raw_version: String) -> Result<String, Error> {
     }
     Ok(raw_version)
 }
+
+/// The version of the ICS29 fee middleware.
+pub const FEE_VERSION: &str = "ics29-1";
+
+/// The version of a fee-enabled channel. The fee middleware wraps the version negotiated by the
+/// underlying application into a JSON object, e.g., the version of a fee-enabled ICS20 channel is
+/// `{"fee_version":"ics29-1","app_version":"ics20-1"}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeMetadata {
+    pub fee_version: String,
+    pub app_version: String,
+}
+
+impl FeeMetadata {
+    /// Wraps `app_version` into the version of a fee-enabled channel.
+    pub fn new(app_version: impl ToString) -> Self {
+        FeeMetadata {
+            fee_version: FEE_VERSION.to_string(),
+            app_version: app_version.to_string(),
+        }
+    }
+}
+
+impl FromStr for FeeMetadata {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(s).map_err(|e| Kind::InvalidVersion.context(e))?)
+    }
+}
+
+impl std::fmt::Display for FeeMetadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", serde_json::to_string(self).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{FeeMetadata, FEE_VERSION};
+
+    #[test]
+    fn fee_metadata_wraps_app_version() {
+        let metadata = FeeMetadata::new("ics20-1");
+        assert_eq!(
+            metadata.to_string(),
+            r#"{"fee_version":"ics29-1","app_version":"ics20-1"}"#
+        );
+
+        let parsed = FeeMetadata::from_str(&metadata.to_string()).unwrap();
+        assert_eq!(parsed.fee_version, FEE_VERSION);
+        assert_eq!(parsed.app_version, "ics20-1");
+
+        assert!(FeeMetadata::from_str("ics20-1").is_err());
+        assert!(FeeMetadata::from_str(r#"{"app_version":"ics20-1"}"#).is_err());
+    }
+}
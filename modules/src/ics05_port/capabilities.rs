@@ -1,18 +1,88 @@
-//! Capabilities: this is a placeholder.
+//! Capabilities: unforgeable keys which entitle their holder to act on an object of the IBC
+//! store, such as a port or a channel end. Capabilities are only ever created by a
+//! `CapabilityKeeper` (see `CapabilityKeeper::new_capability`), so the only way for an application
+//! module to obtain one is to be handed it, e.g., upon binding a port.
+//!
+//! Each module stores the capabilities it holds in its own scope, identified by a `ModuleId`: a
+//! module can only look up the capabilities which it created or claimed itself, through the
+//! `ScopedCapabilityKeeper` which the router handed to it.
 
-#[derive(Clone, Debug, PartialEq)]
+use std::fmt;
+
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics24_host::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Capability {
     index: u64,
 }
 
 impl Capability {
-    pub fn new() -> Capability {
-        Self { index: 0x0 }
+    /// Creates the capability with the given index. Capabilities can only be created from inside
+    /// this crate, which guarantees that each index identifies a single capability.
+    pub(crate) fn new(index: u64) -> Capability {
+        Self { index }
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+/// Identifies a module of the host chain, and thereby the scope in which the capabilities owned
+/// by this module are stored.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModuleId(String);
+
+impl ModuleId {
+    /// Module identifiers are only created by the router, which scopes each application module
+    /// once (see `Router::scope_to_module`), and for the IBC module itself.
+    pub(crate) fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The IBC module itself, which owns the capability of every port and channel end alongside
+    /// the application module that the port or channel end belongs to.
+    pub(crate) fn ibc() -> Self {
+        Self::new("ibc")
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ModuleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The name under which a capability is stored.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapabilityName(String);
+
+impl CapabilityName {
+    /// The name of the capability owning the port `port_id`.
+    pub fn port(port_id: &PortId) -> Self {
+        Self(Path::Ports(port_id.clone()).to_string())
+    }
+
+    /// The name of the capability owning the channel end identified by `port_id` and `channel_id`.
+    pub fn channel(port_id: &PortId, channel_id: &ChannelId) -> Self {
+        Self(format!(
+            "capabilities/ports/{}/channels/{}",
+            port_id, channel_id
+        ))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
     }
 }
 
-impl Default for Capability {
-    fn default() -> Self {
-        Self::new()
+impl fmt::Display for CapabilityName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
@@ -1,10 +1,299 @@
-use crate::ics05_port::capabilities::Capability;
-use crate::ics24_host::identifier::PortId;
+use crate::ics05_port::capabilities::{Capability, CapabilityName, ModuleId};
+use crate::ics05_port::error::{Error, Kind};
+use crate::ics24_host::identifier::{ChannelId, PortId};
 
-// A context supplying all the necessary read-only dependencies for processing any information regarding a port.
-pub trait PortReader {
-    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability>;
-    fn authenticate(&self, key: &Capability, port_id: &PortId) -> bool;
+/// A context supplying read-only access to the capabilities of the host chain. Capabilities are
+/// stored per module: the same capability may be owned by several modules, under the same name or
+/// not, and each module only looks up capabilities in its own scope.
+pub trait CapabilityReader {
+    /// Returns the capability which `module` owns under `name`, if any.
+    fn get_capability(&self, module: &ModuleId, name: &CapabilityName) -> Option<Capability>;
+
+    /// Returns a counter on the number of capabilities created thus far. The value of this
+    /// counter should increase only via method `CapabilityKeeper::increase_capability_counter`.
+    fn capability_counter(&self) -> u64;
+
+    /// Checks that `capability` is the capability which `module` owns under `name`.
+    fn authenticate_capability(
+        &self,
+        module: &ModuleId,
+        name: &CapabilityName,
+        capability: &Capability,
+    ) -> Result<(), Error> {
+        match self.get_capability(module, name) {
+            Some(stored) if &stored == capability => Ok(()),
+            Some(_) => Err(Kind::InvalidCapability(module.clone(), name.clone()).into()),
+            None => Err(Kind::UnknownCapability(module.clone(), name.clone()).into()),
+        }
+    }
+}
+
+/// A context supplying the storage facilities for the capabilities of the host chain. New
+/// capabilities are created exclusively through `new_capability`, which hands out a fresh, unique
+/// capability each time. Application modules access this store through a
+/// `ScopedCapabilityKeeper`, which confines them to their own scope.
+pub trait CapabilityKeeper: CapabilityReader {
+    /// Stores `capability` under `name` in the scope of `module`.
+    fn store_capability(
+        &mut self,
+        module: ModuleId,
+        name: CapabilityName,
+        capability: Capability,
+    ) -> Result<(), Error>;
+
+    /// Called upon creating a new capability, to increase the counter that
+    /// `CapabilityReader::capability_counter` returns.
+    fn increase_capability_counter(&mut self);
+
+    /// Creates a new capability, owned by `module` under `name`, which must not be taken in the
+    /// scope of this module.
+    fn new_capability(
+        &mut self,
+        module: ModuleId,
+        name: CapabilityName,
+    ) -> Result<Capability, Error> {
+        if self.get_capability(&module, &name).is_some() {
+            return Err(Kind::CapabilityTaken(module, name).into());
+        }
+
+        let capability = Capability::new(self.capability_counter());
+        self.increase_capability_counter();
+        self.store_capability(module, name, capability.clone())?;
+
+        Ok(capability)
+    }
+
+    /// Makes `module` an owner of `capability`, obtained from another module, under `name`.
+    /// Claiming a capability under a name which already holds this same capability has no effect.
+    fn claim_capability(
+        &mut self,
+        module: ModuleId,
+        name: CapabilityName,
+        capability: Capability,
+    ) -> Result<(), Error> {
+        match self.get_capability(&module, &name) {
+            Some(stored) if stored == capability => Ok(()),
+            Some(_) => Err(Kind::CapabilityTaken(module, name).into()),
+            None => self.store_capability(module, name, capability),
+        }
+    }
+}
+
+/// The scope of a single module in the capability store of the host chain. The router creates
+/// one scope per application module (see `Router::scope_to_module`), and the module holds it, so
+/// that the module can only create, claim, look up and authenticate the capabilities in its own
+/// scope. Only the router creates scopes, hence a module cannot act on behalf of another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopedCapabilityKeeper {
+    module: ModuleId,
+}
+
+impl ScopedCapabilityKeeper {
+    pub(crate) fn new(module: ModuleId) -> Self {
+        Self { module }
+    }
+
+    pub fn module(&self) -> &ModuleId {
+        &self.module
+    }
+
+    /// Returns the capability which this module owns under `name` in `keeper`, if any.
+    pub fn get_capability<K: CapabilityReader + ?Sized>(
+        &self,
+        keeper: &K,
+        name: &CapabilityName,
+    ) -> Option<Capability> {
+        keeper.get_capability(&self.module, name)
+    }
+
+    /// Checks that `capability` is the capability which this module owns under `name` in
+    /// `keeper`.
+    pub fn authenticate_capability<K: CapabilityReader + ?Sized>(
+        &self,
+        keeper: &K,
+        name: &CapabilityName,
+        capability: &Capability,
+    ) -> Result<(), Error> {
+        keeper.authenticate_capability(&self.module, name, capability)
+    }
+
+    /// Creates a new capability in `keeper`, owned by this module under `name`.
+    pub fn new_capability<K: CapabilityKeeper + ?Sized>(
+        &self,
+        keeper: &mut K,
+        name: CapabilityName,
+    ) -> Result<Capability, Error> {
+        keeper.new_capability(self.module.clone(), name)
+    }
+
+    /// Makes this module an owner of `capability` under `name` in `keeper`.
+    pub fn claim_capability<K: CapabilityKeeper + ?Sized>(
+        &self,
+        keeper: &mut K,
+        name: CapabilityName,
+        capability: Capability,
+    ) -> Result<(), Error> {
+        keeper.claim_capability(self.module.clone(), name, capability)
+    }
 }
 
-//  Result<Capability, Error>//return Ok(Capability::new());
+/// A context supplying all the necessary read-only dependencies for processing any information
+/// regarding a port. The IBC module looks up the capabilities of ports and channel ends in its own
+/// scope.
+pub trait PortReader: CapabilityReader {
+    /// Returns the capability owning the port `port_id`, if the port is bound.
+    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability> {
+        self.get_capability(&ModuleId::ibc(), &CapabilityName::port(port_id))
+    }
+
+    /// Returns the capability owning the channel end identified by `port_id` and `channel_id`,
+    /// if the channel end exists.
+    fn lookup_module_by_channel(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Option<Capability> {
+        self.get_capability(
+            &ModuleId::ibc(),
+            &CapabilityName::channel(port_id, channel_id),
+        )
+    }
+
+    /// Checks that `key` is the capability owning the port `port_id`.
+    fn authenticate(&self, key: &Capability, port_id: &PortId) -> bool {
+        self.authenticate_capability(&ModuleId::ibc(), &CapabilityName::port(port_id), key)
+            .is_ok()
+    }
+}
+
+/// A context supplying the write-only dependencies for binding ports.
+pub trait PortKeeper: PortReader + CapabilityKeeper {
+    /// Binds the port `port_id`, and returns the capability owning it. The port cannot be bound
+    /// again, so this capability identifies the module which the port belongs to. This module
+    /// should claim the capability in its own scope.
+    fn bind_port(&mut self, port_id: &PortId) -> Result<Capability, Error> {
+        if self.lookup_module_by_port(port_id).is_some() {
+            return Err(Kind::PortAlreadyBound(port_id.clone()).into());
+        }
+
+        self.new_capability(ModuleId::ibc(), CapabilityName::port(port_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ics05_port::capabilities::{CapabilityName, ModuleId};
+    use crate::ics05_port::context::{CapabilityKeeper, CapabilityReader, PortKeeper, PortReader};
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+
+    #[test]
+    fn port_and_channel_capabilities() {
+        let mut ctx = MockContext::default();
+        let transfer: PortId = "transfer".parse().unwrap();
+        let other: PortId = "other".parse().unwrap();
+
+        let transfer_cap = ctx.bind_port(&transfer).unwrap();
+        let other_cap = ctx.bind_port(&other).unwrap();
+        assert_ne!(transfer_cap, other_cap);
+
+        // A port can only be bound once.
+        assert!(ctx.bind_port(&transfer).is_err());
+
+        assert_eq!(
+            ctx.lookup_module_by_port(&transfer),
+            Some(transfer_cap.clone())
+        );
+        assert!(ctx.authenticate(&transfer_cap, &transfer));
+        assert!(!ctx.authenticate(&other_cap, &transfer));
+
+        // Each channel end gets its own capability.
+        let channel = CapabilityName::channel(&transfer, &ChannelId::new(0));
+        let channel_cap = ctx
+            .new_capability(ModuleId::ibc(), channel.clone())
+            .unwrap();
+        assert_eq!(
+            ctx.lookup_module_by_channel(&transfer, &ChannelId::new(0)),
+            Some(channel_cap.clone())
+        );
+        assert!(ctx
+            .authenticate_capability(&ModuleId::ibc(), &channel, &channel_cap)
+            .is_ok());
+        assert!(ctx
+            .authenticate_capability(&ModuleId::ibc(), &channel, &transfer_cap)
+            .is_err());
+        assert!(ctx
+            .new_capability(ModuleId::ibc(), channel.clone())
+            .is_err());
+
+        // A capability may be claimed under another name, but no name holds two capabilities.
+        let claimed = CapabilityName::channel(&other, &ChannelId::new(0));
+        assert!(ctx
+            .claim_capability(ModuleId::ibc(), claimed.clone(), channel_cap.clone())
+            .is_ok());
+        assert!(ctx
+            .claim_capability(ModuleId::ibc(), claimed.clone(), channel_cap)
+            .is_ok());
+        assert!(ctx
+            .claim_capability(ModuleId::ibc(), claimed, other_cap)
+            .is_err());
+        assert!(ctx
+            .authenticate_capability(
+                &ModuleId::ibc(),
+                &CapabilityName::channel(&other, &ChannelId::new(1)),
+                &transfer_cap
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn scoped_capabilities() {
+        let mut ctx = MockContext::default();
+        let transfer: PortId = "transfer".parse().unwrap();
+        let port = CapabilityName::port(&transfer);
+        let channel = CapabilityName::channel(&transfer, &ChannelId::new(0));
+
+        let transfer_cap = ctx.bind_port(&transfer).unwrap();
+        let channel_cap = ctx
+            .new_capability(ModuleId::ibc(), channel.clone())
+            .unwrap();
+
+        // The capabilities of the IBC module are out of reach of the application modules, until
+        // they claim them.
+        let app = ctx.scope_to_module("transfer").unwrap();
+        assert_eq!(app.get_capability(&ctx, &port), None);
+        assert!(app
+            .authenticate_capability(&ctx, &port, &transfer_cap)
+            .is_err());
+
+        app.claim_capability(&mut ctx, port.clone(), transfer_cap.clone())
+            .unwrap();
+        app.claim_capability(&mut ctx, channel.clone(), channel_cap.clone())
+            .unwrap();
+        assert_eq!(app.get_capability(&ctx, &port), Some(transfer_cap.clone()));
+        assert!(app
+            .authenticate_capability(&ctx, &channel, &channel_cap)
+            .is_ok());
+        assert!(app
+            .authenticate_capability(&ctx, &channel, &transfer_cap)
+            .is_err());
+
+        // Another module cannot use the capabilities claimed by the transfer module.
+        let other = ctx.scope_to_module("other").unwrap();
+        assert_eq!(other.get_capability(&ctx, &channel), None);
+        assert!(other
+            .authenticate_capability(&ctx, &channel, &channel_cap)
+            .is_err());
+
+        // A module may create capabilities of its own, which no other module owns.
+        let other_cap = other.new_capability(&mut ctx, channel.clone()).unwrap();
+        assert_ne!(other_cap, channel_cap);
+        assert!(ctx
+            .authenticate_capability(&ModuleId::ibc(), &channel, &other_cap)
+            .is_err());
+
+        // Each module is scoped once, and no module is scoped as the IBC module.
+        assert!(ctx.scope_to_module("transfer").is_err());
+        assert!(ctx.scope_to_module(ModuleId::ibc().as_str()).is_err());
+    }
+}
@@ -1,12 +1,27 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics05_port::capabilities::{CapabilityName, ModuleId};
+use crate::ics24_host::identifier::PortId;
+
 pub type Error = anomaly::Error<Kind>;
 
 #[derive(Clone, Debug, Error)]
 pub enum Kind {
     #[error("port unknown")]
     UnknownPort,
+
+    #[error("port {0} is already bound")]
+    PortAlreadyBound(PortId),
+
+    #[error("module {0} already owns a capability named {1}")]
+    CapabilityTaken(ModuleId, CapabilityName),
+
+    #[error("module {0} owns no capability named {1}")]
+    UnknownCapability(ModuleId, CapabilityName),
+
+    #[error("the capability does not match the capability which module {0} owns as {1}")]
+    InvalidCapability(ModuleId, CapabilityName),
 }
 
 impl Kind {
@@ -0,0 +1,659 @@
+use std::convert::TryFrom;
+
+use prost_types::Any;
+use tendermint::signature::Signature;
+use tendermint::PublicKey;
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+    data::Sum, Data as RawSignatureData,
+};
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    ChannelStateData, ClientStateData, ConnectionStateData, ConsensusStateData, DataType,
+    HeaderData, NextSequenceRecvData, PacketAcknowledgementData, PacketCommitmentData,
+    PacketReceiptAbsenceData, SignBytes, TimestampedSignatureData,
+};
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::ClientDef;
+use crate::ics02_client::client_state::{AnyClientState, ClientState as _};
+use crate::ics02_client::context::ClientReader;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::Sequence;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
+use crate::ics06_solomachine::client_state::ClientState;
+use crate::ics06_solomachine::consensus_state::{encode_public_key, ConsensusState};
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics06_solomachine::header::Header;
+use crate::ics06_solomachine::misbehaviour::Misbehaviour;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics23_commitment::merkle::apply_prefix;
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::ics24_host::Path;
+use crate::Height;
+
+/// The solo machine client. Every piece of state is verified by checking the signature of the
+/// machine over the state, the path under which it is stored and the current sequence, which
+/// each successful verification advances.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoloMachineClient;
+
+impl ClientDef for SoloMachineClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        if header.sequence != client_state.sequence {
+            return Err(Kind::SequenceMismatch(header.sequence, client_state.sequence).into());
+        }
+
+        let consensus_state = &client_state.consensus_state;
+        if header.timestamp < consensus_state.timestamp {
+            return Err(Kind::LowTimestamp(header.timestamp, consensus_state.timestamp).into());
+        }
+
+        let data = HeaderData {
+            new_pub_key: Some(encode_public_key(header.new_public_key)),
+            new_diversifier: header.new_diversifier.clone(),
+        };
+        let sign_bytes = sign_bytes(
+            header.sequence,
+            header.timestamp,
+            &consensus_state.diversifier,
+            DataType::Header,
+            encode(&data),
+        );
+        verify_signature(&consensus_state.public_key, &sign_bytes, &header.signature)?;
+
+        let new_consensus_state = ConsensusState::new(
+            header.new_public_key,
+            header.new_diversifier,
+            header.timestamp,
+        );
+        let new_client_state = ClientState {
+            sequence: header.sequence + 1,
+            consensus_state: new_consensus_state.clone(),
+            ..client_state
+        };
+
+        Ok((new_client_state, new_consensus_state))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        if client_state.is_frozen() {
+            return Err(Kind::FrozenClient.into());
+        }
+
+        if misbehaviour.signature_one.data == misbehaviour.signature_two.data {
+            return Err(Kind::InvalidMisbehaviour
+                .context("the two signatures are over the same data")
+                .into());
+        }
+
+        // Both signatures must be valid signatures of the machine at the same sequence.
+        let consensus_state = &client_state.consensus_state;
+        for signature_and_data in [&misbehaviour.signature_one, &misbehaviour.signature_two].iter()
+        {
+            let sign_bytes = sign_bytes(
+                misbehaviour.sequence,
+                signature_and_data.timestamp,
+                &consensus_state.diversifier,
+                signature_and_data.data_type,
+                signature_and_data.data.clone(),
+            );
+            verify_signature(
+                &consensus_state.public_key,
+                &sign_bytes,
+                &signature_and_data.signature,
+            )?;
+        }
+
+        Ok(client_state.with_frozen_sequence(misbehaviour.sequence))
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _client_state: &Self::ClientState,
+        _upgraded_client_state: Self::ClientState,
+        _upgraded_consensus_state: Self::ConsensusState,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        Err(Kind::UpgradeNotSupported.into())
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ClientConsensusState {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let data = ConsensusStateData {
+            path: merkle_path(prefix, path)?,
+            consensus_state: Some(Any::from(expected_consensus_state.clone())),
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::ConsensusState,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_connection_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let connection_id = connection_id.ok_or(Kind::MissingConnectionId)?;
+        let path = Path::Connections(connection_id.clone());
+        let data = ConnectionStateData {
+            path: merkle_path(prefix, path)?,
+            connection: Some(expected_connection_end.clone().into()),
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::ConnectionState,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_channel_state(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ChannelEnds(port_id.clone(), channel_id.clone());
+        let data = ChannelStateData {
+            path: merkle_path(prefix, path)?,
+            channel: Some(expected_channel_end.clone().into()),
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::ChannelState,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_channel_upgrade(
+        &self,
+        _ctx: &dyn ChannelReader,
+        _client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _expected_channel_upgrade: &ChannelUpgrade,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        // The sign bytes of solo machines define no data type for channel upgrades.
+        Err(Kind::ChannelUpgradeNotSupported.into())
+    }
+
+    fn verify_client_full_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        _root: &CommitmentRoot,
+        prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ClientState(client_id.clone());
+        let data = ClientStateData {
+            path: merkle_path(prefix, path)?,
+            client_state: Some(Any::from(expected_client_state.clone())),
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::ClientState,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_packet_data(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Commitments {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = PacketCommitmentData {
+            path: merkle_path(prefix, path)?,
+            commitment,
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::PacketCommitment,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Acks {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = PacketAcknowledgementData {
+            path: merkle_path(prefix, path)?,
+            acknowledgement: ack,
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::PacketAcknowledgement,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::SeqRecvs(port_id.clone(), channel_id.clone());
+        let data = NextSequenceRecvData {
+            path: merkle_path(prefix, path)?,
+            next_seq_recv: u64::from(*seq),
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::NextSequenceRecv,
+            encode(&data),
+        )?)
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Receipts {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+        let data = PacketReceiptAbsenceData {
+            path: merkle_path(prefix, path)?,
+        };
+
+        Ok(verify_signed_data(
+            client_state,
+            height,
+            proof,
+            DataType::PacketReceiptAbsence,
+            encode(&data),
+        )?)
+    }
+}
+
+/// Verifies that `proof` holds a signature of the machine, at the current sequence of the client,
+/// over `data` of type `data_type`. On success, returns the client state at the next sequence.
+fn verify_signed_data(
+    client_state: &ClientState,
+    height: Height,
+    proof: &CommitmentProofBytes,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Result<ClientState, Error> {
+    // All the proofs of a message share the same height, while each of them consumes a sequence,
+    // hence the signature is checked at the sequence of the client rather than the proof height.
+    if height > client_state.latest_height() {
+        return Err(Kind::InvalidProofHeight(height, client_state.latest_height()).into());
+    }
+
+    if proof.is_empty() {
+        return Err(Kind::InvalidProof.context("empty proof").into());
+    }
+
+    let proof: Vec<u8> = proof.clone().into();
+    let signature: TimestampedSignatureData =
+        prost::Message::decode(proof.as_ref()).map_err(|e| Kind::InvalidProof.context(e))?;
+
+    let consensus_state = &client_state.consensus_state;
+    if signature.timestamp < consensus_state.timestamp {
+        return Err(Kind::LowTimestamp(signature.timestamp, consensus_state.timestamp).into());
+    }
+
+    let sign_bytes = sign_bytes(
+        client_state.sequence,
+        signature.timestamp,
+        &consensus_state.diversifier,
+        data_type,
+        data,
+    );
+
+    verify_signature(
+        &consensus_state.public_key,
+        &sign_bytes,
+        &signature.signature_data,
+    )?;
+
+    Ok(ClientState {
+        sequence: client_state.sequence + 1,
+        ..client_state.clone()
+    })
+}
+
+/// Computes the bytes that the machine signs to assert `data` of type `data_type`.
+fn sign_bytes(
+    sequence: u64,
+    timestamp: u64,
+    diversifier: &str,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    encode(&SignBytes {
+        sequence,
+        timestamp,
+        diversifier: diversifier.to_string(),
+        data_type: data_type as i32,
+        data,
+    })
+}
+
+/// Verifies the `signature_data` of the machine, i.e., an encoded single signature as defined by
+/// the Cosmos SDK, over `sign_bytes`.
+fn verify_signature(
+    public_key: &PublicKey,
+    sign_bytes: &[u8],
+    signature_data: &[u8],
+) -> Result<(), Error> {
+    let signature_data: RawSignatureData = prost::Message::decode(signature_data)
+        .map_err(|e| Kind::InvalidSignatureData.context(e))?;
+
+    let signature = match signature_data.sum {
+        Some(Sum::Single(single)) => single.signature,
+        _ => return Err(Kind::UnsupportedSignatureData.into()),
+    };
+    let signature =
+        Signature::try_from(signature).map_err(|e| Kind::InvalidSignatureData.context(e))?;
+
+    public_key
+        .verify(sign_bytes, &signature)
+        .map_err(|e| Kind::SignatureVerificationFailed.context(e).into())
+}
+
+/// Builds the path which the machine signs along with the state stored under `path`. It follows
+/// the string representation of Merkle paths of the Go implementation, in which each key of the
+/// path is escaped as a URL path segment.
+fn merkle_path(prefix: &CommitmentPrefix, path: Path) -> Result<Vec<u8>, Error> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])
+        .map_err(|e| Kind::InvalidProof.context(e.to_string()))?;
+
+    Ok(merkle_path
+        .key_path
+        .iter()
+        .map(|key| format!("/{}", escape_path_segment(key)))
+        .collect::<String>()
+        .into_bytes())
+}
+
+fn escape_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' => (b as char).to_string(),
+            b'-' | b'_' | b'.' | b'~' | b'$' | b'&' | b'+' | b'=' | b':' | b'@' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn encode(message: &impl prost::Message) -> Vec<u8> {
+    let mut buf = Vec::new();
+    prost::Message::encode(message, &mut buf).unwrap();
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::signature::Signer;
+    use tendermint_testgen::Validator;
+
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+        data::{Single, Sum},
+        Data as RawSignatureData,
+    };
+    use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+    use ibc_proto::ibc::lightclients::solomachine::v1::{
+        ConnectionStateData, DataType, HeaderData, TimestampedSignatureData,
+    };
+
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics03_connection::connection::{ConnectionEnd, State};
+    use crate::ics06_solomachine::client_def::{
+        encode, merkle_path, sign_bytes, SoloMachineClient,
+    };
+    use crate::ics06_solomachine::client_state::ClientState;
+    use crate::ics06_solomachine::consensus_state::{encode_public_key, ConsensusState};
+    use crate::ics06_solomachine::header::Header;
+    use crate::ics23_commitment::commitment::{
+        CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+    };
+    use crate::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::ics24_host::Path;
+    use crate::mock::context::MockContext;
+
+    fn sign(validator: &Validator, sign_bytes: &[u8]) -> Vec<u8> {
+        let signature = validator.get_private_key().unwrap().sign(sign_bytes);
+        let signature_data = RawSignatureData {
+            sum: Some(Sum::Single(Single {
+                mode: SignMode::Direct as i32,
+                signature: signature.to_bytes().to_vec(),
+            })),
+        };
+        encode(&signature_data)
+    }
+
+    fn client_state(validator: &Validator) -> ClientState {
+        let consensus_state = ConsensusState::new(
+            validator.get_public_key().unwrap(),
+            "diversifier".to_string(),
+            10,
+        );
+        ClientState::new(1, consensus_state, false)
+    }
+
+    #[test]
+    fn update_with_signed_header() {
+        let ctx = MockContext::default();
+        let machine = Validator::new("machine");
+        let new_machine = Validator::new("new_machine");
+        let client_state = client_state(&machine);
+
+        let data = HeaderData {
+            new_pub_key: Some(encode_public_key(new_machine.get_public_key().unwrap())),
+            new_diversifier: "new_diversifier".to_string(),
+        };
+        let sign_bytes = sign_bytes(1, 11, "diversifier", DataType::Header, encode(&data));
+        let header = Header {
+            sequence: 1,
+            timestamp: 11,
+            signature: sign(&machine, &sign_bytes),
+            new_public_key: new_machine.get_public_key().unwrap(),
+            new_diversifier: "new_diversifier".to_string(),
+        };
+
+        let (new_client_state, new_consensus_state) = SoloMachineClient
+            .check_header_and_update_state(
+                &ctx,
+                ClientId::default(),
+                client_state.clone(),
+                header.clone(),
+            )
+            .unwrap();
+        assert_eq!(new_client_state.sequence, 2);
+        assert_eq!(
+            new_consensus_state.public_key,
+            new_machine.get_public_key().unwrap()
+        );
+        assert_eq!(new_client_state.consensus_state, new_consensus_state);
+
+        // A header signed by any other key than the current key of the machine is rejected.
+        let forged_header = Header {
+            signature: sign(&new_machine, &sign_bytes),
+            ..header
+        };
+        assert!(SoloMachineClient
+            .check_header_and_update_state(&ctx, ClientId::default(), client_state, forged_header,)
+            .is_err());
+    }
+
+    #[test]
+    fn verify_signed_connection_state() {
+        let ctx = MockContext::default();
+        let machine = Validator::new("machine");
+        let client_state = client_state(&machine);
+        let prefix = CommitmentPrefix::from(vec![0]);
+        let connection_id = ConnectionId::default();
+        let connection_end = ConnectionEnd::default();
+
+        let data = ConnectionStateData {
+            path: merkle_path(&prefix, Path::Connections(connection_id.clone())).unwrap(),
+            connection: Some(connection_end.clone().into()),
+        };
+        let sign_bytes = sign_bytes(
+            1,
+            10,
+            "diversifier",
+            DataType::ConnectionState,
+            encode(&data),
+        );
+        let proof: CommitmentProofBytes = encode(&TimestampedSignatureData {
+            signature_data: sign(&machine, &sign_bytes),
+            timestamp: 10,
+        })
+        .into();
+
+        let verify = |client_state: &ClientState, connection_end: &ConnectionEnd| {
+            SoloMachineClient.verify_connection_state(
+                &ctx,
+                client_state,
+                client_state.latest_height(),
+                &prefix,
+                &proof,
+                &CommitmentRoot::from(vec![]),
+                Some(&connection_id),
+                connection_end,
+            )
+        };
+
+        // A successful verification advances the sequence of the client.
+        let new_client_state = verify(&client_state, &connection_end).unwrap();
+        assert_eq!(new_client_state.sequence, client_state.sequence + 1);
+
+        // The signature does not cover any other connection end.
+        let mut other_connection_end = connection_end.clone();
+        other_connection_end.set_state(State::Open);
+        assert!(verify(&client_state, &other_connection_end).is_err());
+
+        // Nor can it be replayed at the next sequence.
+        assert!(verify(&new_client_state, &connection_end).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use std::convert::{TryFrom, TryInto};
+
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::solomachine::v1::ClientState as RawClientState;
+
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics06_solomachine::consensus_state::ConsensusState;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The state of a solo machine client. The sequence of the machine plays the role of the height,
+/// as the revision height of a revision number 0.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ClientState {
+    pub sequence: u64,
+    /// The sequence at which the client was frozen following evidence of misbehaviour, or 0.
+    pub frozen_sequence: u64,
+    pub consensus_state: ConsensusState,
+    pub allow_update_after_proposal: bool,
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl ClientState {
+    pub fn new(
+        sequence: u64,
+        consensus_state: ConsensusState,
+        allow_update_after_proposal: bool,
+    ) -> Self {
+        Self {
+            sequence,
+            frozen_sequence: 0,
+            consensus_state,
+            allow_update_after_proposal,
+        }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence)
+    }
+
+    /// Returns the client state frozen at sequence `sequence`.
+    pub fn with_frozen_sequence(self, sequence: u64) -> Self {
+        Self {
+            frozen_sequence: sequence,
+            ..self
+        }
+    }
+}
+
+impl From<ClientState> for AnyClientState {
+    fn from(cs: ClientState) -> Self {
+        Self::SoloMachine(cs)
+    }
+}
+
+impl crate::ics02_client::client_state::ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        // A solo machine is not a chain, hence has no chain identifier.
+        ChainId::default()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn is_frozen(&self) -> bool {
+        self.frozen_sequence != 0
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::SoloMachine(self)
+    }
+}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            sequence: raw.sequence,
+            frozen_sequence: raw.frozen_sequence,
+            consensus_state: raw
+                .consensus_state
+                .ok_or_else(|| Kind::InvalidRawClientState.context("missing consensus state"))?
+                .try_into()?,
+            allow_update_after_proposal: raw.allow_update_after_proposal,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            sequence: value.sequence,
+            frozen_sequence: value.frozen_sequence,
+            consensus_state: Some(value.consensus_state.into()),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::convert::TryFrom;
+
+use prost_types::Any;
+use serde::Serialize;
+use tendermint::PublicKey;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::cosmos::crypto::ed25519::PubKey as RawEd25519PublicKey;
+use ibc_proto::ibc::lightclients::solomachine::v1::ConsensusState as RawConsensusState;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics06_solomachine::error::{Error, Kind};
+use crate::ics23_commitment::commitment::CommitmentRoot;
+
+pub const ED25519_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+/// The consensus state of a solo machine, i.e., the key with which the machine currently signs,
+/// and the diversifier which allows the machine to reuse that key across clients.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConsensusState {
+    pub public_key: PublicKey,
+    pub diversifier: String,
+    pub timestamp: u64,
+    /// Solo machines sign their state instead of committing to it, hence the root is empty.
+    pub root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: u64) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+            root: CommitmentRoot::from(vec![]),
+        }
+    }
+}
+
+impl From<ConsensusState> for AnyConsensusState {
+    fn from(cs: ConsensusState) -> Self {
+        Self::SoloMachine(cs)
+    }
+}
+
+impl crate::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.timestamp == 0 {
+            return Err(Kind::InvalidRawConsensusState
+                .context("timestamp cannot be 0")
+                .into());
+        }
+        Ok(())
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::SoloMachine(self)
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let public_key = raw
+            .public_key
+            .ok_or_else(|| Kind::InvalidRawConsensusState.context("missing public key"))?;
+
+        Ok(Self::new(
+            decode_public_key(public_key)?,
+            raw.diversifier,
+            raw.timestamp,
+        ))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            public_key: Some(encode_public_key(value.public_key)),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+/// Decodes the public key of a solo machine, which is packed in an `Any` the way the Cosmos SDK
+/// packs keys. Only ed25519 keys are supported, any other key type, e.g., the secp256k1 keys of
+/// the Cosmos SDK, is rejected as unsupported.
+pub fn decode_public_key(raw: Any) -> Result<PublicKey, Error> {
+    match raw.type_url.as_str() {
+        ED25519_PUBLIC_KEY_TYPE_URL => {
+            let raw_key: RawEd25519PublicKey = prost::Message::decode(raw.value.as_ref())
+                .map_err(|e| Kind::InvalidPublicKey.context(e))?;
+
+            PublicKey::from_raw_ed25519(&raw_key.key).ok_or_else(|| Kind::InvalidPublicKey.into())
+        }
+        _ => Err(Kind::UnsupportedPublicKeyType(raw.type_url).into()),
+    }
+}
+
+/// Packs the public key of a solo machine in an `Any`, the way the Cosmos SDK packs keys.
+pub fn encode_public_key(public_key: PublicKey) -> Any {
+    let raw_key = RawEd25519PublicKey {
+        key: public_key.to_bytes(),
+    };
+
+    let mut value = Vec::new();
+    prost::Message::encode(&raw_key, &mut value).unwrap();
+
+    Any {
+        type_url: ED25519_PUBLIC_KEY_TYPE_URL.to_string(),
+        value,
+    }
+}
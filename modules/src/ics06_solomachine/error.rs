@@ -0,0 +1,69 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::Height;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("invalid raw client state")]
+    InvalidRawClientState,
+
+    #[error("invalid raw client consensus state")]
+    InvalidRawConsensusState,
+
+    #[error("invalid raw header")]
+    InvalidRawHeader,
+
+    #[error("invalid raw misbehaviour")]
+    InvalidRawMisbehaviour,
+
+    #[error("invalid public key")]
+    InvalidPublicKey,
+
+    #[error("unsupported public key type {0}, only ed25519 keys are supported")]
+    UnsupportedPublicKeyType(String),
+
+    #[error("invalid signature data")]
+    InvalidSignatureData,
+
+    #[error("unsupported signature data, only single signatures are supported")]
+    UnsupportedSignatureData,
+
+    #[error("signature verification failed")]
+    SignatureVerificationFailed,
+
+    #[error("invalid proof")]
+    InvalidProof,
+
+    #[error("proof height {0} is greater than the latest height {1} of the client")]
+    InvalidProofHeight(Height, Height),
+
+    #[error("header sequence {0} does not match the client sequence {1}")]
+    SequenceMismatch(u64, u64),
+
+    #[error("timestamp {0} is lower than the timestamp {1} of the consensus state")]
+    LowTimestamp(u64, u64),
+
+    #[error("the client is frozen")]
+    FrozenClient,
+
+    #[error("invalid misbehaviour")]
+    InvalidMisbehaviour,
+
+    #[error("solo machine clients cannot be upgraded, the machine updates its key instead")]
+    UpgradeNotSupported,
+
+    #[error("solo machine clients cannot verify the upgrade of a channel")]
+    ChannelUpgradeNotSupported,
+
+    #[error("the counterparty connection identifier is missing")]
+    MissingConnectionId,
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
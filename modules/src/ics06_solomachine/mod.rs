@@ -0,0 +1,13 @@
+//! ICS 06: Solo Machine Client, which tracks a single key-controlled machine. Instead of proving
+//! its state with Merkle proofs, the machine signs every piece of state it asserts, together with
+//! a sequence which it increments on each update.
+//!
+//! The machine must sign with an ed25519 key: unlike the Go implementation, secp256k1 and
+//! multisig keys are not supported, as the signatures of these keys cannot be verified yet.
+
+pub mod client_def;
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod misbehaviour;
@@ -1,16 +1,39 @@
+use std::convert::TryFrom;
+
+use tendermint::Time;
+use tendermint_light_client::operations::voting_power::{
+    ProdVotingPowerCalculator, VotingPowerCalculator,
+};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof};
+
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_def::ClientDef;
 use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
 use crate::ics07_tendermint::client_state::ClientState;
 use crate::ics07_tendermint::consensus_state::ConsensusState;
+use crate::ics07_tendermint::error::{Error, Kind};
 use crate::ics07_tendermint::header::Header;
+use crate::ics07_tendermint::misbehaviour::Misbehaviour;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics23_commitment::merkle::{apply_prefix, MerkleProof};
+use crate::ics23_commitment::specs::ProofSpecs;
 use crate::ics24_host::identifier::ConnectionId;
 use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
-use crate::Height;
+use crate::ics24_host::{Path, UPGRADED_CLIENT_CONSENSUS_STATE, UPGRADED_CLIENT_STATE};
+use crate::{downcast, Height};
+
+/// The commitment root of the consensus state of an upgraded client, which is a placeholder until
+/// the client is updated with the first header of the upgraded chain.
+const SENTINEL_ROOT: &[u8] = b"sentinel_root";
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TendermintClient;
@@ -19,20 +42,16 @@ impl ClientDef for TendermintClient {
     type Header = Header;
     type ClientState = ClientState;
     type ConsensusState = ConsensusState;
+    type Misbehaviour = Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
-        if client_state.latest_height() >= header.height() {
-            return Err(
-                format!("received header height ({:?}) is lower than (or equal to) client latest height ({:?})",
-                    header.height(), client_state.latest_height).into(),
-            );
-        }
-
-        // TODO: Additional verifications should be implemented here.
+        verify_header(ctx, &client_id, &client_state, &header)?;
 
         Ok((
             client_state.with_header(header.clone()),
@@ -40,104 +59,649 @@ impl ClientDef for TendermintClient {
         ))
     }
 
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        verify_misbehaviour(ctx, &client_id, &client_state, &misbehaviour)?;
+
+        Ok(client_state.with_frozen_height(misbehaviour.header1.height()))
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        client_state: &Self::ClientState,
+        upgraded_client_state: Self::ClientState,
+        upgraded_consensus_state: Self::ConsensusState,
+        proof_upgrade_client: RawMerkleProof,
+        proof_upgrade_consensus_state: RawMerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        let latest_height = client_state.latest_height();
+
+        // The upgraded states are proven against the root of the latest consensus state of the
+        // client, which must not have expired.
+        let consensus_state = ctx
+            .consensus_state(&client_id, latest_height)
+            .and_then(|cs| downcast!(cs => AnyConsensusState::Tendermint))
+            .ok_or(Kind::MissingTrustedConsensusState(latest_height))?;
+
+        let now = ctx.host_time();
+        let expires_at = consensus_state.timestamp + client_state.trusting_period;
+        if expires_at <= now {
+            return Err(Kind::TrustedConsensusStateExpired(expires_at, now).into());
+        }
+
+        // The counterparty commits to the upgraded client state with its client-chosen fields
+        // reset, since these are not known to the counterparty chain.
+        let client_key_path = upgrade_key_path(
+            &client_state.upgrade_path,
+            latest_height.revision_height,
+            UPGRADED_CLIENT_STATE,
+        )?;
+        let value = AnyClientState::Tendermint(ClientState::zero_custom_fields(
+            upgraded_client_state.clone(),
+        ))
+        .encode_vec()?;
+        MerkleProof::try_from(proof_upgrade_client)
+            .map_err(|e| Error::from(Kind::InvalidCommitmentProof.context(e)))?
+            .verify_membership(
+                &ProofSpecs::cosmos(),
+                &consensus_state.root,
+                &client_key_path,
+                value,
+            )
+            .map_err(|e| Error::from(Kind::ProofVerificationFailed.context(e)))?;
+
+        let consensus_key_path = upgrade_key_path(
+            &client_state.upgrade_path,
+            latest_height.revision_height,
+            UPGRADED_CLIENT_CONSENSUS_STATE,
+        )?;
+        let value = AnyConsensusState::Tendermint(upgraded_consensus_state.clone()).encode_vec()?;
+        MerkleProof::try_from(proof_upgrade_consensus_state)
+            .map_err(|e| Error::from(Kind::InvalidCommitmentProof.context(e)))?
+            .verify_membership(
+                &ProofSpecs::cosmos(),
+                &consensus_state.root,
+                &consensus_key_path,
+                value,
+            )
+            .map_err(|e| Error::from(Kind::ProofVerificationFailed.context(e)))?;
+
+        // The client-chosen fields of the client carry over to the upgraded client.
+        let new_client_state = ClientState {
+            trust_level: client_state.trust_level,
+            trusting_period: client_state.trusting_period,
+            max_clock_drift: client_state.max_clock_drift,
+            allow_update: client_state.allow_update,
+            frozen_height: Height::zero(),
+            ..upgraded_client_state
+        };
+
+        // The commitment root of the upgraded chain is unknown until the client is updated with
+        // a header of the upgraded chain, hence it is replaced with a sentinel value.
+        let new_consensus_state = ConsensusState::new(
+            CommitmentRoot::from_bytes(SENTINEL_ROOT),
+            upgraded_consensus_state.timestamp,
+            upgraded_consensus_state.next_validators_hash,
+        );
+
+        Ok((new_client_state, new_consensus_state))
+    }
+
     fn verify_client_consensus_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _client_id: &ClientId,
-        _consensus_height: Height,
-        _expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ClientConsensusState {
+            client_id: client_id.clone(),
+            epoch: consensus_height.revision_number,
+            height: consensus_height.revision_height,
+        };
+        let value = expected_consensus_state.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_connection_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _connection_id: Option<&ConnectionId>,
-        _expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let connection_id = connection_id.ok_or_else(|| {
+            Kind::ProofVerificationFailed.context("missing counterparty connection identifier")
+        })?;
+        let path = Path::Connections(connection_id.clone());
+        let value = expected_connection_end.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_channel_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ChannelEnds(port_id.clone(), channel_id.clone());
+        let value = expected_channel_end.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_channel_upgrade(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_upgrade: &ChannelUpgrade,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ChannelUpgrades(port_id.clone(), channel_id.clone());
+        let value = expected_channel_upgrade.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_client_full_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _root: &CommitmentRoot,
-        _prefix: &CommitmentPrefix,
-        _client_id: &ClientId,
-        _proof: &CommitmentProofBytes,
-        _expected_client_state: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        unimplemented!()
+        root: &CommitmentRoot,
+        prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::ClientState(client_id.clone());
+        let value = expected_client_state.encode_vec()?;
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_packet_data(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-        _data: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Commitments {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_membership(prefix, proof, root, path, commitment)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_packet_acknowledgement(
         &self,
-        _client_state: &Self::ClientState,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-        _data: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Acks {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_membership(prefix, proof, root, path, ctx.ack_commitment(&ack))?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_next_sequence_recv(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::SeqRecvs(port_id.clone(), channel_id.clone());
+        let value = u64::from(*seq).to_be_bytes().to_vec();
+
+        verify_membership(prefix, proof, root, path, value)?;
+
+        Ok(client_state.clone())
     }
 
     fn verify_packet_receipt_absence(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
-        _proof: &CommitmentProofBytes,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        todo!()
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let path = Path::Receipts {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence: *seq,
+        };
+
+        verify_non_membership(prefix, proof, root, path)?;
+
+        Ok(client_state.clone())
+    }
+}
+
+/// Checks that `header` was produced by the chain tracked by the client.
+fn check_chain_id(client_state: &ClientState, header: &Header) -> Result<(), Error> {
+    let header_chain_id = header.signed_header.header.chain_id.as_str();
+    if header_chain_id != client_state.chain_id.as_str() {
+        return Err(Kind::MismatchedChainId(
+            header_chain_id.to_string(),
+            client_state.chain_id.to_string(),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Verifies an incoming `header` against the consensus state of the client at the header's
+/// trusted height, as specified in ICS 07: the header must be for the chain and revision of the
+/// client, the trusted consensus state must be within the trusting period, the header must not be from the
+/// future, and the header must be signed by the validator set it declares, with enough voting
+/// power from the trusted validator set.
+fn verify_header(
+    ctx: &dyn ClientReader,
+    client_id: &ClientId,
+    client_state: &ClientState,
+    header: &Header,
+) -> Result<(), Error> {
+    check_chain_id(client_state, header)?;
+
+    let header_height = header.height();
+    let client_revision = client_state.chain_id.version();
+
+    if header_height.revision_number != client_revision
+        || header.trusted_height.revision_number != client_revision
+    {
+        return Err(
+            Kind::MismatchedRevisions(header_height.revision_number, client_revision).into(),
+        );
+    }
+
+    if client_state.latest_height() >= header_height {
+        return Err(Kind::LowHeaderHeight(header_height, client_state.latest_height()).into());
+    }
+
+    let now = ctx.host_time();
+    let trusted_consensus_state =
+        fetch_trusted_consensus_state(ctx, client_id, client_state, header, now)?;
+
+    if header.time() >= now + client_state.max_clock_drift {
+        return Err(Kind::HeaderFromTheFuture(header.time(), now).into());
+    }
+
+    if header.time() <= trusted_consensus_state.timestamp {
+        return Err(
+            Kind::NonMonotonicHeaderTime(header.time(), trusted_consensus_state.timestamp).into(),
+        );
+    }
+
+    verify_header_commit(client_state, header, &trusted_consensus_state)
+}
+
+/// Verifies the evidence of misbehaviour given by two conflicting headers: either two different
+/// headers at the same height, or two headers that break the monotonicity of time. Both headers
+/// must be valid with respect to the consensus states of the client at their trusted heights.
+fn verify_misbehaviour(
+    ctx: &dyn ClientReader,
+    client_id: &ClientId,
+    client_state: &ClientState,
+    misbehaviour: &Misbehaviour,
+) -> Result<(), Error> {
+    let header1 = &misbehaviour.header1;
+    let header2 = &misbehaviour.header2;
+
+    if header1.height() < header2.height() {
+        return Err(Kind::InvalidMisbehaviour
+            .context(format!(
+                "header1 height {} is lower than header2 height {}",
+                header1.height(),
+                header2.height()
+            ))
+            .into());
+    }
+
+    if header1.height() == header2.height() {
+        // A fork: the same height, but different blocks.
+        if header1.signed_header.header.hash() == header2.signed_header.header.hash() {
+            return Err(Kind::InvalidMisbehaviour
+                .context("headers at the same height commit to the same block")
+                .into());
+        }
+    } else if header1.time() > header2.time() {
+        // A time violation: the higher header must not be later than the lower one.
+        return Err(Kind::InvalidMisbehaviour
+            .context("headers at different heights do not violate the monotonicity of time")
+            .into());
+    }
+
+    let now = ctx.host_time();
+    for header in [header1, header2].iter() {
+        check_chain_id(client_state, header)?;
+
+        if header.height().revision_number != client_state.chain_id.version() {
+            return Err(Kind::MismatchedRevisions(
+                header.height().revision_number,
+                client_state.chain_id.version(),
+            )
+            .into());
+        }
+
+        let trusted_consensus_state =
+            fetch_trusted_consensus_state(ctx, client_id, client_state, header, now)?;
+        verify_header_commit(client_state, header, &trusted_consensus_state)?;
+    }
+
+    Ok(())
+}
+
+/// Fetches the consensus state of the client at the trusted height of `header`, and checks that
+/// it is still within the trusting period at time `now`.
+fn fetch_trusted_consensus_state(
+    ctx: &dyn ClientReader,
+    client_id: &ClientId,
+    client_state: &ClientState,
+    header: &Header,
+    now: Time,
+) -> Result<ConsensusState, Error> {
+    if header.trusted_height >= header.height() {
+        return Err(Kind::InvalidTrustedHeight(header.trusted_height, header.height()).into());
+    }
+
+    let trusted_consensus_state = ctx
+        .consensus_state(client_id, header.trusted_height)
+        .and_then(|cs| downcast!(cs => AnyConsensusState::Tendermint))
+        .ok_or(Kind::MissingTrustedConsensusState(header.trusted_height))?;
+
+    let expires_at = trusted_consensus_state.timestamp + client_state.trusting_period;
+    if expires_at <= now {
+        return Err(Kind::TrustedConsensusStateExpired(expires_at, now).into());
+    }
+
+    Ok(trusted_consensus_state)
+}
+
+/// Verifies that `header` is signed by the validator set it declares, and that this set can be
+/// trusted given the validator set of the trusted consensus state.
+fn verify_header_commit(
+    client_state: &ClientState,
+    header: &Header,
+    trusted_consensus_state: &ConsensusState,
+) -> Result<(), Error> {
+    // The trusted validator set must be the one committed to by the trusted consensus state.
+    let trusted_validators_hash = header.trusted_validator_set.hash();
+    if trusted_validators_hash != trusted_consensus_state.next_validators_hash {
+        return Err(Kind::MismatchedTrustedValidatorSet(
+            trusted_validators_hash,
+            trusted_consensus_state.next_validators_hash,
+        )
+        .into());
+    }
+
+    // The validator set must be the one that the header commits to.
+    let signed_header = &header.signed_header;
+    let validators_hash = header.validator_set.hash();
+    if validators_hash != signed_header.header.validators_hash {
+        return Err(Kind::MismatchedValidatorSet(
+            validators_hash,
+            signed_header.header.validators_hash,
+        )
+        .into());
+    }
+
+    // The commit must be for the block described by the header.
+    if signed_header.commit.height != signed_header.header.height
+        || signed_header.commit.block_id.hash != signed_header.header.hash()
+    {
+        return Err(Kind::InvalidCommit.into());
+    }
+
+    let calculator = ProdVotingPowerCalculator::default();
+
+    if header.height() == header.trusted_height.increment() {
+        // For adjacent headers, the validator set must be the one announced by the trusted
+        // consensus state as the next validator set.
+        if signed_header.header.validators_hash != trusted_consensus_state.next_validators_hash {
+            return Err(Kind::MismatchedValidatorSet(
+                trusted_consensus_state.next_validators_hash,
+                signed_header.header.validators_hash,
+            )
+            .into());
+        }
+    } else {
+        // For non-adjacent headers, enough voting power of the trusted validator set must have
+        // signed the header, as given by the trust threshold of the client.
+        calculator
+            .check_enough_trust(
+                signed_header,
+                &header.trusted_validator_set,
+                client_state.trust_level,
+            )
+            .map_err(|e| Kind::NotEnoughTrustedValsSigned.context(e.to_string()))?;
+    }
+
+    // More than two thirds of the voting power of the header validator set must have signed it.
+    calculator
+        .check_signers_overlap(signed_header, &header.validator_set)
+        .map_err(|e| Kind::InsufficientSignersOverlap.context(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Decodes the `CommitmentProofBytes` into the `MerkleProof` it carries, with one ICS23
+/// commitment proof for each (sub-)store on the path from the key to the root.
+fn decode_proof(proof: &CommitmentProofBytes) -> Result<MerkleProof, Error> {
+    MerkleProof::try_from(proof.clone()).map_err(|e| Kind::InvalidCommitmentProof.context(e).into())
+}
+
+/// Verifies that the `value` is stored under the given `path` (prefixed with `prefix`) in the
+/// store committed to by `root`.
+fn verify_membership(
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+    value: Vec<u8>,
+) -> Result<(), Error> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])
+        .map_err(|e| Kind::ProofVerificationFailed.context(e.to_string()))?;
+    let merkle_proof = decode_proof(proof)?;
+
+    merkle_proof
+        .verify_membership(&ProofSpecs::cosmos(), root, &merkle_path, value)
+        .map_err(|e| Kind::ProofVerificationFailed.context(e).into())
+}
+
+/// Verifies that nothing is stored under the given `path` (prefixed with `prefix`) in the store
+/// committed to by `root`.
+fn verify_non_membership(
+    prefix: &CommitmentPrefix,
+    proof: &CommitmentProofBytes,
+    root: &CommitmentRoot,
+    path: Path,
+) -> Result<(), Error> {
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()])
+        .map_err(|e| Kind::ProofVerificationFailed.context(e.to_string()))?;
+    let merkle_proof = decode_proof(proof)?;
+
+    merkle_proof
+        .verify_non_membership(&ProofSpecs::cosmos(), root, &merkle_path)
+        .map_err(|e| Kind::ProofVerificationFailed.context(e).into())
+}
+
+/// Constructs the key path under which the upgraded state stored under `key` is committed by the
+/// counterparty chain, for an upgrade planned at height `upgrade_height`. The last key of the
+/// client `upgrade_path` is extended with the height and the key of the state, as expected by the
+/// upgrade module of a Cosmos-SDK chain.
+fn upgrade_key_path(
+    upgrade_path: &[String],
+    upgrade_height: u64,
+    key: &str,
+) -> Result<MerklePath, Error> {
+    let (last_key, keys) = upgrade_path.split_last().ok_or(Kind::EmptyUpgradePath)?;
+
+    let mut key_path = keys.to_vec();
+    key_path.push(format!("{}/{}/{}", last_key, upgrade_height, key));
+
+    Ok(MerklePath { key_path })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::downcast;
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics02_client::client_state::AnyClientState;
+    use crate::ics04_channel::packet::Sequence;
+    use crate::ics07_tendermint::client_def::TendermintClient;
+    use crate::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+    use crate::ics07_tendermint::client_state::ClientState;
+    use crate::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use crate::ics23_commitment::commitment::{
+        CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+    };
+    use crate::ics23_commitment::merkle::test_util::{membership_proof, non_membership_proof};
+    use crate::ics24_host::identifier::{ChannelId, PortId};
+    use crate::mock::context::MockContext;
+    use crate::Height;
+
+    fn client_state() -> ClientState {
+        let client_state = get_dummy_tendermint_client_state(get_dummy_tendermint_header());
+        downcast!(client_state => AnyClientState::Tendermint).unwrap()
+    }
+
+    fn prefix() -> CommitmentPrefix {
+        CommitmentPrefix::from(b"ibc".to_vec())
+    }
+
+    fn port_and_channel() -> (PortId, ChannelId) {
+        ("transfer".parse().unwrap(), "channel-0".parse().unwrap())
+    }
+
+    #[test]
+    fn verify_next_sequence_recv() {
+        let ctx = MockContext::default();
+        let client_state = client_state();
+        let (port_id, channel_id) = port_and_channel();
+        let fixture = membership_proof();
+        let proof = CommitmentProofBytes::from(fixture.proof);
+
+        let verify = |root: &CommitmentRoot, seq: u64| {
+            TendermintClient.verify_next_sequence_recv(
+                &ctx,
+                &client_state,
+                Height::new(0, 100),
+                &prefix(),
+                &proof,
+                root,
+                &port_id,
+                &channel_id,
+                &Sequence::from(seq),
+            )
+        };
+
+        assert!(verify(&fixture.root, 2).is_ok());
+        // The proof is for another value...
+        assert!(verify(&fixture.root, 3).is_err());
+        // ... or for another root.
+        assert!(verify(&CommitmentRoot::from_bytes(&[0; 32]), 2).is_err());
+    }
+
+    #[test]
+    fn verify_packet_receipt_absence() {
+        let ctx = MockContext::default();
+        let client_state = client_state();
+        let (port_id, channel_id) = port_and_channel();
+        let fixture = non_membership_proof();
+        let proof = CommitmentProofBytes::from(fixture.proof);
+
+        let verify = |root: &CommitmentRoot, seq: u64| {
+            TendermintClient.verify_packet_receipt_absence(
+                &ctx,
+                &client_state,
+                Height::new(0, 100),
+                &prefix(),
+                &proof,
+                root,
+                &port_id,
+                &channel_id,
+                &Sequence::from(seq),
+            )
+        };
+
+        assert!(verify(&fixture.root, 2).is_ok());
+        // The receipt of packet 1 is present.
+        assert!(verify(&fixture.root, 1).is_err());
+        assert!(verify(&CommitmentRoot::from_bytes(&[0; 32]), 2).is_err());
     }
 }
@@ -110,6 +110,15 @@ impl ClientState {
         }
     }
 
+    /// Returns the client state frozen at height `h`, following the submission of evidence of
+    /// misbehaviour at that height.
+    pub fn with_frozen_height(self, h: Height) -> Self {
+        Self {
+            frozen_height: h,
+            ..self
+        }
+    }
+
     /// Helper function to verify the upgrade client procedure.
     /// Resets all fields except the blockchain-specific ones.
     pub fn zero_custom_fields(mut client_state: Self) -> Self {
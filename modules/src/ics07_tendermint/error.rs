@@ -1,7 +1,9 @@
 use anomaly::{BoxError, Context};
+use tendermint::{Hash, Time};
 use thiserror::Error;
 
 use crate::ics24_host::error::ValidationKind;
+use crate::Height;
 
 pub type Error = anomaly::Error<Kind>;
 
@@ -39,6 +41,57 @@ pub enum Kind {
 
     #[error("invalid raw misbehaviour")]
     InvalidRawMisbehaviour,
+
+    #[error("invalid commitment proof bytes")]
+    InvalidCommitmentProof,
+
+    #[error("commitment proof verification failed")]
+    ProofVerificationFailed,
+
+    #[error("header revision number {0} does not match the client revision number {1}")]
+    MismatchedRevisions(u64, u64),
+
+    #[error("header chain id {0} does not match the client chain id {1}")]
+    MismatchedChainId(String, String),
+
+    #[error("header height {0} must be higher than the client latest height {1}")]
+    LowHeaderHeight(Height, Height),
+
+    #[error("trusted height {0} must be lower than the header height {1}")]
+    InvalidTrustedHeight(Height, Height),
+
+    #[error("missing trusted consensus state at height {0}")]
+    MissingTrustedConsensusState(Height),
+
+    #[error("trusted validator set hash {0} does not match the trusted next validators hash {1}")]
+    MismatchedTrustedValidatorSet(Hash, Hash),
+
+    #[error("validator set hash {0} does not match the header validators hash {1}")]
+    MismatchedValidatorSet(Hash, Hash),
+
+    #[error("trusted consensus state expired at {0}, current time is {1}")]
+    TrustedConsensusStateExpired(Time, Time),
+
+    #[error("header time {0} exceeds the current time {1} plus the maximum clock drift")]
+    HeaderFromTheFuture(Time, Time),
+
+    #[error("header time {0} must be later than the trusted consensus state time {1}")]
+    NonMonotonicHeaderTime(Time, Time),
+
+    #[error("commit does not match the signed header")]
+    InvalidCommit,
+
+    #[error("not enough trust, the trusted validators that signed the header lack voting power")]
+    NotEnoughTrustedValsSigned,
+
+    #[error("the validators that signed the header lack voting power")]
+    InsufficientSignersOverlap,
+
+    #[error("invalid misbehaviour")]
+    InvalidMisbehaviour,
+
+    #[error("the client does not define an upgrade path")]
+    EmptyUpgradePath,
 }
 
 impl Kind {
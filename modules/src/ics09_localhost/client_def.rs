@@ -0,0 +1,432 @@
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_def::ClientDef;
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
+use crate::ics02_client::misbehaviour::AnyMisbehaviour;
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
+use crate::ics04_channel::packet::Sequence;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
+use crate::ics09_localhost::client_state::ClientState;
+use crate::ics09_localhost::consensus_state::ConsensusState;
+use crate::ics09_localhost::error::Kind;
+use crate::ics09_localhost::header::Header;
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::Height;
+
+/// The localhost client. The counterparty of a localhost client is the host chain itself, so
+/// every verification function ignores the proof and instead compares the expected state with the
+/// state found in the store of the host chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalhostClient;
+
+impl ClientDef for LocalhostClient {
+    type Header = Header;
+    type ClientState = ClientState;
+    type ConsensusState = ConsensusState;
+    type Misbehaviour = AnyMisbehaviour;
+
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        header: Self::Header,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        if header.height <= client_state.latest_height() {
+            return Err(Kind::LowHeaderHeight(header.height, client_state.latest_height()).into());
+        }
+
+        // The client cannot track the host chain beyond the height it has reached.
+        if header.height > ctx.host_height() {
+            return Err(Kind::HeaderHeightNotReached(header.height, ctx.host_height()).into());
+        }
+
+        Ok((
+            client_state.with_header(header),
+            ConsensusState::from(header),
+        ))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _client_state: Self::ClientState,
+        _misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Err(Kind::MisbehaviourNotSupported.into())
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _client_state: &Self::ClientState,
+        _upgraded_client_state: Self::ClientState,
+        _upgraded_consensus_state: Self::ConsensusState,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        Err(Kind::UpgradeNotSupported.into())
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        client_id: &ClientId,
+        consensus_height: Height,
+        expected_consensus_state: &AnyConsensusState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let consensus_state = ctx
+            .client_consensus_state(client_id, consensus_height)
+            .ok_or_else(|| Kind::MissingConsensusState(client_id.clone(), consensus_height))?;
+
+        if &consensus_state != expected_consensus_state {
+            return Err(Kind::ConsensusStateMismatch(client_id.clone(), consensus_height).into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_connection_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        connection_id: Option<&ConnectionId>,
+        expected_connection_end: &ConnectionEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let connection_id = connection_id.ok_or(Kind::MissingConnectionId)?;
+
+        let connection_end = ctx
+            .connection_end(connection_id)
+            .ok_or_else(|| Kind::MissingConnection(connection_id.clone()))?;
+
+        if &connection_end != expected_connection_end {
+            return Err(Kind::ConnectionMismatch(connection_id.clone()).into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_channel_state(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_end: &ChannelEnd,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let channel_end = ctx
+            .channel_end(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Kind::MissingChannel(port_id.clone(), channel_id.clone()))?;
+
+        if &channel_end != expected_channel_end {
+            return Err(Kind::ChannelMismatch(port_id.clone(), channel_id.clone()).into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_channel_upgrade(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        expected_channel_upgrade: &ChannelUpgrade,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let channel_upgrade = ctx
+            .channel_upgrade(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Kind::MissingChannelUpgrade(port_id.clone(), channel_id.clone()))?;
+
+        if &channel_upgrade != expected_channel_upgrade {
+            return Err(Kind::ChannelUpgradeMismatch(port_id.clone(), channel_id.clone()).into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_client_full_state(
+        &self,
+        ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _root: &CommitmentRoot,
+        _prefix: &CommitmentPrefix,
+        client_id: &ClientId,
+        _proof: &CommitmentProofBytes,
+        expected_client_state: &AnyClientState,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let stored_client_state = ctx
+            .client_state(client_id)
+            .ok_or_else(|| Kind::MissingClientState(client_id.clone()))?;
+
+        if &stored_client_state != expected_client_state {
+            return Err(Kind::ClientStateMismatch(client_id.clone()).into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_packet_data(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        commitment: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let stored_commitment = ctx
+            .get_packet_commitment(&(port_id.clone(), channel_id.clone(), *seq))
+            .ok_or_else(|| {
+                Kind::MissingPacketCommitment(port_id.clone(), channel_id.clone(), *seq)
+            })?;
+
+        if stored_commitment != commitment {
+            return Err(
+                Kind::PacketCommitmentMismatch(port_id.clone(), channel_id.clone(), *seq).into(),
+            );
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_packet_acknowledgement(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+        ack: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let stored_ack = ctx
+            .get_packet_acknowledgement(&(port_id.clone(), channel_id.clone(), *seq))
+            .ok_or_else(|| {
+                Kind::MissingPacketAcknowledgement(port_id.clone(), channel_id.clone(), *seq)
+            })?;
+
+        // The host stores a commitment to the acknowledgement rather than the acknowledgement.
+        if stored_ack != ctx.ack_commitment(&ack) {
+            return Err(Kind::PacketAcknowledgementMismatch(
+                port_id.clone(),
+                channel_id.clone(),
+                *seq,
+            )
+            .into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_next_sequence_recv(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        let next_sequence_recv = ctx
+            .get_next_sequence_recv(&(port_id.clone(), channel_id.clone()))
+            .ok_or_else(|| Kind::MissingNextSequenceRecv(port_id.clone(), channel_id.clone()))?;
+
+        if &next_sequence_recv != seq {
+            return Err(Kind::NextSequenceRecvMismatch(next_sequence_recv, *seq).into());
+        }
+
+        Ok(client_state.clone())
+    }
+
+    fn verify_packet_receipt_absence(
+        &self,
+        ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        seq: &Sequence,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        if ctx
+            .get_packet_receipt(&(port_id.clone(), channel_id.clone(), *seq))
+            .is_some()
+        {
+            return Err(
+                Kind::PacketReceiptExists(port_id.clone(), channel_id.clone(), *seq).into(),
+            );
+        }
+
+        Ok(client_state.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ics02_client::client_consensus::AnyConsensusState;
+    use crate::ics02_client::client_def::ClientDef;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics03_connection::connection::{ConnectionEnd, State};
+    use crate::ics03_connection::context::ConnectionReader;
+    use crate::ics09_localhost::client_def::LocalhostClient;
+    use crate::ics09_localhost::client_state::ClientState;
+    use crate::ics09_localhost::header::Header;
+    use crate::ics23_commitment::commitment::{
+        CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+    };
+    use crate::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+    use crate::mock::client_state::MockConsensusState;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::Height;
+
+    #[test]
+    fn update_to_host_height() {
+        let client_id = ClientId::new(ClientType::Localhost, 0).unwrap();
+        let ctx = MockContext::default();
+        let client_state =
+            ClientState::new(ChainId::new("mockgaia".to_string(), 0), Height::new(0, 3));
+
+        let (new_state, _) = LocalhostClient
+            .check_header_and_update_state(
+                &ctx,
+                client_id.clone(),
+                client_state.clone(),
+                Header::new(Height::new(0, 5)),
+            )
+            .unwrap();
+        assert_eq!(new_state.latest_height(), Height::new(0, 5));
+
+        // The host chain has not reached this height yet.
+        assert!(LocalhostClient
+            .check_header_and_update_state(
+                &ctx,
+                client_id.clone(),
+                client_state.clone(),
+                Header::new(Height::new(0, 6)),
+            )
+            .is_err());
+
+        // The client is already at this height.
+        assert!(LocalhostClient
+            .check_header_and_update_state(
+                &ctx,
+                client_id,
+                client_state,
+                Header::new(Height::new(0, 3)),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn verify_connection_against_host_store() {
+        let client_id = ClientId::new(ClientType::Localhost, 0).unwrap();
+        let connection_id = ConnectionId::new(0);
+        let connection_end = ConnectionEnd::default();
+        let ctx = MockContext::default()
+            .with_client_parametrized(
+                &client_id,
+                Height::new(0, 5),
+                Some(ClientType::Localhost),
+                None,
+            )
+            .with_connection(connection_id.clone(), connection_end.clone());
+
+        let client_state =
+            ClientState::new(ChainId::new("mockgaia".to_string(), 0), Height::new(0, 5));
+        let verify = |connection_id: Option<&ConnectionId>, expected: &ConnectionEnd| {
+            LocalhostClient.verify_connection_state(
+                &ctx,
+                &client_state,
+                Height::new(0, 5),
+                &CommitmentPrefix::from(vec![]),
+                &CommitmentProofBytes::from(vec![]),
+                &CommitmentRoot::from(vec![]),
+                connection_id,
+                expected,
+            )
+        };
+
+        assert!(verify(Some(&connection_id), &connection_end).is_ok());
+
+        let mut open_connection_end = connection_end.clone();
+        open_connection_end.set_state(State::Open);
+        assert!(verify(Some(&connection_id), &open_connection_end).is_err());
+
+        assert!(verify(Some(&ConnectionId::new(1)), &connection_end).is_err());
+        assert!(verify(None, &connection_end).is_err());
+    }
+
+    #[test]
+    fn verify_consensus_state_against_host_store() {
+        let client_id = ClientId::new(ClientType::Localhost, 0).unwrap();
+        let height = Height::new(0, 5);
+        let ctx = MockContext::default().with_client_parametrized(
+            &client_id,
+            height,
+            Some(ClientType::Localhost),
+            None,
+        );
+
+        let client_state = ClientState::new(ChainId::new("mockgaia".to_string(), 0), height);
+        let verify = |consensus_height: Height, expected: &AnyConsensusState| {
+            LocalhostClient.verify_client_consensus_state(
+                &ctx,
+                &client_state,
+                height,
+                &CommitmentPrefix::from(vec![]),
+                &CommitmentProofBytes::from(vec![]),
+                &CommitmentRoot::from(vec![]),
+                &client_id,
+                consensus_height,
+                expected,
+            )
+        };
+
+        let stored = ctx.client_consensus_state(&client_id, height).unwrap();
+        assert!(verify(height, &stored).is_ok());
+
+        let other = MockConsensusState::new(MockHeader::new(height)).into();
+        assert!(verify(height, &other).is_err());
+
+        assert!(verify(height.increment(), &stored).is_err());
+    }
+}
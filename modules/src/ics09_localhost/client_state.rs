@@ -0,0 +1,113 @@
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::lightclients::localhost::v1::ClientState as RawClientState;
+
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics09_localhost::error::{Error, Kind};
+use crate::ics09_localhost::header::Header;
+use crate::ics24_host::identifier::ChainId;
+use crate::Height;
+
+/// The state of a localhost client, i.e., the identifier of the host chain and the latest height
+/// of the host chain the client was updated to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ClientState {
+    pub chain_id: ChainId,
+    pub height: Height,
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl ClientState {
+    pub fn new(chain_id: ChainId, height: Height) -> Self {
+        Self { chain_id, height }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.height
+    }
+
+    pub fn with_header(self, h: Header) -> Self {
+        Self {
+            height: h.height,
+            ..self
+        }
+    }
+}
+
+impl From<ClientState> for AnyClientState {
+    fn from(cs: ClientState) -> Self {
+        Self::Localhost(cs)
+    }
+}
+
+impl crate::ics02_client::client_state::ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn latest_height(&self) -> Height {
+        self.height
+    }
+
+    fn is_frozen(&self) -> bool {
+        false
+    }
+
+    fn wrap_any(self) -> AnyClientState {
+        AnyClientState::Localhost(self)
+    }
+}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            chain_id: ChainId::from_str(raw.chain_id.as_str())
+                .map_err(|_| Kind::InvalidRawClientState.context("invalid chain identifier"))?,
+            height: raw
+                .height
+                .ok_or_else(|| Kind::InvalidRawClientState.context("missing height"))?
+                .try_into()
+                .map_err(|e| Kind::InvalidRawClientState.context(e))?,
+        })
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            chain_id: value.chain_id.to_string(),
+            height: Some(value.height.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint_proto::Protobuf;
+
+    use crate::ics09_localhost::client_state::ClientState;
+    use crate::ics24_host::identifier::ChainId;
+    use crate::Height;
+
+    #[test]
+    fn client_state_roundtrip() {
+        let client_state = ClientState::new(ChainId::new("ibc".to_string(), 1), Height::new(1, 10));
+
+        let encoded = client_state.encode_vec().unwrap();
+        let decoded = ClientState::decode_vec(&encoded).unwrap();
+
+        assert_eq!(decoded, client_state);
+    }
+}
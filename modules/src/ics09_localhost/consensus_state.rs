@@ -0,0 +1,79 @@
+use std::convert::TryFrom;
+
+use serde::Serialize;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::commitment::v1::MerkleRoot as RawMerkleRoot;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics09_localhost::error::Error;
+use crate::ics09_localhost::header::Header;
+use crate::ics23_commitment::commitment::CommitmentRoot;
+
+/// The consensus state of a localhost client. The client reads the store of the host chain
+/// directly instead of verifying proofs against a root, so the root is always empty, and the
+/// consensus state only records that the client was updated to a given height. It is encoded as a
+/// `MerkleRoot` message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConsensusState {
+    pub root: CommitmentRoot,
+}
+
+impl Default for ConsensusState {
+    fn default() -> Self {
+        Self {
+            root: CommitmentRoot::from(vec![]),
+        }
+    }
+}
+
+impl From<ConsensusState> for AnyConsensusState {
+    fn from(cs: ConsensusState) -> Self {
+        Self::Localhost(cs)
+    }
+}
+
+impl crate::ics02_client::client_consensus::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn validate_basic(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    fn wrap_any(self) -> AnyConsensusState {
+        AnyConsensusState::Localhost(self)
+    }
+}
+
+impl Protobuf<RawMerkleRoot> for ConsensusState {}
+
+impl TryFrom<RawMerkleRoot> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawMerkleRoot) -> Result<Self, Self::Error> {
+        Ok(Self {
+            root: raw.hash.into(),
+        })
+    }
+}
+
+impl From<ConsensusState> for RawMerkleRoot {
+    fn from(value: ConsensusState) -> Self {
+        RawMerkleRoot {
+            hash: value.root.into_vec(),
+        }
+    }
+}
+
+impl From<Header> for ConsensusState {
+    fn from(_header: Header) -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,92 @@
+use anomaly::{BoxError, Context};
+use thiserror::Error;
+
+use crate::ics04_channel::packet::Sequence;
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::Height;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("invalid raw client state")]
+    InvalidRawClientState,
+
+    #[error("invalid raw client consensus state")]
+    InvalidRawConsensusState,
+
+    #[error("invalid raw header")]
+    InvalidRawHeader,
+
+    #[error("header height {0} must be higher than the client latest height {1}")]
+    LowHeaderHeight(Height, Height),
+
+    #[error("header height {0} is higher than the height {1} of the host chain")]
+    HeaderHeightNotReached(Height, Height),
+
+    #[error("localhost clients track the host chain, hence cannot misbehave")]
+    MisbehaviourNotSupported,
+
+    #[error("localhost clients follow the upgrades of the host chain, hence cannot be upgraded")]
+    UpgradeNotSupported,
+
+    #[error("client {0} not found on the host chain")]
+    MissingClientState(ClientId),
+
+    #[error("the state of client {0} does not match the expected client state")]
+    ClientStateMismatch(ClientId),
+
+    #[error("client {0} has no consensus state at height {1} on the host chain")]
+    MissingConsensusState(ClientId, Height),
+
+    #[error("the consensus state of client {0} at height {1} does not match the expected one")]
+    ConsensusStateMismatch(ClientId, Height),
+
+    #[error("the counterparty connection identifier is missing")]
+    MissingConnectionId,
+
+    #[error("connection {0} not found on the host chain")]
+    MissingConnection(ConnectionId),
+
+    #[error("connection {0} does not match the expected connection end")]
+    ConnectionMismatch(ConnectionId),
+
+    #[error("channel {1} on port {0} not found on the host chain")]
+    MissingChannel(PortId, ChannelId),
+
+    #[error("channel {1} on port {0} does not match the expected channel end")]
+    ChannelMismatch(PortId, ChannelId),
+
+    #[error("no upgrade of channel {1} on port {0} is in progress on the host chain")]
+    MissingChannelUpgrade(PortId, ChannelId),
+
+    #[error("the upgrade of channel {1} on port {0} does not match the expected upgrade")]
+    ChannelUpgradeMismatch(PortId, ChannelId),
+
+    #[error("no commitment for packet {2} on port {0} and channel {1}")]
+    MissingPacketCommitment(PortId, ChannelId, Sequence),
+
+    #[error("the commitment of packet {2} on port {0} and channel {1} does not match")]
+    PacketCommitmentMismatch(PortId, ChannelId, Sequence),
+
+    #[error("no acknowledgement for packet {2} on port {0} and channel {1}")]
+    MissingPacketAcknowledgement(PortId, ChannelId, Sequence),
+
+    #[error("the acknowledgement of packet {2} on port {0} and channel {1} does not match")]
+    PacketAcknowledgementMismatch(PortId, ChannelId, Sequence),
+
+    #[error("no next receive sequence for port {0} and channel {1}")]
+    MissingNextSequenceRecv(PortId, ChannelId),
+
+    #[error("next receive sequence {0} does not match the expected sequence {1}")]
+    NextSequenceRecvMismatch(Sequence, Sequence),
+
+    #[error("packet {2} on port {0} and channel {1} was received")]
+    PacketReceiptExists(PortId, ChannelId, Sequence),
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
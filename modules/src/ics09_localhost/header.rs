@@ -0,0 +1,57 @@
+use std::convert::TryFrom;
+
+use serde_derive::{Deserialize, Serialize};
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+use crate::ics02_client::client_type::ClientType;
+use crate::ics02_client::header::AnyHeader;
+use crate::ics09_localhost::error::{Error, Kind};
+use crate::Height;
+
+/// The header with which a localhost client is updated. A localhost client has nothing to verify
+/// besides the height of the host chain, hence the header carries only that height, and is
+/// encoded as a `Height` message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Header {
+    pub height: Height,
+}
+
+impl Header {
+    pub fn new(height: Height) -> Self {
+        Self { height }
+    }
+}
+
+impl crate::ics02_client::header::Header for Header {
+    fn client_type(&self) -> ClientType {
+        ClientType::Localhost
+    }
+
+    fn height(&self) -> Height {
+        self.height
+    }
+
+    fn wrap_any(self) -> AnyHeader {
+        AnyHeader::Localhost(self)
+    }
+}
+
+impl Protobuf<RawHeight> for Header {}
+
+impl TryFrom<RawHeight> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeight) -> Result<Self, Self::Error> {
+        Ok(Self {
+            height: Height::try_from(raw).map_err(|e| Kind::InvalidRawHeader.context(e))?,
+        })
+    }
+}
+
+impl From<Header> for RawHeight {
+    fn from(value: Header) -> Self {
+        value.height.into()
+    }
+}
@@ -0,0 +1,9 @@
+//! ICS 09: Localhost Client, a loopback client through which a chain opens connections and
+//! channels to itself. Instead of checking proofs, the client verifies state by reading the store
+//! of the host chain directly.
+
+pub mod client_def;
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
@@ -10,7 +10,7 @@ use crate::ics24_host::identifier::ClientId;
 pub fn build_client_update_datagram<Ctx>(
     dest: &Ctx,
     client_id: &ClientId,
-    src_header: AnyHeader,
+    mut src_header: AnyHeader,
 ) -> Result<ClientMsg, Error>
 where
     Ctx: Ics18Context,
@@ -41,6 +41,12 @@ where
         .into());
     };
 
+    // A Tendermint header is verified against the consensus state of the client at the trusted
+    // height, which is the latest height of the client on the destination chain.
+    if let AnyHeader::Tendermint(header) = &mut src_header {
+        header.trusted_height = dest_client_latest_height;
+    }
+
     // Client on destination chain can be updated.
     Ok(ClientMsg::UpdateClient(MsgUpdateAnyClient {
         client_id: client_id.clone(),
@@ -82,7 +88,8 @@ mod tests {
             5,
             chain_a_start_height,
         )
-        .with_client_parametrized(
+        .with_client_parametrized_with_chain_id(
+            ChainId::new("mockgaiaB".to_string(), 1),
             &client_on_a_for_b,
             client_on_a_for_b_height,
             Some(ClientType::Tendermint), // The target host chain (B) is synthetic TM.
@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
@@ -7,6 +7,7 @@ use subtle_encoding::{Encoding, Hex};
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
 
 use crate::ics23_commitment::error::Error;
+use crate::ics23_commitment::merkle::MerkleProof;
 
 #[derive(Clone, PartialEq, Eq, Serialize)]
 #[serde(transparent)]
@@ -71,13 +72,13 @@ impl From<CommitmentProofBytes> for Vec<u8> {
         p.bytes
     }
 }
-//
-// impl From<MerkleProof> for CommitmentProofBytes {
-//     fn from(proof: MerkleProof) -> Self {
-//         let raw_proof: RawMerkleProof = proof.into();
-//         raw_proof.into()
-//     }
-// }
+
+impl From<MerkleProof> for CommitmentProofBytes {
+    fn from(proof: MerkleProof) -> Self {
+        let raw_proof: RawMerkleProof = proof.into();
+        raw_proof.into()
+    }
+}
 
 impl From<RawMerkleProof> for CommitmentProofBytes {
     fn from(proof: RawMerkleProof) -> Self {
@@ -98,6 +99,14 @@ impl TryFrom<CommitmentProofBytes> for RawMerkleProof {
     }
 }
 
+impl TryFrom<CommitmentProofBytes> for MerkleProof {
+    type Error = Error;
+
+    fn try_from(value: CommitmentProofBytes) -> Result<Self, Self::Error> {
+        RawMerkleProof::try_from(value)?.try_into()
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Deserialize, Default)]
 pub struct CommitmentPrefix {
     bytes: Vec<u8>,
@@ -8,4 +8,20 @@ pub enum Error {
 
     #[error("failed to decode commitment proof")]
     CommitmentProofDecodingFailed(DecodeError),
+
+    #[error("expected {specs} proofs and keys, got {proofs} proofs and {keys} keys")]
+    NumberOfProofsMismatch {
+        specs: usize,
+        proofs: usize,
+        keys: usize,
+    },
+
+    #[error("invalid commitment proof: {0}")]
+    InvalidCommitmentProof(String),
+
+    #[error("failed to verify the proof of key {0}")]
+    VerificationFailure(String),
+
+    #[error("computed root does not match the commitment root")]
+    RootMismatch,
 }
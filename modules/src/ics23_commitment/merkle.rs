@@ -1,10 +1,15 @@
+use std::convert::TryFrom;
+
+use ics23::commitment_proof::Proof as Ics23Proof;
+use ics23::CommitmentProof;
 use tendermint::merkle::proof::Proof;
 
 use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
 
-use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
 use crate::ics23_commitment::error::Error;
+use crate::ics23_commitment::specs::ProofSpecs;
 
 pub fn apply_prefix(
     prefix: &CommitmentPrefix,
@@ -20,9 +25,13 @@ pub fn apply_prefix(
     Ok(MerklePath { key_path: result })
 }
 
+/// The domain type of the `MerkleProof` of ICS 23, with one ICS23 commitment proof for each
+/// (sub-)store on the path from a key to the root. The proofs are ordered from the innermost
+/// store (e.g., the IAVL store of the IBC module) to the outermost one (e.g., the multi-store of
+/// a Cosmos-SDK chain), and each of them is verified against the spec of its store.
 #[derive(Clone, Debug, PartialEq)]
 pub struct MerkleProof {
-    pub proof: Vec<CommitmentProofBytes>,
+    pub proofs: Vec<CommitmentProof>,
 }
 
 // Merkle Proof serialization notes:
@@ -39,8 +48,8 @@ pub struct MerkleProof {
 //  - Vec<u8>: RawMerkleProof is not explicitly used but, serialized as Vec<u8>, it is
 //       included in all handshake messages that require proofs (i.e. all except the two `OpenInit`),
 //       and also in all queries that require proofs
-//  - MerkleProof: Domain type for RawMerkleProof, currently not used and identical to RawMerkleProof.
-//       This will change with verification implementation.
+//  - MerkleProof: Domain type for RawMerkleProof, which holds the decoded ICS23 commitment proofs
+//       and verifies them.
 //  - CommitmentProof: Defined in ibc-rs as Vec<u8> and currently used in all its messages
 //
 // Here are a couple of flows that illustrate the different conversions:
@@ -49,33 +58,174 @@ pub struct MerkleProof {
 //
 // Relayer: from the proof in the  query response to the proof being included in a message
 //    TmProof -> RawProofOps => RawMerkleProof -> MerkleProof -> verify()
-//      -> MerkleProof -> RawMerkleProof -> CommitmentProof -> Vec<u8>
-// Note: current implementation for ^ is simplified since verification is not yet implemented:
-//    TmProof -> RawProofOps => RawMerkleProof -> CommitmentProof -> Vec<u8>
+//    RawMerkleProof -> CommitmentProof -> Vec<u8>
 //
 // Implementations of (de)serializers and conversions:
 //  - commitment.rs:
 //      Vec<u8> <-> CommitmentProof
 //      CommitmentProof <-> RawMerkleProof
+//      CommitmentProof <-> MerkleProof
 //  - merkle.rs:
 //      RawMerkleProof <-> MerkleProof
 //  - tendermint-rs/src/merkle/proof.rs:
 //      TmProof <-> RawProofOps
 //  - cosmos.rs:abci_query() converts from query proof to Merkle proof:
 //      RawProofOps => RawMerkleProof
-//
-// impl TryFrom<RawMerkleProof> for MerkleProof {
-//     type Error = Error;
-//     fn try_from(value: RawMerkleProof) -> Result<Self, Self::Error> {
-//         Ok(MerkleProof { proof: value.proofs.into_iter().map(|v| v.into()).collect() })
-//     }
-// }
-//
-// impl From<MerkleProof> for RawMerkleProof {
-//     fn from(value: MerkleProof) -> Self {
-//         RawMerkleProof { proof: value.proof }
-//     }
-// }
+
+impl TryFrom<RawMerkleProof> for MerkleProof {
+    type Error = Error;
+
+    fn try_from(value: RawMerkleProof) -> Result<Self, Self::Error> {
+        // Both `CommitmentProof` types implement trait `prost::Message`. Convert by encoding, then
+        // decoding into the destination type.
+        let proofs = value
+            .proofs
+            .iter()
+            .map(|raw| prost::Message::decode(&*encode(raw)))
+            .collect::<Result<Vec<CommitmentProof>, _>>()
+            .map_err(Error::CommitmentProofDecodingFailed)?;
+
+        Ok(MerkleProof { proofs })
+    }
+}
+
+impl From<MerkleProof> for RawMerkleProof {
+    fn from(value: MerkleProof) -> Self {
+        // Safety note: the source and target data structures are identical, hence the
+        // encode/decode conversion here should be infallible.
+        let proofs = value
+            .proofs
+            .iter()
+            .map(|proof| prost::Message::decode(&*encode(proof)).unwrap())
+            .collect();
+
+        RawMerkleProof { proofs }
+    }
+}
+
+impl MerkleProof {
+    /// Verifies that `value` is stored under the keys of `path` in the stores committed to by
+    /// `root`. The keys are ordered from the outermost store to the innermost one, that is, the
+    /// other way around than the proofs, with one key, one proof and one spec for each store.
+    pub fn verify_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: &CommitmentRoot,
+        path: &MerklePath,
+        value: Vec<u8>,
+    ) -> Result<(), Error> {
+        self.check_lengths(specs, path)?;
+
+        self.verify_chained_membership(specs, root, &path.key_path, value, 0)
+    }
+
+    /// Verifies that nothing is stored under the keys of `path` in the stores committed to by
+    /// `root`. The innermost proof must prove the absence of the last key in the innermost store,
+    /// whose root is then proven to be present in the outer stores like for `verify_membership`.
+    pub fn verify_non_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: &CommitmentRoot,
+        path: &MerklePath,
+    ) -> Result<(), Error> {
+        self.check_lengths(specs, path)?;
+
+        let key = path.key_path.last().unwrap();
+        let subroot = match &self.proofs[0].proof {
+            Some(Ics23Proof::Nonexist(non_existence_proof)) => {
+                let existence_proof = non_existence_proof
+                    .left
+                    .as_ref()
+                    .or_else(|| non_existence_proof.right.as_ref())
+                    .ok_or_else(|| {
+                        Error::InvalidCommitmentProof("empty non-existence proof".to_string())
+                    })?;
+                ics23::calculate_existence_root(existence_proof)
+                    .map_err(|e| Error::InvalidCommitmentProof(e.to_string()))?
+            }
+            _ => {
+                return Err(Error::InvalidCommitmentProof(
+                    "expected a non-existence proof".to_string(),
+                ))
+            }
+        };
+
+        if !ics23::verify_non_membership(
+            &self.proofs[0],
+            &specs.specs()[0],
+            &subroot,
+            key.as_bytes(),
+        ) {
+            return Err(Error::VerificationFailure(key.clone()));
+        }
+
+        self.verify_chained_membership(specs, root, &path.key_path, subroot, 1)
+    }
+
+    /// Verifies the chain of existence proofs, starting at index `start`, where the value proven
+    /// by each proof is the root of the store proven by the previous one. The root of the last
+    /// proof must match the expected `root`.
+    fn verify_chained_membership(
+        &self,
+        specs: &ProofSpecs,
+        root: &CommitmentRoot,
+        key_path: &[String],
+        value: Vec<u8>,
+        start: usize,
+    ) -> Result<(), Error> {
+        let mut value = value;
+        for (i, key) in key_path.iter().rev().enumerate().skip(start) {
+            let subroot = match &self.proofs[i].proof {
+                Some(Ics23Proof::Exist(existence_proof)) => {
+                    ics23::calculate_existence_root(existence_proof)
+                        .map_err(|e| Error::InvalidCommitmentProof(e.to_string()))?
+                }
+                _ => {
+                    return Err(Error::InvalidCommitmentProof(
+                        "expected an existence proof".to_string(),
+                    ))
+                }
+            };
+
+            if !ics23::verify_membership(
+                &self.proofs[i],
+                &specs.specs()[i],
+                &subroot,
+                key.as_bytes(),
+                &value,
+            ) {
+                return Err(Error::VerificationFailure(key.clone()));
+            }
+
+            value = subroot;
+        }
+
+        if root.as_bytes() != value.as_slice() {
+            return Err(Error::RootMismatch);
+        }
+
+        Ok(())
+    }
+
+    fn check_lengths(&self, specs: &ProofSpecs, path: &MerklePath) -> Result<(), Error> {
+        let specs_len = specs.specs().len();
+        if self.proofs.len() != specs_len || path.key_path.len() != specs_len {
+            return Err(Error::NumberOfProofsMismatch {
+                specs: specs_len,
+                proofs: self.proofs.len(),
+                keys: path.key_path.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn encode(message: &impl prost::Message) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    prost::Message::encode(message, &mut encoded).unwrap();
+    encoded
+}
 
 pub fn convert_tm_to_ics_merkle_proof(tm_proof: &Proof) -> Result<RawMerkleProof, Error> {
     let mut proofs = vec![];
@@ -90,3 +240,198 @@ pub fn convert_tm_to_ics_merkle_proof(tm_proof: &Proof) -> Result<RawMerkleProof
 
     Ok(RawMerkleProof { proofs })
 }
+
+#[cfg(test)]
+pub mod test_util {
+    use subtle_encoding::hex;
+    use tendermint_rpc::endpoint::abci_query::AbciQuery;
+
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+
+    use crate::ics23_commitment::commitment::CommitmentRoot;
+    use crate::ics23_commitment::merkle::convert_tm_to_ics_merkle_proof;
+
+    /// The app hash of the chain queried in the `tests/support/query/ics23` fixtures. The IBC
+    /// store of this chain sits in a multi-store along with the `bank` and `staking` stores, and
+    /// holds the next receive sequence (2) and the receipts of packets 1 and 3 of `channel-0` on
+    /// port `transfer`, as well as the commitment of packet 1.
+    const APP_HASH: &str = "DB9E8C1BBE11B3E4660667507FD5E70B6D68B623F6A564D9DFA382D89067FFF6";
+
+    /// A key of the IBC store, with its value and the proof of this value (or of its absence)
+    /// against the app hash of the chain, as returned by a Cosmos-SDK node to an ABCI query.
+    pub struct ProofFixture {
+        pub key: String,
+        pub value: Vec<u8>,
+        pub proof: RawMerkleProof,
+        pub root: CommitmentRoot,
+    }
+
+    fn load(json: &str) -> ProofFixture {
+        let response = serde_json::from_str::<AbciQuery>(json).unwrap();
+
+        ProofFixture {
+            key: String::from_utf8(response.key).unwrap(),
+            value: response.value,
+            proof: convert_tm_to_ics_merkle_proof(&response.proof.unwrap()).unwrap(),
+            root: CommitmentRoot::from(hex::decode_upper(APP_HASH).unwrap()),
+        }
+    }
+
+    /// The proof of the next receive sequence of `channel-0` on port `transfer`.
+    pub fn membership_proof() -> ProofFixture {
+        load(include_str!(
+            "../../tests/support/query/ics23/membership_proof.json"
+        ))
+    }
+
+    /// The proof of the absence of the receipt of packet 2 of `channel-0` on port `transfer`.
+    pub fn non_membership_proof() -> ProofFixture {
+        load(include_str!(
+            "../../tests/support/query/ics23/non_membership_proof.json"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ics23::commitment_proof::Proof as Ics23Proof;
+    use ics23::{CommitmentProof, ExistenceProof};
+
+    use ibc_proto::ibc::core::commitment::v1::{MerklePath, MerkleProof as RawMerkleProof};
+
+    use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+    use crate::ics23_commitment::error::Error;
+    use crate::ics23_commitment::merkle::test_util::{membership_proof, non_membership_proof};
+    use crate::ics23_commitment::merkle::{apply_prefix, MerkleProof};
+    use crate::ics23_commitment::specs::ProofSpecs;
+
+    fn ibc_path(key: &str) -> MerklePath {
+        apply_prefix(&CommitmentPrefix::from(b"ibc".to_vec()), vec![key.into()]).unwrap()
+    }
+
+    fn existence_proof(key: &str, value: &str) -> CommitmentProof {
+        CommitmentProof {
+            proof: Some(Ics23Proof::Exist(ExistenceProof {
+                key: key.as_bytes().to_vec(),
+                value: value.as_bytes().to_vec(),
+                leaf: ics23::iavl_spec().leaf_spec,
+                path: vec![],
+            })),
+        }
+    }
+
+    #[test]
+    fn raw_merkle_proof_round_trip() {
+        let proof = MerkleProof {
+            proofs: vec![
+                existence_proof("key", "value"),
+                existence_proof("ibc", "root"),
+            ],
+        };
+
+        let raw = RawMerkleProof::from(proof.clone());
+        assert_eq!(raw.proofs.len(), 2);
+        assert_eq!(MerkleProof::try_from(raw).unwrap(), proof);
+    }
+
+    #[test]
+    fn reject_malformed_proofs() {
+        let specs = ProofSpecs::cosmos();
+        let root = CommitmentRoot::from_bytes(b"root");
+        let path =
+            apply_prefix(&CommitmentPrefix::from(b"ibc".to_vec()), vec!["key".into()]).unwrap();
+
+        let proof = MerkleProof {
+            proofs: vec![existence_proof("key", "value")],
+        };
+        assert_eq!(
+            proof.verify_membership(&specs, &root, &path, b"value".to_vec()),
+            Err(Error::NumberOfProofsMismatch {
+                specs: 2,
+                proofs: 1,
+                keys: 2
+            })
+        );
+
+        // The absence of a key cannot be proven with an existence proof.
+        let proof = MerkleProof {
+            proofs: vec![
+                existence_proof("key", "value"),
+                existence_proof("ibc", "root"),
+            ],
+        };
+        assert!(matches!(
+            proof.verify_non_membership(&specs, &root, &path),
+            Err(Error::InvalidCommitmentProof(_))
+        ));
+    }
+
+    #[test]
+    fn verify_chained_membership_proof() {
+        let specs = ProofSpecs::cosmos();
+        let fixture = membership_proof();
+        let proof = MerkleProof::try_from(fixture.proof).unwrap();
+        let path = ibc_path(&fixture.key);
+
+        assert_eq!(
+            proof.verify_membership(&specs, &fixture.root, &path, fixture.value.clone()),
+            Ok(())
+        );
+
+        // The proof is for another value...
+        let mut value = fixture.value.clone();
+        *value.last_mut().unwrap() += 1;
+        assert_eq!(
+            proof.verify_membership(&specs, &fixture.root, &path, value),
+            Err(Error::VerificationFailure(fixture.key.clone()))
+        );
+
+        // ... under another key...
+        let key = "nextSequenceRecv/ports/transfer/channels/channel-1";
+        assert_eq!(
+            proof.verify_membership(&specs, &fixture.root, &ibc_path(key), fixture.value.clone()),
+            Err(Error::VerificationFailure(key.to_string()))
+        );
+
+        // ... against another root.
+        let root = CommitmentRoot::from_bytes(&[0; 32]);
+        assert_eq!(
+            proof.verify_membership(&specs, &root, &path, fixture.value),
+            Err(Error::RootMismatch)
+        );
+    }
+
+    #[test]
+    fn verify_chained_non_membership_proof() {
+        let specs = ProofSpecs::cosmos();
+        let fixture = non_membership_proof();
+        let proof = MerkleProof::try_from(fixture.proof).unwrap();
+        let path = ibc_path(&fixture.key);
+
+        assert_eq!(
+            proof.verify_non_membership(&specs, &fixture.root, &path),
+            Ok(())
+        );
+
+        // The receipt of packet 1 is present, outside of the range proven to be empty.
+        let key = "receipts/ports/transfer/channels/channel-0/sequences/1";
+        assert_eq!(
+            proof.verify_non_membership(&specs, &fixture.root, &ibc_path(key)),
+            Err(Error::VerificationFailure(key.to_string()))
+        );
+
+        let root = CommitmentRoot::from_bytes(&[0; 32]);
+        assert_eq!(
+            proof.verify_non_membership(&specs, &root, &path),
+            Err(Error::RootMismatch)
+        );
+
+        // A proof of absence does not prove the presence of a value.
+        assert!(matches!(
+            proof.verify_membership(&specs, &fixture.root, &path, b"value".to_vec()),
+            Err(Error::InvalidCommitmentProof(_))
+        ));
+    }
+}
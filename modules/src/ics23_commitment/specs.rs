@@ -23,6 +23,12 @@ impl ProofSpecs {
             ],
         }
     }
+
+    /// Returns the specifications, ordered from the innermost (leaf) store to the outermost
+    /// (root) store.
+    pub fn specs(&self) -> &[ProofSpec] {
+        &self.specs
+    }
 }
 
 /// Converts from the domain type (which is represented as a vector of `ics23::ProofSpec`
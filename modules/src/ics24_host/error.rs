@@ -27,6 +27,9 @@ pub enum ValidationKind {
 
     #[error("Invalid channel id in counterparty")]
     InvalidCounterpartyChannelId,
+
+    #[error("{path} is not a path of the IBC store")]
+    InvalidPath { path: String },
 }
 
 impl ValidationKind {
@@ -55,6 +58,10 @@ impl ValidationKind {
         Self::ChainIdInvalidFormat { id }
     }
 
+    pub fn invalid_path(path: String) -> Self {
+        Self::InvalidPath { path }
+    }
+
     pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
         Context::new(self, Some(source.into()))
     }
@@ -157,6 +157,8 @@ impl ClientId {
     pub fn prefix(client_type: ClientType) -> &'static str {
         match client_type {
             ClientType::Tendermint => ClientType::Tendermint.as_string(),
+            ClientType::SoloMachine => ClientType::SoloMachine.as_string(),
+            ClientType::Localhost => ClientType::Localhost.as_string(),
 
             #[cfg(any(test, feature = "mocks"))]
             ClientType::Mock => ClientType::Mock.as_string(),
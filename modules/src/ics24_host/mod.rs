@@ -1,6 +1,9 @@
 //! ICS 24: Host Requirements
 
-pub use path::{ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
+pub use path::{
+    ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH,
+    UPGRADED_CLIENT_CONSENSUS_STATE, UPGRADED_CLIENT_STATE,
+};
 
 pub mod error;
 pub mod identifier;
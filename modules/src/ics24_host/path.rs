@@ -3,8 +3,10 @@
 /// Some of these are implemented in other ICSs, but ICS-024 has a nice summary table.
 ///
 use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
 
 use crate::ics04_channel::packet::Sequence;
+use crate::ics24_host::error::{ValidationError, ValidationKind};
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 
 /// ABCI Query path for the IBC sub-store
@@ -18,9 +20,9 @@ pub const SDK_UPGRADE_QUERY_PATH: &str = "store/upgrade/key";
 /// - The key identifying the upgraded IBC state within the upgrade sub-store
 const UPGRADED_IBC_STATE: &str = "upgradedIBCState";
 ///- The key identifying the upgraded client state
-const UPGRADED_CLIENT_STATE: &str = "upgradedClient";
+pub const UPGRADED_CLIENT_STATE: &str = "upgradedClient";
 /// - The key identifying the upgraded consensus state
-const UPGRADED_CLIENT_CONSENSUS_STATE: &str = "upgradedConsState";
+pub const UPGRADED_CLIENT_CONSENSUS_STATE: &str = "upgradedConsState";
 
 /// The Path enum abstracts out the different sub-paths
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -36,6 +38,7 @@ pub enum Path {
     Connections(ConnectionId),
     Ports(PortId),
     ChannelEnds(PortId, ChannelId),
+    ChannelUpgrades(PortId, ChannelId),
     SeqSends(PortId, ChannelId),
     SeqRecvs(PortId, ChannelId),
     SeqAcks(PortId, ChannelId),
@@ -98,6 +101,11 @@ impl Display for Path {
             Path::ChannelEnds(port_id, channel_id) => {
                 write!(f, "channelEnds/ports/{}/channels/{}", port_id, channel_id)
             }
+            Path::ChannelUpgrades(port_id, channel_id) => write!(
+                f,
+                "channelUpgrades/ports/{}/channels/{}",
+                port_id, channel_id
+            ),
             Path::SeqSends(port_id, channel_id) => write!(
                 f,
                 "nextSequenceSend/ports/{}/channels/{}",
@@ -153,3 +161,160 @@ impl Display for Path {
         }
     }
 }
+
+/// Parses the paths as rendered by `Display`, e.g., `channelEnds/ports/{}/channels/{}`. Only the
+/// canonical rendering of a path parses, so that parsing and rendering round-trip.
+impl FromStr for Path {
+    type Err = ValidationError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let components: Vec<&str> = s.split('/').collect();
+
+        parse_components(&components)
+            .filter(|path| path.to_string() == s)
+            .ok_or_else(|| ValidationKind::invalid_path(s.to_string()).into())
+    }
+}
+
+fn parse_components(components: &[&str]) -> Option<Path> {
+    let path = match components {
+        ["clients", client_id, "clientType"] => Path::ClientType(client_id.parse().ok()?),
+        ["clients", client_id, "clientState"] => Path::ClientState(client_id.parse().ok()?),
+        ["clients", client_id, "consensusStates", height] => {
+            let mut parts = height.splitn(2, '-');
+            Path::ClientConsensusState {
+                client_id: client_id.parse().ok()?,
+                epoch: parts.next()?.parse().ok()?,
+                height: parts.next()?.parse().ok()?,
+            }
+        }
+        ["clients", client_id, "connections"] => Path::ClientConnections(client_id.parse().ok()?),
+        ["connections", connection_id] => Path::Connections(connection_id.parse().ok()?),
+        ["ports", port_id] => Path::Ports(port_id.parse().ok()?),
+        ["channelEnds", "ports", port_id, "channels", channel_id] => {
+            Path::ChannelEnds(port_id.parse().ok()?, channel_id.parse().ok()?)
+        }
+        ["channelUpgrades", "ports", port_id, "channels", channel_id] => {
+            Path::ChannelUpgrades(port_id.parse().ok()?, channel_id.parse().ok()?)
+        }
+        ["nextSequenceSend", "ports", port_id, "channels", channel_id] => {
+            Path::SeqSends(port_id.parse().ok()?, channel_id.parse().ok()?)
+        }
+        ["nextSequenceRecv", "ports", port_id, "channels", channel_id] => {
+            Path::SeqRecvs(port_id.parse().ok()?, channel_id.parse().ok()?)
+        }
+        ["nextSequenceAck", "ports", port_id, "channels", channel_id] => {
+            Path::SeqAcks(port_id.parse().ok()?, channel_id.parse().ok()?)
+        }
+        ["commitments", "ports", port_id, "channels", channel_id, "sequences", sequence] => {
+            Path::Commitments {
+                port_id: port_id.parse().ok()?,
+                channel_id: channel_id.parse().ok()?,
+                sequence: parse_sequence(sequence)?,
+            }
+        }
+        ["acks", "ports", port_id, "channels", channel_id, "sequences", sequence] => Path::Acks {
+            port_id: port_id.parse().ok()?,
+            channel_id: channel_id.parse().ok()?,
+            sequence: parse_sequence(sequence)?,
+        },
+        ["receipts", "ports", port_id, "channels", channel_id, "sequences", sequence] => {
+            Path::Receipts {
+                port_id: port_id.parse().ok()?,
+                channel_id: channel_id.parse().ok()?,
+                sequence: parse_sequence(sequence)?,
+            }
+        }
+        [UPGRADED_IBC_STATE, height, UPGRADED_CLIENT_STATE] => {
+            Path::Upgrade(ClientUpgradePath::UpgradedClientState(height.parse().ok()?))
+        }
+        [UPGRADED_IBC_STATE, height, UPGRADED_CLIENT_CONSENSUS_STATE] => Path::Upgrade(
+            ClientUpgradePath::UpgradedClientConsensusState(height.parse().ok()?),
+        ),
+        _ => return None,
+    };
+
+    Some(path)
+}
+
+fn parse_sequence(sequence: &str) -> Option<Sequence> {
+    sequence.parse::<u64>().ok().map(Sequence::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::ics24_host::{ClientUpgradePath, Path};
+
+    #[test]
+    fn parse_paths() {
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+
+        let paths = vec![
+            Path::ClientType(ClientId::default()),
+            Path::ClientState(ClientId::default()),
+            Path::ClientConsensusState {
+                client_id: ClientId::default(),
+                epoch: 1,
+                height: 20,
+            },
+            Path::ClientConnections(ClientId::default()),
+            Path::Connections(ConnectionId::default()),
+            Path::Ports(port_id.clone()),
+            Path::ChannelEnds(port_id.clone(), channel_id.clone()),
+            Path::ChannelUpgrades(port_id.clone(), channel_id.clone()),
+            Path::SeqSends(port_id.clone(), channel_id.clone()),
+            Path::SeqRecvs(port_id.clone(), channel_id.clone()),
+            Path::SeqAcks(port_id.clone(), channel_id.clone()),
+            Path::Commitments {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                sequence: 1.into(),
+            },
+            Path::Acks {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                sequence: 2.into(),
+            },
+            Path::Receipts {
+                port_id,
+                channel_id,
+                sequence: 3.into(),
+            },
+            Path::Upgrade(ClientUpgradePath::UpgradedClientState(5)),
+            Path::Upgrade(ClientUpgradePath::UpgradedClientConsensusState(5)),
+        ];
+
+        for path in paths {
+            assert_eq!(Path::from_str(&path.to_string()).unwrap(), path);
+        }
+    }
+
+    #[test]
+    fn parse_invalid_paths() {
+        let invalid = vec![
+            "",
+            "clients",
+            "clients/07-tendermint-0",
+            "clients/07-tendermint-0/clientStates",
+            "clients/07-tendermint-0/consensusStates/1",
+            "clients/07-tendermint-0/consensusStates/1-x",
+            // Not the canonical rendering of the height.
+            "clients/07-tendermint-0/consensusStates/01-1",
+            "connections/connection-0/extra",
+            "channelEnds/ports/transfer/channels",
+            "commitments/ports/transfer/channels/channel-0/sequences/-1",
+            "upgradedIBCState/5/upgradedClients",
+            // Invalid identifiers.
+            "ports/a",
+            "connections/connection 0",
+        ];
+
+        for path in invalid {
+            assert!(Path::from_str(path).is_err(), "{} should not parse", path);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use anomaly::{BoxError, Context};
 use thiserror::Error;
 
+use crate::ics05_port::capabilities::ModuleId;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+
 pub type Error = anomaly::Error<Kind>;
 
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
@@ -11,6 +14,33 @@ pub enum Kind {
     #[error("error raised by the keeper functionality in message handler")]
     KeeperRaisedError,
 
+    #[error("error raised by the application module")]
+    ModuleRaisedError,
+
+    #[error("no application module is routed for port {0}")]
+    ModuleNotFound(PortId),
+
+    #[error("the application module routed for port {0} does not support channel upgrades")]
+    ChannelUpgradeNotSupported(PortId),
+
+    #[error("port {0} is not bound")]
+    PortCapabilityNotFound(PortId),
+
+    #[error("no capability exists for channel end {1} on port {0}")]
+    ChannelCapabilityNotFound(PortId, ChannelId),
+
+    #[error("an application module is already routed for port {0}")]
+    RouteAlreadyExists(PortId),
+
+    #[error("an application module is already routed for the ports prefixed with {0}")]
+    PrefixRouteAlreadyExists(String),
+
+    #[error("an application module is already routed for the messages of type URL {0}")]
+    MsgRouteAlreadyExists(String),
+
+    #[error("the capabilities of module {0} are already scoped")]
+    ScopeAlreadyExists(ModuleId),
+
     #[error("unknown type URL {0}")]
     UnknownMessageTypeUrl(String),
 
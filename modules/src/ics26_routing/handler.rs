@@ -1,26 +1,38 @@
+use std::sync::Arc;
+
 use prost_types::Any;
 use tendermint_proto::Protobuf;
 
-use crate::application::ics20_fungible_token_transfer::msgs::transfer;
-use crate::application::ics20_fungible_token_transfer::relay_application_logic::send_transfer::send_transfer as ics20_msg_dispatcher;
+use crate::ics02_client::client_state::AnyClientState;
 use crate::ics02_client::handler::dispatch as ics2_msg_dispatcher;
-use crate::ics02_client::msgs::{create_client, update_client, ClientMsg};
+use crate::ics02_client::msgs::{
+    create_client, misbehavior, update_client, upgrade_client, ClientMsg,
+};
 use crate::ics03_connection::handler::dispatch as ics3_msg_dispatcher;
 use crate::ics03_connection::msgs::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
 };
+use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::ics04_channel::handler::channel_dispatch as ics4_msg_dispatcher;
 use crate::ics04_channel::handler::packet_dispatch as ics04_packet_msg_dispatcher;
+use crate::ics04_channel::handler::write_acknowledgement::process as ics4_write_ack_processor;
+use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, ClientId, PortId};
 use crate::{events::IbcEvent, handler::HandlerOutput};
 
 use crate::ics04_channel::msgs::{
     acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
-    chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg, PacketMsg,
+    chan_open_init, chan_open_try, chan_upgrade_ack, chan_upgrade_cancel, chan_upgrade_confirm,
+    chan_upgrade_init, chan_upgrade_try, recv_packet, timeout, timeout_on_close, ChannelMsg,
+    PacketMsg,
 };
 use crate::ics26_routing::context::Ics26Context;
 use crate::ics26_routing::error::{Error, Kind};
+use crate::ics26_routing::module::{Module, MsgResult};
 use crate::ics26_routing::msgs::Ics26Envelope::{
-    self, Ics20Msg, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
+    self, AppMsg, Ics2Msg, Ics3Msg, Ics4ChannelMsg, Ics4PacketMsg,
 };
 
 /// Mimics the DeliverTx ABCI interface, but a slightly lower level. No need for authentication
@@ -52,6 +64,16 @@ where
                     .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
                 Ok(Ics2Msg(ClientMsg::UpdateClient(domain_msg)))
             }
+            upgrade_client::TYPE_URL => {
+                let domain_msg = upgrade_client::MsgUpgradeAnyClient::decode_vec(&any_msg.value)
+                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics2Msg(ClientMsg::UpgradeClient(domain_msg)))
+            }
+            misbehavior::TYPE_URL => {
+                let domain_msg = misbehavior::MsgSubmitAnyMisbehaviour::decode_vec(&any_msg.value)
+                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics2Msg(ClientMsg::Misbehaviour(domain_msg)))
+            }
 
             // ICS03
             conn_open_init::TYPE_URL => {
@@ -113,11 +135,35 @@ where
                         .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
                 Ok(Ics4ChannelMsg(ChannelMsg::ChannelCloseConfirm(domain_msg)))
             }
-            // ICS20 - 04 - Send packet
-            transfer::TYPE_URL => {
-                let domain_msg = transfer::MsgTransfer::decode_vec(&any_msg.value)
+            chan_upgrade_init::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_init::MsgChannelUpgradeInit::decode_vec(&any_msg.value)
+                        .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics4ChannelMsg(ChannelMsg::ChannelUpgradeInit(domain_msg)))
+            }
+            chan_upgrade_try::TYPE_URL => {
+                let domain_msg = chan_upgrade_try::MsgChannelUpgradeTry::decode_vec(&any_msg.value)
+                    .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics4ChannelMsg(ChannelMsg::ChannelUpgradeTry(domain_msg)))
+            }
+            chan_upgrade_ack::TYPE_URL => {
+                let domain_msg = chan_upgrade_ack::MsgChannelUpgradeAck::decode_vec(&any_msg.value)
                     .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
-                Ok(Ics20Msg(domain_msg))
+                Ok(Ics4ChannelMsg(ChannelMsg::ChannelUpgradeAck(domain_msg)))
+            }
+            chan_upgrade_confirm::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_confirm::MsgChannelUpgradeConfirm::decode_vec(&any_msg.value)
+                        .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics4ChannelMsg(ChannelMsg::ChannelUpgradeConfirm(
+                    domain_msg,
+                )))
+            }
+            chan_upgrade_cancel::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_cancel::MsgChannelUpgradeCancel::decode_vec(&any_msg.value)
+                        .map_err(|e| Kind::MalformedMessageBytes.context(e))?;
+                Ok(Ics4ChannelMsg(ChannelMsg::ChannelUpgradeCancel(domain_msg)))
             }
             // ICS04 packet messages
             recv_packet::TYPE_URL => {
@@ -141,7 +187,8 @@ where
                 Ok(Ics4PacketMsg(PacketMsg::ToClosePacket(domain_msg)))
             }
 
-            _ => Err(Kind::UnknownMessageTypeUrl(any_msg.type_url)),
+            // Messages of the applications, which the router routes by type URL.
+            _ => Ok(AppMsg(any_msg)),
         }?;
 
         // Process the envelope, and accumulate any events that were generated.
@@ -182,6 +229,7 @@ where
                 ics3_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
             // Apply any results to the host chain store.
+            store_verified_client_state(ctx, handler_output.result.client_state.clone())?;
             ctx.store_connection_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
 
@@ -192,12 +240,50 @@ where
         }
 
         Ics4ChannelMsg(msg) => {
-            let handler_output =
-                ics4_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+            let module = lookup_module(ctx, channel_msg_port_id(&msg))?;
+            let cap = channel_msg_capability(ctx, &msg)?;
+
+            let mut handler_output = ics4_msg_dispatcher(ctx, &cap, msg.clone())
+                .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+            // The capability of a new channel end, and the claims of the application module on
+            // it, are only kept if the module accepts the handshake step.
+            let mut ctx_interim = ctx.clone();
+
+            // A new channel end gets its own capability, which the application module claims in
+            // its callback.
+            let channel_cap = match handler_output.result.channel_id_state {
+                ChannelIdState::Generated => ctx_interim
+                    .new_channel_capability((
+                        handler_output.result.port_id.clone(),
+                        handler_output.result.channel_id.clone(),
+                    ))
+                    .map_err(|e| Kind::KeeperRaisedError.context(e))?,
+                ChannelIdState::Reused => channel_capability(
+                    ctx,
+                    &handler_output.result.port_id,
+                    &handler_output.result.channel_id,
+                )?,
+            };
+
+            // Let the application module validate the handshake step before any state is written.
+            channel_callback(
+                &mut ctx_interim,
+                module.as_ref(),
+                &msg,
+                &channel_cap,
+                &mut handler_output.result,
+            )?;
 
             // Apply any results to the host chain store.
-            ctx.store_channel_result(handler_output.result)
+            store_verified_client_state(
+                &mut ctx_interim,
+                handler_output.result.client_state.clone(),
+            )?;
+            ctx_interim
+                .store_channel_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+            *ctx = ctx_interim;
 
             HandlerOutput::builder()
                 .with_log(handler_output.log)
@@ -205,43 +291,377 @@ where
                 .with_result(())
         }
 
-        Ics20Msg(msg) => {
-            let handler_output =
-                ics20_msg_dispatcher(ctx, msg).map_err(|e| Kind::HandlerRaisedError.context(e))?;
+        AppMsg(msg) => {
+            let module = ctx
+                .router()
+                .get_msg_route(&msg.type_url)
+                .ok_or_else(|| Kind::UnknownMessageTypeUrl(msg.type_url.clone()))?;
 
-            // Apply any results to the host chain store.
-            ctx.store_packet_result(handler_output.result)
-                .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+            let module_output = module.on_msg(ctx, &msg)?;
+            let output = HandlerOutput::builder()
+                .with_log(module_output.log)
+                .with_events(module_output.events.clone());
 
-            HandlerOutput::builder()
-                .with_log(handler_output.log)
-                .with_events(handler_output.events)
-                .with_result(())
+            match module_output.result {
+                MsgResult::Done => output.with_result(()),
+                MsgResult::SendPacket(result) => {
+                    send_packet_callback(ctx, &module_output.events)?;
+
+                    // Apply any results to the host chain store.
+                    ctx.store_packet_result(result)
+                        .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+
+                    output.with_result(())
+                }
+                MsgResult::ChannelOpenInit(chan_msg) => {
+                    let chan_output =
+                        dispatch(ctx, Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(chan_msg)))?;
+
+                    output
+                        .with_log(chan_output.log)
+                        .with_events(chan_output.events)
+                        .with_result(())
+                }
+            }
         }
 
         Ics4PacketMsg(msg) => {
-            let handler_output = ics04_packet_msg_dispatcher(ctx, msg)
+            let (port_id, channel_id) = packet_msg_port_channel_id(&msg);
+            let module = lookup_module(ctx, port_id)?;
+            let channel_cap = channel_capability(ctx, port_id, channel_id)?;
+
+            let handler_output = ics04_packet_msg_dispatcher(ctx, &channel_cap, msg.clone())
                 .map_err(|e| Kind::HandlerRaisedError.context(e))?;
 
+            // Hand the packet to the application module before any state is written.
+            let module_output = packet_callback(ctx, module.as_ref(), &msg)?;
+
             // Apply any results to the host chain store.
+            store_verified_client_state(ctx, handler_output.result.client_state().cloned())?;
             ctx.store_packet_result(handler_output.result)
                 .map_err(|e| Kind::KeeperRaisedError.context(e))?;
 
-            HandlerOutput::builder()
+            let mut output = HandlerOutput::builder()
                 .with_log(handler_output.log)
                 .with_events(handler_output.events)
-                .with_result(())
+                .with_log(module_output.log)
+                .with_events(module_output.events);
+
+            // The module acknowledged the received packet synchronously, so write the
+            // acknowledgement right away.
+            if let (PacketMsg::RecvPacket(msg), Some(ack)) = (msg, module_output.result) {
+                let ack_output = write_acknowledgement(ctx, &channel_cap, msg.packet, ack)?;
+                output = output
+                    .with_log(ack_output.log)
+                    .with_events(ack_output.events);
+            }
+
+            output.with_result(())
         }
     };
 
     Ok(output)
 }
 
+/// Writes the acknowledgement `ack` of `packet`, which the host chain received, and returns the
+/// resulting `WriteAcknowledgement` event. Application modules which acknowledge packets
+/// asynchronously call this once the acknowledgement is available, with the capability which
+/// they claimed for the destination channel end of the packet.
+pub fn write_acknowledgement<Ctx>(
+    ctx: &mut Ctx,
+    channel_cap: &Capability,
+    packet: Packet,
+    ack: Vec<u8>,
+) -> Result<HandlerOutput<()>, Error>
+where
+    Ctx: ChannelReader + ChannelKeeper,
+{
+    let handler_output = ics4_write_ack_processor(ctx, channel_cap, packet, ack)
+        .map_err(|e| Kind::HandlerRaisedError.context(e))?;
+
+    // Apply the result to the host chain store.
+    ctx.store_packet_result(handler_output.result)
+        .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+
+    Ok(HandlerOutput::builder()
+        .with_log(handler_output.log)
+        .with_events(handler_output.events)
+        .with_result(()))
+}
+
+/// Persists the state of the client which verified the proofs of a message, as the verification
+/// left it, e.g., a solo machine client at its next sequence.
+fn store_verified_client_state<Ctx>(
+    ctx: &mut Ctx,
+    client_state: Option<(ClientId, AnyClientState)>,
+) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    if let Some((client_id, client_state)) = client_state {
+        ctx.store_client_state(client_id, client_state)
+            .map_err(|e| Kind::KeeperRaisedError.context(e))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the application module which owns port `port_id`.
+fn lookup_module<Ctx>(ctx: &Ctx, port_id: &PortId) -> Result<Arc<dyn Module<Ctx>>, Error>
+where
+    Ctx: Ics26Context,
+{
+    ctx.router()
+        .get_route(port_id)
+        .ok_or_else(|| Kind::ModuleNotFound(port_id.clone()).into())
+}
+
+/// Hands the packet which an application sent, as processed by the ICS4 handler into `events`,
+/// to the module owning the source port of the packet, so that the middleware stacked over the
+/// application gets a chance to veto the packet before it is committed to the host chain store.
+fn send_packet_callback<Ctx>(ctx: &mut Ctx, events: &[IbcEvent]) -> Result<(), Error>
+where
+    Ctx: Ics26Context,
+{
+    for event in events {
+        if let IbcEvent::SendPacket(send_packet) = event {
+            let packet = &send_packet.packet;
+            if let Some(module) = ctx.router().get_route(&packet.source_port) {
+                module.on_send_packet(ctx, packet)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the port on the host chain which the channel handshake message `msg` refers to.
+fn channel_msg_port_id(msg: &ChannelMsg) -> &PortId {
+    match msg {
+        ChannelMsg::ChannelOpenInit(msg) => msg.port_id(),
+        ChannelMsg::ChannelOpenTry(msg) => msg.port_id(),
+        ChannelMsg::ChannelOpenAck(msg) => msg.port_id(),
+        ChannelMsg::ChannelOpenConfirm(msg) => msg.port_id(),
+        ChannelMsg::ChannelCloseInit(msg) => msg.port_id(),
+        ChannelMsg::ChannelCloseConfirm(msg) => msg.port_id(),
+        ChannelMsg::ChannelUpgradeInit(msg) => msg.port_id(),
+        ChannelMsg::ChannelUpgradeTry(msg) => msg.port_id(),
+        ChannelMsg::ChannelUpgradeAck(msg) => msg.port_id(),
+        ChannelMsg::ChannelUpgradeConfirm(msg) => msg.port_id(),
+        ChannelMsg::ChannelUpgradeCancel(msg) => msg.port_id(),
+    }
+}
+
+/// Returns the capability which the IBC module owns for the object that the channel handshake
+/// message `msg` acts upon: the port for the `ChanOpenInit` and `ChanOpenTry` steps, which create
+/// the channel end, and the channel end itself for the other steps.
+fn channel_msg_capability<Ctx>(ctx: &Ctx, msg: &ChannelMsg) -> Result<Capability, Error>
+where
+    Ctx: Ics26Context,
+{
+    let channel_id = match msg {
+        ChannelMsg::ChannelOpenInit(msg) => return port_capability(ctx, msg.port_id()),
+        ChannelMsg::ChannelOpenTry(msg) => return port_capability(ctx, msg.port_id()),
+        ChannelMsg::ChannelOpenAck(msg) => msg.channel_id(),
+        ChannelMsg::ChannelOpenConfirm(msg) => msg.channel_id(),
+        ChannelMsg::ChannelCloseInit(msg) => msg.channel_id(),
+        ChannelMsg::ChannelCloseConfirm(msg) => msg.channel_id(),
+        ChannelMsg::ChannelUpgradeInit(msg) => msg.channel_id(),
+        ChannelMsg::ChannelUpgradeTry(msg) => msg.channel_id(),
+        ChannelMsg::ChannelUpgradeAck(msg) => msg.channel_id(),
+        ChannelMsg::ChannelUpgradeConfirm(msg) => msg.channel_id(),
+        ChannelMsg::ChannelUpgradeCancel(msg) => msg.channel_id(),
+    };
+
+    channel_capability(ctx, channel_msg_port_id(msg), channel_id)
+}
+
+/// Returns the capability which the IBC module owns for the port `port_id`.
+fn port_capability<Ctx>(ctx: &Ctx, port_id: &PortId) -> Result<Capability, Error>
+where
+    Ctx: Ics26Context,
+{
+    ctx.lookup_module_by_port(port_id)
+        .ok_or_else(|| Kind::PortCapabilityNotFound(port_id.clone()).into())
+}
+
+/// Returns the capability which the IBC module owns for the channel end identified by `port_id`
+/// and `channel_id`.
+fn channel_capability<Ctx>(
+    ctx: &Ctx,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<Capability, Error>
+where
+    Ctx: Ics26Context,
+{
+    ctx.lookup_module_by_channel(port_id, channel_id)
+        .ok_or_else(|| Kind::ChannelCapabilityNotFound(port_id.clone(), channel_id.clone()).into())
+}
+
+/// Returns the channel end on the host chain which the packet message `msg` refers to: the
+/// destination channel end for packets received by the host chain, and the source channel end
+/// for packets it sent.
+fn packet_msg_port_channel_id(msg: &PacketMsg) -> (&PortId, &ChannelId) {
+    let packet = match msg {
+        PacketMsg::RecvPacket(msg) => {
+            return (
+                &msg.packet.destination_port,
+                &msg.packet.destination_channel,
+            )
+        }
+        PacketMsg::AckPacket(msg) => &msg.packet,
+        PacketMsg::ToPacket(msg) => &msg.packet,
+        PacketMsg::ToClosePacket(msg) => &msg.packet,
+    };
+
+    (&packet.source_port, &packet.source_channel)
+}
+
+/// Invokes the callback of `module` which corresponds to the channel handshake message `msg`,
+/// processed by the ICS4 handler into `result`. The version which the module picks during the
+/// `ChanOpenTry` step is recorded in the channel end of `result`. The module is handed
+/// `channel_cap`, the capability of the channel end, when the channel end is opened.
+fn channel_callback<Ctx>(
+    ctx: &mut Ctx,
+    module: &dyn Module<Ctx>,
+    msg: &ChannelMsg,
+    channel_cap: &Capability,
+    result: &mut ChannelResult,
+) -> Result<(), Error> {
+    match msg {
+        ChannelMsg::ChannelOpenInit(_) => module.on_chan_open_init(
+            ctx,
+            &result.port_id,
+            &result.channel_id,
+            channel_cap,
+            &result.channel_end,
+        )?,
+        ChannelMsg::ChannelOpenTry(msg) => {
+            let version = module.on_chan_open_try(
+                ctx,
+                &result.port_id,
+                &result.channel_id,
+                channel_cap,
+                &result.channel_end,
+                msg.counterparty_version(),
+            )?;
+            result.channel_end.set_version(version);
+        }
+        ChannelMsg::ChannelOpenAck(msg) => module.on_chan_open_ack(
+            ctx,
+            &result.port_id,
+            &result.channel_id,
+            msg.counterparty_version(),
+        )?,
+        ChannelMsg::ChannelOpenConfirm(_) => {
+            module.on_chan_open_confirm(ctx, &result.port_id, &result.channel_id)?
+        }
+        ChannelMsg::ChannelCloseInit(_) => {
+            module.on_chan_close_init(ctx, &result.port_id, &result.channel_id)?
+        }
+        ChannelMsg::ChannelCloseConfirm(_) => {
+            module.on_chan_close_confirm(ctx, &result.port_id, &result.channel_id)?
+        }
+        ChannelMsg::ChannelUpgradeInit(msg) => module.on_chan_upgrade_init(
+            ctx,
+            &result.port_id,
+            &result.channel_id,
+            msg.proposed_upgrade_channel(),
+        )?,
+        ChannelMsg::ChannelUpgradeTry(msg) => module.on_chan_upgrade_try(
+            ctx,
+            &result.port_id,
+            &result.channel_id,
+            msg.proposed_upgrade_channel(),
+        )?,
+        ChannelMsg::ChannelUpgradeAck(_) => {
+            module.on_chan_upgrade_ack(ctx, &result.port_id, &result.channel_id)?
+        }
+        ChannelMsg::ChannelUpgradeConfirm(_) => {
+            module.on_chan_upgrade_confirm(ctx, &result.port_id, &result.channel_id)?
+        }
+        ChannelMsg::ChannelUpgradeCancel(_) => {
+            module.on_chan_upgrade_cancel(ctx, &result.port_id, &result.channel_id)?
+        }
+    }
+
+    Ok(())
+}
+
+/// Invokes the callback of `module` which corresponds to the packet message `msg`. The result of
+/// the output is the acknowledgement which the module produced synchronously for a received
+/// packet, if any.
+fn packet_callback<Ctx>(
+    ctx: &mut Ctx,
+    module: &dyn Module<Ctx>,
+    msg: &PacketMsg,
+) -> Result<HandlerOutput<Option<Vec<u8>>>, Error> {
+    let output = match msg {
+        PacketMsg::RecvPacket(msg) => {
+            return Ok(module.on_recv_packet(ctx, &msg.packet, &msg.signer));
+        }
+        PacketMsg::AckPacket(msg) => module.on_acknowledgement_packet(
+            ctx,
+            &msg.packet,
+            msg.acknowledgement(),
+            &msg.signer,
+        )?,
+        PacketMsg::ToPacket(msg) => module.on_timeout_packet(ctx, &msg.packet, &msg.signer)?,
+        PacketMsg::ToClosePacket(msg) => module.on_timeout_packet(ctx, &msg.packet, &msg.signer)?,
+    };
+
+    Ok(HandlerOutput::builder()
+        .with_log(output.log)
+        .with_events(output.events)
+        .with_result(None))
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
 
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+    use crate::application::ics20_fungible_token_transfer::denom::prefix_denom;
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer;
     use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics20_fungible_token_transfer::{MODULE_ID, VERSION};
+    use crate::application::ics27_interchain_accounts::account::controller_port_id;
+    use crate::application::ics27_interchain_accounts::controller::module::Ics27ControllerModule;
+    use crate::application::ics27_interchain_accounts::msgs::register_interchain_account::{
+        self, test_util::get_dummy_raw_msg_register_interchain_account,
+        MsgRegisterInterchainAccount,
+    };
+    use crate::application::ics27_interchain_accounts::msgs::send_tx::{
+        self, test_util::get_dummy_raw_msg_send_tx, MsgSendTx,
+    };
+    use crate::application::ics27_interchain_accounts::{
+        CONTROLLER_MODULE_ID, CONTROLLER_PORT_PREFIX,
+    };
+    use crate::application::ics29_fee::acknowledgement::IncentivizedAcknowledgement;
+    use crate::application::ics29_fee::context::Ics29Context;
+    use crate::application::ics29_fee::handler::test_util::get_dummy_fee_context;
+    use crate::application::ics29_fee::module::FeeModule;
+    use crate::application::ics29_fee::msgs::pay_packet_fee::{
+        self, test_util::get_dummy_raw_msg_pay_packet_fee, MsgPayPacketFee,
+    };
+    use crate::application::ics29_fee::msgs::register_counterparty_payee::{
+        self, test_util::get_dummy_raw_msg_register_counterparty_payee,
+        MsgRegisterCounterpartyPayee,
+    };
+    use crate::application::ics721_nft_transfer::class::prefix_class_id;
+    use crate::application::ics721_nft_transfer::context::{Ics721Context, NftKeeper};
+    use crate::application::ics721_nft_transfer::module::Ics721Module;
+    use crate::application::ics721_nft_transfer::msgs::transfer::{
+        self as nft_transfer, test_util::get_dummy_msg_nft_transfer,
+    };
+    use crate::application::ics721_nft_transfer::relay_application_logic::test_util::get_dummy_nft_transfer_context;
+    use crate::application::ics721_nft_transfer::MODULE_ID as NFT_MODULE_ID;
     use crate::events::IbcEvent;
     use crate::ics02_client::client_consensus::AnyConsensusState;
     use crate::ics02_client::client_state::AnyClientState;
@@ -254,7 +674,10 @@ mod tests {
         conn_open_try::{test_util::get_dummy_raw_msg_conn_open_try, MsgConnectionOpenTry},
         ConnectionMsg,
     };
+    use crate::ics04_channel::channel::Order;
+    use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
     use crate::ics04_channel::msgs::{
+        acknowledgement::{test_util::get_dummy_raw_msg_acknowledgement, MsgAcknowledgement},
         chan_close_confirm::{
             test_util::get_dummy_raw_msg_chan_close_confirm, MsgChannelCloseConfirm,
         },
@@ -267,13 +690,18 @@ mod tests {
         ChannelMsg, PacketMsg,
     };
 
-    use crate::ics24_host::identifier::ConnectionId;
+    use crate::ics05_port::capabilities::{CapabilityName, ModuleId};
+    use crate::ics05_port::context::PortReader;
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
     use crate::ics26_routing::handler::dispatch;
     use crate::ics26_routing::msgs::Ics26Envelope;
     use crate::mock::client_state::{MockClientState, MockConsensusState};
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
+    use crate::mock::module::MockModule;
+    use crate::signer::Signer;
     use crate::test_utils::get_dummy_account_id;
+    use crate::tx_msg::Msg;
     use crate::Height;
 
     #[test]
@@ -299,9 +727,15 @@ mod tests {
         // We reuse this same context across all tests. Nothing in particular needs parametrizing.
         let mut ctx = MockContext::default();
 
+        // The transfers below escrow tokens of the sender.
+        ctx.mint_coins(&default_signer, "uatom", Amount::from(100))
+            .unwrap();
+
         let create_client_msg = MsgCreateAnyClient::new(
-            AnyClientState::from(MockClientState(MockHeader::new(start_client_height))),
-            AnyConsensusState::from(MockConsensusState(MockHeader::new(start_client_height))),
+            AnyClientState::from(MockClientState::new(MockHeader::new(start_client_height))),
+            AnyConsensusState::from(MockConsensusState::new(MockHeader::new(
+                start_client_height,
+            ))),
             default_signer.clone(),
         )
         .unwrap();
@@ -362,6 +796,13 @@ mod tests {
         msg_to_on_close.packet.sequence = 2.into();
         msg_to_on_close.packet.timeout_height = msg_transfer_two.timeout_height;
         msg_to_on_close.packet.timeout_timestamp = msg_transfer_two.timeout_timestamp;
+        msg_to_on_close.packet.data = FungibleTokenPacketData {
+            amount: Amount::from(10),
+            denom: "uatom".to_string(),
+            receiver: msg_transfer_two.receiver.clone(),
+            sender: msg_transfer_two.sender.clone(),
+        }
+        .to_json_bytes();
 
         let msg_recv_packet = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(35)).unwrap();
 
@@ -380,6 +821,8 @@ mod tests {
         );
 
         ctx.add_port(msg_chan_init.port_id().clone());
+        ctx.add_route(msg_chan_init.port_id().clone(), MockModule)
+            .unwrap();
 
         // Figure out the ID of the client that was just created.
         let mut events = res.unwrap().events;
@@ -465,10 +908,39 @@ mod tests {
                 msg: Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenAck(msg_chan_ack)),
                 want_pass: true,
             },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let res = dispatch(&mut ctx, test.msg.clone());
+
+            assert_eq!(
+                test.want_pass,
+                res.is_ok(),
+                "ICS26 routing dispatch test '{}' failed for message {:?}\nwith result: {:?}",
+                test.name,
+                test.msg,
+                res
+            );
+        }
+
+        // The ICS20 module sends packets over the channel which the mock module opened, so it
+        // claims the capability of this channel end, as it would upon opening the channel itself.
+        let mut ctx = ctx.with_channel_capability_claimed_by(
+            ModuleId::new(MODULE_ID),
+            PortId::default(),
+            ChannelId::default(),
+        );
+        let scope = ctx.scope_to_module(MODULE_ID).unwrap();
+        ctx.add_msg_route(transfer::TYPE_URL, Ics20Module::new(scope))
+            .unwrap();
+
+        let tests: Vec<Test> = vec![
             //ICS20-04-packet
             Test {
                 name: "Packet send".to_string(),
-                msg: Ics26Envelope::Ics20Msg(msg_transfer),
+                msg: Ics26Envelope::AppMsg(msg_transfer.to_any()),
                 want_pass: true,
             },
             // The client update is required in this test, because the proof associated with
@@ -494,7 +966,7 @@ mod tests {
             },
             Test {
                 name: "Packet send".to_string(),
-                msg: Ics26Envelope::Ics20Msg(msg_transfer_two),
+                msg: Ics26Envelope::AppMsg(msg_transfer_two.to_any()),
                 want_pass: true,
             },
             Test {
@@ -544,4 +1016,408 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    /// Checks that the routing module hands channel handshakes to the application module which
+    /// owns the port, and that nothing is written to the store when the module vetoes a handshake.
+    fn application_module_callbacks() {
+        let mut ctx = get_dummy_transfer_context();
+        // The dummy context already holds the channel end `channel-0`.
+        ctx.increase_channel_counter();
+        let mut msg_chan_init =
+            MsgChannelOpenInit::try_from(get_dummy_raw_msg_chan_open_init()).unwrap();
+
+        // No module owns the port yet.
+        let res = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init.clone())),
+        );
+        assert!(res.is_err());
+
+        let scope = ctx.scope_to_module(MODULE_ID).unwrap();
+        ctx.add_route(PortId::default(), Ics20Module::new(scope.clone()))
+            .unwrap();
+        assert!(ctx.add_route(PortId::default(), MockModule).is_err());
+
+        // The ICS20 module rejects the version of the dummy channel.
+        let res = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init.clone())),
+        );
+        assert!(res.is_err());
+        assert_eq!(ctx.channel_counter(), 1);
+        assert!(ctx
+            .lookup_module_by_channel(&PortId::default(), &ChannelId::new(1))
+            .is_none());
+
+        msg_chan_init.channel.set_version(VERSION.to_string());
+        let res = dispatch(
+            &mut ctx,
+            Ics26Envelope::Ics4ChannelMsg(ChannelMsg::ChannelOpenInit(msg_chan_init)),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert_eq!(ctx.channel_counter(), 2);
+
+        // The ICS20 module claimed the capability of the new channel end.
+        let channel_cap = ctx.lookup_module_by_channel(&PortId::default(), &ChannelId::new(1));
+        assert!(channel_cap.is_some());
+        assert_eq!(
+            scope.get_capability(
+                &ctx,
+                &CapabilityName::channel(&PortId::default(), &ChannelId::new(1))
+            ),
+            channel_cap
+        );
+    }
+
+    #[test]
+    /// Relays an ICS20 transfer between two mock chains: the packet sent by chain A is received by
+    /// chain B, which mints vouchers and acknowledges the packet in the same transaction, and the
+    /// acknowledgement is then relayed back to chain A.
+    fn ics20_round_trip() {
+        let client_height = Height::new(0, 5);
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+        let sender = get_dummy_account_id();
+
+        let mut ctx_a =
+            get_dummy_transfer_context().with_client(&ClientId::default(), client_height);
+        let mut ctx_b =
+            get_dummy_transfer_context().with_client(&ClientId::default(), client_height);
+        let module_a = Ics20Module::new(ctx_a.scope_to_module(MODULE_ID).unwrap());
+        let module_b = Ics20Module::new(ctx_b.scope_to_module(MODULE_ID).unwrap());
+        ctx_a.add_route(port_id.clone(), module_a.clone()).unwrap();
+        ctx_a.add_msg_route(transfer::TYPE_URL, module_a).unwrap();
+        ctx_b.add_route(port_id.clone(), module_b).unwrap();
+        ctx_a
+            .mint_coins(&sender, "uatom", Amount::from(100))
+            .unwrap();
+
+        // Chain A sends the packet.
+        let output = dispatch(
+            &mut ctx_a,
+            Ics26Envelope::AppMsg(get_dummy_msg_transfer(35).to_any()),
+        )
+        .unwrap();
+        let packet = output
+            .events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(send_packet) => Some(send_packet.packet.clone()),
+                _ => None,
+            })
+            .unwrap();
+
+        // Chain B receives the packet and writes the acknowledgement.
+        let mut msg_recv_packet =
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(client_height.revision_height))
+                .unwrap();
+        msg_recv_packet.packet = packet.clone();
+
+        let output = dispatch(
+            &mut ctx_b,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet)),
+        )
+        .unwrap();
+        assert!(matches!(
+            output.events.as_slice(),
+            [
+                IbcEvent::ReceivePacket(_),
+                IbcEvent::FungibleTokenPacket(_),
+                IbcEvent::WriteAcknowledgement(_),
+            ]
+        ));
+        let ack = match &output.events[2] {
+            IbcEvent::WriteAcknowledgement(write_ack) => write_ack.ack.clone(),
+            _ => unreachable!(),
+        };
+        assert!(Acknowledgement::from_json_bytes(&ack)
+            .unwrap()
+            .is_successful());
+        assert!(ctx_b
+            .get_packet_acknowledgement(&(port_id.clone(), channel_id.clone(), packet.sequence))
+            .is_some());
+        let voucher = prefix_denom(&port_id, &channel_id, "uatom");
+        assert_eq!(ctx_b.balance(&sender, &voucher), Amount::from(10));
+
+        // Chain A processes the acknowledgement.
+        let mut msg_ack_packet = MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(
+            client_height.revision_height,
+        ))
+        .unwrap();
+        msg_ack_packet.packet = packet.clone();
+        msg_ack_packet.acknowledgement = ack;
+
+        let output = dispatch(
+            &mut ctx_a,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(msg_ack_packet)),
+        )
+        .unwrap();
+        assert!(matches!(
+            output.events.as_slice(),
+            [
+                IbcEvent::AcknowledgePacket(_),
+                IbcEvent::FungibleTokenPacket(_),
+            ]
+        ));
+        assert!(ctx_a
+            .get_packet_commitment(&(port_id.clone(), channel_id.clone(), packet.sequence))
+            .is_none());
+        assert_eq!(ctx_a.balance(&sender, "uatom"), Amount::from(90));
+        assert_eq!(
+            ctx_a.balance(
+                &ctx_a.get_channel_escrow_address(&port_id, &channel_id),
+                "uatom"
+            ),
+            Amount::from(10)
+        );
+    }
+
+    #[test]
+    /// Relays an ICS721 transfer between two mock chains: chain A escrows the NFT, chain B mints a
+    /// voucher for it with the URIs carried by the packet, and the acknowledgement is then relayed
+    /// back to chain A.
+    fn ics721_round_trip() {
+        let client_height = Height::new(0, 5);
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+        let sender = get_dummy_account_id();
+
+        let mut ctx_a =
+            get_dummy_nft_transfer_context().with_client(&ClientId::default(), client_height);
+        let mut ctx_b =
+            get_dummy_nft_transfer_context().with_client(&ClientId::default(), client_height);
+        let module_a = Ics721Module::new(ctx_a.scope_to_module(NFT_MODULE_ID).unwrap());
+        let module_b = Ics721Module::new(ctx_b.scope_to_module(NFT_MODULE_ID).unwrap());
+        ctx_a.add_route(port_id.clone(), module_a.clone()).unwrap();
+        ctx_a
+            .add_msg_route(nft_transfer::TYPE_URL, module_a)
+            .unwrap();
+        ctx_b.add_route(port_id.clone(), module_b).unwrap();
+
+        // Chain A sends the packet.
+        let output = dispatch(
+            &mut ctx_a,
+            Ics26Envelope::AppMsg(get_dummy_msg_nft_transfer(35).to_any()),
+        )
+        .unwrap();
+        let packet = output
+            .events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(send_packet) => Some(send_packet.packet.clone()),
+                _ => None,
+            })
+            .unwrap();
+
+        // Chain B receives the packet and writes the acknowledgement.
+        let mut msg_recv_packet =
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(client_height.revision_height))
+                .unwrap();
+        msg_recv_packet.packet = packet.clone();
+
+        let output = dispatch(
+            &mut ctx_b,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet)),
+        )
+        .unwrap();
+        assert!(matches!(
+            output.events.as_slice(),
+            [
+                IbcEvent::ReceivePacket(_),
+                IbcEvent::NonFungibleTokenPacket(_),
+                IbcEvent::WriteAcknowledgement(_),
+            ]
+        ));
+        let ack = match &output.events[2] {
+            IbcEvent::WriteAcknowledgement(write_ack) => write_ack.ack.clone(),
+            _ => unreachable!(),
+        };
+        assert!(Acknowledgement::from_json_bytes(&ack)
+            .unwrap()
+            .is_successful());
+        let voucher_class = prefix_class_id(&port_id, &channel_id, "kitties");
+        assert_eq!(
+            ctx_b.get_owner(&voucher_class, "kitty-1"),
+            Some(sender.clone())
+        );
+        assert_eq!(
+            ctx_b.get_class_uri(&voucher_class),
+            Some("https://kitties.example/class".to_string())
+        );
+        assert_eq!(
+            ctx_b.get_token_uri(&voucher_class, "kitty-1"),
+            Some("ipfs://kitty-1".to_string())
+        );
+
+        // Chain A processes the acknowledgement.
+        let mut msg_ack_packet = MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(
+            client_height.revision_height,
+        ))
+        .unwrap();
+        msg_ack_packet.packet = packet.clone();
+        msg_ack_packet.acknowledgement = ack;
+
+        let output = dispatch(
+            &mut ctx_a,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(msg_ack_packet)),
+        )
+        .unwrap();
+        assert!(matches!(
+            output.events.as_slice(),
+            [
+                IbcEvent::AcknowledgePacket(_),
+                IbcEvent::NonFungibleTokenPacket(_),
+            ]
+        ));
+        assert!(ctx_a
+            .get_packet_commitment(&(port_id.clone(), channel_id.clone(), packet.sequence))
+            .is_none());
+        assert_eq!(
+            ctx_a.get_owner("kitties", "kitty-1"),
+            Some(ctx_a.get_nft_escrow_address(&port_id, &channel_id))
+        );
+    }
+
+    #[test]
+    /// Relays an ICS20 transfer over a fee-enabled channel: chain A escrows the fee paid for the
+    /// packet, and pays it out to the relayers once the incentivized acknowledgement written by
+    /// chain B is relayed back.
+    fn incentivized_ics20_round_trip() {
+        let client_height = Height::new(0, 5);
+        let port_id = PortId::default();
+        let channel_id = ChannelId::default();
+        let sender = get_dummy_account_id();
+
+        let mut ctx_a = get_dummy_fee_context().with_client(&ClientId::default(), client_height);
+        let mut ctx_b = get_dummy_fee_context().with_client(&ClientId::default(), client_height);
+        let module_a = FeeModule::new(Ics20Module::new(ctx_a.scope_to_module(MODULE_ID).unwrap()));
+        let module_b = FeeModule::new(Ics20Module::new(ctx_b.scope_to_module(MODULE_ID).unwrap()));
+        ctx_a.add_route(port_id.clone(), module_a.clone()).unwrap();
+        ctx_a
+            .add_msg_route(pay_packet_fee::TYPE_URL, module_a.clone())
+            .unwrap();
+        ctx_a.add_msg_route(transfer::TYPE_URL, module_a).unwrap();
+        ctx_b.add_route(port_id.clone(), module_b.clone()).unwrap();
+        ctx_b
+            .add_msg_route(register_counterparty_payee::TYPE_URL, module_b)
+            .unwrap();
+
+        // The relayer registers on chain B the address where it is paid on chain A.
+        let msg_register_payee =
+            MsgRegisterCounterpartyPayee::try_from(get_dummy_raw_msg_register_counterparty_payee())
+                .unwrap();
+        let payee = msg_register_payee.counterparty_payee.clone();
+        dispatch(
+            &mut ctx_b,
+            Ics26Envelope::AppMsg(msg_register_payee.to_any()),
+        )
+        .unwrap();
+
+        // Chain A escrows the fee, and sends the packet.
+        let msg_pay_fee = MsgPayPacketFee::try_from(get_dummy_raw_msg_pay_packet_fee()).unwrap();
+        dispatch(&mut ctx_a, Ics26Envelope::AppMsg(msg_pay_fee.to_any())).unwrap();
+        let output = dispatch(
+            &mut ctx_a,
+            Ics26Envelope::AppMsg(get_dummy_msg_transfer(35).to_any()),
+        )
+        .unwrap();
+        let packet = output
+            .events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(send_packet) => Some(send_packet.packet.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(ctx_a.balance(&sender, "uatom"), Amount::from(30));
+
+        // Chain B receives the packet and writes the incentivized acknowledgement.
+        let mut msg_recv_packet =
+            MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(client_height.revision_height))
+                .unwrap();
+        msg_recv_packet.packet = packet.clone();
+
+        let output = dispatch(
+            &mut ctx_b,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::RecvPacket(msg_recv_packet)),
+        )
+        .unwrap();
+        let ack = output
+            .events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::WriteAcknowledgement(write_ack) => Some(write_ack.ack.clone()),
+                _ => None,
+            })
+            .unwrap();
+        let incentivized_ack = IncentivizedAcknowledgement::from_json_bytes(&ack).unwrap();
+        assert!(incentivized_ack.underlying_app_success);
+        assert_eq!(incentivized_ack.forward_relayer_address, payee.to_string());
+
+        // Chain A processes the acknowledgement, and pays the relayers.
+        let relayer = Signer::new("relayer");
+        let mut msg_ack_packet = MsgAcknowledgement::try_from(get_dummy_raw_msg_acknowledgement(
+            client_height.revision_height,
+        ))
+        .unwrap();
+        msg_ack_packet.packet = packet;
+        msg_ack_packet.acknowledgement = ack;
+        msg_ack_packet.signer = relayer.clone();
+
+        dispatch(
+            &mut ctx_a,
+            Ics26Envelope::Ics4PacketMsg(PacketMsg::AckPacket(msg_ack_packet)),
+        )
+        .unwrap();
+        assert_eq!(ctx_a.balance(&payee, "uatom"), Amount::from(10));
+        assert_eq!(ctx_a.balance(&relayer, "uatom"), Amount::from(20));
+        assert_eq!(ctx_a.balance(&sender, "uatom"), Amount::from(60));
+        assert_eq!(
+            ctx_a.balance(&ctx_a.get_fee_escrow_address(), "uatom"),
+            Amount::from(0)
+        );
+    }
+
+    #[test]
+    /// Registers an interchain account through the routing module: the controller port of the
+    /// owner is bound on the fly, and routed to the ICS27 controller module by its prefix.
+    fn ics27_account_registration() {
+        let msg =
+            MsgRegisterInterchainAccount::try_from(get_dummy_raw_msg_register_interchain_account())
+                .unwrap();
+        let port_id = controller_port_id(&msg.owner).unwrap();
+        let mut ctx = get_dummy_transfer_context();
+
+        // No module processes the messages of the controller module yet.
+        let res = dispatch(&mut ctx, Ics26Envelope::AppMsg(msg.clone().to_any()));
+        assert!(res.is_err());
+
+        // No module owns the controller ports yet.
+        let module = Ics27ControllerModule::new(ctx.scope_to_module(CONTROLLER_MODULE_ID).unwrap());
+        ctx.add_msg_route(register_interchain_account::TYPE_URL, module.clone())
+            .unwrap();
+        ctx.add_msg_route(send_tx::TYPE_URL, module.clone())
+            .unwrap();
+        let res = dispatch(&mut ctx, Ics26Envelope::AppMsg(msg.clone().to_any()));
+        assert!(res.is_err());
+
+        ctx.add_prefix_route(CONTROLLER_PORT_PREFIX, module)
+            .unwrap();
+        let output = dispatch(&mut ctx, Ics26Envelope::AppMsg(msg.to_any())).unwrap();
+        assert!(matches!(
+            output.events.as_slice(),
+            [IbcEvent::OpenInitChannel(_)]
+        ));
+        let channel_end = ctx.channel_end(&(port_id, ChannelId::new(0))).unwrap();
+        assert_eq!(channel_end.ordering(), &Order::Ordered);
+
+        // The owner cannot send transactions before the handshake completes.
+        let msg_send_tx = MsgSendTx::try_from(get_dummy_raw_msg_send_tx(&Signer::new(
+            "cosmos1interchainaccount",
+        )))
+        .unwrap();
+        let res = dispatch(&mut ctx, Ics26Envelope::AppMsg(msg_send_tx.to_any()));
+        assert!(res.is_err());
+    }
 }
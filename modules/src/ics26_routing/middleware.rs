@@ -0,0 +1,624 @@
+//! Support for stacking middleware over the application module bound to a port, so that the
+//! packets of an application can be rate limited, incentivized, forwarded, etc. without changing
+//! the application itself nor the routing module.
+
+use prost_types::Any;
+
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::Error;
+use crate::ics26_routing::module::{Module, MsgResult};
+use crate::signer::Signer;
+
+/// A layer of middleware, which intercepts the callbacks of the routing module before they reach
+/// the rest of the stack, `next`. Each callback defaults to forwarding the call to `next`
+/// unchanged, so middleware only implements the callbacks it is interested in. A callback may
+/// alter the arguments it forwards or the result it returns, or not forward the call at all,
+/// e.g., to reject a channel handshake or to acknowledge a packet with an error.
+pub trait Middleware<Ctx>: Send + Sync {
+    fn on_chan_open_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_open_init(ctx, port_id, channel_id, channel_cap, channel_end)
+    }
+
+    fn on_chan_open_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+        next: &dyn Module<Ctx>,
+    ) -> Result<String, Error> {
+        next.on_chan_open_try(
+            ctx,
+            port_id,
+            channel_id,
+            channel_cap,
+            channel_end,
+            counterparty_version,
+        )
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &str,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_open_ack(ctx, port_id, channel_id, counterparty_version)
+    }
+
+    fn on_chan_open_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_open_confirm(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_close_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_close_init(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_close_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_close_confirm(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_upgrade_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_upgrade_init(ctx, port_id, channel_id, proposed_channel_end)
+    }
+
+    fn on_chan_upgrade_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_upgrade_try(ctx, port_id, channel_id, proposed_channel_end)
+    }
+
+    fn on_chan_upgrade_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_upgrade_ack(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_upgrade_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_upgrade_confirm(ctx, port_id, channel_id)
+    }
+
+    fn on_chan_upgrade_cancel(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_chan_upgrade_cancel(ctx, port_id, channel_id)
+    }
+
+    fn on_send_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        next: &dyn Module<Ctx>,
+    ) -> Result<(), Error> {
+        next.on_send_packet(ctx, packet)
+    }
+
+    fn on_recv_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+        next: &dyn Module<Ctx>,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        next.on_recv_packet(ctx, packet, relayer)
+    }
+
+    fn on_acknowledgement_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        acknowledgement: &[u8],
+        relayer: &Signer,
+        next: &dyn Module<Ctx>,
+    ) -> Result<HandlerOutput<()>, Error> {
+        next.on_acknowledgement_packet(ctx, packet, acknowledgement, relayer)
+    }
+
+    fn on_timeout_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+        next: &dyn Module<Ctx>,
+    ) -> Result<HandlerOutput<()>, Error> {
+        next.on_timeout_packet(ctx, packet, relayer)
+    }
+
+    fn on_msg(
+        &self,
+        ctx: &mut Ctx,
+        msg: &Any,
+        next: &dyn Module<Ctx>,
+    ) -> Result<HandlerOutput<MsgResult>, Error> {
+        next.on_msg(ctx, msg)
+    }
+}
+
+/// A module made of the middleware `middleware` stacked over the module `next`, which is either
+/// an application module or another stack. Stacks nest, the outermost middleware first, e.g.,
+/// `Stack::new(rate_limit, Stack::new(forward, Ics20Module::new(scope)))` is routed like any
+/// other module, and hands each callback to `rate_limit`, then to `forward`, and finally to the
+/// ICS20 module.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stack<W, M> {
+    middleware: W,
+    next: M,
+}
+
+impl<W, M> Stack<W, M> {
+    pub fn new(middleware: W, next: M) -> Self {
+        Stack { middleware, next }
+    }
+}
+
+impl<Ctx, W, M> Module<Ctx> for Stack<W, M>
+where
+    W: Middleware<Ctx>,
+    M: Module<Ctx>,
+{
+    fn on_chan_open_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        self.middleware.on_chan_open_init(
+            ctx,
+            port_id,
+            channel_id,
+            channel_cap,
+            channel_end,
+            &self.next,
+        )
+    }
+
+    fn on_chan_open_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+    ) -> Result<String, Error> {
+        self.middleware.on_chan_open_try(
+            ctx,
+            port_id,
+            channel_id,
+            channel_cap,
+            channel_end,
+            counterparty_version,
+            &self.next,
+        )
+    }
+
+    fn on_chan_open_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty_version: &str,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_open_ack(ctx, port_id, channel_id, counterparty_version, &self.next)
+    }
+
+    fn on_chan_open_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_open_confirm(ctx, port_id, channel_id, &self.next)
+    }
+
+    fn on_chan_close_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_close_init(ctx, port_id, channel_id, &self.next)
+    }
+
+    fn on_chan_close_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_close_confirm(ctx, port_id, channel_id, &self.next)
+    }
+
+    fn on_chan_upgrade_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        self.middleware.on_chan_upgrade_init(
+            ctx,
+            port_id,
+            channel_id,
+            proposed_channel_end,
+            &self.next,
+        )
+    }
+
+    fn on_chan_upgrade_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        self.middleware.on_chan_upgrade_try(
+            ctx,
+            port_id,
+            channel_id,
+            proposed_channel_end,
+            &self.next,
+        )
+    }
+
+    fn on_chan_upgrade_ack(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_upgrade_ack(ctx, port_id, channel_id, &self.next)
+    }
+
+    fn on_chan_upgrade_confirm(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_upgrade_confirm(ctx, port_id, channel_id, &self.next)
+    }
+
+    fn on_chan_upgrade_cancel(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        self.middleware
+            .on_chan_upgrade_cancel(ctx, port_id, channel_id, &self.next)
+    }
+
+    fn on_send_packet(&self, ctx: &mut Ctx, packet: &Packet) -> Result<(), Error> {
+        self.middleware.on_send_packet(ctx, packet, &self.next)
+    }
+
+    fn on_recv_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        self.middleware
+            .on_recv_packet(ctx, packet, relayer, &self.next)
+    }
+
+    fn on_acknowledgement_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        acknowledgement: &[u8],
+        relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Error> {
+        self.middleware
+            .on_acknowledgement_packet(ctx, packet, acknowledgement, relayer, &self.next)
+    }
+
+    fn on_timeout_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Error> {
+        self.middleware
+            .on_timeout_packet(ctx, packet, relayer, &self.next)
+    }
+
+    fn on_msg(&self, ctx: &mut Ctx, msg: &Any) -> Result<HandlerOutput<MsgResult>, Error> {
+        self.middleware.on_msg(ctx, msg, &self.next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+    use std::sync::{Arc, Mutex};
+
+    use prost_types::Any;
+
+    use crate::application::ics20_fungible_token_transfer::acknowledgement::Acknowledgement;
+    use crate::application::ics20_fungible_token_transfer::amount::Amount;
+    use crate::application::ics20_fungible_token_transfer::context::BankKeeper;
+    use crate::application::ics20_fungible_token_transfer::module::Ics20Module;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer;
+    use crate::application::ics20_fungible_token_transfer::msgs::transfer::test_util::get_dummy_msg_transfer;
+    use crate::application::ics20_fungible_token_transfer::packet::FungibleTokenPacketData;
+    use crate::application::ics20_fungible_token_transfer::relay_application_logic::test_util::get_dummy_transfer_context;
+    use crate::application::ics20_fungible_token_transfer::{MODULE_ID, VERSION};
+    use crate::handler::HandlerOutput;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::packet::test_utils::get_dummy_raw_packet;
+    use crate::ics04_channel::packet::Packet;
+    use crate::ics05_port::context::PortReader;
+    use crate::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::ics26_routing::error::{Error, Kind};
+    use crate::ics26_routing::handler::deliver;
+    use crate::ics26_routing::middleware::{Middleware, Stack};
+    use crate::ics26_routing::module::Module;
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::tx_msg::Msg;
+
+    /// Records the packets which go through the stack, in the order of the layers.
+    #[derive(Clone, Debug)]
+    struct Recorder {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recorder {
+        fn record(&self, callback: &str) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", self.name, callback));
+        }
+    }
+
+    impl<Ctx> Middleware<Ctx> for Recorder {
+        fn on_send_packet(
+            &self,
+            ctx: &mut Ctx,
+            packet: &Packet,
+            next: &dyn Module<Ctx>,
+        ) -> Result<(), Error> {
+            self.record("send");
+            next.on_send_packet(ctx, packet)
+        }
+
+        fn on_recv_packet(
+            &self,
+            ctx: &mut Ctx,
+            packet: &Packet,
+            relayer: &Signer,
+            next: &dyn Module<Ctx>,
+        ) -> HandlerOutput<Option<Vec<u8>>> {
+            self.record("recv");
+            next.on_recv_packet(ctx, packet, relayer)
+        }
+    }
+
+    /// Stops the ICS20 packets carrying tokens of a given denomination.
+    #[derive(Clone, Debug)]
+    struct DenomFilter {
+        denom: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl DenomFilter {
+        fn is_blocked(&self, packet: &Packet) -> bool {
+            FungibleTokenPacketData::from_json_bytes(&packet.data)
+                .map(|data| data.denom == self.denom)
+                .unwrap_or(false)
+        }
+    }
+
+    impl<Ctx> Middleware<Ctx> for DenomFilter {
+        fn on_send_packet(
+            &self,
+            ctx: &mut Ctx,
+            packet: &Packet,
+            next: &dyn Module<Ctx>,
+        ) -> Result<(), Error> {
+            self.calls.lock().unwrap().push("filter:send".to_string());
+            if self.is_blocked(packet) {
+                return Err(Kind::ModuleRaisedError
+                    .context(format!("denomination {} is blocked", self.denom))
+                    .into());
+            }
+            next.on_send_packet(ctx, packet)
+        }
+
+        fn on_recv_packet(
+            &self,
+            ctx: &mut Ctx,
+            packet: &Packet,
+            relayer: &Signer,
+            next: &dyn Module<Ctx>,
+        ) -> HandlerOutput<Option<Vec<u8>>> {
+            self.calls.lock().unwrap().push("filter:recv".to_string());
+            if self.is_blocked(packet) {
+                let ack =
+                    Acknowledgement::from_error(format!("denomination {} is blocked", self.denom));
+                return HandlerOutput::builder().with_result(Some(ack.to_json_bytes()));
+            }
+            next.on_recv_packet(ctx, packet, relayer)
+        }
+    }
+
+    #[test]
+    /// Stacks a recorder and a denomination filter over the ICS20 module, and checks that every
+    /// callback crosses both layers in order, and that the filter can stop packets in either
+    /// direction without the ICS20 module being involved.
+    fn two_layer_stack_over_ics20() {
+        let mut ctx = get_dummy_transfer_context();
+        let scope = ctx.scope_to_module(MODULE_ID).unwrap();
+        let calls = Arc::new(Mutex::new(vec![]));
+        let stack = Stack::new(
+            Recorder {
+                name: "recorder",
+                calls: calls.clone(),
+            },
+            Stack::new(
+                DenomFilter {
+                    denom: "blocked",
+                    calls: calls.clone(),
+                },
+                Ics20Module::new(scope),
+            ),
+        );
+        let take_calls = || std::mem::take(&mut *calls.lock().unwrap());
+
+        let sender = get_dummy_account_id();
+        ctx.mint_coins(&sender, "uatom", Amount::from(100)).unwrap();
+        ctx.mint_coins(&sender, "blocked", Amount::from(100))
+            .unwrap();
+        ctx.add_route(PortId::default(), stack.clone()).unwrap();
+        ctx.add_msg_route(transfer::TYPE_URL, stack.clone())
+            .unwrap();
+
+        // Callbacks which the layers do not intercept reach the ICS20 module unchanged.
+        let channel_end = |version: &str| {
+            ChannelEnd::new(
+                State::Init,
+                Order::Unordered,
+                Counterparty::new(PortId::default(), None),
+                vec![ConnectionId::default()],
+                version.to_string(),
+            )
+        };
+        let channel_cap = ctx
+            .lookup_module_by_channel(&PortId::default(), &ChannelId::default())
+            .unwrap();
+        assert!(stack
+            .on_chan_open_init(
+                &mut ctx,
+                &PortId::default(),
+                &ChannelId::default(),
+                &channel_cap,
+                &channel_end(VERSION),
+            )
+            .is_ok());
+        assert!(stack
+            .on_chan_open_init(
+                &mut ctx,
+                &PortId::default(),
+                &ChannelId::default(),
+                &channel_cap,
+                &channel_end("ics20-2"),
+            )
+            .is_err());
+
+        // Packets sent by the ICS20 module cross both layers.
+        let transfer = |denom: &str| -> Any {
+            let mut msg = get_dummy_msg_transfer(35);
+            msg.token.as_mut().unwrap().denom = denom.to_string();
+            msg.to_any()
+        };
+        assert!(deliver(&mut ctx, vec![transfer("uatom")]).is_ok());
+        assert_eq!(take_calls(), vec!["recorder:send", "filter:send"]);
+        assert_eq!(ctx.balance(&sender, "uatom"), Amount::from(90));
+
+        // The filter vetoes the packet, and the whole transfer is reverted.
+        assert!(deliver(&mut ctx, vec![transfer("blocked")]).is_err());
+        assert_eq!(take_calls(), vec!["recorder:send", "filter:send"]);
+        assert_eq!(ctx.balance(&sender, "blocked"), Amount::from(100));
+
+        // Incoming packets cross both layers as well, and the filter acknowledges the blocked ones
+        // with an error on behalf of the ICS20 module.
+        let packet_data = |denom: &str| {
+            FungibleTokenPacketData {
+                amount: Amount::from(10),
+                denom: denom.to_string(),
+                receiver: sender.clone(),
+                sender: sender.clone(),
+            }
+            .to_json_bytes()
+        };
+        let mut packet: Packet = get_dummy_raw_packet(1, 1).try_into().unwrap();
+        packet.data = packet_data("uatom");
+        let relayer = Signer::new("relayer");
+
+        let output = stack.on_recv_packet(&mut ctx, &packet, &relayer);
+        assert_eq!(take_calls(), vec!["recorder:recv", "filter:recv"]);
+        let ack = Acknowledgement::from_json_bytes(&output.result.unwrap()).unwrap();
+        assert!(ack.is_successful());
+        assert!(!output.events.is_empty());
+
+        packet.data = packet_data("blocked");
+        let output = stack.on_recv_packet(&mut ctx, &packet, &relayer);
+        assert_eq!(take_calls(), vec!["recorder:recv", "filter:recv"]);
+        let ack = Acknowledgement::from_json_bytes(&output.result.unwrap()).unwrap();
+        assert!(!ack.is_successful());
+        assert!(output.events.is_empty());
+    }
+}
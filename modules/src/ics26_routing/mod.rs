@@ -3,4 +3,7 @@
 pub mod context;
 pub mod error;
 pub mod handler;
+pub mod middleware;
+pub mod module;
 pub mod msgs;
+pub mod router;
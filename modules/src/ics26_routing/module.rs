@@ -0,0 +1,223 @@
+//! Definition of the callbacks through which the routing module hands control to the IBC
+//! application owning a port.
+
+use prost_types::Any;
+
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
+use crate::ics04_channel::packet::{Packet, PacketResult};
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::{Error, Kind};
+use crate::signer::Signer;
+
+/// The interface that an IBC application implements in order to be plugged into the message
+/// handling pipeline of the host chain. The routing module invokes these callbacks after the
+/// ICS4 handler validated a channel handshake or packet message, and before the result of the
+/// handler is written to the host chain store. Returning an error from any callback aborts the
+/// processing of the message, e.g., this is how an application vetoes a channel handshake.
+///
+/// Applications keep all their state in the host chain, so every callback receives the context
+/// `Ctx` of the host chain.
+///
+/// Besides the callbacks, an application processes the messages which users of the host chain
+/// submit to it, e.g., the ICS20 `MsgTransfer`, in `on_msg`. The router hands each such message
+/// to the module routed for its type URL, so that the host chain only supports the applications
+/// which it routes.
+///
+/// Middleware implements this trait as well, by wrapping the module of an application: it is
+/// routed in place of that module, and forwards each callback to it, possibly after altering the
+/// callback arguments or before altering the result. For instance, the ICS29 fee middleware
+/// (`application::ics29_fee::module::FeeModule`) negotiates fee-enabled channels on behalf of
+/// the application it wraps. Middleware which only intercepts some callbacks may rather implement
+/// `ics26_routing::middleware::Middleware`, and be stacked over the application with
+/// `ics26_routing::middleware::Stack`.
+pub trait Module<Ctx>: Send + Sync {
+    /// Called when a channel end is initialized on port `port_id`. The application may reject
+    /// the ordering, connection hops, counterparty or version proposed in `channel_end`. Otherwise,
+    /// it should claim `channel_cap`, the capability of the new channel end, which it presents
+    /// later on to act on the channel end, e.g., to send packets.
+    fn on_chan_open_init(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Error>;
+
+    /// Called when the counterparty initialized a channel end, and the handshake is continued on
+    /// port `port_id`. Returns the version which this chain picks for the channel, given the
+    /// `counterparty_version` proposed by the counterparty application. As upon
+    /// `on_chan_open_init`, the application should claim `channel_cap`.
+    fn on_chan_open_try(
+        &self,
+        ctx: &mut Ctx,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        counterparty_version: &str,
+    ) -> Result<String, Error>;
+
+    /// Called when the counterparty acknowledged the opening of the channel with the version
+    /// `counterparty_version`.
+    fn on_chan_open_ack(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when the counterparty confirmed that the channel is open.
+    fn on_chan_open_confirm(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when a user of this chain asks to close the channel.
+    fn on_chan_close_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when the counterparty closed the channel.
+    fn on_chan_close_confirm(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when a user of this chain proposes to upgrade the channel to `proposed_channel_end`.
+    /// The application may reject the ordering, connection hops or version of the upgrade.
+    /// Applications which do not implement this callback do not support channel upgrades.
+    fn on_chan_upgrade_init(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        Err(Kind::ChannelUpgradeNotSupported(port_id.clone()).into())
+    }
+
+    /// Called when the counterparty proposed to upgrade the channel to `proposed_channel_end`,
+    /// and the upgrade handshake is continued on port `port_id`.
+    fn on_chan_upgrade_try(
+        &self,
+        _ctx: &mut Ctx,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        Err(Kind::ChannelUpgradeNotSupported(port_id.clone()).into())
+    }
+
+    /// Called when the counterparty accepted the upgrade of the channel, which is open again.
+    fn on_chan_upgrade_ack(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when the counterparty completed the upgrade of the channel, which is open again.
+    fn on_chan_upgrade_confirm(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when a user of this chain cancels the upgrade of the channel which it proposed. The
+    /// channel carries on as it was before the upgrade.
+    fn on_chan_upgrade_cancel(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when the application sends `packet` on one of its channels, after the ICS4 handler
+    /// validated the packet and before the packet is committed to the host chain store. The
+    /// application itself has nothing to check at this point, but middleware may veto the packet,
+    /// e.g., to enforce a rate limit.
+    fn on_send_packet(&self, _ctx: &mut Ctx, _packet: &Packet) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called when `packet` was received on one of the channels of the application, as relayed
+    /// by `relayer`. The result of the output is the acknowledgement of the packet, which
+    /// encodes any failure of the application, since the packet receipt itself cannot fail.
+    /// An application which acknowledges the packet asynchronously returns `None` instead, and
+    /// writes the acknowledgement later on with `ics26_routing::handler::write_acknowledgement`.
+    fn on_recv_packet(
+        &self,
+        ctx: &mut Ctx,
+        packet: &Packet,
+        relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>>;
+
+    /// Called when the counterparty acknowledged `packet`, which this application sent.
+    fn on_acknowledgement_packet(
+        &self,
+        _ctx: &mut Ctx,
+        _packet: &Packet,
+        _acknowledgement: &[u8],
+        _relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Error> {
+        Ok(HandlerOutput::builder().with_result(()))
+    }
+
+    /// Called when `packet`, which this application sent, timed out before being received.
+    fn on_timeout_packet(
+        &self,
+        _ctx: &mut Ctx,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<HandlerOutput<()>, Error> {
+        Ok(HandlerOutput::builder().with_result(()))
+    }
+
+    /// Called to process `msg`, a message of the application, which the router routes to this
+    /// module by its type URL. The result tells the routing module how to complete the processing
+    /// of the message. Modules which do not process any message reject all of them.
+    fn on_msg(&self, _ctx: &mut Ctx, msg: &Any) -> Result<HandlerOutput<MsgResult>, Error> {
+        Err(Kind::UnknownMessageTypeUrl(msg.type_url.clone()).into())
+    }
+}
+
+/// The result of the processing of an application message by the module routed for it, which the
+/// routing module applies to the host chain store.
+#[derive(Clone, Debug)]
+pub enum MsgResult {
+    /// The module wrote all the state resulting from the message itself.
+    Done,
+    /// The module sent a packet, which the routing module commits unless the middleware stacked
+    /// over the module vetoes it.
+    SendPacket(PacketResult),
+    /// The module opens a channel, whose first handshake step the routing module processes like
+    /// any `ChanOpenInit` message.
+    ChannelOpenInit(MsgChannelOpenInit),
+}
@@ -1,4 +1,5 @@
-use crate::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
+use prost_types::Any;
+
 use crate::ics02_client::msgs::ClientMsg;
 use crate::ics04_channel::msgs::ChannelMsg;
 use crate::{ics03_connection::msgs::ConnectionMsg, ics04_channel::msgs::PacketMsg};
@@ -10,5 +11,6 @@ pub enum Ics26Envelope {
     Ics3Msg(ConnectionMsg),
     Ics4ChannelMsg(ChannelMsg),
     Ics4PacketMsg(PacketMsg),
-    Ics20Msg(MsgTransfer),
+    /// A message of an application, which the router routes to the module processing it.
+    AppMsg(Any),
 }
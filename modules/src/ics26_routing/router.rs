@@ -0,0 +1,146 @@
+//! The router of the ICS26 module, which associates ports with the application modules that own
+//! them, and the messages of these applications with the modules that process them.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::ics05_port::capabilities::ModuleId;
+use crate::ics05_port::context::ScopedCapabilityKeeper;
+use crate::ics24_host::identifier::PortId;
+use crate::ics26_routing::error::{Error, Kind};
+use crate::ics26_routing::module::Module;
+
+/// Maps each port of the host chain to the application module that handles the channel
+/// handshakes and the packets on that port. Besides ports routed individually, a module may own
+/// all the ports whose identifier starts with a given prefix, e.g., the ICS27 controller module
+/// owns one `icacontroller-<owner>` port per owner of an interchain account.
+///
+/// Separately, each type URL of the application messages which the host chain accepts is routed
+/// to the module that processes these messages (see `Module::on_msg`).
+///
+/// The router also hands out the capability scopes of the application modules, at most one per
+/// module name.
+pub struct Router<Ctx> {
+    routes: BTreeMap<PortId, Arc<dyn Module<Ctx>>>,
+    prefix_routes: BTreeMap<String, Arc<dyn Module<Ctx>>>,
+    msg_routes: BTreeMap<String, Arc<dyn Module<Ctx>>>,
+    scopes: BTreeSet<ModuleId>,
+}
+
+impl<Ctx> Router<Ctx> {
+    /// Creates the capability scope of the application module `name`, which the module holds to
+    /// create, claim and look up its capabilities. Fails if the scope of `name` was already
+    /// handed out, or if `name` is the name of the IBC module itself.
+    pub fn scope_to_module(&mut self, name: &str) -> Result<ScopedCapabilityKeeper, Error> {
+        let module = ModuleId::new(name);
+        if module == ModuleId::ibc() || !self.scopes.insert(module.clone()) {
+            return Err(Kind::ScopeAlreadyExists(module).into());
+        }
+
+        Ok(ScopedCapabilityKeeper::new(module))
+    }
+
+    /// Routes all messages for port `port_id` to `module`. Fails if the port is already routed.
+    pub fn add_route(
+        &mut self,
+        port_id: PortId,
+        module: impl Module<Ctx> + 'static,
+    ) -> Result<(), Error> {
+        if self.has_route(&port_id) {
+            return Err(Kind::RouteAlreadyExists(port_id).into());
+        }
+
+        self.routes.insert(port_id, Arc::new(module));
+        Ok(())
+    }
+
+    /// Routes all messages for the ports whose identifier starts with `prefix` to `module`.
+    /// Ports routed individually take precedence over prefix routes. Fails if the prefix is
+    /// already routed.
+    pub fn add_prefix_route(
+        &mut self,
+        prefix: impl Into<String>,
+        module: impl Module<Ctx> + 'static,
+    ) -> Result<(), Error> {
+        let prefix = prefix.into();
+        if self.prefix_routes.contains_key(&prefix) {
+            return Err(Kind::PrefixRouteAlreadyExists(prefix).into());
+        }
+
+        self.prefix_routes.insert(prefix, Arc::new(module));
+        Ok(())
+    }
+
+    /// Routes the application messages of type URL `type_url` to `module`. Fails if the type URL
+    /// is already routed.
+    pub fn add_msg_route(
+        &mut self,
+        type_url: impl Into<String>,
+        module: impl Module<Ctx> + 'static,
+    ) -> Result<(), Error> {
+        let type_url = type_url.into();
+        if self.msg_routes.contains_key(&type_url) {
+            return Err(Kind::MsgRouteAlreadyExists(type_url).into());
+        }
+
+        self.msg_routes.insert(type_url, Arc::new(module));
+        Ok(())
+    }
+
+    /// Returns the module which owns port `port_id`, if any.
+    pub fn get_route(&self, port_id: &PortId) -> Option<Arc<dyn Module<Ctx>>> {
+        self.routes.get(port_id).cloned().or_else(|| {
+            self.prefix_routes
+                .iter()
+                .find(|(prefix, _)| port_id.as_str().starts_with(prefix.as_str()))
+                .map(|(_, module)| module.clone())
+        })
+    }
+
+    pub fn has_route(&self, port_id: &PortId) -> bool {
+        self.routes.contains_key(port_id)
+    }
+
+    /// Returns the module which processes the application messages of type URL `type_url`, if
+    /// any.
+    pub fn get_msg_route(&self, type_url: &str) -> Option<Arc<dyn Module<Ctx>>> {
+        self.msg_routes.get(type_url).cloned()
+    }
+}
+
+impl<Ctx> Default for Router<Ctx> {
+    fn default() -> Self {
+        Router {
+            routes: BTreeMap::new(),
+            prefix_routes: BTreeMap::new(),
+            msg_routes: BTreeMap::new(),
+            scopes: BTreeSet::new(),
+        }
+    }
+}
+
+impl<Ctx> Clone for Router<Ctx> {
+    fn clone(&self) -> Self {
+        Router {
+            routes: self.routes.clone(),
+            prefix_routes: self.prefix_routes.clone(),
+            msg_routes: self.msg_routes.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+}
+
+impl<Ctx> fmt::Debug for Router<Ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.routes.keys())
+            .entries(
+                self.prefix_routes
+                    .keys()
+                    .map(|prefix| format!("{}*", prefix)),
+            )
+            .entries(self.msg_routes.keys())
+            .finish()
+    }
+}
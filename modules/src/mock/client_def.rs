@@ -1,15 +1,22 @@
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::client_def::ClientDef;
 use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::context::ClientReader;
 use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::ConnectionReader;
 use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::ChannelReader;
 use crate::ics04_channel::packet::Sequence;
+use crate::ics04_channel::upgrade::ChannelUpgrade;
 use crate::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes, CommitmentRoot};
 use crate::ics23_commitment::merkle::apply_prefix;
 use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use crate::ics24_host::Path;
 use crate::mock::client_state::{MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
+use crate::mock::misbehaviour::Misbehaviour;
 use crate::Height;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -19,9 +26,12 @@ impl ClientDef for MockClient {
     type Header = MockHeader;
     type ClientState = MockClientState;
     type ConsensusState = MockConsensusState;
+    type Misbehaviour = Misbehaviour;
 
     fn check_header_and_update_state(
         &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
         client_state: Self::ClientState,
         header: Self::Header,
     ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
@@ -30,19 +40,50 @@ impl ClientDef for MockClient {
                 "received header height is lower than (or equal to) client latest height".into(),
             );
         }
-        Ok((MockClientState(header), MockConsensusState(header)))
+        Ok((
+            MockClientState::new(header),
+            MockConsensusState::new(header),
+        ))
     }
 
-    fn verify_client_consensus_state(
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        client_state: Self::ClientState,
+        misbehaviour: Self::Misbehaviour,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        if misbehaviour.header1 == misbehaviour.header2 {
+            return Err("misbehaviour headers are identical".into());
+        }
+        Ok(client_state.with_frozen_height(misbehaviour.header1.height()))
+    }
+
+    fn verify_upgrade_and_update_state(
         &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
         _client_state: &Self::ClientState,
+        upgraded_client_state: Self::ClientState,
+        upgraded_consensus_state: Self::ConsensusState,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+    ) -> Result<(Self::ClientState, Self::ConsensusState), Box<dyn std::error::Error>> {
+        Ok((upgraded_client_state, upgraded_consensus_state))
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
         height: Height,
         prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         client_id: &ClientId,
         _consensus_height: Height,
         _expected_consensus_state: &AnyConsensusState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
         let client_prefixed_path = Path::ClientConsensusState {
             client_id: client_id.clone(),
             epoch: height.revision_number,
@@ -57,94 +98,126 @@ impl ClientDef for MockClient {
         // TODO - implement this
         // proof.verify_membership(cs.root(), path, expected_consensus_state)
 
-        Ok(())
+        Ok(*client_state)
     }
 
     fn verify_connection_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _connection_id: Option<&ConnectionId>,
         _expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 
     fn verify_channel_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
         _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _expected_channel_end: &ChannelEnd,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
+    }
+
+    fn verify_channel_upgrade(
+        &self,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
+        _height: Height,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _expected_channel_upgrade: &ChannelUpgrade,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 
     fn verify_client_full_state(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ConnectionReader,
+        client_state: &Self::ClientState,
         _height: Height,
         _root: &CommitmentRoot,
         _prefix: &CommitmentPrefix,
         _client_id: &ClientId,
         _proof: &CommitmentProofBytes,
         _expected_client_state: &AnyClientState,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 
     fn verify_packet_data(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-        _data: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+        _data: Vec<u8>,
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 
     fn verify_packet_acknowledgement(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
         _data: Vec<u8>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 
     fn verify_next_sequence_recv(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 
     fn verify_packet_receipt_absence(
         &self,
-        _client_state: &Self::ClientState,
+        _ctx: &dyn ChannelReader,
+        client_state: &Self::ClientState,
         _height: Height,
+        _prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
         _port_id: &PortId,
         _channel_id: &ChannelId,
         _seq: &Sequence,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        Ok(())
+    ) -> Result<Self::ClientState, Box<dyn std::error::Error>> {
+        Ok(*client_state)
     }
 }
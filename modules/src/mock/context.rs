@@ -1,13 +1,30 @@
 //! Implementation of a global context mock. Used in testing handlers of all IBC modules.
 
 use std::cmp::min;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
+use std::time::Duration;
 
 use prost_types::Any;
-use sha2::Digest;
-
-use crate::application::ics20_fungible_token_transfer::context::Ics20Context;
+use tendermint::Time;
+use tendermint_proto::Protobuf;
+use tendermint_testgen::Validator;
+
+use crate::application::ics20_fungible_token_transfer::amount::Amount;
+use crate::application::ics20_fungible_token_transfer::context::{BankKeeper, Ics20Context};
+use crate::application::ics20_fungible_token_transfer::error::{
+    Error as Ics20Error, Kind as Ics20Kind,
+};
+use crate::application::ics20_fungible_token_transfer::msgs::transfer;
+use crate::application::ics27_interchain_accounts::context::{Ics27Context, Ics27HostContext};
+use crate::application::ics27_interchain_accounts::error::{
+    Error as Ics27Error, Kind as Ics27Kind,
+};
+use crate::application::ics29_fee::context::Ics29Context;
+use crate::application::ics29_fee::error::Error as Ics29Error;
+use crate::application::ics29_fee::fee::PacketFee;
+use crate::application::ics721_nft_transfer::context::{Ics721Context, NftKeeper};
+use crate::application::ics721_nft_transfer::error::{Error as Ics721Error, Kind as Ics721Kind};
 use crate::events::IbcEvent;
 use crate::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use crate::ics02_client::client_state::AnyClientState;
@@ -18,26 +35,42 @@ use crate::ics02_client::header::AnyHeader;
 use crate::ics03_connection::connection::ConnectionEnd;
 use crate::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
 use crate::ics03_connection::error::Error as Ics3Error;
+use crate::ics03_connection::version::{get_compatible_versions, Version};
 use crate::ics04_channel::channel::ChannelEnd;
 use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::ics04_channel::error::{Error as Ics4Error, Kind as Ics4Kind};
-use crate::ics04_channel::packet::{Receipt, Sequence};
-use crate::ics05_port::capabilities::Capability;
-use crate::ics05_port::context::PortReader;
+use crate::ics04_channel::packet::{PacketId, Receipt, Sequence};
+use crate::ics04_channel::upgrade::ChannelUpgrade;
+use crate::ics05_port::capabilities::{Capability, CapabilityName, ModuleId};
+use crate::ics05_port::context::{
+    CapabilityKeeper, CapabilityReader, PortKeeper, PortReader, ScopedCapabilityKeeper,
+};
+use crate::ics05_port::error::Error as Ics05Error;
+use crate::ics06_solomachine::client_state::ClientState as SoloMachineClientState;
+use crate::ics06_solomachine::consensus_state::ConsensusState as SoloMachineConsensusState;
 use crate::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+use crate::ics09_localhost::client_state::ClientState as LocalhostClientState;
+use crate::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
 use crate::ics18_relayer::context::Ics18Context;
 use crate::ics18_relayer::error::{Error as Ics18Error, Kind as Ics18ErrorKind};
 use crate::ics23_commitment::commitment::CommitmentPrefix;
 use crate::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use crate::ics26_routing::context::Ics26Context;
+use crate::ics26_routing::error::Error as Ics26Error;
 use crate::ics26_routing::handler::{deliver, dispatch};
+use crate::ics26_routing::module::Module;
 use crate::ics26_routing::msgs::Ics26Envelope;
+use crate::ics26_routing::router::Router;
 use crate::mock::client_state::{MockClientRecord, MockClientState, MockConsensusState};
 use crate::mock::header::MockHeader;
 use crate::mock::host::{HostBlock, HostType};
 use crate::signer::Signer;
+use crate::timestamp::Timestamp;
 use crate::Height;
 
+/// The time interval between consecutive blocks of the host chain emulated by a mock context.
+const DEFAULT_BLOCK_TIME_SECS: u64 = 3;
+
 /// A context implementing the dependencies necessary for testing any IBC module.
 #[derive(Clone, Debug)]
 pub struct MockContext {
@@ -54,7 +87,11 @@ pub struct MockContext {
     latest_height: Height,
 
     /// Highest timestamp, i.e., of the most recent block in the history.
-    timestamp: u64,
+    timestamp: Timestamp,
+
+    /// Time of the most recent block in the history. Every new block advances this time by
+    /// `DEFAULT_BLOCK_TIME_SECS`.
+    latest_time: Time,
 
     /// The chain of blocks underlying this context. A vector of size up to `max_history_size`
     /// blocks, ascending order by their height (latest block is on the last position).
@@ -76,6 +113,9 @@ pub struct MockContext {
     /// Counter for connection identifiers (see `increase_connection_counter`).
     connection_ids_counter: u64,
 
+    /// The connection versions which the host chain supports, along with their features.
+    connection_versions: Vec<Version>,
+
     /// Association between connection ids and channel ids.
     connection_channels: HashMap<ConnectionId, Vec<(PortId, ChannelId)>>,
 
@@ -85,6 +125,9 @@ pub struct MockContext {
     /// All the channels in the store. TODO Make new key PortId X ChanneId
     channels: HashMap<(PortId, ChannelId), ChannelEnd>,
 
+    /// The upgrades of the channels which are in progress.
+    channel_upgrades: HashMap<(PortId, ChannelId), ChannelUpgrade>,
+
     /// Tracks the sequence number for the next packet to be sent.
     next_sequence_send: HashMap<(PortId, ChannelId), Sequence>,
 
@@ -94,16 +137,46 @@ pub struct MockContext {
     /// Tracks the sequence number for the next packet to be acknowledged.
     next_sequence_ack: HashMap<(PortId, ChannelId), Sequence>,
 
-    packet_acknowledgement: HashMap<(PortId, ChannelId, Sequence), String>,
+    packet_acknowledgement: HashMap<(PortId, ChannelId, Sequence), Vec<u8>>,
+
+    /// All the capabilities in the store, indexed by the module owning them and their name.
+    capabilities: HashMap<(ModuleId, CapabilityName), Capability>,
 
-    /// Maps ports to their capabilities
-    port_capabilities: HashMap<PortId, Capability>,
+    /// Counter for the capabilities (see `increase_capability_counter`).
+    capability_counter: u64,
 
     /// Constant-size commitments to packets data fields
-    packet_commitment: HashMap<(PortId, ChannelId, Sequence), String>,
+    packet_commitment: HashMap<(PortId, ChannelId, Sequence), Vec<u8>>,
 
     // Used by unordered channel
     packet_receipt: HashMap<(PortId, ChannelId, Sequence), Receipt>,
+
+    /// Token balances of the accounts on the host chain, indexed by account and denomination.
+    balances: HashMap<(Signer, String), Amount>,
+
+    /// The channels on which relayer fees can be paid.
+    fee_enabled_channels: HashSet<(PortId, ChannelId)>,
+
+    /// The counterparty payees registered by relayers, indexed by channel and relayer.
+    counterparty_payees: HashMap<(ChannelId, Signer), Signer>,
+
+    /// The relayer fees escrowed for the packets which are in flight.
+    fees_in_escrow: HashMap<PacketId, Vec<PacketFee>>,
+
+    /// The active channels of the interchain accounts, indexed by connection and controller port.
+    interchain_account_channels: HashMap<(ConnectionId, PortId), ChannelId>,
+
+    /// The addresses of the interchain accounts, indexed by connection and controller port.
+    interchain_accounts: HashMap<(ConnectionId, PortId), Signer>,
+
+    /// The URIs of the NFT classes on the host chain, indexed by class.
+    nft_classes: HashMap<String, String>,
+
+    /// The owner and URI of the NFTs on the host chain, indexed by class and token.
+    nfts: HashMap<(String, String), (Signer, String)>,
+
+    /// Routes the channel handshakes and packets on each port to the owning application module.
+    router: Router<MockContext>,
 }
 
 /// Returns a MockContext with bare minimum initialization: no clients, no connections and no channels are
@@ -147,12 +220,16 @@ impl MockContext {
             "The version in the chain identifier must match the version in the latest height"
         );
 
+        let block_time = Duration::from_secs(DEFAULT_BLOCK_TIME_SECS);
+        let latest_time = Time::now();
+
         MockContext {
             host_chain_type: host_type,
             host_chain_id: host_id.clone(),
             max_history_size,
             latest_height,
             timestamp: Default::default(),
+            latest_time,
             history: (0..n)
                 .rev()
                 .map(|i| {
@@ -160,22 +237,35 @@ impl MockContext {
                         host_id.clone(),
                         host_type,
                         latest_height.sub(i).unwrap().revision_height,
+                        latest_time - block_time * i as u32,
                     )
                 })
                 .collect(),
             connections: Default::default(),
+            connection_versions: get_compatible_versions(),
             client_ids_counter: 0,
             clients: Default::default(),
             client_connections: Default::default(),
             channels: Default::default(),
+            channel_upgrades: Default::default(),
             connection_channels: Default::default(),
             next_sequence_send: Default::default(),
             next_sequence_recv: Default::default(),
             next_sequence_ack: Default::default(),
-            port_capabilities: Default::default(),
+            capabilities: Default::default(),
+            capability_counter: 0,
             packet_commitment: Default::default(),
             packet_receipt: Default::default(),
             packet_acknowledgement: Default::default(),
+            balances: Default::default(),
+            fee_enabled_channels: Default::default(),
+            counterparty_payees: Default::default(),
+            fees_in_escrow: Default::default(),
+            interchain_account_channels: Default::default(),
+            interchain_accounts: Default::default(),
+            nft_classes: Default::default(),
+            nfts: Default::default(),
+            router: Default::default(),
             connection_ids_counter: 0,
             channel_ids_counter: 0,
         }
@@ -195,7 +285,27 @@ impl MockContext {
     /// `consensus_state_height` is None, then the client will be initialized with a consensus
     /// state matching the same height as the client state (`client_state_height`).
     pub fn with_client_parametrized(
+        self,
+        client_id: &ClientId,
+        client_state_height: Height,
+        client_type: Option<ClientType>,
+        consensus_state_height: Option<Height>,
+    ) -> Self {
+        let client_chain_id = self.host_chain_id.clone();
+        self.with_client_parametrized_with_chain_id(
+            client_chain_id,
+            client_id,
+            client_state_height,
+            client_type,
+            consensus_state_height,
+        )
+    }
+
+    /// Similar to `with_client_parametrized`, but the client tracks the chain `client_chain_id`
+    /// instead of this very chain.
+    pub fn with_client_parametrized_with_chain_id(
         mut self,
+        client_chain_id: ChainId,
         client_id: &ClientId,
         client_state_height: Height,
         client_type: Option<ClientType>,
@@ -207,14 +317,15 @@ impl MockContext {
         let (client_state, consensus_state) = match client_type {
             // If it's a mock client, create the corresponding mock states.
             ClientType::Mock => (
-                Some(MockClientState(MockHeader::new(client_state_height)).into()),
-                MockConsensusState(MockHeader::new(cs_height)).into(),
+                Some(MockClientState::new(MockHeader::new(client_state_height)).into()),
+                MockConsensusState::new(MockHeader::new(cs_height)).into(),
             ),
             // If it's a Tendermint client, we need TM states.
             ClientType::Tendermint => {
                 let light_block = HostBlock::generate_tm_block(
-                    self.host_chain_id.clone(),
+                    client_chain_id,
                     cs_height.revision_height,
+                    self.latest_time,
                 );
                 let consensus_state = AnyConsensusState::from(light_block.clone());
                 let client_state =
@@ -223,6 +334,29 @@ impl MockContext {
                 // Return the tuple.
                 (Some(client_state), consensus_state)
             }
+            // If it's a solo machine client, the machine signs with the key of a test validator.
+            ClientType::SoloMachine => {
+                let consensus_state = SoloMachineConsensusState::new(
+                    Validator::new("solomachine").get_public_key().unwrap(),
+                    "diversifier".to_string(),
+                    1,
+                );
+                let client_state = SoloMachineClientState::new(
+                    client_state_height.revision_height,
+                    consensus_state.clone(),
+                    false,
+                );
+
+                (Some(client_state.into()), consensus_state.into())
+            }
+            // If it's a localhost client, it tracks this very chain.
+            ClientType::Localhost => (
+                Some(
+                    LocalhostClientState::new(self.host_chain_id.clone(), client_state_height)
+                        .into(),
+                ),
+                LocalhostConsensusState::default().into(),
+            ),
         };
         let consensus_states = vec![(cs_height, consensus_state)].into_iter().collect();
 
@@ -235,6 +369,15 @@ impl MockContext {
         self
     }
 
+    /// Sets the connection versions which this context supports, e.g., to negotiate connections
+    /// which support only some channel orderings.
+    pub fn with_connection_versions(self, connection_versions: Vec<Version>) -> Self {
+        Self {
+            connection_versions,
+            ..self
+        }
+    }
+
     /// Associates a connection to this context.
     pub fn with_connection(
         mut self,
@@ -245,21 +388,58 @@ impl MockContext {
         self
     }
 
+    /// Binds the port `port_id` in this context, unless it is already bound.
     pub fn with_port_capability(mut self, port_id: PortId) -> Self {
-        self.port_capabilities.insert(port_id, Capability::new());
+        self.add_port(port_id);
         self
     }
 
-    /// Associates a channel (in an arbitrary state) to this context.
+    /// Associates a channel (in an arbitrary state) to this context, together with the capability
+    /// of this channel, owned by the IBC module.
     pub fn with_channel(
-        self,
+        mut self,
         port_id: PortId,
         chan_id: ChannelId,
         channel_end: ChannelEnd,
     ) -> Self {
-        let mut channels = self.channels.clone();
-        channels.insert((port_id, chan_id), channel_end);
-        Self { channels, ..self }
+        if self.lookup_module_by_channel(&port_id, &chan_id).is_none() {
+            self.new_channel_capability((port_id.clone(), chan_id.clone()))
+                .unwrap();
+        }
+        self.channels.insert((port_id, chan_id), channel_end);
+        self
+    }
+
+    /// Associates an upgrade in progress to a channel of this context. The channel must have been
+    /// associated to this context beforehand.
+    pub fn with_channel_upgrade(
+        mut self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        channel_upgrade: ChannelUpgrade,
+    ) -> Self {
+        self.channel_upgrades
+            .insert((port_id, chan_id), channel_upgrade);
+        self
+    }
+
+    /// Lets `module` claim the capability of the channel identified by `port_id` and `chan_id`,
+    /// as the application module which owns the port does when the channel is opened. The
+    /// channel must have been associated to this context beforehand.
+    pub fn with_channel_capability_claimed_by(
+        mut self,
+        module: ModuleId,
+        port_id: PortId,
+        chan_id: ChannelId,
+    ) -> Self {
+        let channel_cap = self.lookup_module_by_channel(&port_id, &chan_id).unwrap();
+        self.claim_capability(
+            module,
+            CapabilityName::channel(&port_id, &chan_id),
+            channel_cap,
+        )
+        .unwrap();
+        self
     }
 
     pub fn with_send_sequence(
@@ -304,7 +484,7 @@ impl MockContext {
         }
     }
 
-    pub fn with_timestamp(self, timestamp: u64) -> Self {
+    pub fn with_timestamp(self, timestamp: Timestamp) -> Self {
         Self { timestamp, ..self }
     }
 
@@ -333,7 +513,7 @@ impl MockContext {
         port_id: PortId,
         chan_id: ChannelId,
         seq: Sequence,
-        data: String,
+        data: Vec<u8>,
     ) -> Self {
         let mut packet_commitment = self.packet_commitment.clone();
         packet_commitment.insert((port_id, chan_id, seq), data);
@@ -359,10 +539,12 @@ impl MockContext {
 
     /// Triggers the advancing of the host chain, by extending the history of blocks (or headers).
     pub fn advance_host_chain_height(&mut self) {
+        self.latest_time = self.latest_time + Duration::from_secs(DEFAULT_BLOCK_TIME_SECS);
         let new_block = HostBlock::generate_block(
             self.host_chain_id.clone(),
             self.host_chain_type,
             self.latest_height.increment().revision_height,
+            self.latest_time,
         );
 
         // Append the new header at the tip of the history.
@@ -415,8 +597,53 @@ impl MockContext {
         Ok(())
     }
 
+    /// Returns the balance of `account` in tokens of denomination `denom`.
+    pub fn balance(&self, account: &Signer, denom: &str) -> Amount {
+        self.balances
+            .get(&(account.clone(), denom.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Binds the port `port_id`, unless it is already bound.
     pub fn add_port(&mut self, port_id: PortId) {
-        self.port_capabilities.insert(port_id, Capability::new());
+        if self.lookup_module_by_port(&port_id).is_none() {
+            self.bind_port(&port_id).unwrap();
+        }
+    }
+
+    /// Routes the channel handshakes and packets on port `port_id` to `module`.
+    pub fn add_route(
+        &mut self,
+        port_id: PortId,
+        module: impl Module<MockContext> + 'static,
+    ) -> Result<(), Ics26Error> {
+        self.router.add_route(port_id, module)
+    }
+
+    /// Routes the channel handshakes and packets on all the ports prefixed with `prefix` to
+    /// `module`.
+    pub fn add_prefix_route(
+        &mut self,
+        prefix: &str,
+        module: impl Module<MockContext> + 'static,
+    ) -> Result<(), Ics26Error> {
+        self.router.add_prefix_route(prefix, module)
+    }
+
+    /// Creates the capability scope of the application module `name`, see
+    /// `Router::scope_to_module`.
+    pub fn scope_to_module(&mut self, name: &str) -> Result<ScopedCapabilityKeeper, Ics26Error> {
+        self.router.scope_to_module(name)
+    }
+
+    /// Routes the application messages of type URL `type_url` to `module`.
+    pub fn add_msg_route(
+        &mut self,
+        type_url: &str,
+        module: impl Module<MockContext> + 'static,
+    ) -> Result<(), Ics26Error> {
+        self.router.add_msg_route(type_url, module)
     }
 
     pub fn consensus_states(&self, client_id: &ClientId) -> Vec<AnyConsensusStateWithHeight> {
@@ -431,25 +658,340 @@ impl MockContext {
     }
 }
 
-impl Ics26Context for MockContext {}
+impl Ics26Context for MockContext {
+    fn router(&self) -> &Router<Self> {
+        &self.router
+    }
+}
+
+impl Ics20Context for MockContext {
+    fn get_channel_escrow_address(&self, port_id: &PortId, channel_id: &ChannelId) -> Signer {
+        Signer::new(format!("escrow/{}/{}", port_id, channel_id))
+    }
+}
+
+impl Ics27Context for MockContext {
+    fn get_active_channel_id(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<ChannelId> {
+        self.interchain_account_channels
+            .get(&(connection_id.clone(), port_id.clone()))
+            .cloned()
+    }
+
+    fn store_active_channel_id(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        channel_id: ChannelId,
+    ) -> Result<(), Ics27Error> {
+        self.interchain_account_channels
+            .insert((connection_id, port_id), channel_id);
+        Ok(())
+    }
+
+    fn get_interchain_account_address(
+        &self,
+        connection_id: &ConnectionId,
+        port_id: &PortId,
+    ) -> Option<Signer> {
+        self.interchain_accounts
+            .get(&(connection_id.clone(), port_id.clone()))
+            .cloned()
+    }
+
+    fn store_interchain_account_address(
+        &mut self,
+        connection_id: ConnectionId,
+        port_id: PortId,
+        address: Signer,
+    ) -> Result<(), Ics27Error> {
+        self.interchain_accounts
+            .insert((connection_id, port_id), address);
+        Ok(())
+    }
+}
+
+/// The mock host chain lets interchain accounts execute ICS20 transfers only, which the router
+/// routes to the module processing transfers.
+impl Ics27HostContext for MockContext {
+    fn format_account_address(&self, address: &[u8]) -> Signer {
+        Signer::new(format!(
+            "ica/{}",
+            String::from_utf8(subtle_encoding::hex::encode(address)).unwrap()
+        ))
+    }
+
+    fn execute_msg(&mut self, account: &Signer, msg: &Any) -> Result<Vec<u8>, Ics27Error> {
+        if msg.type_url != transfer::TYPE_URL {
+            return Err(Ics27Kind::MessageNotAllowed(msg.type_url.clone()).into());
+        }
+
+        let transfer = transfer::MsgTransfer::decode_vec(&msg.value)
+            .map_err(|e| Ics27Kind::MessageExecutionFailed(msg.type_url.clone()).context(e))?;
+        if &transfer.sender != account {
+            return Err(Ics27Kind::UnauthorizedSigner(transfer.sender).into());
+        }
+
+        dispatch(self, Ics26Envelope::AppMsg(msg.clone()))
+            .map_err(|e| Ics27Kind::MessageExecutionFailed(msg.type_url.clone()).context(e))?;
+        Ok(vec![])
+    }
+}
+
+impl Ics29Context for MockContext {
+    fn get_fee_escrow_address(&self) -> Signer {
+        Signer::new("escrow/fee")
+    }
+
+    fn is_fee_enabled(&self, port_id: &PortId, channel_id: &ChannelId) -> bool {
+        self.fee_enabled_channels
+            .contains(&(port_id.clone(), channel_id.clone()))
+    }
+
+    fn store_fee_enabled(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        enabled: bool,
+    ) -> Result<(), Ics29Error> {
+        let port_channel_id = (port_id.clone(), channel_id.clone());
+        if enabled {
+            self.fee_enabled_channels.insert(port_channel_id);
+        } else {
+            self.fee_enabled_channels.remove(&port_channel_id);
+        }
+        Ok(())
+    }
+
+    fn get_counterparty_payee(&self, channel_id: &ChannelId, relayer: &Signer) -> Option<Signer> {
+        self.counterparty_payees
+            .get(&(channel_id.clone(), relayer.clone()))
+            .cloned()
+    }
+
+    fn store_counterparty_payee(
+        &mut self,
+        channel_id: &ChannelId,
+        relayer: &Signer,
+        counterparty_payee: &Signer,
+    ) -> Result<(), Ics29Error> {
+        self.counterparty_payees.insert(
+            (channel_id.clone(), relayer.clone()),
+            counterparty_payee.clone(),
+        );
+        Ok(())
+    }
+
+    fn get_fees_in_escrow(&self, packet_id: &PacketId) -> Vec<PacketFee> {
+        self.fees_in_escrow
+            .get(packet_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn store_fees_in_escrow(
+        &mut self,
+        packet_id: PacketId,
+        packet_fees: Vec<PacketFee>,
+    ) -> Result<(), Ics29Error> {
+        self.fees_in_escrow.insert(packet_id, packet_fees);
+        Ok(())
+    }
+
+    fn delete_fees_in_escrow(&mut self, packet_id: &PacketId) -> Result<(), Ics29Error> {
+        self.fees_in_escrow.remove(packet_id);
+        Ok(())
+    }
+}
+
+impl BankKeeper for MockContext {
+    fn send_coins(
+        &mut self,
+        from: &Signer,
+        to: &Signer,
+        denom: &str,
+        amount: Amount,
+    ) -> Result<(), Ics20Error> {
+        self.burn_coins(from, denom, amount)?;
+        self.mint_coins(to, denom, amount)
+    }
 
-impl Ics20Context for MockContext {}
+    fn mint_coins(
+        &mut self,
+        account: &Signer,
+        denom: &str,
+        amount: Amount,
+    ) -> Result<(), Ics20Error> {
+        let balance = self
+            .balances
+            .entry((account.clone(), denom.to_string()))
+            .or_default();
+        *balance = balance
+            .checked_add(amount)
+            .ok_or_else(|| Ics20Kind::InvalidTokenAmount(amount.to_string()))?;
+        Ok(())
+    }
 
-impl PortReader for MockContext {
-    fn lookup_module_by_port(&self, port_id: &PortId) -> Option<Capability> {
-        self.port_capabilities.get(port_id).cloned()
+    fn burn_coins(
+        &mut self,
+        account: &Signer,
+        denom: &str,
+        amount: Amount,
+    ) -> Result<(), Ics20Error> {
+        let balance = self
+            .balances
+            .get_mut(&(account.clone(), denom.to_string()))
+            .ok_or_else(|| Ics20Kind::InsufficientFunds(account.clone(), denom.to_string()))?;
+        *balance = balance
+            .checked_sub(amount)
+            .ok_or_else(|| Ics20Kind::InsufficientFunds(account.clone(), denom.to_string()))?;
+        Ok(())
     }
+}
 
-    fn authenticate(&self, _cap: &Capability, _port_id: &PortId) -> bool {
-        true
+impl Ics721Context for MockContext {
+    fn get_nft_escrow_address(&self, port_id: &PortId, channel_id: &ChannelId) -> Signer {
+        Signer::new(format!("escrow/nft/{}/{}", port_id, channel_id))
     }
 }
 
+impl NftKeeper for MockContext {
+    fn get_class_uri(&self, class_id: &str) -> Option<String> {
+        self.nft_classes.get(class_id).cloned()
+    }
+
+    fn save_class(&mut self, class_id: &str, class_uri: &str) -> Result<(), Ics721Error> {
+        self.nft_classes
+            .insert(class_id.to_string(), class_uri.to_string());
+        Ok(())
+    }
+
+    fn get_owner(&self, class_id: &str, token_id: &str) -> Option<Signer> {
+        self.nfts
+            .get(&(class_id.to_string(), token_id.to_string()))
+            .map(|(owner, _)| owner.clone())
+    }
+
+    fn get_token_uri(&self, class_id: &str, token_id: &str) -> Option<String> {
+        self.nfts
+            .get(&(class_id.to_string(), token_id.to_string()))
+            .map(|(_, uri)| uri.clone())
+    }
+
+    fn transfer_nft(
+        &mut self,
+        class_id: &str,
+        token_id: &str,
+        from: &Signer,
+        to: &Signer,
+    ) -> Result<(), Ics721Error> {
+        let (owner, _) = self
+            .nfts
+            .get_mut(&(class_id.to_string(), token_id.to_string()))
+            .ok_or_else(|| Ics721Kind::TokenNotFound(class_id.to_string(), token_id.to_string()))?;
+        if *owner != *from {
+            return Err(Ics721Kind::UnauthorizedOwner(
+                class_id.to_string(),
+                token_id.to_string(),
+                from.clone(),
+            )
+            .into());
+        }
+        *owner = to.clone();
+        Ok(())
+    }
+
+    fn mint_nft(
+        &mut self,
+        class_id: &str,
+        token_id: &str,
+        token_uri: &str,
+        receiver: &Signer,
+    ) -> Result<(), Ics721Error> {
+        if !self.nft_classes.contains_key(class_id) {
+            return Err(Ics721Kind::ClassNotFound(class_id.to_string()).into());
+        }
+
+        let key = (class_id.to_string(), token_id.to_string());
+        if self.nfts.contains_key(&key) {
+            return Err(
+                Ics721Kind::TokenAlreadyExists(class_id.to_string(), token_id.to_string()).into(),
+            );
+        }
+        self.nfts
+            .insert(key, (receiver.clone(), token_uri.to_string()));
+        Ok(())
+    }
+
+    fn burn_nft(
+        &mut self,
+        class_id: &str,
+        token_id: &str,
+        owner: &Signer,
+    ) -> Result<(), Ics721Error> {
+        match self.get_owner(class_id, token_id) {
+            None => {
+                Err(Ics721Kind::TokenNotFound(class_id.to_string(), token_id.to_string()).into())
+            }
+            Some(current) if &current != owner => Err(Ics721Kind::UnauthorizedOwner(
+                class_id.to_string(),
+                token_id.to_string(),
+                owner.clone(),
+            )
+            .into()),
+            Some(_) => {
+                self.nfts
+                    .remove(&(class_id.to_string(), token_id.to_string()));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl CapabilityReader for MockContext {
+    fn get_capability(&self, module: &ModuleId, name: &CapabilityName) -> Option<Capability> {
+        self.capabilities
+            .get(&(module.clone(), name.clone()))
+            .cloned()
+    }
+
+    fn capability_counter(&self) -> u64 {
+        self.capability_counter
+    }
+}
+
+impl CapabilityKeeper for MockContext {
+    fn store_capability(
+        &mut self,
+        module: ModuleId,
+        name: CapabilityName,
+        capability: Capability,
+    ) -> Result<(), Ics05Error> {
+        self.capabilities.insert((module, name), capability);
+        Ok(())
+    }
+
+    fn increase_capability_counter(&mut self) {
+        self.capability_counter += 1;
+    }
+}
+
+impl PortReader for MockContext {}
+
+impl PortKeeper for MockContext {}
+
 impl ChannelReader for MockContext {
     fn channel_end(&self, pcid: &(PortId, ChannelId)) -> Option<ChannelEnd> {
         self.channels.get(pcid).cloned()
     }
 
+    fn channel_upgrade(&self, pcid: &(PortId, ChannelId)) -> Option<ChannelUpgrade> {
+        self.channel_upgrades.get(pcid).cloned()
+    }
+
     fn connection_end(&self, cid: &ConnectionId) -> Option<ConnectionEnd> {
         self.connections.get(cid).cloned()
     }
@@ -470,20 +1012,40 @@ impl ChannelReader for MockContext {
         ClientReader::consensus_state(self, client_id, height)
     }
 
-    fn authenticated_capability(&self, port_id: &PortId) -> Result<Capability, Ics4Error> {
-        let cap = PortReader::lookup_module_by_port(self, port_id);
-        match cap {
-            Some(key) => {
-                if !PortReader::authenticate(self, &key, port_id) {
-                    Err(Ics4Kind::InvalidPortCapability.into())
-                } else {
-                    Ok(key)
-                }
-            }
+    fn connection_reader(&self) -> &dyn ConnectionReader {
+        self
+    }
+
+    fn authenticate_port_capability(
+        &self,
+        port_id: &PortId,
+        capability: &Capability,
+    ) -> Result<(), Ics4Error> {
+        match PortReader::lookup_module_by_port(self, port_id) {
+            Some(key) if &key == capability => Ok(()),
+            Some(_) => Err(Ics4Kind::InvalidPortCapability.into()),
             None => Err(Ics4Kind::NoPortCapability(port_id.clone()).into()),
         }
     }
 
+    fn authenticate_channel_capability(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        capability: &Capability,
+    ) -> Result<(), Ics4Error> {
+        let (port_id, channel_id) = port_channel_id;
+        self.authenticate_capability(
+            &ModuleId::ibc(),
+            &CapabilityName::channel(port_id, channel_id),
+            capability,
+        )
+        .map_err(|e| {
+            Ics4Kind::InvalidChannelCapability(port_id.clone(), channel_id.clone())
+                .context(e)
+                .into()
+        })
+    }
+
     fn get_next_sequence_send(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence> {
         self.next_sequence_send.get(port_channel_id).cloned()
     }
@@ -496,7 +1058,7 @@ impl ChannelReader for MockContext {
         self.next_sequence_ack.get(port_channel_id).cloned()
     }
 
-    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String> {
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
         self.packet_commitment.get(key).cloned()
     }
 
@@ -504,20 +1066,15 @@ impl ChannelReader for MockContext {
         self.packet_receipt.get(key).cloned()
     }
 
-    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<String> {
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
         self.packet_acknowledgement.get(key).cloned()
     }
 
-    fn hash(&self, input: String) -> String {
-        let r = sha2::Sha256::digest(input.as_bytes());
-        format!("{:x}", r)
-    }
-
     fn host_height(&self) -> Height {
         self.latest_height
     }
 
-    fn host_timestamp(&self) -> u64 {
+    fn host_timestamp(&self) -> Timestamp {
         self.timestamp
     }
 
@@ -527,16 +1084,32 @@ impl ChannelReader for MockContext {
 }
 
 impl ChannelKeeper for MockContext {
+    fn new_channel_capability(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<Capability, Ics4Error> {
+        let (port_id, channel_id) = port_channel_id;
+        self.new_capability(
+            ModuleId::ibc(),
+            CapabilityName::channel(&port_id, &channel_id),
+        )
+        .map_err(|e| {
+            Ics4Kind::ChannelCapabilityCreationFailed(port_id, channel_id)
+                .context(e)
+                .into()
+        })
+    }
+
     fn store_packet_commitment(
         &mut self,
         key: (PortId, ChannelId, Sequence),
-        timeout_timestamp: u64,
+        timeout_timestamp: Timestamp,
         timeout_height: Height,
         data: Vec<u8>,
     ) -> Result<(), Ics4Error> {
-        let input = format!("{:?},{:?},{:?}", timeout_timestamp, timeout_height, data);
-        self.packet_commitment
-            .insert(key, ChannelReader::hash(self, input));
+        let commitment =
+            ChannelReader::packet_commitment(self, timeout_timestamp, timeout_height, &data);
+        self.packet_commitment.insert(key, commitment);
         Ok(())
     }
 
@@ -545,9 +1118,8 @@ impl ChannelKeeper for MockContext {
         key: (PortId, ChannelId, Sequence),
         ack: Vec<u8>,
     ) -> Result<(), Ics4Error> {
-        let input = format!("{:?}", ack);
-        self.packet_acknowledgement
-            .insert(key, ChannelReader::hash(self, input));
+        let commitment = ChannelReader::ack_commitment(self, &ack);
+        self.packet_acknowledgement.insert(key, commitment);
         Ok(())
     }
 
@@ -580,6 +1152,24 @@ impl ChannelKeeper for MockContext {
         Ok(())
     }
 
+    fn store_channel_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_upgrade: &ChannelUpgrade,
+    ) -> Result<(), Ics4Error> {
+        self.channel_upgrades
+            .insert(port_channel_id, channel_upgrade.clone());
+        Ok(())
+    }
+
+    fn delete_channel_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics4Error> {
+        self.channel_upgrades.remove(&port_channel_id);
+        Ok(())
+    }
+
     fn store_next_sequence_send(
         &mut self,
         port_channel_id: (PortId, ChannelId),
@@ -666,6 +1256,10 @@ impl ConnectionReader for MockContext {
         block_ref.cloned().map(Into::into)
     }
 
+    fn get_compatible_versions(&self) -> Vec<Version> {
+        self.connection_versions.clone()
+    }
+
     fn connection_counter(&self) -> u64 {
         self.connection_ids_counter
     }
@@ -722,6 +1316,16 @@ impl ClientReader for MockContext {
         }
     }
 
+    /// The host time is that of the block currently being executed, i.e., the block that follows
+    /// the latest block in the history.
+    fn host_time(&self) -> Time {
+        self.latest_time + Duration::from_secs(DEFAULT_BLOCK_TIME_SECS)
+    }
+
+    fn host_height(&self) -> Height {
+        self.latest_height
+    }
+
     fn client_counter(&self) -> u64 {
         self.client_ids_counter
     }
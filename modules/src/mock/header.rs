@@ -46,10 +46,11 @@ impl MockHeader {
     pub fn height(&self) -> Height {
         self.height
     }
+    /// A header at `height`, stamped with the timestamp of the blocks of mock host chains.
     pub fn new(height: Height) -> Self {
         Self {
             height,
-            timestamp: Default::default(),
+            timestamp: 1,
         }
     }
 }
@@ -76,6 +77,6 @@ impl Header for MockHeader {
 
 impl From<MockHeader> for AnyConsensusState {
     fn from(h: MockHeader) -> Self {
-        AnyConsensusState::Mock(MockConsensusState(h))
+        AnyConsensusState::Mock(MockConsensusState::new(h))
     }
 }
@@ -1,10 +1,12 @@
 //! Host chain types and methods, used by context mock.
 
-use std::convert::TryFrom;
-
-use tendermint::chain::Id as TMChainId;
+use chrono::{DateTime, Utc};
+use tendermint::Time;
 use tendermint_testgen::light_block::TmLightBlock;
-use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+use tendermint_testgen::{
+    Commit as TestgenCommit, Generator, Header as TestgenHeader, LightBlock as TestgenLightBlock,
+    Validator as TestgenValidator,
+};
 
 use crate::ics02_client::client_consensus::AnyConsensusState;
 use crate::ics02_client::header::AnyHeader;
@@ -45,24 +47,41 @@ impl HostBlock {
         }
     }
 
-    /// Generates a new block at `height` for the given chain identifier and chain type.
-    pub fn generate_block(chain_id: ChainId, chain_type: HostType, height: u64) -> HostBlock {
+    /// Generates a new block at `height` and with timestamp `time` for the given chain identifier
+    /// and chain type.
+    pub fn generate_block(
+        chain_id: ChainId,
+        chain_type: HostType,
+        height: u64,
+        time: Time,
+    ) -> HostBlock {
         match chain_type {
             HostType::Mock => HostBlock::Mock(MockHeader {
                 height: Height::new(chain_id.version(), height),
                 timestamp: 1,
             }),
-            HostType::SyntheticTendermint => {
-                HostBlock::SyntheticTendermint(Box::new(Self::generate_tm_block(chain_id, height)))
-            }
+            HostType::SyntheticTendermint => HostBlock::SyntheticTendermint(Box::new(
+                Self::generate_tm_block(chain_id, height, time),
+            )),
         }
     }
 
-    pub fn generate_tm_block(chain_id: ChainId, height: u64) -> TmLightBlock {
-        let mut block = TestgenLightBlock::new_default(height).generate().unwrap();
-        block.signed_header.header.chain_id = TMChainId::try_from(chain_id.to_string()).unwrap();
+    /// Generates a Tendermint light block at `height` and with timestamp `time` (truncated to
+    /// seconds) for the given chain identifier. The block is signed by a constant validator set,
+    /// which is also the next validator set, so that consecutive blocks verify against each other.
+    pub fn generate_tm_block(chain_id: ChainId, height: u64, time: Time) -> TmLightBlock {
+        let validators = [
+            TestgenValidator::new("1").voting_power(50),
+            TestgenValidator::new("2").voting_power(50),
+        ];
+        let header = TestgenHeader::new(&validators)
+            .height(height)
+            .chain_id(chain_id.as_str())
+            .next_validators(&validators)
+            .time(DateTime::<Utc>::from(time).timestamp() as u64);
+        let commit = TestgenCommit::new(header.clone(), 1);
 
-        block
+        TestgenLightBlock::new(header, commit).generate().unwrap()
     }
 }
 
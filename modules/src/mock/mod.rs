@@ -1,4 +1,4 @@
-//! Implementation of mocks for context, host chain, and client.
+//! Implementation of mocks for context, host chain, client, and application module.
 
 pub mod client_def;
 pub mod client_state;
@@ -6,3 +6,4 @@ pub mod context;
 pub mod header;
 pub mod host;
 pub mod misbehaviour;
+pub mod module;
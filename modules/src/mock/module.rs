@@ -0,0 +1,69 @@
+//! Implementation of a mock application module.
+
+use crate::handler::HandlerOutput;
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::packet::Packet;
+use crate::ics05_port::capabilities::Capability;
+use crate::ics24_host::identifier::{ChannelId, PortId};
+use crate::ics26_routing::error::Error;
+use crate::ics26_routing::module::Module;
+use crate::signer::Signer;
+
+/// An application module which accepts any channel handshake or upgrade, keeping the version
+/// proposed for the channel, and which acknowledges any packet successfully.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MockModule;
+
+impl<Ctx> Module<Ctx> for MockModule {
+    fn on_chan_open_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _channel_cap: &Capability,
+        _channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_open_try(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _channel_cap: &Capability,
+        channel_end: &ChannelEnd,
+        _counterparty_version: &str,
+    ) -> Result<String, Error> {
+        Ok(channel_end.version())
+    }
+
+    fn on_chan_upgrade_init(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_chan_upgrade_try(
+        &self,
+        _ctx: &mut Ctx,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _proposed_channel_end: &ChannelEnd,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_recv_packet(
+        &self,
+        _ctx: &mut Ctx,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> HandlerOutput<Option<Vec<u8>>> {
+        HandlerOutput::builder().with_result(Some(vec![1]))
+    }
+}
@@ -0,0 +1,577 @@
+//! An AVL tree whose leaves hold the key-value pairs, and whose nodes are hashed into a Merkle
+//! root, such that the tree can prove the existence and the absence of keys with ICS 23 proofs.
+//!
+//! A leaf hashes as `sha256(0x00 || len(key) || key || len(value) || value)`, where the lengths
+//! are protobuf varints, and an inner node hashes as `sha256(0x01 || left || right)`. See
+//! `proof_spec` for the corresponding ICS 23 proof specification.
+
+use std::cmp::Ordering;
+
+use ics23::commitment_proof::Proof;
+use ics23::{
+    CommitmentProof, ExistenceProof, HashOp, InnerOp, InnerSpec, LeafOp, LengthOp,
+    NonExistenceProof, ProofSpec,
+};
+use prost::encoding::encode_varint;
+use sha2::{Digest, Sha256};
+
+const LEAF_PREFIX: u8 = 0;
+const INNER_PREFIX: u8 = 1;
+const HASH_SIZE: usize = 32;
+
+/// Returns the ICS 23 specification of the proofs of an `AvlTree`.
+pub fn proof_spec() -> ProofSpec {
+    ProofSpec {
+        leaf_spec: Some(leaf_op()),
+        inner_spec: Some(InnerSpec {
+            child_order: vec![0, 1],
+            child_size: HASH_SIZE as i32,
+            min_prefix_length: 1,
+            max_prefix_length: 1,
+            empty_child: vec![],
+            hash: HashOp::Sha256 as i32,
+        }),
+        max_depth: 0,
+        min_depth: 0,
+    }
+}
+
+fn leaf_op() -> LeafOp {
+    LeafOp {
+        hash: HashOp::Sha256 as i32,
+        prehash_key: HashOp::NoHash as i32,
+        prehash_value: HashOp::NoHash as i32,
+        length: LengthOp::VarProto as i32,
+        prefix: vec![LEAF_PREFIX],
+    }
+}
+
+fn inner_op(prefix: Vec<u8>, suffix: Vec<u8>) -> InnerOp {
+    InnerOp {
+        hash: HashOp::Sha256 as i32,
+        prefix,
+        suffix,
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node<K, V> {
+    Leaf {
+        key: K,
+        value: V,
+        hash: Vec<u8>,
+    },
+    /// An inner node, whose `key` is the smallest key of its right subtree.
+    Inner {
+        key: K,
+        height: u32,
+        left: Box<Node<K, V>>,
+        right: Box<Node<K, V>>,
+        hash: Vec<u8>,
+    },
+}
+
+impl<K, V> Node<K, V>
+where
+    K: Ord + AsRef<[u8]> + Clone,
+    V: AsRef<[u8]>,
+{
+    fn leaf(key: K, value: V) -> Box<Self> {
+        let mut data = vec![LEAF_PREFIX];
+        encode_varint(key.as_ref().len() as u64, &mut data);
+        data.extend_from_slice(key.as_ref());
+        encode_varint(value.as_ref().len() as u64, &mut data);
+        data.extend_from_slice(value.as_ref());
+
+        Box::new(Node::Leaf {
+            hash: Sha256::digest(&data).to_vec(),
+            key,
+            value,
+        })
+    }
+
+    fn inner(key: K, left: Box<Self>, right: Box<Self>) -> Box<Self> {
+        let mut hasher = Sha256::new();
+        hasher.update(&[INNER_PREFIX]);
+        hasher.update(left.hash());
+        hasher.update(right.hash());
+
+        Box::new(Node::Inner {
+            key,
+            height: 1 + left.height().max(right.height()),
+            hash: hasher.finalize().to_vec(),
+            left,
+            right,
+        })
+    }
+
+    fn hash(&self) -> &[u8] {
+        match self {
+            Node::Leaf { hash, .. } | Node::Inner { hash, .. } => hash,
+        }
+    }
+
+    fn height(&self) -> u32 {
+        match self {
+            Node::Leaf { .. } => 0,
+            Node::Inner { height, .. } => *height,
+        }
+    }
+
+    fn min_key(&self) -> &K {
+        match self {
+            Node::Leaf { key, .. } => key,
+            Node::Inner { left, .. } => left.min_key(),
+        }
+    }
+
+    fn max_key(&self) -> &K {
+        match self {
+            Node::Leaf { key, .. } => key,
+            Node::Inner { right, .. } => right.max_key(),
+        }
+    }
+
+    /// Inserts `value` under `key` in the subtree of this node, and returns the new subtree. The
+    /// value previously stored under `key`, if any, goes into `old`.
+    fn insert(self: Box<Self>, key: K, value: V, old: &mut Option<V>) -> Box<Self> {
+        match *self {
+            Node::Leaf {
+                key: leaf_key,
+                value: leaf_value,
+                ..
+            } => match key.cmp(&leaf_key) {
+                Ordering::Equal => {
+                    *old = Some(leaf_value);
+                    Node::leaf(key, value)
+                }
+                Ordering::Less => Node::inner(
+                    leaf_key.clone(),
+                    Node::leaf(key, value),
+                    Node::leaf(leaf_key, leaf_value),
+                ),
+                Ordering::Greater => Node::inner(
+                    key.clone(),
+                    Node::leaf(leaf_key, leaf_value),
+                    Node::leaf(key, value),
+                ),
+            },
+            Node::Inner {
+                key: split_key,
+                left,
+                right,
+                ..
+            } => {
+                if key < split_key {
+                    Node::balance(split_key, left.insert(key, value, old), right)
+                } else {
+                    Node::balance(split_key, left, right.insert(key, value, old))
+                }
+            }
+        }
+    }
+
+    /// Removes `key` from the subtree of this node, and returns the new subtree, if any. The
+    /// removed value goes into `removed`.
+    fn remove(self: Box<Self>, key: &[u8], removed: &mut Option<V>) -> Option<Box<Self>> {
+        match *self {
+            Node::Leaf {
+                key: leaf_key,
+                value,
+                hash,
+            } => {
+                if leaf_key.as_ref() == key {
+                    *removed = Some(value);
+                    None
+                } else {
+                    Some(Box::new(Node::Leaf {
+                        key: leaf_key,
+                        value,
+                        hash,
+                    }))
+                }
+            }
+            Node::Inner {
+                key: split_key,
+                left,
+                right,
+                ..
+            } => {
+                if key < split_key.as_ref() {
+                    match left.remove(key, removed) {
+                        Some(left) => Some(Node::balance(split_key, left, right)),
+                        None => Some(right),
+                    }
+                } else {
+                    match right.remove(key, removed) {
+                        // The smallest key of the right subtree may be the removed one.
+                        Some(right) => Some(Node::balance(right.min_key().clone(), left, right)),
+                        None => Some(left),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the inner node with the given children, after rotating them if their heights
+    /// differ by more than one. The smallest key of each right subtree is preserved by rotations.
+    fn balance(key: K, left: Box<Self>, right: Box<Self>) -> Box<Self> {
+        if left.height() > right.height() + 1 {
+            match *left {
+                Node::Inner {
+                    key: left_key,
+                    left: left_left,
+                    right: left_right,
+                    ..
+                } => {
+                    if left_left.height() >= left_right.height() {
+                        Node::inner(left_key, left_left, Node::inner(key, left_right, right))
+                    } else {
+                        match *left_right {
+                            Node::Inner {
+                                key: middle_key,
+                                left: middle_left,
+                                right: middle_right,
+                                ..
+                            } => Node::inner(
+                                middle_key,
+                                Node::inner(left_key, left_left, middle_left),
+                                Node::inner(key, middle_right, right),
+                            ),
+                            Node::Leaf { .. } => unreachable!("a leaf is higher than its sibling"),
+                        }
+                    }
+                }
+                Node::Leaf { .. } => unreachable!("a leaf is higher than its sibling"),
+            }
+        } else if right.height() > left.height() + 1 {
+            match *right {
+                Node::Inner {
+                    key: right_key,
+                    left: right_left,
+                    right: right_right,
+                    ..
+                } => {
+                    if right_right.height() >= right_left.height() {
+                        Node::inner(right_key, Node::inner(key, left, right_left), right_right)
+                    } else {
+                        match *right_left {
+                            Node::Inner {
+                                key: middle_key,
+                                left: middle_left,
+                                right: middle_right,
+                                ..
+                            } => Node::inner(
+                                middle_key,
+                                Node::inner(key, left, middle_left),
+                                Node::inner(right_key, middle_right, right_right),
+                            ),
+                            Node::Leaf { .. } => unreachable!("a leaf is higher than its sibling"),
+                        }
+                    }
+                }
+                Node::Leaf { .. } => unreachable!("a leaf is higher than its sibling"),
+            }
+        } else {
+            Node::inner(key, left, right)
+        }
+    }
+}
+
+/// A balanced Merkle tree of key-value pairs. The ordering of the keys must be the lexicographic
+/// ordering of their bytes, as ICS 23 non-existence proofs rely on it.
+#[derive(Clone, Debug)]
+pub struct AvlTree<K, V> {
+    root: Option<Box<Node<K, V>>>,
+}
+
+impl<K, V> Default for AvlTree<K, V> {
+    fn default() -> Self {
+        Self { root: None }
+    }
+}
+
+impl<K, V> AvlTree<K, V>
+where
+    K: Ord + AsRef<[u8]> + Clone,
+    V: AsRef<[u8]>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value stored under `key`, if any.
+    pub fn get(&self, key: &[u8]) -> Option<&V> {
+        let mut node = self.root.as_deref()?;
+        loop {
+            match node {
+                Node::Inner {
+                    key: split_key,
+                    left,
+                    right,
+                    ..
+                } => {
+                    node = if key < split_key.as_ref() {
+                        left.as_ref()
+                    } else {
+                        right.as_ref()
+                    };
+                }
+                Node::Leaf {
+                    key: leaf_key,
+                    value,
+                    ..
+                } => return Some(value).filter(|_| leaf_key.as_ref() == key),
+            }
+        }
+    }
+
+    /// Stores `value` under `key`, and returns the value previously stored there, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut old = None;
+        self.root = Some(match self.root.take() {
+            Some(root) => root.insert(key, value, &mut old),
+            None => Node::leaf(key, value),
+        });
+        old
+    }
+
+    /// Removes the value stored under `key`, and returns it, if any.
+    pub fn remove(&mut self, key: &[u8]) -> Option<V> {
+        self.get(key)?;
+
+        let mut removed = None;
+        self.root = self
+            .root
+            .take()
+            .and_then(|root| root.remove(key, &mut removed));
+        removed
+    }
+
+    /// Returns the Merkle root of the tree, which is empty if the tree is empty.
+    pub fn root_hash(&self) -> Vec<u8> {
+        self.root
+            .as_ref()
+            .map_or_else(Vec::new, |root| root.hash().to_vec())
+    }
+
+    /// Returns all the key-value pairs of the tree, ordered by key.
+    pub fn entries(&self) -> Vec<(&K, &V)> {
+        fn collect<'a, K, V>(node: &'a Node<K, V>, entries: &mut Vec<(&'a K, &'a V)>) {
+            match node {
+                Node::Leaf { key, value, .. } => entries.push((key, value)),
+                Node::Inner { left, right, .. } => {
+                    collect(left, entries);
+                    collect(right, entries);
+                }
+            }
+        }
+
+        let mut entries = Vec::new();
+        if let Some(root) = &self.root {
+            collect(root, &mut entries);
+        }
+        entries
+    }
+
+    /// Returns the proof of existence of `key` against the root of the tree, or the proof of
+    /// its absence if there is no value under `key`. The absence of a key cannot be proven in
+    /// an empty tree, which has no proofs.
+    pub fn get_proof(&self, key: &[u8]) -> Option<CommitmentProof> {
+        self.root.as_ref()?;
+
+        let proof = match self.existence_proof(key) {
+            Some(proof) => Proof::Exist(proof),
+            None => {
+                // The neighbors of an absent key are adjacent leaves of the tree, so proving
+                // their existence proves that there is no leaf in between.
+                let (left, right) = self.neighbors(key);
+                Proof::Nonexist(NonExistenceProof {
+                    key: key.to_vec(),
+                    left: left.and_then(|left| self.existence_proof(left.as_ref())),
+                    right: right.and_then(|right| self.existence_proof(right.as_ref())),
+                })
+            }
+        };
+
+        Some(CommitmentProof { proof: Some(proof) })
+    }
+
+    fn existence_proof(&self, key: &[u8]) -> Option<ExistenceProof> {
+        let mut node = self.root.as_deref()?;
+        let mut path = Vec::new();
+        loop {
+            match node {
+                Node::Inner {
+                    key: split_key,
+                    left,
+                    right,
+                    ..
+                } => {
+                    if key < split_key.as_ref() {
+                        path.push(inner_op(vec![INNER_PREFIX], right.hash().to_vec()));
+                        node = left.as_ref();
+                    } else {
+                        let mut prefix = vec![INNER_PREFIX];
+                        prefix.extend_from_slice(left.hash());
+                        path.push(inner_op(prefix, vec![]));
+                        node = right.as_ref();
+                    }
+                }
+                Node::Leaf {
+                    key: leaf_key,
+                    value,
+                    ..
+                } => {
+                    if leaf_key.as_ref() != key {
+                        return None;
+                    }
+
+                    // The proof goes from the leaf up to the root.
+                    path.reverse();
+                    return Some(ExistenceProof {
+                        key: key.to_vec(),
+                        value: value.as_ref().to_vec(),
+                        leaf: Some(leaf_op()),
+                        path,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the largest key smaller than `key` and the smallest key larger than `key`.
+    fn neighbors(&self, key: &[u8]) -> (Option<&K>, Option<&K>) {
+        let (mut smaller, mut larger) = (None, None);
+        let mut node = match self.root.as_deref() {
+            Some(root) => root,
+            None => return (smaller, larger),
+        };
+
+        loop {
+            match node {
+                Node::Inner {
+                    key: split_key,
+                    left,
+                    right,
+                    ..
+                } => {
+                    if key < split_key.as_ref() {
+                        larger = Some(split_key);
+                        node = left.as_ref();
+                    } else {
+                        smaller = Some(left.max_key());
+                        node = right.as_ref();
+                    }
+                }
+                Node::Leaf { key: leaf_key, .. } => {
+                    match leaf_key.as_ref().cmp(key) {
+                        Ordering::Less => smaller = Some(leaf_key),
+                        Ordering::Greater => larger = Some(leaf_key),
+                        Ordering::Equal => {}
+                    }
+                    return (smaller, larger);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::avl::{proof_spec, AvlTree};
+
+    fn key(i: u32) -> Vec<u8> {
+        format!("key-{:03}", i).into_bytes()
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut tree = AvlTree::new();
+        // Inserting the keys in order exercises the rotations.
+        for i in 0..100 {
+            assert_eq!(tree.insert(key(i), i.to_be_bytes().to_vec()), None);
+        }
+        assert!(tree.root.as_ref().unwrap().height() <= 10);
+
+        assert_eq!(tree.get(&key(42)), Some(&42u32.to_be_bytes().to_vec()));
+        assert_eq!(
+            tree.insert(key(42), vec![42]),
+            Some(42u32.to_be_bytes().to_vec())
+        );
+        assert_eq!(tree.get(&key(42)), Some(&vec![42]));
+
+        assert_eq!(tree.remove(&key(42)), Some(vec![42]));
+        assert_eq!(tree.remove(&key(42)), None);
+        assert_eq!(tree.get(&key(42)), None);
+
+        let keys: Vec<Vec<u8>> = tree.entries().into_iter().map(|(k, _)| k.clone()).collect();
+        let expected: Vec<Vec<u8>> = (0..100).filter(|i| *i != 42).map(key).collect();
+        assert_eq!(keys, expected);
+
+        for i in 0..100 {
+            tree.remove(&key(i));
+        }
+        assert!(tree.root.is_none());
+        assert!(tree.root_hash().is_empty());
+    }
+
+    #[test]
+    fn existence_and_non_existence_proofs() {
+        let spec = proof_spec();
+        let mut tree = AvlTree::new();
+
+        // The absence of a key cannot be proven in an empty tree.
+        assert!(tree.get_proof(&key(0)).is_none());
+
+        // Only the even keys are in the tree.
+        for i in (0..50).map(|i| 2 * i) {
+            tree.insert(key(i), i.to_be_bytes().to_vec());
+        }
+        let root = tree.root_hash();
+
+        for i in 0..100 {
+            let proof = tree.get_proof(&key(i)).unwrap();
+            match tree.get(&key(i)) {
+                Some(value) => {
+                    assert!(ics23::verify_membership(
+                        &proof,
+                        &spec,
+                        &root,
+                        &key(i),
+                        value
+                    ));
+                    assert!(!ics23::verify_non_membership(&proof, &spec, &root, &key(i)));
+                }
+                None => {
+                    assert!(ics23::verify_non_membership(&proof, &spec, &root, &key(i)));
+                }
+            }
+        }
+
+        // Keys before the first key and after the last key.
+        for absent in &[b"a".to_vec(), b"z".to_vec()] {
+            let proof = tree.get_proof(absent).unwrap();
+            assert!(ics23::verify_non_membership(&proof, &spec, &root, absent));
+        }
+
+        // Proofs do not hold against the root of another state of the tree.
+        let value = tree.get(&key(0)).unwrap().clone();
+        tree.insert(key(1), vec![1]);
+        let proof = tree.get_proof(&key(0)).unwrap();
+        assert!(!ics23::verify_membership(
+            &proof,
+            &spec,
+            &root,
+            &key(0),
+            &value
+        ));
+        assert!(ics23::verify_membership(
+            &proof,
+            &spec,
+            &tree.root_hash(),
+            &key(0),
+            &value
+        ));
+    }
+}
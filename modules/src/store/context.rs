@@ -0,0 +1,837 @@
+//! Implementations of the client, connection and channel contexts for any host chain whose IBC
+//! state lives in a `ProvableStore`. The values are encoded as in the Cosmos SDK. The proofs are
+//! however those of the `ProvableStore`, e.g., the single-layer AVL tree proofs of the
+//! `InMemoryStore`, and not the chained IAVL and simple Merkle proofs of the multistore of a
+//! Cosmos SDK chain, so a counterparty chain only verifies them with a matching proof
+//! specification.
+
+use std::convert::TryFrom;
+
+use prost::Message;
+use tendermint::Time;
+use tendermint_proto::Protobuf;
+
+use ibc_proto::ibc::core::connection::v1::ClientPaths;
+
+use crate::ics02_client::client_consensus::AnyConsensusState;
+use crate::ics02_client::client_state::AnyClientState;
+use crate::ics02_client::client_type::ClientType;
+use crate::ics02_client::context::{ClientKeeper, ClientReader};
+use crate::ics02_client::error::{Error as Ics02Error, Kind as Ics02Kind};
+use crate::ics03_connection::connection::ConnectionEnd;
+use crate::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
+use crate::ics03_connection::error::{Error as Ics03Error, Kind as Ics03Kind};
+use crate::ics03_connection::version::{get_compatible_versions, Version};
+use crate::ics04_channel::channel::ChannelEnd;
+use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+use crate::ics04_channel::error::{Error as Ics04Error, Kind as Ics04Kind};
+use crate::ics04_channel::packet::{Receipt, Sequence};
+use crate::ics04_channel::upgrade::ChannelUpgrade;
+use crate::ics05_port::capabilities::{Capability, CapabilityName, ModuleId};
+use crate::ics05_port::context::{CapabilityKeeper, CapabilityReader, PortReader};
+use crate::ics23_commitment::commitment::CommitmentPrefix;
+use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use crate::ics24_host::Path;
+use crate::store::{ProvableStore, Store};
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+/// A host chain whose IBC state lives in a `ProvableStore`. Such a host gets `ClientReader`,
+/// `ClientKeeper`, `ConnectionReader`, `ConnectionKeeper`, `ChannelReader` and `ChannelKeeper`
+/// from the implementations in this module, and only supplies what the store does not hold: the
+/// view of its own chain, and the capabilities of its ports and channels.
+///
+/// The counters of clients, connections and channels are the numbers of identifiers of each kind
+/// in the store, and the channels of a connection are looked up among the channel ends.
+pub trait StoreContext: PortReader + CapabilityKeeper {
+    type Store: ProvableStore;
+
+    /// Returns the store holding the IBC state of the host chain.
+    fn store(&self) -> &Self::Store;
+
+    /// Returns the store holding the IBC state of the host chain, for writing.
+    fn store_mut(&mut self) -> &mut Self::Store;
+
+    /// Returns the current height of the host chain.
+    fn host_height(&self) -> Height;
+
+    /// Returns the oldest height of the host chain, whose consensus state is still available.
+    fn host_oldest_height(&self) -> Height;
+
+    /// Returns the current time of the host chain.
+    fn host_time(&self) -> Time;
+
+    /// Returns the consensus state of the host chain at `height`.
+    fn host_consensus_state(&self, height: Height) -> Option<AnyConsensusState>;
+
+    /// Returns the prefix under which the counterparty chains find the IBC store of the host
+    /// chain in its state, e.g., the name of the IBC sub-store of a Cosmos SDK chain.
+    fn commitment_prefix(&self) -> CommitmentPrefix {
+        CommitmentPrefix::from(b"ibc".to_vec())
+    }
+
+    /// Returns the connection versions which the host chain supports (see
+    /// `ConnectionReader::get_compatible_versions`).
+    fn get_compatible_versions(&self) -> Vec<Version> {
+        get_compatible_versions()
+    }
+}
+
+fn consensus_state_path(client_id: &ClientId, height: Height) -> Path {
+    Path::ClientConsensusState {
+        client_id: client_id.clone(),
+        epoch: height.revision_number,
+        height: height.revision_height,
+    }
+}
+
+fn commitment_path(key: &(PortId, ChannelId, Sequence)) -> Path {
+    Path::Commitments {
+        port_id: key.0.clone(),
+        channel_id: key.1.clone(),
+        sequence: key.2,
+    }
+}
+
+fn ack_path(key: &(PortId, ChannelId, Sequence)) -> Path {
+    Path::Acks {
+        port_id: key.0.clone(),
+        channel_id: key.1.clone(),
+        sequence: key.2,
+    }
+}
+
+fn receipt_path(key: &(PortId, ChannelId, Sequence)) -> Path {
+    Path::Receipts {
+        port_id: key.0.clone(),
+        channel_id: key.1.clone(),
+        sequence: key.2,
+    }
+}
+
+/// Sequence numbers are stored as big-endian 64-bit integers.
+fn get_sequence<S: Store>(store: &S, path: &Path) -> Option<Sequence> {
+    let bytes = <[u8; 8]>::try_from(store.get(path)?.as_slice()).ok()?;
+    Some(u64::from_be_bytes(bytes).into())
+}
+
+fn set_sequence<S: Store>(store: &mut S, path: Path, seq: Sequence) -> Result<(), Ics04Error> {
+    store
+        .set(path, u64::from(seq).to_be_bytes().to_vec())
+        .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+    Ok(())
+}
+
+impl<T: StoreContext> ClientReader for T {
+    fn client_type(&self, client_id: &ClientId) -> Option<ClientType> {
+        let bytes = self.store().get(&Path::ClientType(client_id.clone()))?;
+        String::from_utf8(bytes).ok()?.parse().ok()
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Option<AnyClientState> {
+        let bytes = self.store().get(&Path::ClientState(client_id.clone()))?;
+        AnyClientState::decode_vec(&bytes).ok()
+    }
+
+    fn consensus_state(&self, client_id: &ClientId, height: Height) -> Option<AnyConsensusState> {
+        let bytes = self.store().get(&consensus_state_path(client_id, height))?;
+        AnyConsensusState::decode_vec(&bytes).ok()
+    }
+
+    fn host_time(&self) -> Time {
+        StoreContext::host_time(self)
+    }
+
+    fn host_height(&self) -> Height {
+        StoreContext::host_height(self)
+    }
+
+    fn client_counter(&self) -> u64 {
+        self.store()
+            .paths("clients/")
+            .iter()
+            .filter(|path| matches!(path, Path::ClientType(_)))
+            .count() as u64
+    }
+}
+
+impl<T: StoreContext> ClientKeeper for T {
+    fn store_client_type(
+        &mut self,
+        client_id: ClientId,
+        client_type: ClientType,
+    ) -> Result<(), Ics02Error> {
+        self.store_mut()
+            .set(
+                Path::ClientType(client_id),
+                client_type.as_string().as_bytes().to_vec(),
+            )
+            .map_err(|e| Ics02Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_client_state(
+        &mut self,
+        client_id: ClientId,
+        client_state: AnyClientState,
+    ) -> Result<(), Ics02Error> {
+        let bytes = client_state
+            .encode_vec()
+            .map_err(|e| Ics02Kind::ImplementationSpecific.context(e))?;
+        self.store_mut()
+            .set(Path::ClientState(client_id), bytes)
+            .map_err(|e| Ics02Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        consensus_state: AnyConsensusState,
+    ) -> Result<(), Ics02Error> {
+        let bytes = consensus_state
+            .encode_vec()
+            .map_err(|e| Ics02Kind::ImplementationSpecific.context(e))?;
+        self.store_mut()
+            .set(consensus_state_path(&client_id, height), bytes)
+            .map_err(|e| Ics02Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn increase_client_counter(&mut self) {
+        // The counter is the number of client types in the store, which the new client increased.
+    }
+}
+
+impl<T: StoreContext> ConnectionReader for T {
+    fn connection_end(&self, conn_id: &ConnectionId) -> Option<ConnectionEnd> {
+        let bytes = self.store().get(&Path::Connections(conn_id.clone()))?;
+        ConnectionEnd::decode_vec(&bytes).ok()
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Option<AnyClientState> {
+        ClientReader::client_state(self, client_id)
+    }
+
+    fn host_current_height(&self) -> Height {
+        StoreContext::host_height(self)
+    }
+
+    fn host_oldest_height(&self) -> Height {
+        StoreContext::host_oldest_height(self)
+    }
+
+    fn commitment_prefix(&self) -> CommitmentPrefix {
+        StoreContext::commitment_prefix(self)
+    }
+
+    fn client_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Option<AnyConsensusState> {
+        ClientReader::consensus_state(self, client_id, height)
+    }
+
+    fn host_consensus_state(&self, height: Height) -> Option<AnyConsensusState> {
+        StoreContext::host_consensus_state(self, height)
+    }
+
+    fn get_compatible_versions(&self) -> Vec<Version> {
+        StoreContext::get_compatible_versions(self)
+    }
+
+    fn connection_counter(&self) -> u64 {
+        self.store().paths("connections/").len() as u64
+    }
+}
+
+impl<T: StoreContext> ConnectionKeeper for T {
+    fn store_connection(
+        &mut self,
+        connection_id: ConnectionId,
+        connection_end: &ConnectionEnd,
+    ) -> Result<(), Ics03Error> {
+        let bytes = connection_end
+            .encode_vec()
+            .map_err(|e| Ics03Kind::ImplementationSpecific.context(e))?;
+        self.store_mut()
+            .set(Path::Connections(connection_id), bytes)
+            .map_err(|e| Ics03Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_connection_to_client(
+        &mut self,
+        connection_id: ConnectionId,
+        client_id: &ClientId,
+    ) -> Result<(), Ics03Error> {
+        let path = Path::ClientConnections(client_id.clone());
+
+        let mut client_paths = match self.store().get(&path) {
+            Some(bytes) => ClientPaths::decode(bytes.as_slice())
+                .map_err(|e| Ics03Kind::ImplementationSpecific.context(e))?,
+            None => ClientPaths::default(),
+        };
+        client_paths.paths.push(connection_id.to_string());
+
+        let mut bytes = Vec::new();
+        client_paths
+            .encode(&mut bytes)
+            .map_err(|e| Ics03Kind::ImplementationSpecific.context(e))?;
+        self.store_mut()
+            .set(path, bytes)
+            .map_err(|e| Ics03Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn increase_connection_counter(&mut self) {
+        // The counter is the number of connection ends in the store, which the new connection
+        // increased.
+    }
+}
+
+impl<T: StoreContext> ChannelReader for T {
+    fn channel_end(&self, port_channel_id: &(PortId, ChannelId)) -> Option<ChannelEnd> {
+        let (port_id, channel_id) = port_channel_id.clone();
+        let bytes = self.store().get(&Path::ChannelEnds(port_id, channel_id))?;
+        ChannelEnd::decode_vec(&bytes).ok()
+    }
+
+    fn channel_upgrade(&self, port_channel_id: &(PortId, ChannelId)) -> Option<ChannelUpgrade> {
+        let (port_id, channel_id) = port_channel_id.clone();
+        let bytes = self
+            .store()
+            .get(&Path::ChannelUpgrades(port_id, channel_id))?;
+        ChannelUpgrade::decode_vec(&bytes).ok()
+    }
+
+    fn connection_end(&self, connection_id: &ConnectionId) -> Option<ConnectionEnd> {
+        ConnectionReader::connection_end(self, connection_id)
+    }
+
+    fn connection_channels(&self, cid: &ConnectionId) -> Option<Vec<(PortId, ChannelId)>> {
+        let channels: Vec<(PortId, ChannelId)> = self
+            .store()
+            .paths("channelEnds/")
+            .into_iter()
+            .filter_map(|path| match path {
+                Path::ChannelEnds(port_id, channel_id) => Some((port_id, channel_id)),
+                _ => None,
+            })
+            .filter(|port_channel_id| {
+                ChannelReader::channel_end(self, port_channel_id).map_or(false, |channel_end| {
+                    channel_end.connection_hops().first() == Some(cid)
+                })
+            })
+            .collect();
+
+        Some(channels).filter(|channels| !channels.is_empty())
+    }
+
+    fn client_state(&self, client_id: &ClientId) -> Option<AnyClientState> {
+        ClientReader::client_state(self, client_id)
+    }
+
+    fn client_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Option<AnyConsensusState> {
+        ClientReader::consensus_state(self, client_id, height)
+    }
+
+    fn connection_reader(&self) -> &dyn ConnectionReader {
+        self
+    }
+
+    fn authenticate_port_capability(
+        &self,
+        port_id: &PortId,
+        capability: &Capability,
+    ) -> Result<(), Ics04Error> {
+        if PortReader::lookup_module_by_port(self, port_id).is_none() {
+            return Err(Ics04Kind::NoPortCapability(port_id.clone()).into());
+        }
+
+        if !PortReader::authenticate(self, capability, port_id) {
+            return Err(Ics04Kind::InvalidPortCapability.into());
+        }
+
+        Ok(())
+    }
+
+    fn authenticate_channel_capability(
+        &self,
+        port_channel_id: &(PortId, ChannelId),
+        capability: &Capability,
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        self.authenticate_capability(
+            &ModuleId::ibc(),
+            &CapabilityName::channel(port_id, channel_id),
+            capability,
+        )
+        .map_err(|e| {
+            Ics04Kind::InvalidChannelCapability(port_id.clone(), channel_id.clone())
+                .context(e)
+                .into()
+        })
+    }
+
+    fn get_next_sequence_send(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence> {
+        let (port_id, channel_id) = port_channel_id.clone();
+        get_sequence(self.store(), &Path::SeqSends(port_id, channel_id))
+    }
+
+    fn get_next_sequence_recv(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence> {
+        let (port_id, channel_id) = port_channel_id.clone();
+        get_sequence(self.store(), &Path::SeqRecvs(port_id, channel_id))
+    }
+
+    fn get_next_sequence_ack(&self, port_channel_id: &(PortId, ChannelId)) -> Option<Sequence> {
+        let (port_id, channel_id) = port_channel_id.clone();
+        get_sequence(self.store(), &Path::SeqAcks(port_id, channel_id))
+    }
+
+    fn get_packet_commitment(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
+        self.store().get(&commitment_path(key))
+    }
+
+    fn get_packet_receipt(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Receipt> {
+        self.store().get(&receipt_path(key)).map(|_| Receipt::Ok)
+    }
+
+    fn get_packet_acknowledgement(&self, key: &(PortId, ChannelId, Sequence)) -> Option<Vec<u8>> {
+        self.store().get(&ack_path(key))
+    }
+
+    fn host_height(&self) -> Height {
+        StoreContext::host_height(self)
+    }
+
+    fn host_timestamp(&self) -> Timestamp {
+        // Only a time before the Unix epoch has no timestamp.
+        Timestamp::try_from(StoreContext::host_time(self)).unwrap_or_else(|_| Timestamp::none())
+    }
+
+    fn channel_counter(&self) -> u64 {
+        self.store().paths("channelEnds/").len() as u64
+    }
+}
+
+impl<T: StoreContext> ChannelKeeper for T {
+    fn new_channel_capability(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<Capability, Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        self.new_capability(
+            ModuleId::ibc(),
+            CapabilityName::channel(&port_id, &channel_id),
+        )
+        .map_err(|e| {
+            Ics04Kind::ChannelCapabilityCreationFailed(port_id, channel_id)
+                .context(e)
+                .into()
+        })
+    }
+
+    fn store_packet_commitment(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        timestamp: Timestamp,
+        height: Height,
+        data: Vec<u8>,
+    ) -> Result<(), Ics04Error> {
+        let commitment = ChannelReader::packet_commitment(self, timestamp, height, &data);
+        self.store_mut()
+            .set(commitment_path(&key), commitment)
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn delete_packet_commitment(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        self.store_mut()
+            .delete(&commitment_path(&key))
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_packet_receipt(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        receipt: Receipt,
+    ) -> Result<(), Ics04Error> {
+        // As in the Cosmos SDK, the receipt of a packet is a single byte.
+        let value = match receipt {
+            Receipt::Ok => vec![1],
+        };
+        self.store_mut()
+            .set(receipt_path(&key), value)
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_packet_acknowledgement(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+        ack: Vec<u8>,
+    ) -> Result<(), Ics04Error> {
+        let commitment = ChannelReader::ack_commitment(self, &ack);
+        self.store_mut()
+            .set(ack_path(&key), commitment)
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn delete_packet_acknowledgement(
+        &mut self,
+        key: (PortId, ChannelId, Sequence),
+    ) -> Result<(), Ics04Error> {
+        self.store_mut()
+            .delete(&ack_path(&key))
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_connection_channels(
+        &mut self,
+        _conn_id: ConnectionId,
+        _port_channel_id: &(PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        // The channels of a connection are looked up among the channel ends in the store.
+        Ok(())
+    }
+
+    fn store_channel(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_end: &ChannelEnd,
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        let bytes = channel_end
+            .encode_vec()
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        self.store_mut()
+            .set(Path::ChannelEnds(port_id, channel_id), bytes)
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_channel_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        channel_upgrade: &ChannelUpgrade,
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        let bytes = channel_upgrade
+            .encode_vec()
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        self.store_mut()
+            .set(Path::ChannelUpgrades(port_id, channel_id), bytes)
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn delete_channel_upgrade(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        self.store_mut()
+            .delete(&Path::ChannelUpgrades(port_id, channel_id))
+            .map_err(|e| Ics04Kind::ImplementationSpecific.context(e))?;
+        Ok(())
+    }
+
+    fn store_next_sequence_send(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        set_sequence(self.store_mut(), Path::SeqSends(port_id, channel_id), seq)
+    }
+
+    fn store_next_sequence_recv(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        set_sequence(self.store_mut(), Path::SeqRecvs(port_id, channel_id), seq)
+    }
+
+    fn store_next_sequence_ack(
+        &mut self,
+        port_channel_id: (PortId, ChannelId),
+        seq: Sequence,
+    ) -> Result<(), Ics04Error> {
+        let (port_id, channel_id) = port_channel_id;
+        set_sequence(self.store_mut(), Path::SeqAcks(port_id, channel_id), seq)
+    }
+
+    fn increase_channel_counter(&mut self) {
+        // The counter is the number of channel ends in the store, which the new channel increased.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tendermint::Time;
+
+    use crate::ics02_client::client_consensus::AnyConsensusState;
+    use crate::ics02_client::client_type::ClientType;
+    use crate::ics02_client::context::{ClientKeeper, ClientReader};
+    use crate::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use crate::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
+    use crate::ics03_connection::handler::{ConnectionIdState, ConnectionResult};
+    use crate::ics03_connection::version::get_compatible_versions;
+    use crate::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::ics04_channel::context::{ChannelKeeper, ChannelReader};
+    use crate::ics04_channel::handler::{ChannelIdState, ChannelResult};
+    use crate::ics04_channel::upgrade::{ChannelUpgrade, UpgradeState};
+    use crate::ics05_port::capabilities::{Capability, CapabilityName, ModuleId};
+    use crate::ics05_port::context::{CapabilityKeeper, CapabilityReader, PortKeeper, PortReader};
+    use crate::ics05_port::error::Error as Ics05Error;
+    use crate::ics23_commitment::commitment::CommitmentPrefix;
+    use crate::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    use crate::ics24_host::Path;
+    use crate::mock::client_state::{MockClientState, MockConsensusState};
+    use crate::mock::header::MockHeader;
+    use crate::store::context::StoreContext;
+    use crate::store::memory::InMemoryStore;
+    use crate::store::{ProvableStore, Store};
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    /// A host chain which keeps its IBC state in an `InMemoryStore`, and commits it at every
+    /// height.
+    #[derive(Default)]
+    struct StoreHost {
+        store: InMemoryStore,
+        capabilities: HashMap<(ModuleId, CapabilityName), Capability>,
+        capability_counter: u64,
+    }
+
+    impl CapabilityReader for StoreHost {
+        fn get_capability(&self, module: &ModuleId, name: &CapabilityName) -> Option<Capability> {
+            self.capabilities
+                .get(&(module.clone(), name.clone()))
+                .cloned()
+        }
+
+        fn capability_counter(&self) -> u64 {
+            self.capability_counter
+        }
+    }
+
+    impl CapabilityKeeper for StoreHost {
+        fn store_capability(
+            &mut self,
+            module: ModuleId,
+            name: CapabilityName,
+            capability: Capability,
+        ) -> Result<(), Ics05Error> {
+            self.capabilities.insert((module, name), capability);
+            Ok(())
+        }
+
+        fn increase_capability_counter(&mut self) {
+            self.capability_counter += 1;
+        }
+    }
+
+    impl PortReader for StoreHost {}
+
+    impl PortKeeper for StoreHost {}
+
+    impl StoreContext for StoreHost {
+        type Store = InMemoryStore;
+
+        fn store(&self) -> &Self::Store {
+            &self.store
+        }
+
+        fn store_mut(&mut self) -> &mut Self::Store {
+            &mut self.store
+        }
+
+        fn host_height(&self) -> Height {
+            Height::new(0, self.store.current_height() + 1)
+        }
+
+        fn host_oldest_height(&self) -> Height {
+            Height::new(0, 1)
+        }
+
+        fn host_time(&self) -> Time {
+            Time::now()
+        }
+
+        fn host_consensus_state(&self, _height: Height) -> Option<AnyConsensusState> {
+            None
+        }
+    }
+
+    #[test]
+    fn store_context() {
+        let mut host = StoreHost::default();
+        let height = Height::new(0, 10);
+        let client_id = ClientId::new(ClientType::Mock, 0).unwrap();
+        let connection_id = ConnectionId::new(0);
+        let port_id: PortId = "transfer".parse().unwrap();
+        let channel_id = ChannelId::new(0);
+
+        // Clients
+        host.store_client_type(client_id.clone(), ClientType::Mock)
+            .unwrap();
+        host.store_client_state(
+            client_id.clone(),
+            MockClientState::new(MockHeader::new(height)).into(),
+        )
+        .unwrap();
+        host.store_consensus_state(
+            client_id.clone(),
+            height,
+            MockConsensusState::new(MockHeader::new(height)).into(),
+        )
+        .unwrap();
+        host.increase_client_counter();
+
+        assert_eq!(
+            ClientReader::client_type(&host, &client_id),
+            Some(ClientType::Mock)
+        );
+        assert!(ClientReader::client_state(&host, &client_id).is_some());
+        assert!(ClientReader::consensus_state(&host, &client_id, height).is_some());
+        assert!(ClientReader::consensus_state(&host, &client_id, height.increment()).is_none());
+        assert_eq!(host.client_counter(), 1);
+
+        // Connections
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            client_id.clone(),
+            ConnectionCounterparty::new(client_id, None, CommitmentPrefix::from(b"ibc".to_vec())),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+        host.store_connection_result(ConnectionResult {
+            connection_id: connection_id.clone(),
+            connection_id_state: ConnectionIdState::Generated,
+            connection_end: connection_end.clone(),
+            client_state: None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            ConnectionReader::connection_end(&host, &connection_id),
+            Some(connection_end)
+        );
+        assert_eq!(host.connection_counter(), 1);
+
+        // Channels
+        let port_cap = host.bind_port(&port_id).unwrap();
+        let channel_end = ChannelEnd::new(
+            State::Init,
+            Order::Unordered,
+            Counterparty::new(port_id.clone(), None),
+            vec![connection_id.clone()],
+            "ics20-1".to_string(),
+        );
+        host.store_channel_result(ChannelResult {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            channel_id_state: ChannelIdState::Generated,
+            channel_end: channel_end.clone(),
+            client_state: None,
+            channel_upgrade: None,
+        })
+        .unwrap();
+        let port_channel_id = (port_id.clone(), channel_id.clone());
+        let channel_cap = host
+            .new_channel_capability(port_channel_id.clone())
+            .unwrap();
+
+        assert_eq!(
+            host.channel_end(&port_channel_id),
+            Some(channel_end.clone())
+        );
+        assert!(host
+            .authenticate_port_capability(&port_id, &port_cap)
+            .is_ok());
+        assert!(host
+            .authenticate_port_capability(&port_id, &channel_cap)
+            .is_err());
+        assert!(host
+            .authenticate_channel_capability(&port_channel_id, &channel_cap)
+            .is_ok());
+        assert_eq!(
+            host.get_next_sequence_send(&port_channel_id),
+            Some(1.into())
+        );
+        assert_eq!(
+            host.connection_channels(&connection_id),
+            Some(vec![port_channel_id.clone()])
+        );
+        assert_eq!(host.connection_channels(&ConnectionId::new(1)), None);
+        assert_eq!(host.channel_counter(), 1);
+
+        // Channel upgrades, stored apart from the channel end until the upgrade completes.
+        let upgraded_channel_end = ChannelEnd {
+            ordering: Order::Ordered,
+            ..channel_end.clone()
+        };
+        let channel_upgrade = ChannelUpgrade::new(UpgradeState::Init, upgraded_channel_end);
+        host.store_channel_upgrade(port_channel_id.clone(), &channel_upgrade)
+            .unwrap();
+        assert_eq!(
+            host.channel_upgrade(&port_channel_id),
+            Some(channel_upgrade)
+        );
+        assert_eq!(host.channel_end(&port_channel_id), Some(channel_end));
+        assert_eq!(host.channel_counter(), 1);
+        host.delete_channel_upgrade(port_channel_id.clone())
+            .unwrap();
+        assert_eq!(host.channel_upgrade(&port_channel_id), None);
+
+        // Packets, whose commitments are proven by the store once committed.
+        let key = (port_id.clone(), channel_id.clone(), 1.into());
+        host.store_packet_commitment(key.clone(), Timestamp::none(), height, vec![1])
+            .unwrap();
+        let commitment = host.get_packet_commitment(&key).unwrap();
+        assert_eq!(
+            commitment,
+            host.packet_commitment(Timestamp::none(), height, &[1])
+        );
+
+        let root = host.store_mut().commit().unwrap();
+        let path = Path::Commitments {
+            port_id,
+            channel_id,
+            sequence: 1.into(),
+        };
+        let (value, proof) = host.store().get_proof(1, &path).unwrap();
+        assert_eq!(value, Some(commitment.clone()));
+        assert!(ics23::verify_membership(
+            &proof,
+            &host.store().proof_spec(),
+            &root,
+            path.to_string().as_bytes(),
+            &commitment
+        ));
+
+        host.delete_packet_commitment(key.clone()).unwrap();
+        assert_eq!(host.get_packet_commitment(&key), None);
+    }
+}
@@ -0,0 +1,212 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use ics23::{CommitmentProof, ProofSpec};
+
+use crate::ics24_host::Path;
+use crate::store::avl::{self, AvlTree};
+use crate::store::error::Error;
+use crate::store::{ProvableStore, Store};
+
+/// The key under which the value of a path is stored in the tree. Keys are ordered by the bytes
+/// of the paths, as ICS 23 requires, rather than by the derived ordering of `Path`.
+#[derive(Clone, Debug)]
+struct PathKey {
+    path: Path,
+    bytes: Vec<u8>,
+}
+
+impl From<Path> for PathKey {
+    fn from(path: Path) -> Self {
+        Self {
+            bytes: path.clone().into_bytes(),
+            path,
+        }
+    }
+}
+
+impl AsRef<[u8]> for PathKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl PartialEq for PathKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl Eq for PathKey {}
+
+impl PartialOrd for PathKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PathKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+/// An in-memory `ProvableStore`, which keeps the values under the provable paths in an
+/// `AvlTree`, and a copy of this tree for every committed height. The values under the other
+/// paths are kept aside, and are neither committed nor proven.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryStore {
+    /// The working state of the provable paths.
+    working: AvlTree<PathKey, Vec<u8>>,
+    /// The committed states, where the state at height `h` has index `h - 1`.
+    committed: Vec<AvlTree<PathKey, Vec<u8>>>,
+    /// The values under the paths which are not provable.
+    private: BTreeMap<Path, Vec<u8>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn committed_state(&self, height: u64) -> Option<&AvlTree<PathKey, Vec<u8>>> {
+        let index = height.checked_sub(1)?;
+        self.committed.get(index as usize)
+    }
+}
+
+impl Store for InMemoryStore {
+    fn get(&self, path: &Path) -> Option<Vec<u8>> {
+        if path.is_provable() {
+            self.working.get(path.to_string().as_bytes()).cloned()
+        } else {
+            self.private.get(path).cloned()
+        }
+    }
+
+    fn set(&mut self, path: Path, value: Vec<u8>) -> Result<(), Error> {
+        if path.is_provable() {
+            self.working.insert(path.into(), value);
+        } else {
+            self.private.insert(path, value);
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &Path) -> Result<(), Error> {
+        if path.is_provable() {
+            self.working.remove(path.to_string().as_bytes());
+        } else {
+            self.private.remove(path);
+        }
+        Ok(())
+    }
+
+    fn paths(&self, prefix: &str) -> Vec<Path> {
+        let provable = self
+            .working
+            .entries()
+            .into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| key.bytes.starts_with(prefix.as_bytes()))
+            .map(|key| key.path.clone());
+
+        let private = self
+            .private
+            .keys()
+            .filter(|path| path.to_string().starts_with(prefix))
+            .cloned();
+
+        provable.chain(private).collect()
+    }
+
+    fn commit(&mut self) -> Result<Vec<u8>, Error> {
+        self.committed.push(self.working.clone());
+        Ok(self.working.root_hash())
+    }
+
+    fn current_height(&self) -> u64 {
+        self.committed.len() as u64
+    }
+}
+
+impl ProvableStore for InMemoryStore {
+    fn root_hash(&self, height: u64) -> Option<Vec<u8>> {
+        self.committed_state(height).map(AvlTree::root_hash)
+    }
+
+    fn get_proof(&self, height: u64, path: &Path) -> Option<(Option<Vec<u8>>, CommitmentProof)> {
+        if !path.is_provable() {
+            return None;
+        }
+
+        let state = self.committed_state(height)?;
+        let key = path.to_string().into_bytes();
+        let proof = state.get_proof(&key)?;
+
+        Some((state.get(&key).cloned(), proof))
+    }
+
+    fn proof_spec(&self) -> ProofSpec {
+        avl::proof_spec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ics24_host::identifier::{ClientId, PortId};
+    use crate::ics24_host::Path;
+    use crate::store::memory::InMemoryStore;
+    use crate::store::{ProvableStore, Store};
+
+    #[test]
+    fn commit_and_prove() {
+        let mut store = InMemoryStore::new();
+        let client_state = Path::ClientState(ClientId::default());
+        let client_type = Path::ClientType(ClientId::default());
+        let port = Path::Ports(PortId::default());
+
+        store.set(client_state.clone(), vec![1]).unwrap();
+        store.set(port.clone(), vec![2]).unwrap();
+        assert_eq!(store.current_height(), 0);
+        assert!(store.get_proof(1, &client_state).is_none());
+
+        let root = store.commit().unwrap();
+        assert_eq!(store.current_height(), 1);
+        assert_eq!(store.root_hash(1), Some(root.clone()));
+
+        // Later writes do not change the committed state.
+        store.set(client_state.clone(), vec![3]).unwrap();
+        store.set(client_type.clone(), vec![4]).unwrap();
+        assert_eq!(store.get(&client_state), Some(vec![3]));
+
+        let spec = store.proof_spec();
+        let (value, proof) = store.get_proof(1, &client_state).unwrap();
+        assert_eq!(value, Some(vec![1]));
+        assert!(ics23::verify_membership(
+            &proof,
+            &spec,
+            &root,
+            client_state.to_string().as_bytes(),
+            &[1]
+        ));
+
+        let (value, proof) = store.get_proof(1, &client_type).unwrap();
+        assert_eq!(value, None);
+        assert!(ics23::verify_non_membership(
+            &proof,
+            &spec,
+            &root,
+            client_type.to_string().as_bytes()
+        ));
+
+        // The ports are kept out of the Merkle tree.
+        assert_eq!(store.get(&port), Some(vec![2]));
+        assert!(store.get_proof(1, &port).is_none());
+
+        assert_eq!(store.paths("clients/").len(), 2);
+        store.delete(&client_type).unwrap();
+        assert_eq!(store.paths("clients/"), vec![client_state]);
+        assert_eq!(store.paths("ports/"), vec![port]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Provable key-value stores, keyed by the ICS 024 paths, in which a host chain keeps its IBC
+//! state. A host whose state lives in such a store gets the client, connection and channel
+//! contexts (`ClientReader`, `ClientKeeper`, `ConnectionReader`, and so on) for free, by
+//! implementing `StoreContext`.
+//!
+//! The `InMemoryStore` is a reference implementation, which keeps the provable paths in an AVL
+//! Merkle tree and returns ICS 23 proofs of existence and non-existence for them.
+
+use ics23::{CommitmentProof, ProofSpec};
+
+use crate::ics24_host::Path;
+
+pub use avl::AvlTree;
+pub use context::StoreContext;
+pub use memory::InMemoryStore;
+
+pub mod avl;
+pub mod context;
+pub mod error;
+pub mod memory;
+
+/// A key-value store, keyed by the ICS 024 paths. Writes go to a working state, which becomes
+/// the state of the store at the next height upon `commit`.
+pub trait Store {
+    /// Returns the value stored under `path` in the working state.
+    fn get(&self, path: &Path) -> Option<Vec<u8>>;
+
+    /// Stores `value` under `path` in the working state.
+    fn set(&mut self, path: Path, value: Vec<u8>) -> Result<(), error::Error>;
+
+    /// Deletes the value stored under `path` in the working state, if any.
+    fn delete(&mut self, path: &Path) -> Result<(), error::Error>;
+
+    /// Returns the paths in the working state whose string representation starts with `prefix`.
+    fn paths(&self, prefix: &str) -> Vec<Path>;
+
+    /// Commits the working state as the state of the store at the next height, and returns the
+    /// root hash of this state.
+    fn commit(&mut self) -> Result<Vec<u8>, error::Error>;
+
+    /// Returns the height of the latest committed state, or zero if nothing was committed yet.
+    fn current_height(&self) -> u64;
+}
+
+/// A store which commits the values under the provable paths (see `Path::is_provable`) to a
+/// Merkle root, and which proves their existence or absence against this root.
+pub trait ProvableStore: Store {
+    /// Returns the root hash of the committed state at `height`.
+    fn root_hash(&self, height: u64) -> Option<Vec<u8>>;
+
+    /// Returns the value stored under `path` in the committed state at `height`, along with the
+    /// proof of its existence, or the proof of its absence if there is no such value. Returns
+    /// `None` if `path` is not provable, or if there is no committed state at `height`.
+    fn get_proof(&self, height: u64, path: &Path) -> Option<(Option<Vec<u8>>, CommitmentProof)>;
+
+    /// Returns the specification of the proofs returned by `get_proof`.
+    fn proof_spec(&self) -> ProofSpec;
+}
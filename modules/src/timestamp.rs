@@ -0,0 +1,272 @@
+//! Timestamps of IBC packets and consensus states, in nanoseconds since the Unix epoch.
+
+use std::convert::TryFrom;
+use std::fmt::{Display, Error as FmtError, Formatter};
+use std::num::ParseIntError;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anomaly::{BoxError, Context};
+use chrono::{DateTime, TimeZone, Utc};
+use prost_types::Timestamp as RawTimestamp;
+use serde_derive::{Deserialize, Serialize};
+use tendermint::Time;
+use thiserror::Error;
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+pub type Error = anomaly::Error<Kind>;
+
+#[derive(Clone, Debug, Error)]
+pub enum Kind {
+    #[error("time {0} is before the Unix epoch")]
+    BeforeUnixEpoch(String),
+
+    #[error("timestamp overflow")]
+    Overflow,
+}
+
+impl Kind {
+    pub fn context(self, source: impl Into<BoxError>) -> Context<Self> {
+        Context::new(self, Some(source.into()))
+    }
+}
+
+/// A timestamp in nanoseconds since the Unix epoch, or no timestamp at all. IBC messages encode
+/// the absence of a timestamp as 0, e.g., a packet with a zero timeout timestamp never times out
+/// on time, and so does this type: the zero timestamp is the absence of a timestamp.
+///
+/// Timestamps are deliberately not ordered, as the absence of a timestamp is neither before nor
+/// after any point in time: compare them with `check_expiry` or `duration_since` instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Timestamp {
+    nanoseconds: Option<u64>,
+}
+
+/// Whether a timeout timestamp expired at some point in time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expiry {
+    Expired,
+    NotExpired,
+    /// The point in time has no timestamp, hence the expiry of the timeout cannot be decided.
+    InvalidTimestamp,
+}
+
+impl Timestamp {
+    /// The absence of a timestamp.
+    pub fn none() -> Self {
+        Self { nanoseconds: None }
+    }
+
+    /// The timestamp `nanoseconds` after the Unix epoch, or no timestamp if `nanoseconds` is 0.
+    pub fn from_nanoseconds(nanoseconds: u64) -> Self {
+        Self {
+            nanoseconds: Some(nanoseconds).filter(|n| *n != 0),
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.nanoseconds.is_some()
+    }
+
+    /// The nanoseconds since the Unix epoch, or 0 if there is no timestamp.
+    pub fn as_nanoseconds(&self) -> u64 {
+        self.nanoseconds.unwrap_or(0)
+    }
+
+    pub fn as_time(&self) -> Option<Time> {
+        self.nanoseconds.map(|n| {
+            Utc.timestamp((n / NANOS_PER_SECOND) as i64, (n % NANOS_PER_SECOND) as u32)
+                .into()
+        })
+    }
+
+    /// The time elapsed from `earlier` to this timestamp, if both timestamps are set and
+    /// `earlier` is not after this timestamp.
+    pub fn duration_since(&self, earlier: &Timestamp) -> Option<Duration> {
+        match (self.nanoseconds, earlier.nanoseconds) {
+            (Some(n), Some(earlier)) => n.checked_sub(earlier).map(Duration::from_nanos),
+            _ => None,
+        }
+    }
+
+    /// Checks whether this timeout timestamp expired at time `now`, i.e., whether `now` is not
+    /// before the timeout. The absence of a timeout never expires.
+    pub fn check_expiry(&self, now: &Timestamp) -> Expiry {
+        match (self.nanoseconds, now.nanoseconds) {
+            (None, _) => Expiry::NotExpired,
+            (Some(_), None) => Expiry::InvalidTimestamp,
+            (Some(timeout), Some(now)) if timeout <= now => Expiry::Expired,
+            (Some(_), Some(_)) => Expiry::NotExpired,
+        }
+    }
+
+    fn from_seconds_and_nanos(seconds: i64, nanos: u32, time: impl Display) -> Result<Self, Error> {
+        let seconds =
+            u64::try_from(seconds).map_err(|_| Kind::BeforeUnixEpoch(time.to_string()))?;
+
+        seconds
+            .checked_mul(NANOS_PER_SECOND)
+            .and_then(|n| n.checked_add(u64::from(nanos)))
+            .map(Self::from_nanoseconds)
+            .ok_or_else(|| Kind::Overflow.into())
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self.as_time() {
+            Some(time) => write!(f, "{}", time),
+            None => write!(f, "no timestamp"),
+        }
+    }
+}
+
+/// Parses the number of nanoseconds since the Unix epoch, as found in event attributes.
+impl FromStr for Timestamp {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_nanoseconds(s.parse()?))
+    }
+}
+
+impl TryFrom<Time> for Timestamp {
+    type Error = Error;
+
+    fn try_from(time: Time) -> Result<Self, Self::Error> {
+        let date_time = DateTime::<Utc>::from(time);
+
+        Self::from_seconds_and_nanos(
+            date_time.timestamp(),
+            date_time.timestamp_subsec_nanos(),
+            time,
+        )
+    }
+}
+
+impl TryFrom<RawTimestamp> for Timestamp {
+    type Error = Error;
+
+    fn try_from(raw: RawTimestamp) -> Result<Self, Self::Error> {
+        let nanos =
+            u32::try_from(raw.nanos).map_err(|_| Kind::BeforeUnixEpoch(format!("{:?}", raw)))?;
+
+        Self::from_seconds_and_nanos(raw.seconds, nanos, format!("{:?}", raw))
+    }
+}
+
+impl From<Timestamp> for Option<RawTimestamp> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.nanoseconds.map(|n| RawTimestamp {
+            seconds: (n / NANOS_PER_SECOND) as i64,
+            nanos: (n % NANOS_PER_SECOND) as i32,
+        })
+    }
+}
+
+impl Add<Duration> for Timestamp {
+    type Output = Result<Timestamp, Error>;
+
+    /// Offsets the timestamp by `duration`. The absence of a timestamp stays absent.
+    fn add(self, duration: Duration) -> Self::Output {
+        match self.nanoseconds {
+            Some(n) => u64::try_from(duration.as_nanos())
+                .ok()
+                .and_then(|d| n.checked_add(d))
+                .map(Self::from_nanoseconds)
+                .ok_or_else(|| Kind::Overflow.into()),
+            None => Ok(self),
+        }
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Result<Timestamp, Error>;
+
+    /// Offsets the timestamp by `duration` into the past. The absence of a timestamp stays absent.
+    fn sub(self, duration: Duration) -> Self::Output {
+        match self.nanoseconds {
+            Some(n) => u64::try_from(duration.as_nanos())
+                .ok()
+                .and_then(|d| n.checked_sub(d))
+                .filter(|n| *n != 0)
+                .map(Self::from_nanoseconds)
+                .ok_or_else(|| Kind::Overflow.into()),
+            None => Ok(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use tendermint::Time;
+
+    use super::{Expiry, Timestamp};
+
+    #[test]
+    fn zero_is_no_timestamp() {
+        let timestamp = Timestamp::from_nanoseconds(0);
+        assert!(!timestamp.is_set());
+        assert_eq!(timestamp, Timestamp::none());
+        assert_eq!(timestamp.as_nanoseconds(), 0);
+        assert_eq!(timestamp.as_time(), None);
+        assert_eq!((timestamp + Duration::from_secs(1)).unwrap(), timestamp);
+    }
+
+    #[test]
+    fn time_conversions() {
+        let time: Time = Utc.timestamp(1_600_000_000, 123).into();
+        let timestamp = Timestamp::try_from(time).unwrap();
+
+        assert_eq!(timestamp.as_nanoseconds(), 1_600_000_000_000_000_123);
+        assert_eq!(timestamp.as_time(), Some(time));
+
+        let before_epoch: Time = Utc.timestamp(-1, 0).into();
+        assert!(Timestamp::try_from(before_epoch).is_err());
+    }
+
+    #[test]
+    fn duration_arithmetic() {
+        let timestamp = Timestamp::from_nanoseconds(5_000_000_000);
+        let later = (timestamp + Duration::from_secs(2)).unwrap();
+
+        assert_eq!(later.as_nanoseconds(), 7_000_000_000);
+        assert_eq!(
+            later.duration_since(&timestamp),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(timestamp.duration_since(&later), None);
+        assert_eq!((later - Duration::from_secs(2)).unwrap(), timestamp);
+
+        assert!((Timestamp::from_nanoseconds(u64::MAX) + Duration::from_nanos(1)).is_err());
+        assert!((timestamp - Duration::from_secs(5)).is_err());
+    }
+
+    #[test]
+    fn expiry() {
+        let timeout = Timestamp::from_nanoseconds(10);
+
+        assert_eq!(
+            timeout.check_expiry(&Timestamp::from_nanoseconds(9)),
+            Expiry::NotExpired
+        );
+        assert_eq!(
+            timeout.check_expiry(&Timestamp::from_nanoseconds(10)),
+            Expiry::Expired
+        );
+        assert_eq!(
+            timeout.check_expiry(&Timestamp::none()),
+            Expiry::InvalidTimestamp
+        );
+        assert_eq!(
+            Timestamp::none().check_expiry(&Timestamp::from_nanoseconds(10)),
+            Expiry::NotExpired
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::fs::remove_dir_all;
-use std::fs::{copy, create_dir_all};
+use std::fs::{copy, create_dir_all, File, OpenOptions};
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
 
@@ -131,11 +132,12 @@ impl CompileCmd {
 
         // Paths
         let mut proto_paths = vec![
-            format!("{}/../proto/definitions/mock", root),
+            format!("{}/../proto/definitions", root),
             format!("{}/proto/cosmos/auth", sdk_dir.display()),
             format!("{}/proto/cosmos/gov", sdk_dir.display()),
             format!("{}/proto/cosmos/tx", sdk_dir.display()),
             format!("{}/proto/cosmos/base", sdk_dir.display()),
+            format!("{}/proto/cosmos/crypto/ed25519", sdk_dir.display()),
             format!("{}/proto/cosmos/staking", sdk_dir.display()),
             format!("{}/proto/cosmos/upgrade", sdk_dir.display()),
         ];
@@ -262,6 +264,10 @@ impl CompileCmd {
                         );
                     }
                     Ok(0)
+                } else if Path::new(&target_fname).exists() {
+                    // The package is extended by the definitions of this repository, e.g., the
+                    // channel upgrade messages in `ibc.core.channel.v1`, which must be kept.
+                    Self::append_file(e.path(), Path::new(&target_fname))
                 } else {
                     copy(
                         e.path(),
@@ -281,4 +287,9 @@ impl CompileCmd {
             }
         }
     }
+
+    fn append_file(from: &Path, to: &Path) -> io::Result<u64> {
+        let mut target = OpenOptions::new().append(true).open(to)?;
+        io::copy(&mut File::open(from)?, &mut target)
+    }
 }
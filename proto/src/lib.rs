@@ -64,6 +64,9 @@ pub mod cosmos {
         }
     }
     pub mod crypto {
+        pub mod ed25519 {
+            include!("prost/cosmos.crypto.ed25519.rs");
+        }
         pub mod multisig {
             pub mod v1beta1 {
                 include!("prost/cosmos.crypto.multisig.v1beta1.rs");
@@ -94,6 +97,26 @@ pub mod cosmos {
 
 pub mod ibc {
     pub mod apps {
+        pub mod fee {
+            pub mod v1 {
+                include!("prost/ibc.applications.fee.v1.rs");
+            }
+        }
+        pub mod interchain_accounts {
+            pub mod v1 {
+                include!("prost/ibc.applications.interchain_accounts.v1.rs");
+            }
+            pub mod controller {
+                pub mod v1 {
+                    include!("prost/ibc.applications.interchain_accounts.controller.v1.rs");
+                }
+            }
+        }
+        pub mod nft_transfer {
+            pub mod v1 {
+                include!("prost/ibc.applications.nft_transfer.v1.rs");
+            }
+        }
         pub mod transfer {
             pub mod v1 {
                 include!("prost/ibc.applications.transfer.v1.rs");
@@ -0,0 +1,17 @@
+/// PubKey is an ed25519 public key for handling Tendermint keys in SDK.
+/// It's needed for Any serialization and SDK compatibility.
+/// It must not be used in a non Tendermint key context because it doesn't implement
+/// ADR-28. Nevertheless, you will like to use ed25519 in app user level
+/// then you must create a new proto message and follow ADR-28 for Address construction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PubKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+}
+/// Deprecated: PrivKey defines a ed25519 private key.
+/// NOTE: ed25519 keys must not be used in SDK apps except in a tendermint validator context.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PrivKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+}
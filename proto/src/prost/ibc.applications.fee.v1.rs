@@ -0,0 +1,128 @@
+/// Fee defines the ICS29 receive, acknowledgement and timeout fees
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Fee {
+    /// the packet receive fee
+    #[prost(message, repeated, tag="1")]
+    pub recv_fee: ::prost::alloc::vec::Vec<super::super::super::super::cosmos::base::v1beta1::Coin>,
+    /// the packet acknowledgement fee
+    #[prost(message, repeated, tag="2")]
+    pub ack_fee: ::prost::alloc::vec::Vec<super::super::super::super::cosmos::base::v1beta1::Coin>,
+    /// the packet timeout fee
+    #[prost(message, repeated, tag="3")]
+    pub timeout_fee: ::prost::alloc::vec::Vec<super::super::super::super::cosmos::base::v1beta1::Coin>,
+}
+/// PacketFee contains ICS29 relayer fees, refund address and optional list of permitted relayers
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PacketFee {
+    /// fee encapsulates the recv, ack and timeout fees associated with an IBC packet
+    #[prost(message, optional, tag="1")]
+    pub fee: ::core::option::Option<Fee>,
+    /// the refund address for unspent fees
+    #[prost(string, tag="2")]
+    pub refund_address: ::prost::alloc::string::String,
+    /// optional list of relayers permitted to receive fees
+    #[prost(string, repeated, tag="3")]
+    pub relayers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// PacketFees contains a list of type PacketFee
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PacketFees {
+    /// list of packet fees
+    #[prost(message, repeated, tag="1")]
+    pub packet_fees: ::prost::alloc::vec::Vec<PacketFee>,
+}
+/// IdentifiedPacketFees contains a list of type PacketFee and associated PacketId
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdentifiedPacketFees {
+    /// unique packet identifier comprised of the channel ID, port ID and sequence
+    #[prost(message, optional, tag="1")]
+    pub packet_id: ::core::option::Option<super::super::super::core::channel::v1::PacketId>,
+    /// list of packet fees
+    #[prost(message, repeated, tag="2")]
+    pub packet_fees: ::prost::alloc::vec::Vec<PacketFee>,
+}
+/// IncentivizedAcknowledgement is the acknowledgement format to be used by applications wrapped in the fee middleware
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IncentivizedAcknowledgement {
+    /// the underlying app acknowledgement bytes
+    #[prost(bytes="vec", tag="1")]
+    pub app_acknowledgement: ::prost::alloc::vec::Vec<u8>,
+    /// the relayer address which submits the recv packet message
+    #[prost(string, tag="2")]
+    pub forward_relayer_address: ::prost::alloc::string::String,
+    /// success flag of the base application callback
+    #[prost(bool, tag="3")]
+    pub underlying_app_success: bool,
+}
+/// Metadata defines the ICS29 channel specific metadata encoded into the channel version bytestring
+/// See ICS004: https://github.com/cosmos/ibc/tree/master/spec/core/ics-004-channel-and-packet-semantics#Versioning
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Metadata {
+    /// fee_version defines the ICS29 fee version
+    #[prost(string, tag="1")]
+    pub fee_version: ::prost::alloc::string::String,
+    /// app_version defines the underlying application version, which may or may not be a JSON encoded bytestring
+    #[prost(string, tag="2")]
+    pub app_version: ::prost::alloc::string::String,
+}
+/// MsgRegisterCounterpartyPayee defines the request type for the RegisterCounterpartyPayee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterCounterpartyPayee {
+    /// unique port identifier
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    /// unique channel identifier
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// the relayer address
+    #[prost(string, tag="3")]
+    pub relayer: ::prost::alloc::string::String,
+    /// the counterparty payee address
+    #[prost(string, tag="4")]
+    pub counterparty_payee: ::prost::alloc::string::String,
+}
+/// MsgRegisterCounterpartyPayeeResponse defines the response type for the RegisterCounterpartyPayee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterCounterpartyPayeeResponse {
+}
+/// MsgPayPacketFee defines the request type for the PayPacketFee rpc
+/// This Msg can be used to pay for a packet at the next sequence send & should be combined with the Msg that will be
+/// paid for
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPayPacketFee {
+    /// fee encapsulates the recv, ack and timeout fees associated with an IBC packet
+    #[prost(message, optional, tag="1")]
+    pub fee: ::core::option::Option<Fee>,
+    /// the source port unique identifier
+    #[prost(string, tag="2")]
+    pub source_port_id: ::prost::alloc::string::String,
+    /// the source channel unique identifer
+    #[prost(string, tag="3")]
+    pub source_channel_id: ::prost::alloc::string::String,
+    /// account address to refund fee if necessary
+    #[prost(string, tag="4")]
+    pub signer: ::prost::alloc::string::String,
+    /// optional list of relayers permitted to the receive packet fees
+    #[prost(string, repeated, tag="5")]
+    pub relayers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// MsgPayPacketFeeResponse defines the response type for the PayPacketFee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPayPacketFeeResponse {
+}
+/// MsgPayPacketFeeAsync defines the request type for the PayPacketFeeAsync rpc
+/// This Msg can be used to pay for a packet at a specified sequence (instead of the next sequence send)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPayPacketFeeAsync {
+    /// unique packet identifier comprised of the channel ID, port ID and sequence
+    #[prost(message, optional, tag="1")]
+    pub packet_id: ::core::option::Option<super::super::super::core::channel::v1::PacketId>,
+    /// the packet fee associated with a particular IBC packet
+    #[prost(message, optional, tag="2")]
+    pub packet_fee: ::core::option::Option<PacketFee>,
+}
+/// MsgPayPacketFeeAsyncResponse defines the response type for the PayPacketFeeAsync rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPayPacketFeeAsyncResponse {
+}
+# [doc = r" Generated client implementations."] pub mod msg_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Msg defines the ICS29 Msg service."] pub struct MsgClient < T > { inner : tonic :: client :: Grpc < T > , } impl MsgClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > MsgClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = " RegisterCounterpartyPayee defines a rpc handler method for MsgRegisterCounterpartyPayee"] pub async fn register_counterparty_payee (& mut self , request : impl tonic :: IntoRequest < super :: MsgRegisterCounterpartyPayee > ,) -> Result < tonic :: Response < super :: MsgRegisterCounterpartyPayeeResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/ibc.applications.fee.v1.Msg/RegisterCounterpartyPayee") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " PayPacketFee defines a rpc handler method for MsgPayPacketFee"] pub async fn pay_packet_fee (& mut self , request : impl tonic :: IntoRequest < super :: MsgPayPacketFee > ,) -> Result < tonic :: Response < super :: MsgPayPacketFeeResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/ibc.applications.fee.v1.Msg/PayPacketFee") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " PayPacketFeeAsync defines a rpc handler method for MsgPayPacketFeeAsync"] pub async fn pay_packet_fee_async (& mut self , request : impl tonic :: IntoRequest < super :: MsgPayPacketFeeAsync > ,) -> Result < tonic :: Response < super :: MsgPayPacketFeeAsyncResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/ibc.applications.fee.v1.Msg/PayPacketFeeAsync") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for MsgClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for MsgClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "MsgClient {{ ... }}") } } }
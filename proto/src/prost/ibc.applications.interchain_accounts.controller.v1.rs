@@ -0,0 +1,39 @@
+/// MsgRegisterInterchainAccount defines the payload for Msg/RegisterAccount
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterInterchainAccount {
+    #[prost(string, tag="1")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub connection_id: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub version: ::prost::alloc::string::String,
+}
+/// MsgRegisterInterchainAccountResponse defines the response for Msg/RegisterAccount
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterInterchainAccountResponse {
+    #[prost(string, tag="1")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub port_id: ::prost::alloc::string::String,
+}
+/// MsgSendTx defines the payload for Msg/SendTx
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSendTx {
+    #[prost(string, tag="1")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub connection_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub packet_data: ::core::option::Option<super::super::v1::InterchainAccountPacketData>,
+    /// Relative timeout timestamp provided will be added to the current block time during transaction execution.
+    /// The timeout timestamp must be non-zero.
+    #[prost(uint64, tag="4")]
+    pub relative_timeout: u64,
+}
+/// MsgSendTxResponse defines the response for MsgSendTx
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSendTxResponse {
+    #[prost(uint64, tag="1")]
+    pub sequence: u64,
+}
+# [doc = r" Generated client implementations."] pub mod msg_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Msg defines the 27-interchain-accounts/controller Msg service."] pub struct MsgClient < T > { inner : tonic :: client :: Grpc < T > , } impl MsgClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > MsgClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = " RegisterInterchainAccount defines a rpc handler for MsgRegisterInterchainAccount."] pub async fn register_interchain_account (& mut self , request : impl tonic :: IntoRequest < super :: MsgRegisterInterchainAccount > ,) -> Result < tonic :: Response < super :: MsgRegisterInterchainAccountResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/ibc.applications.interchain_accounts.controller.v1.Msg/RegisterInterchainAccount") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " SendTx defines a rpc handler for MsgSendTx."] pub async fn send_tx (& mut self , request : impl tonic :: IntoRequest < super :: MsgSendTx > ,) -> Result < tonic :: Response < super :: MsgSendTxResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/ibc.applications.interchain_accounts.controller.v1.Msg/SendTx") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for MsgClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for MsgClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "MsgClient {{ ... }}") } } }
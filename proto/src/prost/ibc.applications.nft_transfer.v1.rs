@@ -0,0 +1,61 @@
+/// MsgTransfer defines a msg to transfer non-fungible tokens (i.e NFTs) of a
+/// class between ICS721 enabled chains. See ICS Spec here:
+/// https://github.com/cosmos/ibc/tree/master/spec/app/ics-721-nft-transfer#data-structures
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgTransfer {
+    /// the port on which the packet will be sent
+    #[prost(string, tag="1")]
+    pub source_port: ::prost::alloc::string::String,
+    /// the channel by which the packet will be sent
+    #[prost(string, tag="2")]
+    pub source_channel: ::prost::alloc::string::String,
+    /// the class to which the NFTs to be transferred belong
+    #[prost(string, tag="3")]
+    pub class_id: ::prost::alloc::string::String,
+    /// the identifiers of the NFTs to be transferred
+    #[prost(string, repeated, tag="4")]
+    pub token_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// the sender address
+    #[prost(string, tag="5")]
+    pub sender: ::prost::alloc::string::String,
+    /// the recipient address on the destination chain
+    #[prost(string, tag="6")]
+    pub receiver: ::prost::alloc::string::String,
+    /// Timeout height relative to the current block height.
+    /// The timeout is disabled when set to 0.
+    #[prost(message, optional, tag="7")]
+    pub timeout_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+    /// Timeout timestamp (in nanoseconds) relative to the current block timestamp.
+    /// The timeout is disabled when set to 0.
+    #[prost(uint64, tag="8")]
+    pub timeout_timestamp: u64,
+}
+/// MsgTransferResponse defines the Msg/Transfer response type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgTransferResponse {
+}
+# [doc = r" Generated client implementations."] pub mod msg_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Msg defines the ibc/nft-transfer Msg service."] pub struct MsgClient < T > { inner : tonic :: client :: Grpc < T > , } impl MsgClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > MsgClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = " Transfer defines a rpc handler method for MsgTransfer."] pub async fn transfer (& mut self , request : impl tonic :: IntoRequest < super :: MsgTransfer > ,) -> Result < tonic :: Response < super :: MsgTransferResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/ibc.applications.nft_transfer.v1.Msg/Transfer") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for MsgClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for MsgClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "MsgClient {{ ... }}") } } }/// NonFungibleTokenPacketData defines a struct for the packet payload
+/// NonFungibleTokenPacketData defines a struct for the packet payload
+/// See NonFungibleTokenPacketData spec:
+/// https://github.com/cosmos/ibc/tree/master/spec/app/ics-721-nft-transfer#data-structures
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NonFungibleTokenPacketData {
+    /// the class to which the NFTs belong, including its trace prefixes
+    #[prost(string, tag="1")]
+    pub class_id: ::prost::alloc::string::String,
+    /// the URI of the class
+    #[prost(string, tag="2")]
+    pub class_uri: ::prost::alloc::string::String,
+    /// the identifiers of the NFTs
+    #[prost(string, repeated, tag="3")]
+    pub token_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// the URIs of the NFTs, in the order of their identifiers
+    #[prost(string, repeated, tag="4")]
+    pub token_uris: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// the sender address
+    #[prost(string, tag="5")]
+    pub sender: ::prost::alloc::string::String,
+    /// the recipient address on the destination chain
+    #[prost(string, tag="6")]
+    pub receiver: ::prost::alloc::string::String,
+}
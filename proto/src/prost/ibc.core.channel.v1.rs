@@ -1,3 +1,165 @@
+/// ChannelUpgrade is the upgrade of a channel end in progress. It is stored
+/// apart from the channel end, which stays as it is until the upgrade
+/// handshake completes.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelUpgrade {
+    /// step of the upgrade handshake which the channel end reached
+    #[prost(enumeration="UpgradeState", tag="1")]
+    pub state: i32,
+    /// the channel end once upgraded
+    #[prost(message, optional, tag="2")]
+    pub channel: ::core::option::Option<Channel>,
+}
+/// MsgChannelUpgradeInit defines a msg sent by a Relayer to Chain A to
+/// propose an upgrade of an OPEN channel with Chain B.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeInit {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub proposed_upgrade_channel: ::core::option::Option<Channel>,
+    #[prost(string, tag="4")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeInitResponse defines the Msg/ChannelUpgradeInit response type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeInitResponse {
+}
+/// MsgChannelUpgradeTry defines a msg sent by a Relayer to Chain B to accept
+/// the upgrade proposed by Chain A.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTry {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub proposed_upgrade_channel: ::core::option::Option<Channel>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_upgrade_init: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeTryResponse defines the Msg/ChannelUpgradeTry response type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeTryResponse {
+}
+/// MsgChannelUpgradeAck defines a msg sent by a Relayer to Chain A to
+/// acknowledge that Chain B accepted the upgrade.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeAck {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="3")]
+    pub proof_upgrade_try: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="4")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="5")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeAckResponse defines the Msg/ChannelUpgradeAck response type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeAckResponse {
+}
+/// MsgChannelUpgradeConfirm defines a msg sent by a Relayer to Chain B to
+/// confirm that Chain A completed the upgrade.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeConfirm {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="3")]
+    pub proof_upgrade_ack: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="4")]
+    pub proof_height: ::core::option::Option<super::super::client::v1::Height>,
+    #[prost(string, tag="5")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeConfirmResponse defines the Msg/ChannelUpgradeConfirm response type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeConfirmResponse {
+}
+/// MsgChannelUpgradeCancel defines a msg sent by the owner of a channel end on
+/// Chain A to withdraw the upgrade which it proposed, as long as the upgrade is
+/// not acknowledged on Chain A.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeCancel {
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgChannelUpgradeCancelResponse defines the Msg/ChannelUpgradeCancel response type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgChannelUpgradeCancelResponse {
+}
+/// UpgradeState defines the step of the upgrade handshake which a channel end
+/// reached: INIT or TRY.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum UpgradeState {
+    /// Default State
+    UninitializedUnspecified = 0,
+    /// The channel end proposed an upgrade to the counterparty chain.
+    Init = 1,
+    /// The channel end accepted the upgrade proposed by the counterparty chain.
+    Try = 2,
+}
+/// MultihopProof proves, on an intermediate chain of a multi-hop channel, the
+/// connection end leading to the next chain along with the consensus state
+/// which the intermediate chain stores for the next chain.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultihopProof {
+    /// connection end of the hop on the intermediate chain
+    #[prost(message, optional, tag="1")]
+    pub connection: ::core::option::Option<super::super::connection::v1::ConnectionEnd>,
+    #[prost(bytes="vec", tag="2")]
+    pub proof_connection: ::prost::alloc::vec::Vec<u8>,
+    /// consensus state of the next chain, as stored on the intermediate chain
+    #[prost(message, optional, tag="3")]
+    pub consensus_state: ::core::option::Option<::prost_types::Any>,
+    #[prost(bytes="vec", tag="4")]
+    pub proof_consensus: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="5")]
+    pub consensus_height: ::core::option::Option<super::super::client::v1::Height>,
+}
+/// MsgMultihopProofs is the proof of a key stored on the counterparty chain of
+/// a multi-hop channel. It is carried in place of the proof bytes of channel
+/// and packet messages.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgMultihopProofs {
+    /// proof of the key on the counterparty chain
+    #[prost(bytes="vec", tag="1")]
+    pub key_proof: ::prost::alloc::vec::Vec<u8>,
+    /// proofs on the intermediate chains, ordered from the chain closest to the
+    /// verifying chain to the chain closest to the counterparty chain
+    #[prost(message, repeated, tag="2")]
+    pub hops: ::prost::alloc::vec::Vec<MultihopProof>,
+}
+/// PacketId is an identifer for a unique Packet
+/// Source chains refer to packets by source port/channel
+/// Destination chains refer to packets by destination port/channel
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PacketId {
+    /// channel port identifier
+    #[prost(string, tag="1")]
+    pub port_id: ::prost::alloc::string::String,
+    /// channel unique identifier
+    #[prost(string, tag="2")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// packet sequence
+    #[prost(uint64, tag="3")]
+    pub sequence: u64,
+}
 /// Channel defines pipeline for exactly-once packet delivery between specific
 /// modules on separate blockchains, which has at least one end capable of
 /// sending packets and one end capable of receiving packets.
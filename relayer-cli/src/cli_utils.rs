@@ -48,3 +48,15 @@ pub fn spawn_chain_runtime(
 
     Ok(handle)
 }
+
+/// Spawns the chain runtimes of the intermediate chains of a multi-hop channel, given by their
+/// identifiers. Returns the corresponding handles if successful.
+pub fn spawn_hop_chain_runtimes(
+    config: &Config,
+    hop_chain_ids: &[ChainId],
+) -> Result<Vec<Box<dyn ChainHandle>>, Error> {
+    hop_chain_ids
+        .iter()
+        .map(|chain_id| spawn_chain_runtime(config, chain_id))
+        .collect()
+}
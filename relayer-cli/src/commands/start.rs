@@ -4,7 +4,7 @@ use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer::link::LinkParameters;
 use ibc_relayer::relay::{channel_relay, relay_on_new_link};
 
-use crate::cli_utils::ChainHandlePair;
+use crate::cli_utils::{spawn_hop_chain_runtimes, ChainHandlePair};
 use crate::conclude::Output;
 use crate::prelude::*;
 
@@ -21,6 +21,14 @@ pub struct StartCmd {
 
     #[options(help = "identifier of the source channel", short = "c")]
     src_channel_id: Option<ChannelId>,
+
+    #[options(
+        help = "identifier of an intermediate chain of a multi-hop channel, can be repeated",
+        long = "hop-chain",
+        meta = "ID",
+        no_short
+    )]
+    hop_chain_ids: Vec<ChainId>,
 }
 
 impl Runnable for StartCmd {
@@ -34,9 +42,15 @@ impl Runnable for StartCmd {
 
         match (&self.src_port_id, &self.src_channel_id) {
             (Some(src_port_id), Some(src_channel_id)) => {
+                let hop_chains = match spawn_hop_chain_runtimes(&config, &self.hop_chain_ids) {
+                    Ok(hop_chains) => hop_chains,
+                    Err(e) => return Output::error(format!("{}", e)).exit(),
+                };
+
                 match channel_relay(
                     chains.src,
                     chains.dst,
+                    &hop_chains,
                     LinkParameters {
                         src_port_id: src_port_id.clone(),
                         src_channel_id: src_channel_id.clone(),
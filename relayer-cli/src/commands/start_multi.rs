@@ -1,7 +1,7 @@
 use abscissa_core::{Command, Options, Runnable};
 
 use ibc::ics24_host::identifier::ChainId;
-use ibc_relayer::{config::Config, supervisor::Supervisor};
+use ibc_relayer::{chain::handle::ChainHandle, config::Config, supervisor::Supervisor};
 
 use crate::conclude::Output;
 use crate::prelude::*;
@@ -60,10 +60,11 @@ fn start_specified(
     info!("spawning supervisor for chains {} and {}", chain_a, chain_b);
 
     let mut registry = Registry::new(&config);
+    let hop_chains = spawn_hop_chains(&mut registry, config, chain_a, chain_b);
     let chain_a = registry.get_or_spawn(chain_a)?;
     let chain_b = registry.get_or_spawn(chain_b)?;
 
-    let supervisor = Supervisor::spawn(chain_a, chain_b)?;
+    let supervisor = Supervisor::spawn(chain_a, chain_b)?.with_hop_chains(hop_chains);
     supervisor.run()?;
 
     Ok(Output::success_msg("ok"))
@@ -85,11 +86,14 @@ fn start_all_connections(config: &Config) -> Result<Output, BoxError> {
                 conn.a_chain, conn.b_chain
             );
 
+            let hop_chains = spawn_hop_chains(&mut registry, config, &conn.a_chain, &conn.b_chain);
             let chain_a = registry.get_or_spawn(&conn.a_chain)?;
             let chain_b = registry.get_or_spawn(&conn.b_chain)?;
 
             s.spawn(|_| {
-                let supervisor = Supervisor::spawn(chain_a, chain_b).unwrap();
+                let supervisor = Supervisor::spawn(chain_a, chain_b)
+                    .unwrap()
+                    .with_hop_chains(hop_chains);
                 supervisor.run()
             });
         }
@@ -103,3 +107,25 @@ fn start_all_connections(config: &Config) -> Result<Output, BoxError> {
         Err(e) => std::panic::resume_unwind(e),
     }
 }
+
+/// Spawns the configured chains other than `chain_a` and `chain_b`, which may be the intermediate
+/// chains of the multi-hop channels between them. The chains which cannot be spawned are skipped.
+fn spawn_hop_chains(
+    registry: &mut Registry<'_>,
+    config: &Config,
+    chain_a: &ChainId,
+    chain_b: &ChainId,
+) -> Vec<Box<dyn ChainHandle>> {
+    config
+        .chains
+        .iter()
+        .filter(|chain| &chain.id != chain_a && &chain.id != chain_b)
+        .filter_map(|chain| match registry.get_or_spawn(&chain.id) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("skipping intermediate chain {}: {}", chain.id, e);
+                None
+            }
+        })
+        .collect()
+}
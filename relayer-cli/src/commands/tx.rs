@@ -6,6 +6,8 @@ use crate::commands::tx::client::{TxCreateClientCmd, TxUpdateClientCmd, TxUpgrad
 mod channel;
 pub(crate) mod client;
 mod connection;
+mod fee;
+mod interchain_account;
 mod packet;
 mod transfer;
 mod upgrade;
@@ -95,4 +97,16 @@ pub enum TxRawCommands {
     /// The `tx raw upgrade-chain` subcommand
     #[options(help = "Send an upgrade plan")]
     UpgradeChain(upgrade::TxUpgradeChainCmd),
+
+    /// The `tx raw register-payee` subcommand
+    #[options(help = "Register the counterparty address to which receive fees are paid (ICS29)")]
+    RegisterPayee(fee::TxRegisterPayeeCmd),
+
+    /// The `tx raw ica-register` subcommand
+    #[options(help = "Register an interchain account on a host chain (ICS27)")]
+    IcaRegister(interchain_account::TxRegisterInterchainAccountCmd),
+
+    /// The `tx raw ica-send-tx` subcommand
+    #[options(help = "Send a transaction for an interchain account to execute (ICS27)")]
+    IcaSendTx(interchain_account::TxInterchainAccountSendTxCmd),
 }
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use abscissa_core::{Command, Options, Runnable};
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc::events::IbcEvent;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::fee::{build_and_send_register_payee_message, RegisterPayeeOptions};
+use ibc_relayer::{
+    chain::{Chain, CosmosSdkChain},
+    config::Config,
+};
+
+use crate::conclude::Output;
+use crate::error::{Error, Kind};
+use crate::prelude::*;
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct TxRegisterPayeeCmd {
+    #[options(
+        free,
+        required,
+        help = "identifier of the chain to which the relayer delivers packets"
+    )]
+    chain_id: ChainId,
+
+    #[options(free, required, help = "identifier of the port")]
+    port_id: PortId,
+
+    #[options(free, required, help = "identifier of the channel")]
+    channel_id: ChannelId,
+
+    #[options(
+        free,
+        required,
+        help = "address on the counterparty chain to which the receive fees are paid"
+    )]
+    counterparty_payee: String,
+}
+
+impl TxRegisterPayeeCmd {
+    fn validate_options(&self, config: &Config) -> Result<RegisterPayeeOptions, String> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| "missing chain configuration".to_string())?;
+
+        if self.counterparty_payee.trim().is_empty() {
+            return Err("counterparty payee address cannot be empty".to_string());
+        }
+
+        let opts = RegisterPayeeOptions {
+            chain_config: chain_config.clone(),
+            port_id: self.port_id.clone(),
+            channel_id: self.channel_id.clone(),
+            counterparty_payee: self.counterparty_payee.clone(),
+        };
+
+        Ok(opts)
+    }
+}
+
+impl Runnable for TxRegisterPayeeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.validate_options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+        info!("Message {:?}", opts);
+
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let chain_res = CosmosSdkChain::bootstrap(opts.chain_config.clone(), rt)
+            .map_err(|e| Kind::Runtime.context(e));
+        let chain = match chain_res {
+            Ok(chain) => chain,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        let res: Result<Vec<IbcEvent>, Error> = build_and_send_register_payee_message(chain, &opts)
+            .map_err(|e| Kind::Tx.context(e).into());
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
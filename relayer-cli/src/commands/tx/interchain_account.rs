@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use abscissa_core::{Command, Options, Runnable};
+use prost_types::Any;
+use serde::Deserialize;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc::events::IbcEvent;
+use ibc::ics24_host::identifier::{ChainId, ConnectionId};
+use ibc_relayer::interchain_account::{
+    build_and_send_register_interchain_account_message, build_and_send_tx_message,
+    RegisterInterchainAccountOptions, SendTxOptions,
+};
+use ibc_relayer::{
+    chain::{Chain, CosmosSdkChain},
+    config::Config,
+};
+
+use crate::conclude::Output;
+use crate::error::{Error, Kind};
+use crate::prelude::*;
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct TxRegisterInterchainAccountCmd {
+    #[options(free, required, help = "identifier of the controller chain")]
+    chain_id: ChainId,
+
+    #[options(
+        free,
+        required,
+        help = "identifier of the connection to the host chain, on the controller chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[options(
+        help = "version metadata of the channel to the host chain (picked by the controller module, if omitted)",
+        short = "v"
+    )]
+    version: Option<String>,
+}
+
+impl TxRegisterInterchainAccountCmd {
+    fn validate_options(
+        &self,
+        config: &Config,
+    ) -> Result<RegisterInterchainAccountOptions, String> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| "missing chain configuration".to_string())?;
+
+        let opts = RegisterInterchainAccountOptions {
+            chain_config: chain_config.clone(),
+            connection_id: self.connection_id.clone(),
+            version: self.version.clone().unwrap_or_default(),
+        };
+
+        Ok(opts)
+    }
+}
+
+impl Runnable for TxRegisterInterchainAccountCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.validate_options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+        info!("Message {:?}", opts);
+
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let chain_res = CosmosSdkChain::bootstrap(opts.chain_config.clone(), rt)
+            .map_err(|e| Kind::Runtime.context(e));
+        let chain = match chain_res {
+            Ok(chain) => chain,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        let res: Result<Vec<IbcEvent>, Error> =
+            build_and_send_register_interchain_account_message(chain, &opts)
+                .map_err(|e| Kind::Tx.context(e).into());
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[derive(Clone, Command, Debug, Options)]
+pub struct TxInterchainAccountSendTxCmd {
+    #[options(free, required, help = "identifier of the controller chain")]
+    chain_id: ChainId,
+
+    #[options(
+        free,
+        required,
+        help = "identifier of the connection to the host chain, on the controller chain"
+    )]
+    connection_id: ConnectionId,
+
+    #[options(
+        free,
+        required,
+        help = "JSON file with the messages for the interchain account to execute, as a list of `{\"type_url\": ..., \"value\": <base64 protobuf bytes>}`"
+    )]
+    messages_file: PathBuf,
+
+    #[options(help = "memo of the transaction", short = "m")]
+    memo: Option<String>,
+
+    #[options(
+        help = "timeout of the packet, in seconds since the current block time",
+        short = "t",
+        default = "600"
+    )]
+    timeout_seconds: u64,
+}
+
+/// A message for the interchain account to execute, as read from the messages file.
+#[derive(Debug, Deserialize)]
+struct JsonAny {
+    type_url: String,
+    value: String,
+}
+
+impl TxInterchainAccountSendTxCmd {
+    fn validate_options(&self, config: &Config) -> Result<SendTxOptions, String> {
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| "missing chain configuration".to_string())?;
+
+        if self.timeout_seconds == 0 {
+            return Err("timeout should be greater than zero".to_string());
+        }
+
+        let messages = read_messages(&self.messages_file)?;
+        if messages.is_empty() {
+            return Err("the transaction should contain at least one message".to_string());
+        }
+
+        let opts = SendTxOptions {
+            chain_config: chain_config.clone(),
+            connection_id: self.connection_id.clone(),
+            messages,
+            memo: self.memo.clone().unwrap_or_default(),
+            relative_timeout: Duration::from_secs(self.timeout_seconds),
+        };
+
+        Ok(opts)
+    }
+}
+
+/// Reads the messages of the transaction from the JSON file at `path`.
+fn read_messages(path: &Path) -> Result<Vec<Any>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read messages file {}: {}", path.display(), e))?;
+    let messages: Vec<JsonAny> = serde_json::from_str(&content)
+        .map_err(|e| format!("failed to parse messages file {}: {}", path.display(), e))?;
+
+    messages
+        .into_iter()
+        .map(|msg| {
+            let value = subtle_encoding::base64::decode(&msg.value)
+                .map_err(|e| format!("invalid base64 value of message {}: {}", msg.type_url, e))?;
+            Ok(Any {
+                type_url: msg.type_url,
+                value,
+            })
+        })
+        .collect()
+}
+
+impl Runnable for TxInterchainAccountSendTxCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let opts = match self.validate_options(&config) {
+            Err(err) => return Output::error(err).exit(),
+            Ok(result) => result,
+        };
+        info!("Message {:?}", opts);
+
+        let rt = Arc::new(TokioRuntime::new().unwrap());
+
+        let chain_res = CosmosSdkChain::bootstrap(opts.chain_config.clone(), rt)
+            .map_err(|e| Kind::Runtime.context(e));
+        let chain = match chain_res {
+            Ok(chain) => chain,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
+        let res: Result<Vec<IbcEvent>, Error> =
+            build_and_send_tx_message(chain, &opts).map_err(|e| Kind::Tx.context(e).into());
+
+        match res {
+            Ok(ev) => Output::success(ev).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
@@ -4,7 +4,7 @@ use ibc::events::IbcEvent;
 use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer::link::{Link, LinkParameters};
 
-use crate::cli_utils::ChainHandlePair;
+use crate::cli_utils::{spawn_hop_chain_runtimes, ChainHandlePair};
 use crate::conclude::Output;
 use crate::error::{Error, Kind};
 use crate::prelude::*;
@@ -22,6 +22,14 @@ pub struct TxRawPacketRecvCmd {
 
     #[options(free, required, help = "identifier of the source channel")]
     src_channel_id: ChannelId,
+
+    #[options(
+        help = "identifier of an intermediate chain of a multi-hop channel, can be repeated",
+        long = "hop-chain",
+        meta = "ID",
+        no_short
+    )]
+    hop_chain_ids: Vec<ChainId>,
 }
 
 impl Runnable for TxRawPacketRecvCmd {
@@ -33,11 +41,16 @@ impl Runnable for TxRawPacketRecvCmd {
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
 
+        let hop_chains = match spawn_hop_chain_runtimes(&config, &self.hop_chain_ids) {
+            Ok(hop_chains) => hop_chains,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
         let opts = LinkParameters {
             src_port_id: self.src_port_id.clone(),
             src_channel_id: self.src_channel_id.clone(),
         };
-        let mut link = match Link::new_from_opts(chains.src, chains.dst, opts) {
+        let mut link = match Link::new_from_opts(chains.src, chains.dst, &hop_chains, opts) {
             Ok(link) => link,
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
@@ -66,6 +79,14 @@ pub struct TxRawPacketAckCmd {
 
     #[options(free, required, help = "identifier of the source channel")]
     src_channel_id: ChannelId,
+
+    #[options(
+        help = "identifier of an intermediate chain of a multi-hop channel, can be repeated",
+        long = "hop-chain",
+        meta = "ID",
+        no_short
+    )]
+    hop_chain_ids: Vec<ChainId>,
 }
 
 impl Runnable for TxRawPacketAckCmd {
@@ -77,11 +98,16 @@ impl Runnable for TxRawPacketAckCmd {
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
 
+        let hop_chains = match spawn_hop_chain_runtimes(&config, &self.hop_chain_ids) {
+            Ok(hop_chains) => hop_chains,
+            Err(e) => return Output::error(format!("{}", e)).exit(),
+        };
+
         let opts = LinkParameters {
             src_port_id: self.src_port_id.clone(),
             src_channel_id: self.src_channel_id.clone(),
         };
-        let mut link = match Link::new_from_opts(chains.src, chains.dst, opts) {
+        let mut link = match Link::new_from_opts(chains.src, chains.dst, &hop_chains, opts) {
             Ok(link) => link,
             Err(e) => return Output::error(format!("{}", e)).exit(),
         };
@@ -14,11 +14,13 @@ use ibc::ics03_connection::connection::{ConnectionEnd, State};
 use ibc::ics03_connection::version::{get_compatible_versions, Version};
 use ibc::ics04_channel::channel::ChannelEnd;
 use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::ics04_channel::upgrade::ChannelUpgrade;
 use ibc::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use ibc::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::proofs::{ConsensusProof, Proofs};
 use ibc::query::QueryTxRequest;
 use ibc::signer::Signer;
+use ibc::timestamp::Timestamp;
 use ibc::Height as ICSHeight;
 use ibc_proto::ibc::core::channel::v1::{
     PacketState, QueryChannelsRequest, QueryConnectionChannelsRequest,
@@ -123,6 +125,9 @@ pub trait Chain: Sized {
     /// Query the latest height the chain is at
     fn query_latest_height(&self) -> Result<ICSHeight, Error>;
 
+    /// Query the timestamp of the latest block of the chain
+    fn query_latest_timestamp(&self) -> Result<Timestamp, Error>;
+
     /// Performs a query to retrieve the identifiers of all clients associated with a chain.
     fn query_clients(&self, request: QueryClientStatesRequest) -> Result<Vec<ClientId>, Error>;
 
@@ -181,6 +186,14 @@ pub trait Chain: Sized {
         height: ICSHeight,
     ) -> Result<ChannelEnd, Error>;
 
+    /// Performs a query to retrieve the upgrade in progress of a channel end, if any.
+    fn query_channel_upgrade(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<Option<ChannelUpgrade>, Error>;
+
     // TODO: Introduce a newtype for the module version string
     fn query_module_version(&self, port_id: &PortId) -> String {
         // TODO - query the chain, currently hardcoded
@@ -245,6 +258,13 @@ pub trait Chain: Sized {
         height: ICSHeight,
     ) -> Result<(ChannelEnd, MerkleProof), Error>;
 
+    fn proven_channel_upgrade(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<(ChannelUpgrade, MerkleProof), Error>;
+
     fn proven_packet(
         &self,
         packet_type: PacketMsgType,
@@ -366,6 +386,23 @@ pub trait Chain: Sized {
         )
     }
 
+    /// Builds the proof for the channel upgrade handshake messages which prove the upgrade in
+    /// progress on the counterparty channel end.
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<Proofs, Error> {
+        let upgrade_proof =
+            CommitmentProofBytes::from(self.proven_channel_upgrade(port_id, channel_id, height)?.1);
+
+        Ok(
+            Proofs::new(upgrade_proof, None, None, None, height.increment())
+                .map_err(|_| Kind::MalformedProof)?,
+        )
+    }
+
     /// Builds the proof for packet messages.
     fn build_packet_proofs(
         &self,
@@ -1,6 +1,11 @@
 use std::{
-    convert::TryFrom, convert::TryInto, future::Future, str::FromStr, sync::Arc, thread,
-    time::Duration,
+    convert::TryFrom,
+    convert::TryInto,
+    future::Future,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
 };
 
 use anomaly::fail;
@@ -31,17 +36,22 @@ use ibc::ics03_connection::connection::ConnectionEnd;
 use ibc::ics04_channel::channel::{ChannelEnd, QueryPacketEventDataRequest};
 use ibc::ics04_channel::events as ChannelEvents;
 use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::ics04_channel::upgrade::ChannelUpgrade;
 use ibc::ics07_tendermint::client_state::{AllowUpdate, ClientState};
 use ibc::ics07_tendermint::consensus_state::ConsensusState as TMConsensusState;
 use ibc::ics07_tendermint::header::Header as TmHeader;
-use ibc::ics23_commitment::commitment::CommitmentPrefix;
-use ibc::ics23_commitment::merkle::convert_tm_to_ics_merkle_proof;
+use ibc::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
+use ibc::ics23_commitment::merkle::{
+    apply_prefix, convert_tm_to_ics_merkle_proof, MerkleProof as DomainMerkleProof,
+};
+use ibc::ics23_commitment::specs::ProofSpecs;
 use ibc::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::ics24_host::Path::ClientConsensusState as ClientConsensusPath;
 use ibc::ics24_host::Path::ClientState as ClientStatePath;
-use ibc::ics24_host::{ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
+use ibc::ics24_host::{ClientUpgradePath, Path, SDK_UPGRADE_QUERY_PATH};
 use ibc::query::QueryTxRequest;
 use ibc::signer::Signer;
+use ibc::timestamp::Timestamp;
 use ibc::Height as ICSHeight;
 // Support for GRPC
 use ibc_proto::cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest};
@@ -79,12 +89,25 @@ const DEFAULT_MAX_MSG_NUM: usize = 30;
 const DEFAULT_MAX_TX_SIZE: usize = 2 * 1048576; // 2 MBytes
 const DEFAULT_GAS_FEE_AMOUNT: u64 = 1000;
 
+/// The key of the sub-store of the upgrade module
+const UPGRADE_STORE_KEY: &str = "upgrade";
+
 pub struct CosmosSdkChain {
     config: ChainConfig,
     rpc_client: HttpClient,
     grpc_addr: Uri,
     rt: Arc<TokioRuntime>,
     keybase: KeyRing,
+    /// Verifies the proofs of the query responses, initialized upon the first proven query
+    proof_verifier: Mutex<Option<ProofVerifier>>,
+}
+
+/// The light client which verifies the headers whose app hashes the proofs of the query
+/// responses are checked against, along with the light block it trusts.
+struct ProofVerifier {
+    light_client: TmLightClient,
+    client_state: AnyClientState,
+    trusted_block: TMLightBlock,
 }
 
 impl CosmosSdkChain {
@@ -268,7 +291,8 @@ impl CosmosSdkChain {
     fn query(&self, data: Path, height: ICSHeight, prove: bool) -> Result<QueryResponse, Error> {
         crate::time!("query");
 
-        let path = TendermintABCIPath::from_str(IBC_QUERY_PATH).unwrap();
+        let path = TendermintABCIPath::from_str(&format!("store/{}/key", self.config.store_prefix))
+            .map_err(|e| Kind::Store.context(e))?;
 
         let height =
             Height::try_from(height.revision_height).map_err(|e| Kind::InvalidHeight.context(e))?;
@@ -281,8 +305,10 @@ impl CosmosSdkChain {
 
         let response = self.block_on(abci_query(&self, path, data.to_string(), height, prove))?;
 
-        // TODO - Verify response proof, if requested.
-        if prove {}
+        if prove {
+            let prefix = self.query_commitment_prefix()?;
+            self.verify_response_proof(&prefix, data.to_string(), &response)?;
+        }
 
         Ok(response)
     }
@@ -297,13 +323,11 @@ impl CosmosSdkChain {
             Height::try_from(height.value() - 1).map_err(|e| Kind::InvalidHeight.context(e))?;
 
         let path = TendermintABCIPath::from_str(SDK_UPGRADE_QUERY_PATH).unwrap();
-        let response = self.block_on(abci_query(
-            &self,
-            path,
-            Path::Upgrade(data).to_string(),
-            prev_height,
-            true,
-        ))?;
+        let key = Path::Upgrade(data).to_string();
+        let response = self.block_on(abci_query(&self, path, key.clone(), prev_height, true))?;
+
+        let prefix = CommitmentPrefix::from(UPGRADE_STORE_KEY.as_bytes().to_vec());
+        self.verify_response_proof(&prefix, key, &response)?;
 
         let proof = response.proof.ok_or(Kind::EmptyResponseProof)?;
 
@@ -314,6 +338,142 @@ impl CosmosSdkChain {
 
         Ok((proof, height))
     }
+
+    /// Verifies the proof of the value in the `response` to the query of `key` in the sub-store
+    /// with the given `prefix`, or the proof of its absence if the value is empty. The proof is
+    /// checked against the app hash of the header following the height of the response, which
+    /// commits to the state queried, as verified by the light client.
+    fn verify_response_proof(
+        &self,
+        prefix: &CommitmentPrefix,
+        key: String,
+        response: &QueryResponse,
+    ) -> Result<(), Error> {
+        crate::time!("verify_response_proof");
+
+        let proof = response.proof.clone().ok_or(Kind::EmptyResponseProof)?;
+        let proof = DomainMerkleProof::try_from(proof).map_err(Kind::Ics023)?;
+
+        let height = ICSHeight::new(
+            self.config.id.version(),
+            response.height.increment().value(),
+        );
+        let root = self.verified_root(height)?;
+
+        let path = apply_prefix(prefix, vec![key])
+            .map_err(|e| Kind::InvalidResponseProof.context(e.to_string()))?;
+        let specs = ProofSpecs::cosmos();
+
+        if response.value.is_empty() {
+            proof.verify_non_membership(&specs, &root, &path)
+        } else {
+            proof.verify_membership(&specs, &root, &path, response.value.clone())
+        }
+        .map_err(|e| Kind::InvalidResponseProof.context(e).into())
+    }
+
+    /// Returns the app hash of the header at `height`, as verified by the light client from the
+    /// light block trusted by the proof verifier. The latest light block verified so far becomes
+    /// the trusted one, so that the trusting period does not elapse while the relayer runs.
+    fn verified_root(&self, height: ICSHeight) -> Result<CommitmentRoot, Error> {
+        // The header at `height` does not exist until the block at `height` is committed, which
+        // may not be the case yet when the latest state of the chain was queried.
+        self.wait_for_height(height)?;
+
+        let mut proof_verifier = self.proof_verifier.lock().unwrap();
+        if proof_verifier.is_none() {
+            *proof_verifier = Some(self.init_proof_verifier()?);
+        }
+        let verifier = proof_verifier.as_mut().unwrap();
+
+        let light_block = verifier.light_client.verify_from(
+            verifier.trusted_block.clone(),
+            height,
+            &verifier.client_state,
+        )?;
+
+        let root = CommitmentRoot::from_bytes(light_block.signed_header.header.app_hash.as_ref());
+
+        if light_block.height() > verifier.trusted_block.height() {
+            verifier.trusted_block = light_block;
+        }
+
+        Ok(root)
+    }
+
+    /// Initializes the verifier of the query proofs. If the configuration of the chain identifies
+    /// a trusted block by its height and hash, the verifier trusts this block rather than any
+    /// light block which the full node serves. Otherwise, like a client of this chain created by
+    /// the relayer, the verifier trusts the latest light block fetched by the light client.
+    fn init_proof_verifier(&self) -> Result<ProofVerifier, Error> {
+        let mut light_client = self.tm_light_client()?;
+
+        let (trusted_height, trusted_block) = match &self.config.trusted_block {
+            Some(trusted) => {
+                let trusted_height = ICSHeight::new(self.config.id.version(), trusted.height);
+                let trusted_block = light_client.fetch(trusted_height)?;
+
+                // The header is identified by its hash, and commits to the validator sets of
+                // the block.
+                let header = &trusted_block.signed_header.header;
+                if header.hash() != trusted.hash
+                    || trusted_block.validators.hash() != header.validators_hash
+                    || trusted_block.next_validators.hash() != header.next_validators_hash
+                {
+                    return Err(Kind::TrustedBlockMismatch {
+                        chain_id: self.config.id.clone(),
+                        height: trusted.height,
+                    }
+                    .into());
+                }
+
+                (trusted_height, trusted_block)
+            }
+            None => {
+                let latest_height = self.query_latest_height()?;
+                (latest_height, light_client.fetch(latest_height)?)
+            }
+        };
+
+        let client_state = AnyClientState::Tendermint(self.build_client_state(trusted_height)?);
+
+        Ok(ProofVerifier {
+            light_client,
+            client_state,
+            trusted_block,
+        })
+    }
+
+    /// Waits until the chain reaches `height`, for at most the RPC timeout of the chain.
+    fn wait_for_height(&self, height: ICSHeight) -> Result<(), Error> {
+        let start = Instant::now();
+
+        while self.query_latest_height()? < height {
+            if start.elapsed() > self.config.rpc_timeout {
+                fail!(
+                    Kind::Rpc(self.config.rpc_addr.clone()),
+                    "timed out waiting for chain {} to reach height {}",
+                    self.config.id,
+                    height
+                );
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
+
+        Ok(())
+    }
+
+    fn tm_light_client(&self) -> Result<TmLightClient, Error> {
+        use tendermint_light_client::types::PeerId;
+
+        let peer_id: PeerId = self
+            .rt
+            .block_on(self.rpc_client.status())
+            .map(|s| s.node_info.id)
+            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
+
+        TmLightClient::from_config(&self.config, peer_id)
+    }
 }
 
 impl Chain for CosmosSdkChain {
@@ -333,27 +493,24 @@ impl Chain for CosmosSdkChain {
         let grpc_addr =
             Uri::from_str(&config.grpc_addr.to_string()).map_err(|e| Kind::Grpc.context(e))?;
 
+        // The queries go to the store named by the configured prefix, so a prefix which names no
+        // store of the chain is reported here rather than upon each query.
+        rt.block_on(abci_query_store_prefix(&rpc_client, &config))?;
+
         Ok(Self {
             rt,
             config,
             keybase,
             rpc_client,
             grpc_addr,
+            proof_verifier: Mutex::new(None),
         })
     }
 
     fn init_light_client(&self) -> Result<Box<dyn LightClient<Self>>, Error> {
-        use tendermint_light_client::types::PeerId;
-
         crate::time!("init_light_client");
 
-        let peer_id: PeerId = self
-            .rt
-            .block_on(self.rpc_client.status())
-            .map(|s| s.node_info.id)
-            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
-
-        let light_client = TmLightClient::from_config(&self.config, peer_id)?;
+        let light_client = self.tm_light_client()?;
 
         Ok(Box::new(light_client))
     }
@@ -458,7 +615,9 @@ impl Chain for CosmosSdkChain {
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         crate::time!("query_commitment_prefix");
 
-        // TODO - do a real chain query
+        // The prefix is the key of the IBC sub-store, which the queries go to and the proofs of
+        // their responses are verified under. The counterparty connection ends record the same
+        // prefix, against which links check it upon creation.
         Ok(CommitmentPrefix::from(
             self.config().store_prefix.as_bytes().to_vec(),
         ))
@@ -487,6 +646,18 @@ impl Chain for CosmosSdkChain {
         })
     }
 
+    /// Query the timestamp of the latest block via a RPC query
+    fn query_latest_timestamp(&self) -> Result<Timestamp, Error> {
+        crate::time!("query_latest_timestamp");
+
+        let status = self
+            .block_on(self.rpc_client().status())
+            .map_err(|e| Kind::Rpc(self.config.rpc_addr.clone()).context(e))?;
+
+        Ok(Timestamp::try_from(status.sync_info.latest_block_time)
+            .map_err(|e| Kind::Query("latest block time".to_string()).context(e))?)
+    }
+
     fn query_clients(&self, request: QueryClientStatesRequest) -> Result<Vec<ClientId>, Error> {
         crate::time!("query_chain_clients");
 
@@ -813,6 +984,28 @@ impl Chain for CosmosSdkChain {
             .map_err(|e| Kind::Query("channel".into()).context(e))?)
     }
 
+    fn query_channel_upgrade(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<Option<ChannelUpgrade>, Error> {
+        let res = self.query(
+            Path::ChannelUpgrades(port_id.clone(), channel_id.clone()),
+            height,
+            false,
+        )?;
+
+        // No value is stored when no upgrade of the channel end is in progress.
+        if res.value.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(ChannelUpgrade::decode_vec(&res.value).map_err(
+            |e| Kind::Query("channel upgrade".into()).context(e),
+        )?))
+    }
+
     /// Queries the packet commitment hashes associated with a channel.
     fn query_packet_commitments(
         &self,
@@ -1152,6 +1345,31 @@ impl Chain for CosmosSdkChain {
         ))
     }
 
+    fn proven_channel_upgrade(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<(ChannelUpgrade, MerkleProof), Error> {
+        let res = self
+            .query(
+                Path::ChannelUpgrades(port_id.clone(), channel_id.clone()),
+                height,
+                true,
+            )
+            .map_err(|e| Kind::Query("proven channel upgrade".into()).context(e))?;
+
+        let channel_upgrade = ChannelUpgrade::decode_vec(&res.value)
+            .map_err(|e| Kind::Query("proven channel upgrade".into()).context(e))?;
+
+        Ok((
+            channel_upgrade,
+            res.proof.ok_or_else(|| {
+                Kind::Query("proven channel upgrade".into()).context("empty proof".to_string())
+            })?,
+        ))
+    }
+
     fn proven_packet(
         &self,
         packet_type: PacketMsgType,
@@ -1404,6 +1622,33 @@ async fn abci_query(
     Ok(response)
 }
 
+/// Check that the configured store prefix of the chain names one of its stores, by querying a key
+/// of this store.
+async fn abci_query_store_prefix(
+    rpc_client: &HttpClient,
+    config: &ChainConfig,
+) -> Result<(), Error> {
+    let path = TendermintABCIPath::from_str(&format!("store/{}/key", config.store_prefix))
+        .map_err(|e| Kind::Store.context(e))?;
+
+    let response = rpc_client
+        .abci_query(Some(path), b"clients/".to_vec(), None, false)
+        .await
+        .map_err(|e| Kind::Rpc(config.rpc_addr.clone()).context(e))?;
+
+    if !response.code.is_ok() {
+        // Fail with response log.
+        return Err(Kind::UnknownStorePrefix {
+            chain_id: config.id.clone(),
+            store_prefix: config.store_prefix.clone(),
+        }
+        .context(response.log.to_string())
+        .into());
+    }
+
+    Ok(())
+}
+
 /// Perform a `broadcast_tx_commit`, and return the corresponding deserialized response data.
 async fn broadcast_tx_commit(
     chain: &CosmosSdkChain,
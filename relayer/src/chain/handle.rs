@@ -16,11 +16,13 @@ use ibc::{
     ics04_channel::{
         channel::ChannelEnd,
         packet::{PacketMsgType, Sequence},
+        upgrade::ChannelUpgrade,
     },
     ics23_commitment::commitment::CommitmentPrefix,
     ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
     proofs::Proofs,
     signer::Signer,
+    timestamp::Timestamp,
     Height,
 };
 use ibc_proto::ibc::core::channel::v1::{
@@ -82,6 +84,10 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Height>,
     },
 
+    QueryLatestTimestamp {
+        reply_to: ReplyTo<Timestamp>,
+    },
+
     BuildHeader {
         trusted_height: Height,
         target_height: Height,
@@ -162,6 +168,13 @@ pub enum ChainRequest {
         reply_to: ReplyTo<ChannelEnd>,
     },
 
+    QueryChannelUpgrade {
+        port_id: PortId,
+        channel_id: ChannelId,
+        height: Height,
+        reply_to: ReplyTo<Option<ChannelUpgrade>>,
+    },
+
     QueryNextSequenceReceive {
         request: QueryNextSequenceReceiveRequest,
         reply_to: ReplyTo<Sequence>,
@@ -193,6 +206,13 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Proofs>,
     },
 
+    BuildChannelUpgradeProofs {
+        port_id: PortId,
+        channel_id: ChannelId,
+        height: Height,
+        reply_to: ReplyTo<Proofs>,
+    },
+
     BuildPacketProofs {
         packet_type: PacketMsgType,
         port_id: PortId,
@@ -247,6 +267,8 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
 
     fn query_latest_height(&self) -> Result<Height, Error>;
 
+    fn query_latest_timestamp(&self) -> Result<Timestamp, Error>;
+
     fn query_clients(&self, request: QueryClientStatesRequest) -> Result<Vec<ClientId>, Error>;
 
     fn query_client_state(
@@ -292,6 +314,13 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
         height: Height,
     ) -> Result<ChannelEnd, Error>;
 
+    fn query_channel_upgrade(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Option<ChannelUpgrade>, Error>;
+
     fn proven_client_state(
         &self,
         client_id: &ClientId,
@@ -350,6 +379,13 @@ pub trait ChainHandle: DynClone + Send + Sync + Debug {
         height: Height,
     ) -> Result<Proofs, Error>;
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Proofs, Error>;
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -14,12 +14,14 @@ use ibc::{
     ics03_connection::connection::ConnectionEnd,
     ics03_connection::version::Version,
     ics04_channel::channel::ChannelEnd,
+    ics04_channel::upgrade::ChannelUpgrade,
     ics23_commitment::commitment::CommitmentPrefix,
     ics24_host::identifier::ChainId,
     ics24_host::identifier::ChannelId,
     ics24_host::identifier::{ClientId, ConnectionId, PortId},
     proofs::Proofs,
     signer::Signer,
+    timestamp::Timestamp,
     Height,
 };
 use ibc_proto::ibc::core::channel::v1::{
@@ -105,6 +107,10 @@ impl ChainHandle for ProdChainHandle {
         self.send(|reply_to| ChainRequest::QueryLatestHeight { reply_to })
     }
 
+    fn query_latest_timestamp(&self) -> Result<Timestamp, Error> {
+        self.send(|reply_to| ChainRequest::QueryLatestTimestamp { reply_to })
+    }
+
     fn query_clients(&self, request: QueryClientStatesRequest) -> Result<Vec<ClientId>, Error> {
         self.send(|reply_to| ChainRequest::QueryClients { request, reply_to })
     }
@@ -183,6 +189,20 @@ impl ChainHandle for ProdChainHandle {
         })
     }
 
+    fn query_channel_upgrade(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Option<ChannelUpgrade>, Error> {
+        self.send(|reply_to| ChainRequest::QueryChannelUpgrade {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            height,
+            reply_to,
+        })
+    }
+
     fn proven_client_state(
         &self,
         client_id: &ClientId,
@@ -297,6 +317,20 @@ impl ChainHandle for ProdChainHandle {
         })
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.send(|reply_to| ChainRequest::BuildChannelUpgradeProofs {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            height,
+            reply_to,
+        })
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -14,7 +14,9 @@ use ibc::ics02_client::client_consensus::AnyConsensusStateWithHeight;
 use ibc::ics02_client::client_state::AnyClientState;
 use ibc::ics03_connection::connection::ConnectionEnd;
 use ibc::ics04_channel::channel::ChannelEnd;
+use ibc::ics04_channel::context::ChannelReader;
 use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::ics04_channel::upgrade::ChannelUpgrade;
 use ibc::ics07_tendermint::client_state::{AllowUpdate, ClientState as TendermintClientState};
 use ibc::ics07_tendermint::consensus_state::ConsensusState as TendermintConsensusState;
 use ibc::ics07_tendermint::header::Header as TendermintHeader;
@@ -26,6 +28,7 @@ use ibc::mock::host::HostType;
 use ibc::query::QueryTxRequest;
 use ibc::signer::Signer;
 use ibc::test_utils::get_dummy_account_id;
+use ibc::timestamp::Timestamp;
 use ibc::Height;
 use ibc_proto::ibc::core::channel::v1::{
     PacketState, QueryChannelsRequest, QueryConnectionChannelsRequest,
@@ -128,6 +131,10 @@ impl Chain for MockChain {
         Ok(self.context.query_latest_height())
     }
 
+    fn query_latest_timestamp(&self) -> Result<Timestamp, Error> {
+        Ok(self.context.host_timestamp())
+    }
+
     fn query_clients(&self, _request: QueryClientStatesRequest) -> Result<Vec<ClientId>, Error> {
         unimplemented!()
     }
@@ -197,6 +204,15 @@ impl Chain for MockChain {
         unimplemented!()
     }
 
+    fn query_channel_upgrade(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _height: Height,
+    ) -> Result<Option<ChannelUpgrade>, Error> {
+        unimplemented!()
+    }
+
     fn query_packet_commitments(
         &self,
         _request: QueryPacketCommitmentsRequest,
@@ -270,6 +286,15 @@ impl Chain for MockChain {
         unimplemented!()
     }
 
+    fn proven_channel_upgrade(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _height: Height,
+    ) -> Result<(ChannelUpgrade, MerkleProof), Error> {
+        unimplemented!()
+    }
+
     fn proven_packet(
         &self,
         _packet_type: PacketMsgType,
@@ -368,6 +393,7 @@ pub mod test_utils {
             clock_drift: Duration::from_secs(5),
             trusting_period: Duration::from_secs(14 * 24 * 60 * 60), // 14 days
             trust_threshold: Default::default(),
+            trusted_block: None,
         }
     }
 }
@@ -17,11 +17,13 @@ use ibc::{
     ics03_connection::version::Version,
     ics04_channel::channel::ChannelEnd,
     ics04_channel::packet::{PacketMsgType, Sequence},
+    ics04_channel::upgrade::ChannelUpgrade,
     ics23_commitment::commitment::CommitmentPrefix,
     ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     proofs::Proofs,
     query::QueryTxRequest,
     signer::Signer,
+    timestamp::Timestamp,
     Height,
 };
 use ibc_proto::ibc::core::client::v1::{QueryClientStatesRequest, QueryConsensusStatesRequest};
@@ -210,10 +212,18 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                             self.build_channel_proofs(port_id, channel_id, height, reply_to)?
                         },
 
+                        Ok(ChainRequest::BuildChannelUpgradeProofs { port_id, channel_id, height, reply_to }) => {
+                            self.build_channel_upgrade_proofs(port_id, channel_id, height, reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryLatestHeight { reply_to }) => {
                             self.query_latest_height(reply_to)?
                         }
 
+                        Ok(ChainRequest::QueryLatestTimestamp { reply_to }) => {
+                            self.query_latest_timestamp(reply_to)?
+                        }
+
                         Ok(ChainRequest::QueryClients { request, reply_to }) => {
                             self.query_clients(request, reply_to)?
                         },
@@ -250,6 +260,10 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
                             self.query_channel(port_id, channel_id, height, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryChannelUpgrade { port_id, channel_id, height, reply_to }) => {
+                            self.query_channel_upgrade(port_id, channel_id, height, reply_to)?
+                        },
+
                         Ok(ChainRequest::ProvenClientState { client_id, height, reply_to }) => {
                             self.proven_client_state(client_id, height, reply_to)?
                         },
@@ -333,6 +347,16 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
+    fn query_latest_timestamp(&self, reply_to: ReplyTo<Timestamp>) -> Result<(), Error> {
+        let latest_timestamp = self.chain.query_latest_timestamp();
+
+        reply_to
+            .send(latest_timestamp)
+            .map_err(|e| Kind::Channel.context(e))?;
+
+        Ok(())
+    }
+
     fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
         let result = self.chain.get_signer();
 
@@ -612,6 +636,24 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
+    fn query_channel_upgrade(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        height: Height,
+        reply_to: ReplyTo<Option<ChannelUpgrade>>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .query_channel_upgrade(&port_id, &channel_id, height);
+
+        reply_to
+            .send(result)
+            .map_err(|e| Kind::Channel.context(e))?;
+
+        Ok(())
+    }
+
     fn proven_client_state(
         &self,
         client_id: ClientId,
@@ -682,6 +724,24 @@ impl<C: Chain + Send + 'static> ChainRuntime<C> {
         Ok(())
     }
 
+    fn build_channel_upgrade_proofs(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        height: Height,
+        reply_to: ReplyTo<Proofs>,
+    ) -> Result<(), Error> {
+        let result = self
+            .chain
+            .build_channel_upgrade_proofs(&port_id, &channel_id, height);
+
+        reply_to
+            .send(result)
+            .map_err(|e| Kind::Channel.context(e))?;
+
+        Ok(())
+    }
+
     fn build_packet_proofs(
         &self,
         packet_type: PacketMsgType,
@@ -11,6 +11,12 @@ use ibc::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use ibc::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use ibc::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use ibc::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use ibc::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use ibc::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use ibc::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use ibc::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use ibc::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use ibc::ics04_channel::upgrade::UpgradeState;
 use ibc::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use ibc::tx_msg::Msg;
 use ibc::Height;
@@ -305,6 +311,145 @@ impl Channel {
         )))
     }
 
+    /// Executes the channel upgrade handshake, which upgrades the open channel to the given
+    /// `ordering` and `version`. The upgrade is proposed on the a side of the channel.
+    pub fn upgrade(&mut self, ordering: Order, version: String) -> Result<(), ChannelError> {
+        let done = '🥳';
+
+        let a_chain = self.src_chain();
+        let b_chain = self.dst_chain();
+
+        // Try chanUpgradeInit on a_chain
+        let mut counter = 0;
+        let mut init_success = false;
+        while counter < MAX_ITER {
+            counter += 1;
+            match self
+                .flipped()
+                .build_chan_upgrade_init_and_send(ordering, version.clone())
+            {
+                Err(e) => {
+                    error!("Failed ChanUpgradeInit {:?}: {:?}", self.a_side, e);
+                    continue;
+                }
+                Ok(event) => {
+                    println!("{}  {} => {:#?}\n", done, a_chain.id(), event);
+                    init_success = true;
+                    break;
+                }
+            }
+        }
+
+        if !init_success {
+            return Err(ChannelError::Failed(format!(
+                "Failed to finish channel upgrade init in {} iterations for {:?}",
+                MAX_ITER, self
+            )));
+        };
+
+        // Try chanUpgradeTry on b_chain
+        counter = 0;
+        let mut try_success = false;
+        while counter < MAX_ITER {
+            counter += 1;
+            match self.build_chan_upgrade_try_and_send() {
+                Err(e) => {
+                    error!("Failed ChanUpgradeTry {:?}: {:?}", self.b_side, e);
+                    continue;
+                }
+                Ok(event) => {
+                    println!("{}  {} => {:#?}\n", done, b_chain.id(), event);
+                    try_success = true;
+                    break;
+                }
+            }
+        }
+
+        if !try_success {
+            return Err(ChannelError::Failed(format!(
+                "Failed to finish channel upgrade try in {} iterations for {:?}",
+                MAX_ITER, self
+            )));
+        };
+
+        counter = 0;
+        while counter < MAX_ITER {
+            counter += 1;
+
+            // Continue loop if query error. The channel ends keep their state during the upgrade,
+            // which is tracked by the upgrades in progress on both chains instead.
+            let a_upgrade = a_chain.query_channel_upgrade(
+                &self.src_port_id(),
+                &self.src_channel_id(),
+                Height::zero(),
+            );
+            if a_upgrade.is_err() {
+                continue;
+            }
+            let b_upgrade = b_chain.query_channel_upgrade(
+                &self.dst_port_id(),
+                &self.dst_channel_id(),
+                Height::zero(),
+            );
+            if b_upgrade.is_err() {
+                continue;
+            }
+
+            match (
+                a_upgrade.unwrap().map(|upgrade| *upgrade.state()),
+                b_upgrade.unwrap().map(|upgrade| *upgrade.state()),
+            ) {
+                (Some(UpgradeState::Init), Some(UpgradeState::Try)) => {
+                    // Ack to a_chain
+                    match self.flipped().build_chan_upgrade_ack_and_send() {
+                        Err(e) => error!("Failed ChanUpgradeAck {:?}: {}", self.a_side, e),
+                        Ok(event) => println!("{}  {} => {:#?}\n", done, a_chain.id(), event),
+                    }
+                }
+                (None, Some(UpgradeState::Try)) => {
+                    // Confirm to b_chain
+                    match self.build_chan_upgrade_confirm_and_send() {
+                        Err(e) => error!("Failed ChanUpgradeConfirm {:?}: {}", self.b_side, e),
+                        Ok(event) => println!("{}  {} => {:#?}\n", done, b_chain.id(), event),
+                    }
+                }
+                (None, None) => {
+                    let a_channel = a_chain.query_channel(
+                        &self.src_port_id(),
+                        &self.src_channel_id(),
+                        Height::zero(),
+                    );
+                    let a_channel = match a_channel {
+                        Ok(a_channel) => a_channel,
+                        Err(_) => continue,
+                    };
+
+                    // Both upgrades are over, which either completed or was cancelled.
+                    if *a_channel.ordering() != ordering || a_channel.version() != version {
+                        return Err(ChannelError::Failed(format!(
+                            "channel upgrade was cancelled for {:?}",
+                            self
+                        )));
+                    }
+
+                    self.ordering = ordering;
+                    self.version = Some(version);
+                    println!(
+                        "{}  {}  {}  Channel upgrade handshake finished for {:#?}\n",
+                        done, done, done, self
+                    );
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        Err(ChannelError::Failed(format!(
+            "Failed to finish channel upgrade handshake in {} iterations for {:?}",
+            MAX_ITER, self
+        )))
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, ChannelError> {
         let client = ForeignClient {
             id: self.dst_client_id().clone(),
@@ -844,6 +989,354 @@ impl Channel {
             _ => panic!("internal error"),
         }
     }
+
+    pub fn build_chan_upgrade_init(
+        &self,
+        ordering: Order,
+        version: String,
+    ) -> Result<Vec<Any>, ChannelError> {
+        let dst_channel = self
+            .dst_chain()
+            .query_channel(self.dst_port_id(), self.dst_channel_id(), Height::default())
+            .map_err(|e| ChannelError::QueryError(self.dst_chain().id(), e))?;
+
+        if !dst_channel.state_matches(&State::Open) {
+            return Err(ChannelError::Failed(format!(
+                "channel {} must be open to be upgraded",
+                self.dst_channel_id()
+            )));
+        }
+
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ChannelError::Failed(format!(
+                "failed while fetching the signer for dst chain ({}) with error: {}",
+                self.dst_chain().id(),
+                e
+            ))
+        })?;
+
+        // The upgrade keeps the counterparty of the channel, which stays open once upgraded.
+        let proposed_upgrade_channel = ChannelEnd::new(
+            State::Open,
+            ordering,
+            dst_channel.counterparty().clone(),
+            vec![self.dst_connection_id().clone()],
+            version,
+        );
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeInit {
+            port_id: self.dst_port_id().clone(),
+            channel_id: self.dst_channel_id().clone(),
+            proposed_upgrade_channel,
+            signer,
+        };
+
+        Ok(vec![new_msg.to_any()])
+    }
+
+    pub fn build_chan_upgrade_init_and_send(
+        &self,
+        ordering: Order,
+        version: String,
+    ) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_init(ordering, version)?;
+
+        let events = self
+            .dst_chain()
+            .send_msgs(dst_msgs)
+            .map_err(|e| ChannelError::SubmitError(self.dst_chain().id(), e))?;
+
+        // Find the relevant event for channel upgrade init
+        let result = events
+            .into_iter()
+            .find(|event| {
+                matches!(event, IbcEvent::UpgradeInitChannel(_))
+                    || matches!(event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::Failed("no chan upgrade init event was in the response".to_string())
+            })?;
+
+        match result {
+            IbcEvent::UpgradeInitChannel(_) => Ok(result),
+            IbcEvent::ChainError(e) => {
+                Err(ChannelError::Failed(format!("tx response error: {}", e)))
+            }
+            _ => panic!("internal error"),
+        }
+    }
+
+    pub fn build_chan_upgrade_try(&self) -> Result<Vec<Any>, ChannelError> {
+        // The upgrade proposed on the source chain
+        let src_upgrade = self
+            .src_chain()
+            .query_channel_upgrade(self.src_port_id(), self.src_channel_id(), Height::default())
+            .map_err(|e| ChannelError::QueryError(self.src_chain().id(), e))?
+            .filter(|upgrade| upgrade.state_matches(&UpgradeState::Init))
+            .ok_or_else(|| {
+                ChannelError::Failed(format!(
+                    "no upgrade was proposed for channel {} on source chain",
+                    self.src_channel_id()
+                ))
+            })?;
+        let src_channel = src_upgrade.channel_end();
+
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::QueryError(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_upgrade_proofs(self.src_port_id(), self.src_channel_id(), query_height)
+            .map_err(|e| {
+                ChannelError::Failed(format!("failed to build channel upgrade proofs: {}", e))
+            })?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        let counterparty = Counterparty::new(
+            self.src_port_id().clone(),
+            Some(self.src_channel_id().clone()),
+        );
+
+        let proposed_upgrade_channel = ChannelEnd::new(
+            State::Open,
+            *src_channel.ordering(),
+            counterparty,
+            vec![self.dst_connection_id().clone()],
+            src_channel.version(),
+        );
+
+        // Get signer
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ChannelError::Failed(format!(
+                "failed while fetching the signer for dst chain ({}) with error: {}",
+                self.dst_chain().id(),
+                e
+            ))
+        })?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeTry {
+            port_id: self.dst_port_id().clone(),
+            channel_id: self.dst_channel_id().clone(),
+            proposed_upgrade_channel,
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_try_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_try()?;
+
+        let events = self
+            .dst_chain()
+            .send_msgs(dst_msgs)
+            .map_err(|e| ChannelError::SubmitError(self.dst_chain().id(), e))?;
+
+        // Find the relevant event for channel upgrade try
+        let result = events
+            .into_iter()
+            .find(|event| {
+                matches!(event, IbcEvent::UpgradeTryChannel(_))
+                    || matches!(event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::Failed("no chan upgrade try event was in the response".to_string())
+            })?;
+
+        match result {
+            IbcEvent::UpgradeTryChannel(_) => Ok(result),
+            IbcEvent::ChainError(e) => {
+                Err(ChannelError::Failed(format!("tx response error: {}", e)))
+            }
+            _ => panic!("internal error"),
+        }
+    }
+
+    pub fn build_chan_upgrade_ack(&self) -> Result<Vec<Any>, ChannelError> {
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::QueryError(self.src_chain().id(), e))?;
+
+        // The proofs show the upgrade which the source chain accepted.
+        let proofs = self
+            .src_chain()
+            .build_channel_upgrade_proofs(self.src_port_id(), self.src_channel_id(), query_height)
+            .map_err(|e| {
+                ChannelError::Failed(format!("failed to build channel upgrade proofs: {}", e))
+            })?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ChannelError::Failed(format!(
+                "failed while fetching the signer for dst chain ({}) with error: {}",
+                self.dst_chain().id(),
+                e
+            ))
+        })?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeAck {
+            port_id: self.dst_port_id().clone(),
+            channel_id: self.dst_channel_id().clone(),
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_ack_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_ack()?;
+
+        let events = self
+            .dst_chain()
+            .send_msgs(dst_msgs)
+            .map_err(|e| ChannelError::SubmitError(self.dst_chain().id(), e))?;
+
+        // Find the relevant event for channel upgrade ack
+        let result = events
+            .into_iter()
+            .find(|event| {
+                matches!(event, IbcEvent::UpgradeAckChannel(_))
+                    || matches!(event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::Failed("no chan upgrade ack event was in the response".to_string())
+            })?;
+
+        match result {
+            IbcEvent::UpgradeAckChannel(_) => Ok(result),
+            IbcEvent::ChainError(e) => {
+                Err(ChannelError::Failed(format!("tx response error: {}", e)))
+            }
+            _ => panic!("internal error"),
+        }
+    }
+
+    pub fn build_chan_upgrade_confirm(&self) -> Result<Vec<Any>, ChannelError> {
+        let query_height = self
+            .src_chain()
+            .query_latest_height()
+            .map_err(|e| ChannelError::QueryError(self.src_chain().id(), e))?;
+
+        let proofs = self
+            .src_chain()
+            .build_channel_proofs(self.src_port_id(), self.src_channel_id(), query_height)
+            .map_err(|e| ChannelError::Failed(format!("failed to build channel proofs: {}", e)))?;
+
+        // Build message(s) to update client on destination
+        let mut msgs = self.build_update_client_on_dst(proofs.height())?;
+
+        // Get signer
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ChannelError::Failed(format!(
+                "failed while fetching the signer for dst chain ({}) with error: {}",
+                self.dst_chain().id(),
+                e
+            ))
+        })?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeConfirm {
+            port_id: self.dst_port_id().clone(),
+            channel_id: self.dst_channel_id().clone(),
+            proofs,
+            signer,
+        };
+
+        msgs.push(new_msg.to_any());
+        Ok(msgs)
+    }
+
+    pub fn build_chan_upgrade_confirm_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_confirm()?;
+
+        let events = self
+            .dst_chain()
+            .send_msgs(dst_msgs)
+            .map_err(|e| ChannelError::SubmitError(self.dst_chain().id(), e))?;
+
+        // Find the relevant event for channel upgrade confirm
+        let result = events
+            .into_iter()
+            .find(|event| {
+                matches!(event, IbcEvent::UpgradeConfirmChannel(_))
+                    || matches!(event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::Failed(
+                    "no chan upgrade confirm event was in the response".to_string(),
+                )
+            })?;
+
+        match result {
+            IbcEvent::UpgradeConfirmChannel(_) => Ok(result),
+            IbcEvent::ChainError(e) => {
+                Err(ChannelError::Failed(format!("tx response error: {}", e)))
+            }
+            _ => panic!("internal error"),
+        }
+    }
+
+    pub fn build_chan_upgrade_cancel(&self) -> Result<Vec<Any>, ChannelError> {
+        let signer = self.dst_chain().get_signer().map_err(|e| {
+            ChannelError::Failed(format!(
+                "failed while fetching the signer for dst chain ({}) with error: {}",
+                self.dst_chain().id(),
+                e
+            ))
+        })?;
+
+        // Build the domain type message
+        let new_msg = MsgChannelUpgradeCancel {
+            port_id: self.dst_port_id().clone(),
+            channel_id: self.dst_channel_id().clone(),
+            signer,
+        };
+
+        Ok(vec![new_msg.to_any()])
+    }
+
+    pub fn build_chan_upgrade_cancel_and_send(&self) -> Result<IbcEvent, ChannelError> {
+        let dst_msgs = self.build_chan_upgrade_cancel()?;
+
+        let events = self
+            .dst_chain()
+            .send_msgs(dst_msgs)
+            .map_err(|e| ChannelError::SubmitError(self.dst_chain().id(), e))?;
+
+        // Find the relevant event for channel upgrade cancel
+        let result = events
+            .into_iter()
+            .find(|event| {
+                matches!(event, IbcEvent::UpgradeCancelChannel(_))
+                    || matches!(event, IbcEvent::ChainError(_))
+            })
+            .ok_or_else(|| {
+                ChannelError::Failed("no chan upgrade cancel event was in the response".to_string())
+            })?;
+
+        match result {
+            IbcEvent::UpgradeCancelChannel(_) => Ok(result),
+            IbcEvent::ChainError(e) => {
+                Err(ChannelError::Failed(format!("tx response error: {}", e)))
+            }
+            _ => panic!("internal error"),
+        }
+    }
 }
 
 fn extract_channel_id(event: &IbcEvent) -> Result<&ChannelId, ChannelError> {
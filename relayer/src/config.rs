@@ -126,6 +126,18 @@ pub struct ChainConfig {
     pub trusting_period: Duration,
     #[serde(default)]
     pub trust_threshold: TrustThreshold,
+    /// The block from which the relayer verifies the proofs of the query responses of the chain.
+    /// If unset, the relayer trusts the latest block upon the first proven query.
+    #[serde(default)]
+    pub trusted_block: Option<TrustedBlock>,
+}
+
+/// A block of a chain which the relayer trusts, identified by its height and hash, which must
+/// be obtained from a source other than the full node which the relayer queries.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TrustedBlock {
+    pub height: u64,
+    pub hash: tendermint::Hash,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
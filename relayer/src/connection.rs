@@ -14,12 +14,13 @@ use ibc::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
 use ibc::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
 use ibc::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
 use ibc::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use ibc::ics23_commitment::commitment::CommitmentPrefix;
 use ibc::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
 use ibc::tx_msg::Msg;
 use ibc::Height as ICSHeight;
 
 use crate::chain::handle::ChainHandle;
-use crate::error::Error;
+use crate::error::{Error, Kind};
 use crate::foreign_client::{ForeignClient, ForeignClientError};
 use crate::relay::MAX_ITER;
 
@@ -400,6 +401,13 @@ impl Connection {
             )));
         }
 
+        // The destination connection end records the prefix under which the destination chain
+        // verifies the proofs of the source chain.
+        check_commitment_prefix(
+            self.src_chain().as_ref(),
+            dst_connection.counterparty().prefix(),
+        )?;
+
         check_destination_connection_state(
             self.dst_connection_id().clone(),
             dst_connection,
@@ -507,6 +515,11 @@ impl Connection {
 
         // TODO - check that the src connection is consistent with the try options
 
+        check_commitment_prefix(
+            self.dst_chain().as_ref(),
+            src_connection.counterparty().prefix(),
+        )?;
+
         // Cross-check the delay_period
         let delay = if src_connection.delay_period() != self.delay_period {
             warn!("`delay_period` for ConnectionEnd @{} is {}s; delay period on local Connection object is set to {}s",
@@ -639,6 +652,11 @@ impl Connection {
 
         // TODO - check that the src connection is consistent with the ack options
 
+        check_commitment_prefix(
+            self.dst_chain().as_ref(),
+            src_connection.counterparty().prefix(),
+        )?;
+
         // Build add **send** the message(s) for updating client on source.
         // TODO - add check if it is required
         let src_client_target_height = self
@@ -842,7 +860,7 @@ fn check_destination_connection_state(
         || existing_connection.counterparty().connection_id()
             == expected_connection.counterparty().connection_id();
 
-    // TODO check versions and store prefix
+    // TODO check versions
 
     if good_state && good_client_ids && good_connection_ids {
         Ok(())
@@ -853,3 +871,29 @@ fn check_destination_connection_state(
         )))
     }
 }
+
+/// Checks that the commitment prefix of `chain`, as configured, is the `prefix` which a
+/// counterparty connection end records for it. The counterparty chain verifies the proofs of
+/// `chain` under this prefix, so a misconfigured prefix is reported here rather than as proof
+/// verification failures on the counterparty chain.
+pub fn check_commitment_prefix(
+    chain: &dyn ChainHandle,
+    prefix: &CommitmentPrefix,
+) -> Result<(), ConnectionError> {
+    let store_prefix = chain
+        .query_commitment_prefix()
+        .map_err(|e| ConnectionError::QueryError(chain.id(), e))?;
+
+    if &store_prefix != prefix {
+        return Err(ConnectionError::Failed(
+            Kind::CommitmentPrefixMismatch {
+                chain_id: chain.id(),
+                prefix: format!("{:?}", prefix),
+                store_prefix: format!("{:?}", store_prefix),
+            }
+            .to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use thiserror::Error;
 
 use ibc::{
     ics02_client::client_type::ClientType,
-    ics24_host::identifier::{ChannelId, ConnectionId},
+    ics24_host::identifier::{ChainId, ChannelId, ConnectionId},
 };
 
 /// An error that can be raised by the relayer.
@@ -66,6 +66,10 @@ pub enum Kind {
     #[error("Malformed proof")]
     MalformedProof,
 
+    /// Response proof does not match the app hash of a verified header
+    #[error("Invalid response proof")]
+    InvalidResponseProof,
+
     /// Invalid height
     #[error("Invalid height")]
     InvalidHeight,
@@ -134,6 +138,10 @@ pub enum Kind {
     #[error("Failed to build timeout packet {0}: {1}")]
     TimeoutPacket(ChannelId, String),
 
+    /// Multi-hop proof construction failure
+    #[error("Failed to build multi-hop proof: {0}")]
+    Multihop(String),
+
     /// A message transaction failure
     #[error("Message transaction failure: {0}")]
     MessageTransaction(String),
@@ -181,6 +189,22 @@ pub enum Kind {
         expected: ClientType,
         got: ClientType,
     },
+
+    #[error("the counterparty connection end records the commitment prefix '{prefix}' for chain {chain_id}, but its configured store_prefix is '{store_prefix}'")]
+    CommitmentPrefixMismatch {
+        chain_id: ChainId,
+        prefix: String,
+        store_prefix: String,
+    },
+
+    #[error("chain {chain_id} has no store named by its configured store_prefix '{store_prefix}'")]
+    UnknownStorePrefix {
+        chain_id: ChainId,
+        store_prefix: String,
+    },
+
+    #[error("the light block of chain {chain_id} at height {height} does not match the configured trusted block")]
+    TrustedBlockMismatch { chain_id: ChainId, height: u64 },
 }
 
 impl Kind {
@@ -93,6 +93,17 @@ pub fn build_event(mut object: RawObject) -> Result<IbcEvent, BoxError> {
         "channel_close_confirm" => Ok(IbcEvent::from(ChannelEvents::CloseConfirm::try_from(
             object,
         )?)),
+        "channel_upgrade_init" => Ok(IbcEvent::from(ChannelEvents::UpgradeInit::try_from(
+            object,
+        )?)),
+        "channel_upgrade_try" => Ok(IbcEvent::from(ChannelEvents::UpgradeTry::try_from(object)?)),
+        "channel_upgrade_ack" => Ok(IbcEvent::from(ChannelEvents::UpgradeAck::try_from(object)?)),
+        "channel_upgrade_confirm" => Ok(IbcEvent::from(ChannelEvents::UpgradeConfirm::try_from(
+            object,
+        )?)),
+        "channel_upgrade_cancel" => Ok(IbcEvent::from(ChannelEvents::UpgradeCancel::try_from(
+            object,
+        )?)),
 
         // Packet events
         // Note: There is no message.action "send_packet", the only one we can hook into is the
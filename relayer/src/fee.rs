@@ -0,0 +1,71 @@
+use thiserror::Error;
+
+use ibc::application::ics29_fee::msgs::register_counterparty_payee::MsgRegisterCounterpartyPayee;
+use ibc::events::IbcEvent;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::tx_msg::Msg;
+
+use crate::chain::{Chain, CosmosSdkChain};
+use crate::config::ChainConfig;
+use crate::error::Error;
+
+#[derive(Debug, Error)]
+pub enum FeeError {
+    #[error("failed with underlying cause: {0}")]
+    Failed(String),
+
+    #[error("key error with underlying cause: {0}")]
+    KeyError(Error),
+
+    #[error(
+        "failed during a transaction submission step to chain id {0} with underlying error: {1}"
+    )]
+    SubmitError(ChainId, Error),
+}
+
+#[derive(Clone, Debug)]
+pub struct RegisterPayeeOptions {
+    pub chain_config: ChainConfig,
+    pub port_id: PortId,
+    pub channel_id: ChannelId,
+    pub counterparty_payee: String,
+}
+
+/// Registers `counterparty_payee` as the address, on the counterparty chain of the channel, to
+/// which the receive fees are paid for the packets which this relayer delivers to `chain`.
+pub fn build_and_send_register_payee_message(
+    mut chain: CosmosSdkChain, // the chain to which the relayer delivers packets
+    opts: &RegisterPayeeOptions,
+) -> Result<Vec<IbcEvent>, FeeError> {
+    let relayer = chain.get_signer().map_err(FeeError::KeyError)?;
+
+    let msg = MsgRegisterCounterpartyPayee::new(
+        opts.port_id.clone(),
+        opts.channel_id.clone(),
+        relayer,
+        opts.counterparty_payee.clone().into(),
+    );
+
+    let events = chain
+        .send_msgs(vec![msg.to_any()])
+        .map_err(|e| FeeError::SubmitError(chain.id().clone(), e))?;
+
+    // Check if the chain rejected the transaction
+    let result = events
+        .iter()
+        .find(|event| matches!(event, IbcEvent::ChainError(_)));
+
+    match result {
+        None => Ok(events),
+        Some(err) => {
+            if let IbcEvent::ChainError(err) = err {
+                Err(FeeError::Failed(err.to_string()))
+            } else {
+                panic!(
+                    "internal error, expected IBCEvent::ChainError, got {:?}",
+                    err
+                )
+            }
+        }
+    }
+}
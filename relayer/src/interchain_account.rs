@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use prost_types::Any;
+use thiserror::Error;
+
+use ibc::application::ics27_interchain_accounts::msgs::register_interchain_account::MsgRegisterInterchainAccount;
+use ibc::application::ics27_interchain_accounts::msgs::send_tx::MsgSendTx;
+use ibc::application::ics27_interchain_accounts::packet::InterchainAccountPacketData;
+use ibc::events::IbcEvent;
+use ibc::ics24_host::identifier::{ChainId, ConnectionId};
+use ibc::tx_msg::Msg;
+
+use crate::chain::{Chain, CosmosSdkChain};
+use crate::config::ChainConfig;
+use crate::error::Error;
+
+#[derive(Debug, Error)]
+pub enum InterchainAccountError {
+    #[error("failed with underlying cause: {0}")]
+    Failed(String),
+
+    #[error("key error with underlying cause: {0}")]
+    KeyError(Error),
+
+    #[error(
+        "failed during a transaction submission step to chain id {0} with underlying error: {1}"
+    )]
+    SubmitError(ChainId, Error),
+}
+
+#[derive(Clone, Debug)]
+pub struct RegisterInterchainAccountOptions {
+    pub chain_config: ChainConfig,
+    pub connection_id: ConnectionId,
+    pub version: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct SendTxOptions {
+    pub chain_config: ChainConfig,
+    pub connection_id: ConnectionId,
+    pub messages: Vec<Any>,
+    pub memo: String,
+    pub relative_timeout: Duration,
+}
+
+/// Registers an interchain account, controlled by the key of `chain`, on the host chain at the
+/// other end of connection `opts.connection_id`. This opens the channel to the host chain, whose
+/// handshake the relayer then completes like that of any other channel.
+pub fn build_and_send_register_interchain_account_message(
+    mut chain: CosmosSdkChain, // the controller chain
+    opts: &RegisterInterchainAccountOptions,
+) -> Result<Vec<IbcEvent>, InterchainAccountError> {
+    let owner = chain
+        .get_signer()
+        .map_err(InterchainAccountError::KeyError)?;
+
+    let msg =
+        MsgRegisterInterchainAccount::new(owner, opts.connection_id.clone(), opts.version.clone());
+
+    send_msg(&mut chain, msg.to_any())
+}
+
+/// Sends `opts.messages` for the interchain account, controlled by the key of `chain`, to
+/// execute on the host chain at the other end of connection `opts.connection_id`.
+pub fn build_and_send_tx_message(
+    mut chain: CosmosSdkChain, // the controller chain
+    opts: &SendTxOptions,
+) -> Result<Vec<IbcEvent>, InterchainAccountError> {
+    let owner = chain
+        .get_signer()
+        .map_err(InterchainAccountError::KeyError)?;
+
+    let msg = MsgSendTx {
+        owner,
+        connection_id: opts.connection_id.clone(),
+        packet_data: InterchainAccountPacketData::execute_tx(
+            opts.messages.clone(),
+            opts.memo.clone(),
+        ),
+        relative_timeout: opts.relative_timeout.as_nanos() as u64,
+    };
+
+    send_msg(&mut chain, msg.to_any())
+}
+
+fn send_msg(chain: &mut CosmosSdkChain, msg: Any) -> Result<Vec<IbcEvent>, InterchainAccountError> {
+    let events = chain
+        .send_msgs(vec![msg])
+        .map_err(|e| InterchainAccountError::SubmitError(chain.id().clone(), e))?;
+
+    // Check if the chain rejected the transaction
+    let result = events
+        .iter()
+        .find(|event| matches!(event, IbcEvent::ChainError(_)));
+
+    match result {
+        None => Ok(events),
+        Some(err) => {
+            if let IbcEvent::ChainError(err) = err {
+                Err(InterchainAccountError::Failed(err.to_string()))
+            } else {
+                panic!(
+                    "internal error, expected IBCEvent::ChainError, got {:?}",
+                    err
+                )
+            }
+        }
+    }
+}
@@ -19,11 +19,14 @@ pub mod config;
 pub mod connection;
 pub mod error;
 pub mod event;
+pub mod fee;
 pub mod foreign_client;
+pub mod interchain_account;
 pub mod keyring;
 pub mod light_client;
 pub mod link;
 pub mod macros;
+pub mod multihop;
 pub mod relay;
 pub mod supervisor;
 pub mod transfer;
@@ -1,3 +1,4 @@
+use tendermint::Time;
 use tendermint_testgen::light_block::TmLightBlock;
 
 use ibc::ics02_client::client_state::AnyClientState;
@@ -23,9 +24,9 @@ impl LightClient {
         }
     }
 
-    /// Returns a LightBlock at the requested height `h`.
+    /// Returns a LightBlock at the requested height `h`, timestamped with the current time.
     fn light_block(&self, h: Height) -> TmLightBlock {
-        HostBlock::generate_tm_block(self.chain_id.clone(), h.revision_height)
+        HostBlock::generate_tm_block(self.chain_id.clone(), h.revision_height, Time::now())
     }
 }
 
@@ -44,17 +44,9 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
         target: ibc::Height,
         client_state: &AnyClientState,
     ) -> Result<LightBlock, Error> {
-        let target_height = TMHeight::try_from(target.revision_height)
-            .map_err(|e| error::Kind::InvalidHeight.context(e))?;
+        let trusted_block = self.fetch(trusted)?;
 
-        let client = self.prepare_client(client_state)?;
-        let mut state = self.prepare_state(trusted)?;
-
-        let light_block = client
-            .verify_to_target(target_height, &mut state)
-            .map_err(|e| error::Kind::LightClient(self.chain_id.to_string()).context(e))?;
-
-        Ok(light_block)
+        self.verify_from(trusted_block, target, client_state)
     }
 
     fn fetch(&mut self, height: ibc::Height) -> Result<LightBlock, Error> {
@@ -152,6 +144,30 @@ impl LightClient {
         })
     }
 
+    /// Verifies the header at the `target` height from the given `trusted` light block, instead
+    /// of a trusted light block fetched from the full node, as `verify` does.
+    pub fn verify_from(
+        &self,
+        trusted: LightBlock,
+        target: ibc::Height,
+        client_state: &AnyClientState,
+    ) -> Result<LightBlock, Error> {
+        let target_height = TMHeight::try_from(target.revision_height)
+            .map_err(|e| error::Kind::InvalidHeight.context(e))?;
+
+        let client = self.prepare_client(client_state)?;
+
+        let mut store = MemoryStore::new();
+        store.insert(trusted, Status::Trusted);
+        let mut state = LightClientState::new(store);
+
+        let light_block = client
+            .verify_to_target(target_height, &mut state)
+            .map_err(|e| error::Kind::LightClient(self.chain_id.to_string()).context(e))?;
+
+        Ok(light_block)
+    }
+
     fn prepare_client(&self, client_state: &AnyClientState) -> Result<TmLightClient, Error> {
         let clock = components::clock::SystemClock;
         let hasher = operations::hasher::ProdHasher;
@@ -183,18 +199,6 @@ impl LightClient {
         ))
     }
 
-    fn prepare_state(&self, trusted: ibc::Height) -> Result<LightClientState, Error> {
-        let trusted_height = TMHeight::try_from(trusted.revision_height)
-            .map_err(|e| error::Kind::InvalidHeight.context(e))?;
-
-        let trusted_block = self.fetch_light_block(AtHeight::At(trusted_height))?;
-
-        let mut store = MemoryStore::new();
-        store.insert(trusted_block, Status::Trusted);
-
-        Ok(LightClientState::new(store))
-    }
-
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
         use tendermint_light_client::components::io::Io;
 
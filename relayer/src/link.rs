@@ -10,7 +10,7 @@ use tracing::{debug, error, info, trace, warn};
 use ibc::{
     downcast,
     events::{IbcEvent, IbcEventType},
-    ics03_connection::connection::State as ConnectionState,
+    ics03_connection::connection::{ConnectionEnd, State as ConnectionState},
     ics04_channel::{
         channel::{ChannelEnd, Order, QueryPacketEventDataRequest, State as ChannelState},
         events::{SendPacket, WriteAcknowledgement},
@@ -21,8 +21,10 @@ use ibc::{
         packet::{Packet, PacketMsgType, Sequence},
     },
     ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
+    proofs::Proofs,
     query::QueryTxRequest,
     signer::Signer,
+    timestamp::{Expiry, Timestamp},
     tx_msg::Msg,
     Height,
 };
@@ -33,10 +35,13 @@ use ibc_proto::ibc::core::channel::v1::{
 
 use crate::chain::handle::ChainHandle;
 use crate::channel::{Channel, ChannelError, ChannelSide};
-use crate::connection::ConnectionError;
+use crate::connection::{check_commitment_prefix, ConnectionError};
 use crate::error::Error;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
+use crate::multihop::{
+    build_multihop_channel_proofs, build_multihop_packet_proofs, follow_connection_hops,
+};
 use crate::relay::MAX_ITER;
 use ibc::events::VecIbcEvents;
 
@@ -185,6 +190,9 @@ pub struct RelayPath {
     src_chain: Box<dyn ChainHandle>,
     dst_chain: Box<dyn ChainHandle>,
     channel: Channel,
+    // The intermediate chains of a multi-hop channel, ordered from the source to the destination
+    // chain. Empty if the channel ends are connected directly.
+    hop_chains: Vec<Box<dyn ChainHandle>>,
     clear_packets: bool,
 
     // Operational data, targeting both the source and destination chain.
@@ -206,6 +214,7 @@ impl RelayPath {
             src_chain,
             dst_chain,
             channel,
+            hop_chains: vec![],
             clear_packets: true,
             src_operational_data: Default::default(),
             dst_operational_data: Default::default(),
@@ -296,6 +305,12 @@ impl RelayPath {
             .map_err(|e| LinkError::QueryError(self.dst_chain.id(), e))
     }
 
+    pub fn dst_latest_timestamp(&self) -> Result<Timestamp, LinkError> {
+        self.dst_chain
+            .query_latest_timestamp()
+            .map_err(|e| LinkError::QueryError(self.dst_chain.id(), e))
+    }
+
     fn unordered_channel(&self) -> bool {
         self.channel.ordering == Order::Unordered
     }
@@ -304,11 +319,127 @@ impl RelayPath {
         self.channel.ordering == Order::Ordered
     }
 
+    /// Returns the chain tracked by the client of the destination channel end: the source chain,
+    /// or the last intermediate chain of a multi-hop channel.
+    fn dst_client_chain(&self) -> Box<dyn ChainHandle> {
+        self.hop_chains
+            .last()
+            .cloned()
+            .unwrap_or_else(|| self.src_chain())
+    }
+
+    /// Returns the chain tracked by the client of the source channel end: the destination chain,
+    /// or the first intermediate chain of a multi-hop channel.
+    fn src_client_chain(&self) -> Box<dyn ChainHandle> {
+        self.hop_chains
+            .first()
+            .cloned()
+            .unwrap_or_else(|| self.dst_chain())
+    }
+
+    /// Returns the height at which to query the proofs of the data stored on the source chain at
+    /// `src_height`, which the destination chain verifies. For a multi-hop channel, this is the
+    /// latest height of the last intermediate chain, from which the proofs follow the hops.
+    fn dst_proofs_height(&self, src_height: Height) -> Result<Height, LinkError> {
+        if self.hop_chains.is_empty() {
+            return Ok(src_height);
+        }
+
+        let chain = self.dst_client_chain();
+        chain
+            .query_latest_height()
+            .map_err(|e| LinkError::QueryError(chain.id(), e))
+    }
+
+    /// Returns the height at which to query the proofs of the data stored on the destination
+    /// chain at `dst_height`, which the source chain verifies. For a multi-hop channel, this is
+    /// the latest height of the first intermediate chain, from which the proofs follow the hops.
+    fn src_proofs_height(&self, dst_height: Height) -> Result<Height, LinkError> {
+        if self.hop_chains.is_empty() {
+            return Ok(dst_height);
+        }
+
+        let chain = self.src_client_chain();
+        chain
+            .query_latest_height()
+            .map_err(|e| LinkError::QueryError(chain.id(), e))
+    }
+
+    /// Builds the proofs of the packet data stored on the source chain, which the destination
+    /// chain verifies, at the height returned by [`RelayPath::dst_proofs_height`].
+    fn build_src_packet_proofs(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, LinkError> {
+        let proofs = if self.hop_chains.is_empty() {
+            self.src_chain
+                .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+        } else {
+            // The connection hops of the destination channel end lead to the source chain.
+            let mut chains: Vec<_> = self.hop_chains.iter().rev().cloned().collect();
+            chains.push(self.src_chain());
+
+            build_multihop_packet_proofs(
+                &chains,
+                self.dst_channel(Height::zero())?.connection_hops(),
+                packet_type,
+                port_id,
+                channel_id,
+                sequence,
+                height,
+            )
+        };
+
+        let (_, proofs) =
+            proofs.map_err(|e| LinkError::PacketProofsConstructor(self.src_chain.id(), e))?;
+
+        Ok(proofs)
+    }
+
+    /// Builds the proofs of the packet data stored on the destination chain, which the source
+    /// chain verifies, at the height returned by [`RelayPath::src_proofs_height`].
+    fn build_dst_packet_proofs(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, LinkError> {
+        let proofs = if self.hop_chains.is_empty() {
+            self.dst_chain
+                .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+        } else {
+            // The connection hops of the source channel end lead to the destination chain.
+            let mut chains = self.hop_chains.clone();
+            chains.push(self.dst_chain());
+
+            build_multihop_packet_proofs(
+                &chains,
+                self.src_channel(Height::zero())?.connection_hops(),
+                packet_type,
+                port_id,
+                channel_id,
+                sequence,
+                height,
+            )
+        };
+
+        let (_, proofs) =
+            proofs.map_err(|e| LinkError::PacketProofsConstructor(self.dst_chain.id(), e))?;
+
+        Ok(proofs)
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, LinkError> {
         let client = ForeignClient {
             id: self.dst_client_id().clone(),
             dst_chain: self.dst_chain(),
-            src_chain: self.src_chain(),
+            src_chain: self.dst_client_chain(),
         };
 
         client
@@ -320,7 +451,7 @@ impl RelayPath {
         let client = ForeignClient {
             id: self.src_client_id().clone(),
             dst_chain: self.src_chain(),
-            src_chain: self.dst_chain(),
+            src_chain: self.src_client_chain(),
         };
 
         client
@@ -328,11 +459,23 @@ impl RelayPath {
             .map_err(LinkError::ClientError)
     }
 
-    fn build_chan_close_confirm_from_event(&self, event: &IbcEvent) -> Result<Any, LinkError> {
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(self.src_port_id(), self.src_channel_id(), event.height())
-            .map_err(|e| ChannelError::Failed(format!("failed to build channel proofs: {}", e)))?;
+    fn build_chan_close_confirm(&self, height: Height) -> Result<Any, LinkError> {
+        let proofs = if self.hop_chains.is_empty() {
+            self.src_chain()
+                .build_channel_proofs(self.src_port_id(), self.src_channel_id(), height)
+        } else {
+            let mut chains: Vec<_> = self.hop_chains.iter().rev().cloned().collect();
+            chains.push(self.src_chain());
+
+            build_multihop_channel_proofs(
+                &chains,
+                self.dst_channel(Height::zero())?.connection_hops(),
+                self.src_port_id(),
+                self.src_channel_id(),
+                height,
+            )
+        }
+        .map_err(|e| ChannelError::Failed(format!("failed to build channel proofs: {}", e)))?;
 
         // Build the domain type message
         let new_msg = MsgChannelCloseConfirm {
@@ -464,17 +607,25 @@ impl RelayPath {
             Some(ev) => ev.height(),
         };
 
+        let dst_height = self.dst_latest_height()?;
+        let dst_timestamp = self.dst_latest_timestamp()?;
+
         // Operational data targeting the source chain (e.g., Timeout packets)
-        let mut src_od =
-            OperationalData::new(self.dst_latest_height()?, OperationalDataTarget::Source);
+        let mut src_od = OperationalData::new(
+            self.src_proofs_height(dst_height)?,
+            OperationalDataTarget::Source,
+        );
         // Operational data targeting the destination chain (e.g., SendPacket messages)
-        let mut dst_od = OperationalData::new(src_height, OperationalDataTarget::Destination);
+        let mut dst_od = OperationalData::new(
+            self.dst_proofs_height(src_height)?,
+            OperationalDataTarget::Destination,
+        );
 
         for event in input {
             debug!("[{}] {} => {}", self, self.src_chain.id(), event);
             let (dst_msg, src_msg) = match event {
                 IbcEvent::CloseInitChannel(_) => (
-                    Some(self.build_chan_close_confirm_from_event(&event)?),
+                    Some(self.build_chan_close_confirm(dst_od.proofs_height)?),
                     None,
                 ),
                 IbcEvent::TimeoutPacket(ref timeout_ev) => {
@@ -489,7 +640,7 @@ impl RelayPath {
                             .state_matches(&ChannelState::Closed)
                     {
                         (
-                            Some(self.build_chan_close_confirm_from_event(&event)?),
+                            Some(self.build_chan_close_confirm(dst_od.proofs_height)?),
                             None,
                         )
                     } else {
@@ -499,16 +650,22 @@ impl RelayPath {
                 IbcEvent::SendPacket(ref send_packet_ev) => self
                     .build_recv_or_timeout_from_send_packet_event(
                         &send_packet_ev,
+                        dst_height,
+                        dst_timestamp,
+                        dst_od.proofs_height,
                         src_od.proofs_height,
                     )?,
                 IbcEvent::WriteAcknowledgement(ref write_ack_ev) => {
                     if self
-                        .dst_channel(dst_od.proofs_height)?
+                        .dst_channel(dst_height)?
                         .state_matches(&ChannelState::Closed)
                     {
                         (None, None)
                     } else {
-                        (self.build_ack_from_recv_event(&write_ack_ev)?, None)
+                        (
+                            self.build_ack_from_recv_event(&write_ack_ev, dst_od.proofs_height)?,
+                            None,
+                        )
                     }
                 }
                 _ => (None, None),
@@ -1026,16 +1183,13 @@ impl RelayPath {
     }
 
     fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Any, LinkError> {
-        let (_, proofs) = self
-            .src_chain
-            .build_packet_proofs(
-                PacketMsgType::Recv,
-                &packet.source_port,
-                &packet.source_channel,
-                packet.sequence,
-                height,
-            )
-            .map_err(|e| LinkError::PacketProofsConstructor(self.src_chain.id(), e))?;
+        let proofs = self.build_src_packet_proofs(
+            PacketMsgType::Recv,
+            &packet.source_port,
+            &packet.source_channel,
+            packet.sequence,
+            height,
+        )?;
 
         let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
 
@@ -1052,6 +1206,7 @@ impl RelayPath {
     fn build_ack_from_recv_event(
         &self,
         event: &WriteAcknowledgement,
+        height: Height,
     ) -> Result<Option<Any>, LinkError> {
         let packet = event.packet.clone();
         let acked =
@@ -1065,16 +1220,13 @@ impl RelayPath {
             return Ok(None);
         }
 
-        let (_, proofs) = self
-            .src_chain
-            .build_packet_proofs(
-                PacketMsgType::Ack,
-                &packet.destination_port,
-                &packet.destination_channel,
-                packet.sequence,
-                event.height,
-            )
-            .map_err(|e| LinkError::PacketProofsConstructor(self.src_chain.id(), e))?;
+        let proofs = self.build_src_packet_proofs(
+            PacketMsgType::Ack,
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+            height,
+        )?;
 
         let msg = MsgAcknowledgement::new(
             packet,
@@ -1121,16 +1273,13 @@ impl RelayPath {
             (PacketMsgType::TimeoutUnordered, packet.sequence)
         };
 
-        let (_, proofs) = self
-            .dst_chain
-            .build_packet_proofs(
-                packet_type,
-                &packet.destination_port,
-                &packet.destination_channel,
-                next_sequence_received,
-                height,
-            )
-            .map_err(|e| LinkError::PacketProofsConstructor(self.dst_chain.id(), e))?;
+        let proofs = self.build_dst_packet_proofs(
+            packet_type,
+            &packet.destination_port,
+            &packet.destination_channel,
+            next_sequence_received,
+            height,
+        )?;
 
         let msg = MsgTimeout::new(
             packet.clone(),
@@ -1154,16 +1303,13 @@ impl RelayPath {
         packet: &Packet,
         height: Height,
     ) -> Result<Any, LinkError> {
-        let (_, proofs) = self
-            .dst_chain
-            .build_packet_proofs(
-                PacketMsgType::TimeoutOnClose,
-                &packet.destination_port,
-                &packet.destination_channel,
-                packet.sequence,
-                height,
-            )
-            .map_err(|e| LinkError::PacketProofsConstructor(self.dst_chain.id(), e))?;
+        let proofs = self.build_dst_packet_proofs(
+            PacketMsgType::TimeoutOnClose,
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+            height,
+        )?;
 
         let msg = MsgTimeoutOnClose::new(
             packet.clone(),
@@ -1182,10 +1328,15 @@ impl RelayPath {
         Ok(msg.to_any())
     }
 
+    /// Builds the timeout message of the packet sent by `event`, if the packet timed out on the
+    /// destination chain at `dst_chain_height`. The proofs are queried at `proofs_height`, as
+    /// returned by [`RelayPath::src_proofs_height`].
     fn build_timeout_from_send_packet_event(
         &self,
         event: &SendPacket,
         dst_chain_height: Height,
+        dst_chain_timestamp: Timestamp,
+        proofs_height: Height,
     ) -> Result<Option<Any>, LinkError> {
         let packet = event.packet.clone();
         if self
@@ -1193,32 +1344,48 @@ impl RelayPath {
             .state_matches(&ChannelState::Closed)
         {
             return Ok(Some(
-                self.build_timeout_on_close_packet(&event.packet, dst_chain_height)?,
+                self.build_timeout_on_close_packet(&event.packet, proofs_height)?,
             ));
         }
 
-        if packet.timeout_height != Height::zero() && packet.timeout_height < dst_chain_height {
+        let height_timeout =
+            packet.timeout_height != Height::zero() && packet.timeout_height < dst_chain_height;
+        let timestamp_timeout =
+            packet.timeout_timestamp.check_expiry(&dst_chain_timestamp) == Expiry::Expired;
+
+        if height_timeout || timestamp_timeout {
             debug!(
                 "[{}] new timeout message emerged for seq {}, with proofs for height {}",
                 self, event.packet.sequence, dst_chain_height
             );
-            return self.build_timeout_packet(&event.packet, dst_chain_height);
+            return self.build_timeout_packet(&event.packet, proofs_height);
         }
 
         Ok(None)
     }
 
+    /// Builds either the receive message of the packet sent by `event`, with proofs queried at
+    /// `recv_proofs_height`, or its timeout message, with proofs queried at
+    /// `timeout_proofs_height`.
     fn build_recv_or_timeout_from_send_packet_event(
         &self,
         event: &SendPacket,
         dst_chain_height: Height,
+        dst_chain_timestamp: Timestamp,
+        recv_proofs_height: Height,
+        timeout_proofs_height: Height,
     ) -> Result<(Option<Any>, Option<Any>), LinkError> {
-        let timeout = self.build_timeout_from_send_packet_event(event, dst_chain_height)?;
+        let timeout = self.build_timeout_from_send_packet_event(
+            event,
+            dst_chain_height,
+            dst_chain_timestamp,
+            timeout_proofs_height,
+        )?;
         if timeout.is_some() {
             Ok((None, timeout))
         } else {
             Ok((
-                Some(self.build_recv_packet(&event.packet, event.height)?),
+                Some(self.build_recv_packet(&event.packet, recv_proofs_height)?),
                 None,
             ))
         }
@@ -1244,6 +1411,8 @@ impl RelayPath {
     /// to source operational data, and adjusts the events and messages accordingly.
     pub fn refresh_schedule(&mut self) -> Result<(), LinkError> {
         let dst_current_height = self.dst_latest_height()?;
+        let dst_current_timestamp = self.dst_latest_timestamp()?;
+        let proofs_height = self.src_proofs_height(dst_current_height)?;
 
         // Intermediary data struct to help better manage the transfer from dst. operational data
         // to source operational data.
@@ -1262,7 +1431,12 @@ impl RelayPath {
                 if let IbcEvent::SendPacket(e) = event {
                     if let Some(new_msg) =
                         // Catch any SendPacket event that timed-out
-                        self.build_timeout_from_send_packet_event(e, dst_current_height)?
+                        self.build_timeout_from_send_packet_event(
+                            e,
+                            dst_current_height,
+                            dst_current_timestamp,
+                            proofs_height,
+                        )?
                     {
                         debug!("[{}] found a timed-out msg in the op data {}", self, odata);
                         timed_out
@@ -1292,8 +1466,7 @@ impl RelayPath {
 
         // Schedule new operational data targeting the source chain
         for (&_pos, batch) in timed_out.iter() {
-            let mut new_od =
-                OperationalData::new(dst_current_height, OperationalDataTarget::Source);
+            let mut new_od = OperationalData::new(proofs_height, OperationalDataTarget::Source);
             new_od.batch = batch.clone();
 
             info!(
@@ -1454,6 +1627,14 @@ impl Link {
         }
     }
 
+    /// Relays a multi-hop channel through the given intermediate chains, ordered from chain A to
+    /// chain B.
+    pub fn with_hop_chains(mut self, hop_chains: Vec<Box<dyn ChainHandle>>) -> Self {
+        self.b_to_a.hop_chains = hop_chains.iter().rev().cloned().collect();
+        self.a_to_b.hop_chains = hop_chains;
+        self
+    }
+
     pub fn relay(&mut self) -> Result<(), LinkError> {
         info!(
             "relaying packets on path {} <-> {} with delay of {:?}",
@@ -1536,9 +1717,12 @@ impl Link {
         }
     }
 
+    /// Creates the link of the channel given by `opts` from chain A to chain B. The intermediate
+    /// chains of a multi-hop channel are looked up among `hop_chains`.
     pub fn new_from_opts(
         a_chain: Box<dyn ChainHandle>,
         b_chain: Box<dyn ChainHandle>,
+        hop_chains: &[Box<dyn ChainHandle>],
         opts: LinkParameters,
     ) -> Result<Link, LinkError> {
         // Check that the packet's channel on source chain is Open
@@ -1579,6 +1763,34 @@ impl Link {
             )));
         }
 
+        // The intermediate chains of a multi-hop channel, from chain A to chain B.
+        let hop_chains = if a_channel.connection_hops().len() > 1 {
+            let (hop_chains, chain_id) =
+                follow_connection_hops(a_chain.as_ref(), hop_chains, a_channel.connection_hops())
+                    .map_err(|e| {
+                    LinkError::Failed(format!(
+                        "cannot follow the connection hops of channel {} on chain {}: {}",
+                        a_channel_id,
+                        a_chain.id(),
+                        e
+                    ))
+                })?;
+
+            if chain_id != b_chain.id() {
+                return Err(LinkError::Failed(format!(
+                    "the connection hops of channel {} on chain {} lead to chain {} instead of {}",
+                    a_channel_id,
+                    a_chain.id(),
+                    chain_id,
+                    b_chain.id()
+                )));
+            }
+
+            hop_chains
+        } else {
+            vec![]
+        };
+
         let a_connection_id = a_channel.connection_hops()[0].clone();
         let a_connection = a_chain.query_connection(&a_connection_id, Height::zero())?;
 
@@ -1590,6 +1802,32 @@ impl Link {
             )));
         }
 
+        let b_port_id = a_channel.counterparty().port_id.clone();
+        let b_channel = b_chain.query_channel(&b_port_id, &b_channel_id, Height::zero())?;
+        let b_connection_id = b_channel
+            .connection_hops()
+            .first()
+            .cloned()
+            .ok_or_else(|| {
+                LinkError::Failed(format!(
+                    "channel {} on chain {} has no connection hops",
+                    b_channel_id,
+                    b_chain.id()
+                ))
+            })?;
+        let b_connection = b_chain.query_connection(&b_connection_id, Height::zero())?;
+
+        // Each chain verifies the proofs of the next one under the commitment prefix which its
+        // connection end records for it. A misconfigured prefix is reported here rather than as
+        // proof verification failures on the other chain.
+        match (hop_chains.first(), hop_chains.last()) {
+            (Some(a_hop_chain), Some(b_hop_chain)) => {
+                check_connection_prefixes(a_chain.as_ref(), &a_connection, a_hop_chain.as_ref())?;
+                check_connection_prefixes(b_chain.as_ref(), &b_connection, b_hop_chain.as_ref())?;
+            }
+            _ => check_connection_prefixes(a_chain.as_ref(), &a_connection, b_chain.as_ref())?,
+        }
+
         let channel = Channel {
             ordering: Default::default(),
             a_side: ChannelSide::new(
@@ -1601,16 +1839,16 @@ impl Link {
             ),
             b_side: ChannelSide::new(
                 b_chain,
-                a_connection.counterparty().client_id().clone(),
-                a_connection.counterparty().connection_id().unwrap().clone(),
-                a_channel.counterparty().port_id.clone(),
+                b_connection.client_id().clone(),
+                b_connection_id,
+                b_port_id,
                 b_channel_id,
             ),
             connection_delay: a_connection.delay_period(),
             version: None,
         };
 
-        Ok(Link::new(channel))
+        Ok(Link::new(channel).with_hop_chains(hop_chains))
     }
 
     pub fn build_and_send_recv_packet_messages(&mut self) -> Result<Vec<IbcEvent>, LinkError> {
@@ -1641,3 +1879,26 @@ impl Link {
         Ok(results)
     }
 }
+
+/// Checks the commitment prefixes of `chain` and of the `counterparty_chain` which its
+/// `connection` leads to, against the ones which their connection ends record for each other.
+fn check_connection_prefixes(
+    chain: &dyn ChainHandle,
+    connection: &ConnectionEnd,
+    counterparty_chain: &dyn ChainHandle,
+) -> Result<(), LinkError> {
+    let counterparty_connection_id =
+        connection.counterparty().connection_id().ok_or_else(|| {
+            LinkError::Failed(format!(
+                "counterparty connection id not found for a connection of chain {}",
+                chain.id()
+            ))
+        })?;
+    let counterparty_connection =
+        counterparty_chain.query_connection(counterparty_connection_id, Height::zero())?;
+
+    check_commitment_prefix(chain, counterparty_connection.counterparty().prefix())?;
+    check_commitment_prefix(counterparty_chain, connection.counterparty().prefix())?;
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Construction of the proofs for multi-hop channels, i.e., channels whose connection hops
+//! traverse intermediate chains. See [`ibc::ics04_channel::multihop`] for their verification.
+
+use ibc::ics02_client::client_state::ClientState;
+use ibc::ics03_connection::connection::State as ConnectionState;
+use ibc::ics04_channel::multihop::{MultihopProof, MultihopProofs};
+use ibc::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::ics23_commitment::commitment::CommitmentProofBytes;
+use ibc::ics24_host::identifier::{ChainId, ChannelId, ConnectionId, PortId};
+use ibc::proofs::Proofs;
+use ibc::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::error::{Error, Kind};
+
+/// Follows the `connection_hops` of a channel end on `chain`. Each hop leads to the chain tracked
+/// by the client of its connection end, which stores the next hop, if any. Returns the
+/// intermediate chains, looked up among `hop_chains` in the order of the hops, along with the
+/// identifier of the chain reached by the last hop.
+pub fn follow_connection_hops(
+    chain: &dyn ChainHandle,
+    hop_chains: &[Box<dyn ChainHandle>],
+    connection_hops: &[ConnectionId],
+) -> Result<(Vec<Box<dyn ChainHandle>>, ChainId), Error> {
+    let mut intermediate_chains: Vec<Box<dyn ChainHandle>> = vec![];
+    let mut chain_id = chain.id();
+
+    for (i, connection_id) in connection_hops.iter().enumerate() {
+        if i > 0 {
+            let hop_chain = hop_chains
+                .iter()
+                .find(|hop_chain| hop_chain.id() == chain_id)
+                .ok_or_else(|| {
+                    Kind::Multihop(format!(
+                        "missing intermediate chain {} for hop {}",
+                        chain_id, connection_id
+                    ))
+                })?;
+            intermediate_chains.push(hop_chain.clone());
+        }
+
+        let current = match intermediate_chains.last() {
+            Some(hop_chain) => hop_chain.as_ref(),
+            None => chain,
+        };
+
+        let connection = current.query_connection(connection_id, Height::zero())?;
+        if connection.state_matches(&ConnectionState::Uninitialized) {
+            return Err(Kind::Multihop(format!(
+                "missing connection {} on chain {}",
+                connection_id, chain_id
+            ))
+            .into());
+        }
+
+        chain_id = current
+            .query_client_state(connection.client_id(), Height::zero())?
+            .chain_id();
+    }
+
+    Ok((intermediate_chains, chain_id))
+}
+
+/// Builds the proofs for the channel handshake messages of a multi-hop channel, which prove the
+/// channel end `(port_id, channel_id)` stored on the last of `chains`.
+///
+/// `chains` are the chains reached by each of the `connection_hops` of the channel end on the
+/// chain which verifies the proofs. The first of these chains is queried at `height`, so the
+/// client of the first hop must be updated to `height + 1`.
+pub fn build_multihop_channel_proofs(
+    chains: &[Box<dyn ChainHandle>],
+    connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    height: Height,
+) -> Result<Proofs, Error> {
+    let (hops, key_height) = build_hop_proofs(chains, connection_hops, height)?;
+    let counterparty = chains.last().unwrap();
+
+    let channel_proofs = counterparty.build_channel_proofs(port_id, channel_id, key_height)?;
+    let channel_proof = MultihopProofs::new(channel_proofs.object_proof().clone(), hops).into();
+
+    Ok(
+        Proofs::new(channel_proof, None, None, None, height.increment())
+            .map_err(|_| Kind::MalformedProof)?,
+    )
+}
+
+/// Builds the proofs for the packet messages of a multi-hop channel, which prove the packet
+/// data stored on the last of `chains`. Returns the proven data along with the proofs. The
+/// `chains`, `connection_hops` and `height` are as in [`build_multihop_channel_proofs`].
+pub fn build_multihop_packet_proofs(
+    chains: &[Box<dyn ChainHandle>],
+    connection_hops: &[ConnectionId],
+    packet_type: PacketMsgType,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+    height: Height,
+) -> Result<(Vec<u8>, Proofs), Error> {
+    let (hops, key_height) = build_hop_proofs(chains, connection_hops, height)?;
+    let counterparty = chains.last().unwrap();
+
+    let (bytes, packet_proofs) =
+        counterparty.build_packet_proofs(packet_type, port_id, channel_id, sequence, key_height)?;
+
+    // The proof of the channel closure, if any, goes through the same hops.
+    let channel_proof = packet_proofs
+        .other_proof()
+        .clone()
+        .map(|proof| MultihopProofs::new(proof, hops.clone()).into());
+    let packet_proof = MultihopProofs::new(packet_proofs.object_proof().clone(), hops).into();
+
+    let proofs = Proofs::new(packet_proof, None, None, channel_proof, height.increment())
+        .map_err(|_| Kind::MalformedProof)?;
+
+    Ok((bytes, proofs))
+}
+
+/// Queries the proofs of the connection ends and consensus states stored on the intermediate
+/// chains, i.e., all `chains` but the last. Each intermediate chain is queried at the height
+/// preceding the consensus state which the previous chain stores for it. Returns the proofs along
+/// with the height at which the last chain must be queried for the key proof.
+fn build_hop_proofs(
+    chains: &[Box<dyn ChainHandle>],
+    connection_hops: &[ConnectionId],
+    height: Height,
+) -> Result<(Vec<MultihopProof>, Height), Error> {
+    if chains.len() < 2 || chains.len() != connection_hops.len() {
+        return Err(Kind::Multihop(format!(
+            "expected one chain per connection hop over at least two hops, got {} chains for {} hops",
+            chains.len(),
+            connection_hops.len()
+        ))
+        .into());
+    }
+
+    let mut hops = Vec::with_capacity(chains.len() - 1);
+    let mut query_height = height;
+
+    for (chain, connection_id) in chains.iter().zip(&connection_hops[1..]) {
+        let (connection_end, proof_connection) =
+            chain.proven_connection(connection_id, query_height)?;
+
+        // The consensus state of the next chain is the latest one of the client of the hop.
+        let client_id = connection_end.client_id().clone();
+        let consensus_height = chain
+            .query_client_state(&client_id, query_height)?
+            .latest_height();
+        let (consensus_state, proof_consensus) =
+            chain.proven_client_consensus(&client_id, consensus_height, query_height)?;
+
+        hops.push(MultihopProof::new(
+            connection_end,
+            CommitmentProofBytes::from(proof_connection),
+            consensus_state,
+            CommitmentProofBytes::from(proof_consensus),
+            consensus_height,
+        ));
+
+        query_height = consensus_height
+            .decrement()
+            .map_err(|e| Kind::Multihop(format!("hop {}", connection_id)).context(e))?;
+    }
+
+    Ok((hops, query_height))
+}
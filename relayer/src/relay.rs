@@ -36,9 +36,10 @@ pub fn relay_on_new_link(
 pub fn channel_relay(
     a_chain: Box<dyn ChainHandle>,
     b_chain: Box<dyn ChainHandle>,
+    hop_chains: &[Box<dyn ChainHandle>],
     opts: LinkParameters,
 ) -> Result<(), BoxError> {
-    let mut link = Link::new_from_opts(a_chain, b_chain, opts)?;
+    let mut link = Link::new_from_opts(a_chain, b_chain, hop_chains, opts)?;
     Ok(link.relay()?)
 }
 
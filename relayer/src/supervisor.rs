@@ -10,7 +10,6 @@ use crossbeam_channel::{Receiver, Sender};
 use ibc::{
     events::IbcEvent,
     ics02_client::events::NewBlock,
-    ics03_connection::connection::State as ConnectionState,
     ics04_channel::{
         channel::State as ChannelState,
         events::{CloseInit, SendPacket, TimeoutPacket, WriteAcknowledgement},
@@ -24,6 +23,7 @@ use crate::{
     chain::handle::ChainHandle,
     event::monitor::EventBatch,
     link::{Link, LinkParameters},
+    multihop::follow_connection_hops,
 };
 
 /// A command for a [`Worker`].
@@ -99,6 +99,7 @@ impl ChainHandlePair {
 /// worker, based on the [`Object`] associated with each event.
 pub struct Supervisor {
     chains: ChainHandlePair,
+    hop_chains: Vec<Box<dyn ChainHandle>>,
     workers: HashMap<Object, WorkerHandle>,
 }
 
@@ -115,10 +116,18 @@ impl Supervisor {
 
         Ok(Self {
             chains,
+            hop_chains: vec![],
             workers: HashMap::new(),
         })
     }
 
+    /// Register the intermediate chains of the multi-hop channels between the two chains, through
+    /// which the counterparty chain of these channels is found and their packets are relayed.
+    pub fn with_hop_chains(mut self, hop_chains: Vec<Box<dyn ChainHandle>>) -> Self {
+        self.hop_chains = hop_chains;
+        self
+    }
+
     /// Run the supervisor event loop.
     pub fn run(mut self) -> Result<(), BoxError> {
         let subscription_a = self.chains.a.subscribe()?;
@@ -154,7 +163,7 @@ impl Supervisor {
             Direction::BtoA
         };
 
-        let mut collected = collect_events(src_chain.as_ref(), batch);
+        let mut collected = collect_events(src_chain.as_ref(), &self.hop_chains, batch);
 
         for (object, events) in collected.per_object.drain() {
             if events.is_empty() {
@@ -204,7 +213,7 @@ impl Supervisor {
                 return None;
             }
 
-            let worker = Worker::spawn(chains, object.clone());
+            let worker = Worker::spawn(chains, self.hop_chains.clone(), object.clone());
             let worker = self.workers.entry(object).or_insert(worker);
             Some(worker)
         }
@@ -223,12 +232,18 @@ pub enum Direction {
 /// A worker processes batches of events associated with a given [`Object`].
 pub struct Worker {
     chains: ChainHandlePair,
+    hop_chains: Vec<Box<dyn ChainHandle>>,
     rx: Receiver<WorkerCmd>,
 }
 
 impl Worker {
-    /// Spawn a worker which relay events pertaining to `object` between two `chains`.
-    pub fn spawn(chains: ChainHandlePair, object: Object) -> WorkerHandle {
+    /// Spawn a worker which relay events pertaining to `object` between two `chains`, through
+    /// the `hop_chains` if `object` pertains to a multi-hop channel.
+    pub fn spawn(
+        chains: ChainHandlePair,
+        hop_chains: Vec<Box<dyn ChainHandle>>,
+        object: Object,
+    ) -> WorkerHandle {
         let (tx, rx) = crossbeam_channel::unbounded();
 
         println!(
@@ -237,7 +252,11 @@ impl Worker {
             object
         );
 
-        let worker = Self { chains, rx };
+        let worker = Self {
+            chains,
+            hop_chains,
+            rx,
+        };
         let thread_handle = std::thread::spawn(move || worker.run(object));
 
         WorkerHandle { tx, thread_handle }
@@ -259,6 +278,7 @@ impl Worker {
         let mut link = Link::new_from_opts(
             self.chains.a.clone(),
             self.chains.b.clone(),
+            &self.hop_chains,
             LinkParameters {
                 src_port_id: path.src_port_id,
                 src_channel_id: path.src_channel_id,
@@ -356,9 +376,17 @@ impl Object {
     }
 
     /// Build the object associated with the given [`SendPacket`] event.
-    pub fn for_send_packet(e: &SendPacket, src_chain: &dyn ChainHandle) -> Result<Self, BoxError> {
-        let dst_chain_id =
-            get_counterparty_chain(src_chain, &e.packet.source_channel, &e.packet.source_port)?;
+    pub fn for_send_packet(
+        e: &SendPacket,
+        src_chain: &dyn ChainHandle,
+        hop_chains: &[Box<dyn ChainHandle>],
+    ) -> Result<Self, BoxError> {
+        let dst_chain_id = get_counterparty_chain(
+            src_chain,
+            hop_chains,
+            &e.packet.source_channel,
+            &e.packet.source_port,
+        )?;
 
         Ok(UnidirectionalChannelPath {
             dst_chain_id,
@@ -373,9 +401,11 @@ impl Object {
     pub fn for_write_ack(
         e: &WriteAcknowledgement,
         src_chain: &dyn ChainHandle,
+        hop_chains: &[Box<dyn ChainHandle>],
     ) -> Result<Self, BoxError> {
         let dst_chain_id = get_counterparty_chain(
             src_chain,
+            hop_chains,
             &e.packet.destination_channel,
             &e.packet.destination_port,
         )?;
@@ -393,9 +423,14 @@ impl Object {
     pub fn for_timeout_packet(
         e: &TimeoutPacket,
         src_chain: &dyn ChainHandle,
+        hop_chains: &[Box<dyn ChainHandle>],
     ) -> Result<Self, BoxError> {
-        let dst_chain_id =
-            get_counterparty_chain(src_chain, &e.packet.source_channel, &e.packet.source_port)?;
+        let dst_chain_id = get_counterparty_chain(
+            src_chain,
+            hop_chains,
+            &e.packet.source_channel,
+            &e.packet.source_port,
+        )?;
 
         Ok(UnidirectionalChannelPath {
             dst_chain_id,
@@ -410,8 +445,10 @@ impl Object {
     pub fn for_close_init_channel(
         e: &CloseInit,
         src_chain: &dyn ChainHandle,
+        hop_chains: &[Box<dyn ChainHandle>],
     ) -> Result<Self, BoxError> {
-        let dst_chain_id = get_counterparty_chain(src_chain, e.channel_id(), &e.port_id())?;
+        let dst_chain_id =
+            get_counterparty_chain(src_chain, hop_chains, e.channel_id(), &e.port_id())?;
 
         Ok(UnidirectionalChannelPath {
             dst_chain_id,
@@ -454,7 +491,13 @@ impl CollectedEvents {
 
 /// Collect the events we are interested in from an [`EventBatch`],
 /// and maps each [`IbcEvent`] to their corresponding [`Object`].
-pub fn collect_events(src_chain: &dyn ChainHandle, batch: EventBatch) -> CollectedEvents {
+/// The `hop_chains` are the intermediate chains of the multi-hop
+/// channels of the source chain.
+pub fn collect_events(
+    src_chain: &dyn ChainHandle,
+    hop_chains: &[Box<dyn ChainHandle>],
+    batch: EventBatch,
+) -> CollectedEvents {
     let mut collected = CollectedEvents::new(batch.height, batch.chain_id);
 
     for event in batch.events {
@@ -463,22 +506,22 @@ pub fn collect_events(src_chain: &dyn ChainHandle, batch: EventBatch) -> Collect
                 collected.new_blocks.push(inner);
             }
             IbcEvent::SendPacket(ref packet) => {
-                if let Ok(object) = Object::for_send_packet(packet, src_chain) {
+                if let Ok(object) = Object::for_send_packet(packet, src_chain, hop_chains) {
                     collected.per_object.entry(object).or_default().push(event);
                 }
             }
             IbcEvent::TimeoutPacket(ref packet) => {
-                if let Ok(object) = Object::for_timeout_packet(packet, src_chain) {
+                if let Ok(object) = Object::for_timeout_packet(packet, src_chain, hop_chains) {
                     collected.per_object.entry(object).or_default().push(event);
                 }
             }
             IbcEvent::WriteAcknowledgement(ref packet) => {
-                if let Ok(object) = Object::for_write_ack(packet, src_chain) {
+                if let Ok(object) = Object::for_write_ack(packet, src_chain, hop_chains) {
                     collected.per_object.entry(object).or_default().push(event);
                 }
             }
             IbcEvent::CloseInitChannel(ref packet) => {
-                if let Ok(object) = Object::for_close_init_channel(packet, src_chain) {
+                if let Ok(object) = Object::for_close_init_channel(packet, src_chain, hop_chains) {
                     collected.per_object.entry(object).or_default().push(event);
                 }
             }
@@ -490,8 +533,11 @@ pub fn collect_events(src_chain: &dyn ChainHandle, batch: EventBatch) -> Collect
 }
 
 // TODO: Memoize this result
+/// Returns the identifier of the chain at the other end of the given channel. For a multi-hop
+/// channel, the connection hops are followed through the `hop_chains`.
 fn get_counterparty_chain(
     src_chain: &dyn ChainHandle,
+    hop_chains: &[Box<dyn ChainHandle>],
     src_channel_id: &ChannelId,
     src_port_id: &PortId,
 ) -> Result<ChainId, BoxError> {
@@ -502,30 +548,22 @@ fn get_counterparty_chain(
         "getting counterparty chain"
     );
 
-    use ibc::ics02_client::client_state::ClientState;
-
     let src_channel = src_chain.query_channel(src_port_id, src_channel_id, Height::zero())?;
     if src_channel.state_matches(&ChannelState::Uninitialized) {
         return Err(format!("missing channel '{}' on source chain", src_channel_id).into());
     }
 
-    let src_connection_id = src_channel
-        .connection_hops()
-        .first()
-        .ok_or_else(|| format!("no connection hops for channel '{}'", src_channel_id))?;
-
-    let src_connection = src_chain.query_connection(&src_connection_id, Height::zero())?;
-    if src_connection.state_matches(&ConnectionState::Uninitialized) {
-        return Err(format!("missing connection '{}' on source chain", src_connection_id).into());
+    if src_channel.connection_hops().is_empty() {
+        return Err(format!("no connection hops for channel '{}'", src_channel_id).into());
     }
 
-    let client_id = src_connection.client_id();
-    let client_state = src_chain.query_client_state(client_id, Height::zero())?;
+    let (_, chain_id) =
+        follow_connection_hops(src_chain, hop_chains, src_channel.connection_hops())?;
 
     info!(
         chain_id=%src_chain.id(), src_channel_id=%src_channel_id, src_port_id=%src_port_id,
-        "counterparty chain: {}", client_state.chain_id()
+        "counterparty chain: {}", chain_id
     );
 
-    Ok(client_state.chain_id())
+    Ok(chain_id)
 }
@@ -4,6 +4,7 @@ use tracing::error;
 use ibc::application::ics20_fungible_token_transfer::msgs::transfer::MsgTransfer;
 use ibc::events::IbcEvent;
 use ibc::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::timestamp::Timestamp;
 use ibc::tx_msg::Msg;
 
 use crate::chain::{Chain, CosmosSdkChain};
@@ -66,7 +67,7 @@ pub fn build_and_send_transfer_messages(
         sender,
         receiver,
         timeout_height: latest_height.add(opts.height_offset),
-        timeout_timestamp: 0,
+        timeout_timestamp: Timestamp::none(),
     };
 
     let raw_msg = msg.to_any();